@@ -1,7 +1,10 @@
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Style of indentation used in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CFunctionStyle {
     Typedefs,
     ForwardDeclarations,
@@ -9,6 +12,7 @@ pub enum CFunctionStyle {
 
 /// Style of indentation used in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CIndentationStyle {
     /// Braces on their own lines, not indented
     Allman,
@@ -22,6 +26,7 @@ pub enum CIndentationStyle {
 
 /// Style of documentation in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CNamingStyle {
     /// Names all in lowercase without spacing e.g. 'thetypename'
     Lowercase,
@@ -62,6 +67,7 @@ impl ToNamingStyle for &str {
 
 /// Style of documentation in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CDocumentationStyle {
     // No documentation comments are added to header file
     None,
@@ -71,6 +77,7 @@ pub enum CDocumentationStyle {
 
 /// Configures C code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
 pub struct Config {
     /// Whether to write conditional directives like `#ifndef _X`.
     pub directives: bool,
@@ -102,6 +109,8 @@ pub struct Config {
     pub function_parameter_naming: CNamingStyle,
     /// How to emit functions
     pub function_style: CFunctionStyle,
+    /// Emit `_Static_assert` checks of `sizeof` and `offsetof` for structs, as laid out by Rust on the generating machine.
+    pub layout_asserts: bool,
 }
 
 impl Default for Config {
@@ -122,6 +131,14 @@ impl Default for Config {
             const_naming: CNamingStyle::Uppercase,
             function_parameter_naming: CNamingStyle::Lowercase,
             function_style: CFunctionStyle::ForwardDeclarations,
+            layout_asserts: false,
         }
     }
 }
+
+/// Configures C conformance test generation.
+#[derive(Clone, Debug, Default)]
+pub struct ConformanceConfig {
+    /// Header holding the generated bindings, included as `#include "{header}"`.
+    pub header: String,
+}
@@ -0,0 +1,95 @@
+use crate::{CTypeConverter, CWriter, ConformanceConfig};
+use interoptopus::conformance::{ConformanceWriter, TestSuite};
+use interoptopus::lang::c::PrimitiveValue;
+use interoptopus::writer::IndentWriter;
+use interoptopus::{indented, Error, Interop, Inventory};
+
+/// Writes a C program calling functions with the vectors of a [`TestSuite`].
+///
+/// The program prints each failed check and exits with a non-zero status if any failed.
+pub struct ConformanceGenerator<'a, W> {
+    c_writer: &'a W,
+    suite: &'a TestSuite,
+    config: ConformanceConfig,
+}
+
+impl<'a, W: CWriter> ConformanceGenerator<'a, W> {
+    pub fn new(w: &'a W, suite: &'a TestSuite, config: ConformanceConfig) -> Self {
+        Self { c_writer: w, suite, config }
+    }
+
+    pub fn config(&self) -> &ConformanceConfig {
+        &self.config
+    }
+}
+
+impl<W: CWriter> ConformanceWriter for ConformanceGenerator<'_, W> {
+    fn inventory(&self) -> &Inventory {
+        self.c_writer.inventory()
+    }
+
+    fn suite(&self) -> &TestSuite {
+        self.suite
+    }
+
+    fn literal(&self, value: &PrimitiveValue) -> String {
+        // Literals of `MIN` would be parsed as negated, out of range positive values.
+        match value {
+            PrimitiveValue::Bool(x) => x.to_string(),
+            PrimitiveValue::U8(x) => x.to_string(),
+            PrimitiveValue::U16(x) => x.to_string(),
+            PrimitiveValue::U32(x) => format!("{}U", x),
+            PrimitiveValue::U64(x) => format!("{}ULL", x),
+            PrimitiveValue::I8(x) => x.to_string(),
+            PrimitiveValue::I16(x) => x.to_string(),
+            PrimitiveValue::I32(i32::MIN) => "INT32_MIN".to_string(),
+            PrimitiveValue::I32(x) => x.to_string(),
+            PrimitiveValue::I64(i64::MIN) => "INT64_MIN".to_string(),
+            PrimitiveValue::I64(x) => format!("{}LL", x),
+            PrimitiveValue::F32(x) => format!("{:?}f", x),
+            PrimitiveValue::F64(x) => format!("{:?}", x),
+        }
+    }
+}
+
+impl<W: CWriter> Interop for ConformanceGenerator<'_, W> {
+    fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        indented!(w, r#"// Automatically generated by Interoptopus."#)?;
+        indented!(w, r#"#include <stdio.h>"#)?;
+        w.newline()?;
+        indented!(w, r#"#include "{}""#, self.config().header)?;
+        w.newline()?;
+        indented!(w, r#"static int failures = 0;"#)?;
+        w.newline()?;
+        indented!(w, r#"static void check(int ok, const char* call)"#)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"if (!ok)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"printf("Conformance check failed: %s\n", call);"#)?;
+        indented!(w, [_ _], r#"failures++;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(w, r#"int main(void)"#)?;
+        indented!(w, r#"{{"#)?;
+
+        for (function, vectors) in self.tested_functions() {
+            let name = self.c_writer.converter().function_name_to_c_name(function);
+
+            for vector in vectors {
+                let call = format!("{}({})", name, self.arguments(vector));
+
+                match vector.expected() {
+                    Some(expected) => indented!(w, [_], r#"check({} == {}, "{}");"#, call, self.literal(expected), call)?,
+                    None => indented!(w, [_], r#"{};"#, call)?,
+                }
+            }
+        }
+
+        w.newline()?;
+        indented!(w, [_], r#"return failures != 0;"#)?;
+        indented!(w, r#"}}"#)?;
+
+        Ok(())
+    }
+}
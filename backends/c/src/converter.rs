@@ -3,7 +3,7 @@ use crate::Config;
 use interoptopus::lang::c::{CType, CompositeType, Constant, ConstantValue, EnumType, FnPointerType, Function, OpaqueType, PrimitiveType, PrimitiveValue, Variant};
 use interoptopus::patterns::callbacks::NamedCallback;
 use interoptopus::patterns::TypePattern;
-use interoptopus::util::safe_name;
+use interoptopus::util::{escape_string_literal, safe_name, ControlEscape};
 
 /// Implements [`CTypeConverter`].
 #[derive(Clone)]
@@ -43,6 +43,7 @@ pub trait CTypeConverter {
 
     fn constant_value_to_value(&self, value: &ConstantValue) -> String;
 
+    /// The name under which a function is declared, unless overridden via `name(c = "...")`.
     fn function_name_to_c_name(&self, function: &Function) -> String;
 }
 
@@ -65,6 +66,11 @@ impl CTypeConverter for Converter {
             PrimitiveType::I64 => "int64_t".to_string(),
             PrimitiveType::F32 => "float".to_string(),
             PrimitiveType::F64 => "double".to_string(),
+            PrimitiveType::Char => "uint32_t".to_string(),
+            PrimitiveType::USize => "size_t".to_string(),
+            PrimitiveType::ISize => "intptr_t".to_string(),
+            PrimitiveType::U128 => format!("{}u128", self.config().prefix).to_naming_style(&self.config.type_naming),
+            PrimitiveType::I128 => format!("{}i128", self.config().prefix).to_naming_style(&self.config.type_naming),
         }
     }
 
@@ -131,10 +137,22 @@ impl CTypeConverter for Converter {
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => format!(r#""{}""#, escape_string_literal(x, ControlEscape::Octal)),
+            ConstantValue::Array(_, values) => {
+                let values = values.iter().map(|x| self.constant_value_to_value(x)).collect::<Vec<_>>();
+                format!("{{ {} }}", values.join(", "))
+            }
+            ConstantValue::Composite(_, fields) => {
+                let values = fields.iter().map(|(_, x)| self.constant_value_to_value(x)).collect::<Vec<_>>();
+                format!("{{ {} }}", values.join(", "))
+            }
         }
     }
 
     fn function_name_to_c_name(&self, function: &Function) -> String {
-        function.name().to_string()
+        match function.meta().overrides().name("c") {
+            Some(name) => name.to_string(),
+            None => function.name().to_string(),
+        }
     }
 }
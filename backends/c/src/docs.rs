@@ -1,8 +1,10 @@
+use crate::converter::CTypeConverter;
 use crate::CWriter;
-use interoptopus::indented;
+use interoptopus::docs::html::HtmlConfig;
+use interoptopus::docs::{DocFormatter, DocModel};
 use interoptopus::lang::c::{CType, Function};
-use interoptopus::util::sort_types_by_dependencies;
-use interoptopus::writer::IndentWriter;
+use interoptopus::patterns::service::Service;
+use interoptopus::writer::{IndentWriter, OutputSet};
 use interoptopus::{Error, Inventory};
 use std::fs::File;
 use std::path::Path;
@@ -21,92 +23,80 @@ impl<W: CWriter> DocGenerator<W> {
         &self.inventory
     }
 
-    pub fn write_types(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Types "#)?;
-
-        let mut known_function_pointers = vec![];
+    pub fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        DocModel::from_inventory(&self.inventory, self)?.write_markdown(w)?;
 
-        for the_type in &sort_types_by_dependencies(self.inventory().ctypes().to_vec()) {
-            self.write_type_definition(w, the_type, &mut known_function_pointers)?;
-        }
+        w.newline()?;
 
         Ok(())
     }
 
-    pub fn write_type_definition(&self, w: &mut IndentWriter, the_type: &CType, known_function_pointers: &mut Vec<String>) -> Result<(), Error> {
-        let meta = match the_type {
-            CType::Primitive(_) => return Ok(()),
-            CType::Array(_) => return Ok(()),
-            CType::Enum(e) => e.meta(),
-            CType::Opaque(o) => o.meta(),
-            CType::Composite(c) => c.meta(),
-            CType::FnPointer(_) => return Ok(()),
-            CType::ReadPointer(_) => return Ok(()),
-            CType::ReadWritePointer(_) => return Ok(()),
-            CType::Pattern(_) => return Ok(()),
-        };
-
-        w.newline()?;
-        w.newline()?;
+    /// Renders the documentation as a static HTML site into `output`.
+    pub fn write_html(&self, output: &mut OutputSet, config: &HtmlConfig) -> Result<(), Error> {
+        DocModel::from_inventory(&self.inventory, self)?.write_html(output, config)
+    }
 
-        indented!(w, r#"## {} "#, the_type.name_within_lib())?;
-        w.newline()?;
+    pub fn write_file<P: AsRef<Path>>(&self, file_name: P) -> Result<(), Error> {
+        let mut file = File::create(file_name)?;
+        let mut writer = IndentWriter::new(&mut file);
 
-        for line in meta.documentation().lines() {
-            indented!(w, r#"{}"#, line.trim())?;
-            w.newline()?;
-        }
+        self.write_to(&mut writer)
+    }
 
-        indented!(w, r#"```"#)?;
-        self.c_writer.write_type_definition(w, the_type, known_function_pointers)?;
-        indented!(w, r#"```"#)?;
+    #[deprecated(
+        since = "0.14.26",
+        note = "Use `write_to`, or `DocModel::write_markdown_composites` and `DocModel::write_markdown_enums`."
+    )]
+    pub fn write_types(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let model = DocModel::from_inventory(&self.inventory, self)?;
 
-        Ok(())
+        model.write_markdown_composites(w)?;
+        model.write_markdown_enums(w)
     }
 
-    pub fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Functions "#)?;
-
-        for the_type in self.inventory().functions() {
-            self.write_function(w, the_type)?;
+    /// Only documents enums and data structs, other types are skipped.
+    #[deprecated(
+        since = "0.14.26",
+        note = "Use `write_to`, or `DocModel::write_markdown_composite` and `DocModel::write_markdown_enum`."
+    )]
+    pub fn write_type_definition(&self, w: &mut IndentWriter, the_type: &CType, _known_function_pointers: &mut Vec<String>) -> Result<(), Error> {
+        let model = DocModel::from_inventory(&self.inventory, self)?;
+
+        match the_type {
+            CType::Composite(x) => model.composite(x).map_or(Ok(()), |x| model.write_markdown_composite(w, x)),
+            CType::Enum(x) => model.enumeration(x).map_or(Ok(()), |x| model.write_markdown_enum(w, x)),
+            _ => Ok(()),
         }
+    }
 
-        Ok(())
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_functions`.")]
+    pub fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        DocModel::from_inventory(&self.inventory, self)?.write_markdown_functions(w)
     }
+}
 
-    fn write_function(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
-        indented!(w, r#"## {} "#, function.name())?;
+impl<W: CWriter> DocFormatter for DocGenerator<W> {
+    fn language(&self) -> &str {
+        "c"
+    }
 
-        for line in function.meta().documentation().lines() {
-            if line.trim().starts_with('#') {
-                write!(w.writer(), "##")?;
-            }
-            indented!(w, r#"{}"#, line.trim())?;
-            w.newline()?;
+    fn type_name(&self, the_type: &CType) -> String {
+        match the_type {
+            CType::Array(x) => format!("{}[{}]", self.type_name(x.array_type()), x.len()),
+            _ => self.c_writer.converter().to_type_specifier(the_type),
         }
-
-        indented!(w, r#"```"#)?;
-        self.c_writer.write_function_declaration(w, function, 80)?;
-        indented!(w, r#"```"#)?;
-
-        w.newline()?;
-
-        Ok(())
     }
 
-    pub fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        self.write_types(w)?;
-        self.write_functions(w)?;
-
-        w.newline()?;
-
-        Ok(())
+    /// C has no methods, so service functions keep their full name.
+    fn function_name(&self, function: &Function, _service: Option<&Service>) -> String {
+        self.c_writer.converter().function_name_to_c_name(function)
     }
 
-    pub fn write_file<P: AsRef<Path>>(&self, file_name: P) -> Result<(), Error> {
-        let mut file = File::create(file_name)?;
-        let mut writer = IndentWriter::new(&mut file);
+    fn write_signature(&self, w: &mut IndentWriter, function: &Function, _service: Option<&Service>) -> Result<(), Error> {
+        self.c_writer.write_function_declaration(w, function, 80)
+    }
 
-        self.write_to(&mut writer)
+    fn write_type_definition(&self, w: &mut IndentWriter, the_type: &CType) -> Result<(), Error> {
+        self.c_writer.write_type_definition(w, the_type, &mut Vec::new())
     }
 }
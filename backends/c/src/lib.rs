@@ -115,12 +115,14 @@ use interoptopus::Interop;
 use interoptopus::{Error, Inventory};
 
 mod config;
+mod conformance;
 mod converter;
 mod docs;
 mod testing;
 mod writer;
 
-pub use config::{CDocumentationStyle, CFunctionStyle, CIndentationStyle, CNamingStyle, Config};
+pub use config::{CDocumentationStyle, CFunctionStyle, CIndentationStyle, CNamingStyle, Config, ConformanceConfig};
+pub use conformance::ConformanceGenerator;
 pub use converter::{CTypeConverter, Converter};
 pub use docs::DocGenerator;
 pub use testing::compile_c_app_if_installed;
@@ -137,7 +139,7 @@ impl Generator {
     pub fn new(config: Config, inventory: Inventory) -> Self {
         Self {
             config: config.clone(),
-            inventory,
+            inventory: inventory.without_skipped("c"),
             converter: Converter { config },
         }
     }
@@ -1,8 +1,10 @@
 use interoptopus::indented;
-use interoptopus::lang::c::{CType, CompositeType, Constant, Documentation, EnumType, Field, FnPointerType, Function, OpaqueType, Variant};
+use interoptopus::lang::c::{
+    CType, CompositeType, Constant, ConstantValue, Deprecation, Documentation, EnumType, Field, FnPointerType, Function, Layout, OpaqueType, PrimitiveType, Variant,
+};
 use interoptopus::patterns::callbacks::NamedCallback;
-use interoptopus::patterns::TypePattern;
-use interoptopus::util::sort_types_by_dependencies;
+use interoptopus::patterns::{LibraryPattern, TypePattern};
+use interoptopus::util::{has_deprecations, sort_types_by_dependencies};
 use interoptopus::writer::IndentWriter;
 use interoptopus::{Error, Inventory};
 
@@ -34,6 +36,10 @@ pub trait CWriter {
         indented!(w, r#"#include <stdint.h>"#)?;
         indented!(w, r#"#include <stdbool.h>"#)?;
 
+        if self.config().layout_asserts || self.uses_primitive(PrimitiveType::USize) {
+            indented!(w, r#"#include <stddef.h>"#)?;
+        }
+
         // Write any user supplied includes into the file.
         for include in &self.config().additional_includes {
             indented!(w, "#include {}", include)?;
@@ -42,26 +48,137 @@ pub trait CWriter {
         Ok(())
     }
 
+    /// Checks if any type in the inventory is the given primitive.
+    fn uses_primitive(&self, primitive: PrimitiveType) -> bool {
+        self.inventory().ctypes().contains(&CType::Primitive(primitive))
+    }
+
+    /// Defines the 128-bit integer types used, as `__int128` where available and as two words otherwise.
+    ///
+    /// The fallback is aligned to 16 bytes like Rust's `u128` and `i128`, so struct offsets agree.
+    fn write_int128_typedefs(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let used = [PrimitiveType::U128, PrimitiveType::I128]
+            .into_iter()
+            .filter(|x| self.uses_primitive(*x))
+            .collect::<Vec<_>>();
+
+        if used.is_empty() {
+            return Ok(());
+        }
+
+        indented!(w, r#"#if defined(__SIZEOF_INT128__)"#)?;
+        for primitive in &used {
+            let native = match primitive {
+                PrimitiveType::U128 => "unsigned __int128",
+                _ => "__int128",
+            };
+            indented!(w, r#"typedef {} {};"#, native, self.converter().primitive_to_typename(primitive))?;
+        }
+        indented!(w, r#"#else"#)?;
+        let align = format!("{}ALIGN_16", self.config().prefix.to_uppercase());
+        indented!(w, r#"#if defined(__cplusplus)"#)?;
+        indented!(w, r#"#define {} alignas(16)"#, align)?;
+        indented!(w, r#"#else"#)?;
+        indented!(w, r#"#define {} _Alignas(16)"#, align)?;
+        indented!(w, r#"#endif"#)?;
+        for primitive in &used {
+            let name = self.converter().primitive_to_typename(primitive);
+            let high = match primitive {
+                PrimitiveType::U128 => "uint64_t",
+                _ => "int64_t",
+            };
+            indented!(w, r#"typedef struct {} {{ {} uint64_t lo; {} hi; }} {};"#, name, align, high, name)?;
+        }
+        indented!(w, r#"#endif"#)?;
+        w.newline()?;
+
+        Ok(())
+    }
+
+    /// Name of the macro marking items as deprecated, e.g., `MY_LIBRARY_DEPRECATED`.
+    fn deprecated_macro_name(&self) -> String {
+        format!("{}DEPRECATED", self.config().prefix.to_uppercase())
+    }
+
+    /// Defines the deprecation macro if any item in the inventory is deprecated.
+    fn write_deprecated_define(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        if !has_deprecations(self.inventory()) {
+            return Ok(());
+        }
+
+        let name = self.deprecated_macro_name();
+
+        indented!(w, r#"#ifndef {}"#, name)?;
+        indented!(w, r#"#if defined(__GNUC__) || defined(__clang__)"#)?;
+        indented!(w, r#"#define {}(msg) __attribute__((deprecated(msg)))"#, name)?;
+        indented!(w, r#"#else"#)?;
+        indented!(w, r#"#define {}(msg)"#, name)?;
+        indented!(w, r#"#endif"#)?;
+        indented!(w, r#"#endif"#)?;
+        w.newline()?;
+
+        Ok(())
+    }
+
+    /// Returns the deprecation attribute to place after a declarator, including a leading space, or an empty string.
+    fn deprecated_attribute(&self, deprecation: &Deprecation) -> String {
+        if !deprecation.is_deprecated() {
+            return String::new();
+        }
+
+        let note = deprecation.note().unwrap_or_default().replace('\\', "\\\\").replace('"', "\\\"");
+        format!(r#" {}("{}")"#, self.deprecated_macro_name(), note)
+    }
+
     fn write_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        for constant in self.inventory().constants() {
+        for constant in self.inventory().constants().iter().filter(|x| !x.value().is_aggregate()) {
             self.write_constant(w, constant)?;
         }
 
         Ok(())
     }
 
+    /// Writes array and struct constants, which can only be defined after their types.
+    fn write_typed_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let constants = self.inventory().constants().iter().filter(|x| x.value().is_aggregate()).collect::<Vec<_>>();
+
+        for constant in &constants {
+            self.write_constant(w, constant)?;
+        }
+
+        if !constants.is_empty() {
+            w.newline()?;
+        }
+
+        Ok(())
+    }
+
     fn write_constant(&self, w: &mut IndentWriter, constant: &Constant) -> Result<(), Error> {
         let name = self.converter().const_name_to_name(constant);
-        let the_type = match constant.the_type() {
-            CType::Primitive(x) => self.converter().primitive_to_typename(&x),
-            _ => return Err(Error::Null),
-        };
 
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, constant.meta().documentation())?;
+            self.write_deprecation_documentation(w, constant.meta().deprecation())?;
         }
 
-        indented!(w, r#"const {} {} = {};"#, the_type, name, self.converter().constant_value_to_value(constant.value()))?;
+        let deprecated = self.deprecated_attribute(constant.meta().deprecation());
+        let value = self.converter().constant_value_to_value(constant.value());
+
+        match constant.value() {
+            ConstantValue::Primitive(_) => {
+                let the_type = self.converter().to_type_specifier(&constant.the_type());
+                indented!(w, r#"const {} {}{} = {};"#, the_type, name, deprecated, value)?;
+            }
+            ConstantValue::String(_) => indented!(w, r#"#define {} {}"#, name, value)?,
+            ConstantValue::Array(x, _) => {
+                let the_type = self.converter().to_type_specifier(x.array_type());
+                indented!(w, r#"static const {} {}[{}]{} = {};"#, the_type, name, x.len(), deprecated, value)?;
+            }
+            ConstantValue::Composite(_, _) => {
+                let the_type = self.converter().to_type_specifier(&constant.the_type());
+                indented!(w, r#"static const {} {}{} = {};"#, the_type, name, deprecated, value)?;
+            }
+        }
 
         Ok(())
     }
@@ -77,6 +194,8 @@ pub trait CWriter {
     fn write_function(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, function.meta().documentation())?;
+            self.write_parameter_documentation(w, function)?;
+            self.write_deprecation_documentation(w, function.meta().deprecation())?;
         }
 
         match self.config().function_style {
@@ -93,8 +212,11 @@ pub trait CWriter {
 
     fn write_function_declaration(&self, w: &mut IndentWriter, function: &Function, max_line: usize) -> Result<(), Error> {
         let attr = &self.config().function_attribute;
+        let deprecated = self.deprecated_attribute(function.meta().deprecation());
         let rval = self.converter().to_type_specifier(function.signature().rval());
-        let name = self.converter().function_name_to_c_name(function);
+        // The declaration must match the exported symbol, a renamed function becomes an alias.
+        let name = function.name();
+        let alias = self.converter().function_name_to_c_name(function);
 
         let mut params = Vec::new();
 
@@ -119,16 +241,20 @@ pub trait CWriter {
         }
 
         // Test print line to see if we need to break it
-        let line = format!(r#"{}{} {}({});"#, attr, rval, name, params.join(", "));
+        let line = format!(r#"{}{} {}({}){};"#, attr, rval, name, params.join(", "), deprecated);
 
         if line.len() <= max_line {
-            indented!(w, r#"{}{} {}({});"#, attr, rval, name, params.join(", "))?
+            indented!(w, r#"{}{} {}({}){};"#, attr, rval, name, params.join(", "), deprecated)?
         } else {
             indented!(w, r#"{}{} {}("#, attr, rval, name)?;
             for p in params {
                 indented!(w, [_], r#"{}"#, p)?;
             }
-            indented!(w, [_], r#");"#)?
+            indented!(w, [_], r#"){};"#, deprecated)?
+        }
+
+        if alias != name {
+            indented!(w, r#"#define {} {}"#, alias, name)?;
         }
 
         Ok(())
@@ -138,6 +264,7 @@ pub trait CWriter {
         let _attr = &self.config().function_attribute;
         let rval = self.converter().to_type_specifier(function.signature().rval());
         let name = self.converter().function_name_to_c_name(function);
+        let deprecated = self.deprecated_attribute(function.meta().deprecation());
 
         let mut params = Vec::new();
 
@@ -152,7 +279,7 @@ pub trait CWriter {
             }
         }
 
-        indented!(w, r#"typedef {} (*{})({});"#, rval, name, params.join(", "))?;
+        indented!(w, r#"typedef {} (*{})({}){};"#, rval, name, params.join(", "), deprecated)?;
 
         Ok(())
     }
@@ -165,6 +292,30 @@ pub trait CWriter {
         Ok(())
     }
 
+    /// Writes `@param` lines for all documented parameters.
+    fn write_parameter_documentation(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
+        for param in function.signature().params() {
+            if let Some((first, rest)) = param.documentation().lines().split_first() {
+                indented!(w, r#"/// @param {}{}"#, param.name(), first)?;
+
+                for line in rest {
+                    indented!(w, r#"///{}"#, line)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a `@deprecated` line if the item is deprecated.
+    fn write_deprecation_documentation(&self, w: &mut IndentWriter, deprecation: &Deprecation) -> Result<(), Error> {
+        match deprecation {
+            Deprecation::Deprecated(Some(note)) => indented!(w, r#"/// @deprecated {}"#, note),
+            Deprecation::Deprecated(None) => indented!(w, r#"/// @deprecated"#),
+            Deprecation::NotDeprecated => Ok(()),
+        }
+    }
+
     fn write_type_definitions(&self, w: &mut IndentWriter) -> Result<(), Error> {
         let mut known_function_pointers = vec![];
 
@@ -203,6 +354,14 @@ pub trait CWriter {
                     self.write_type_definition_named_callback(w, e)?;
                     w.newline()?;
                 }
+                TypePattern::ContextCallback(e) => {
+                    self.write_type_definition_fn_pointer(w, e.callback(), known_function_pointers)?;
+                    w.newline()?;
+                    self.write_type_definition_fn_pointer(w, e.destructor(), known_function_pointers)?;
+                    w.newline()?;
+                    self.write_type_definition_composite(w, e.composite())?;
+                    w.newline()?;
+                }
                 TypePattern::FFIErrorEnum(e) => {
                     self.write_type_definition_enum(w, e.the_enum())?;
                     w.newline()?;
@@ -277,6 +436,7 @@ pub trait CWriter {
 
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, the_type.meta().documentation())?;
+            self.write_deprecation_documentation(w, the_type.meta().deprecation())?;
         }
 
         self.write_braced_declaration_opening(w, format!("typedef enum {}", name))?;
@@ -285,7 +445,9 @@ pub trait CWriter {
             self.write_type_definition_enum_variant(w, variant, the_type)?;
         }
 
-        self.write_braced_declaration_closing(w, name)
+        let deprecated = self.deprecated_attribute(the_type.meta().deprecation());
+
+        self.write_braced_declaration_closing(w, format!("{}{}", name, deprecated))
     }
 
     fn write_type_definition_enum_variant(&self, w: &mut IndentWriter, variant: &Variant, the_enum: &EnumType) -> Result<(), Error> {
@@ -293,15 +455,28 @@ pub trait CWriter {
         let variant_value = variant.value();
 
         if self.config().documentation == CDocumentationStyle::Inline {
-            self.write_documentation(w, variant.documentation())?
+            self.write_documentation(w, variant.documentation())?;
+            self.write_deprecation_documentation(w, variant.deprecation())?;
         }
 
-        indented!(w, r#"{} = {},"#, variant_name, variant_value)
+        let deprecated = self.deprecated_attribute(variant.deprecation());
+
+        indented!(w, r#"{}{} = {},"#, variant_name, deprecated, variant_value)
     }
 
     fn write_type_definition_opaque(&self, w: &mut IndentWriter, the_type: &OpaqueType) -> Result<(), Error> {
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, the_type.meta().documentation())?;
+            self.write_deprecation_documentation(w, the_type.meta().deprecation())?;
+
+            // Services note whether their instances may be used across threads.
+            let service = self.inventory().patterns().iter().find_map(|x| match x {
+                LibraryPattern::Service(x) => Some(x).filter(|x| x.the_type() == the_type),
+            });
+
+            if let Some(service) = service {
+                indented!(w, r#"/// {}"#, service.thread_safety().description())?;
+            }
         }
 
         self.write_type_definition_opaque_body(w, the_type)?;
@@ -315,29 +490,84 @@ pub trait CWriter {
 
     fn write_type_definition_opaque_body(&self, w: &mut IndentWriter, the_type: &OpaqueType) -> Result<(), Error> {
         let name = self.converter().opaque_to_typename(the_type);
-        indented!(w, r#"typedef struct {} {};"#, name, name)
+        let deprecated = self.deprecated_attribute(the_type.meta().deprecation());
+        indented!(w, r#"typedef struct {} {}{};"#, name, name, deprecated)
     }
 
     fn write_type_definition_composite(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, the_type.meta().documentation())?;
+            self.write_deprecation_documentation(w, the_type.meta().deprecation())?;
         }
 
         let name = self.converter().composite_to_typename(the_type);
 
         if the_type.is_empty() {
             // C doesn't allow us writing empty structs.
-            indented!(w, r#"typedef struct {} {};"#, name, name)?;
+            let deprecated = self.deprecated_attribute(the_type.meta().deprecation());
+            indented!(w, r#"typedef struct {} {}{};"#, name, name, deprecated)?;
             Ok(())
         } else {
-            self.write_type_definition_composite_body(w, the_type)
+            self.write_type_definition_composite_body(w, the_type)?;
+
+            if self.config().layout_asserts {
+                self.write_type_definition_composite_layout_asserts(w, the_type)?;
+            }
+
+            Ok(())
         }
     }
 
+    fn write_type_definition_composite_layout_asserts(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
+        let Some(layout) = the_type.memory_layout() else {
+            return Ok(());
+        };
+
+        // Packing is only emitted for an explicit alignment, otherwise the C layout of packed types differs.
+        if the_type.repr().layout() == Layout::Packed && the_type.repr().alignment().is_none() {
+            return Ok(());
+        }
+
+        let name = self.converter().composite_to_typename(the_type);
+
+        // Taking the `offsetof` of deprecated fields would otherwise warn in every translation unit.
+        let has_deprecated_fields = the_type.fields().iter().any(|x| x.deprecation().is_deprecated());
+
+        if has_deprecated_fields {
+            indented!(w, r#"#if defined(__GNUC__) || defined(__clang__)"#)?;
+            indented!(w, r#"#pragma GCC diagnostic push"#)?;
+            indented!(w, r#"#pragma GCC diagnostic ignored "-Wdeprecated-declarations""#)?;
+            indented!(w, r#"#endif"#)?;
+        }
+
+        indented!(w, r#"_Static_assert(sizeof({}) == {}, "Size of {} differs from Rust.");"#, name, layout.size(), name)?;
+
+        for (field, offset) in the_type.fields().iter().zip(layout.field_offsets()) {
+            indented!(
+                w,
+                r#"_Static_assert(offsetof({}, {}) == {}, "Offset of {}.{} differs from Rust.");"#,
+                name,
+                field.name(),
+                offset,
+                name,
+                field.name()
+            )?;
+        }
+
+        if has_deprecated_fields {
+            indented!(w, r#"#if defined(__GNUC__) || defined(__clang__)"#)?;
+            indented!(w, r#"#pragma GCC diagnostic pop"#)?;
+            indented!(w, r#"#endif"#)?;
+        }
+
+        Ok(())
+    }
+
     fn write_type_definition_composite_body(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
         let name = self.converter().composite_to_typename(the_type);
 
         let alignment = the_type.repr().alignment();
+
         if let Some(align) = alignment {
             indented!(w, "#pragma pack(push, {})", align)?;
         }
@@ -348,7 +578,9 @@ pub trait CWriter {
             self.write_type_definition_composite_body_field(w, field, the_type)?;
         }
 
-        self.write_braced_declaration_closing(w, name)?;
+        let deprecated = self.deprecated_attribute(the_type.meta().deprecation());
+
+        self.write_braced_declaration_closing(w, format!("{}{}", name, deprecated))?;
 
         if alignment.is_some() {
             indented!(w, "#pragma pack(pop)")?;
@@ -359,18 +591,21 @@ pub trait CWriter {
     fn write_type_definition_composite_body_field(&self, w: &mut IndentWriter, field: &Field, _the_type: &CompositeType) -> Result<(), Error> {
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, field.documentation())?;
+            self.write_deprecation_documentation(w, field.deprecation())?;
         }
 
+        let deprecated = self.deprecated_attribute(field.deprecation());
+
         match field.the_type() {
             CType::Array(x) => {
                 let field_name = field.name();
                 let type_name = self.converter().to_type_specifier(x.array_type());
-                indented!(w, r#"{} {}[{}];"#, type_name, field_name, x.len())
+                indented!(w, r#"{} {}[{}]{};"#, type_name, field_name, x.len(), deprecated)
             }
             _ => {
                 let field_name = field.name();
                 let type_name = self.converter().to_type_specifier(field.the_type());
-                indented!(w, r#"{} {};"#, type_name, field_name)
+                indented!(w, r#"{} {}{};"#, type_name, field_name, deprecated)
             }
         }
     }
@@ -425,12 +660,17 @@ pub trait CWriter {
                 self.write_custom_defines(w)?;
                 w.newline()?;
 
+                self.write_deprecated_define(w)?;
+                self.write_int128_typedefs(w)?;
+
                 self.write_constants(w)?;
                 w.newline()?;
 
                 self.write_type_definitions(w)?;
                 w.newline()?;
 
+                self.write_typed_constants(w)?;
+
                 self.write_functions(w)?;
 
                 Ok(())
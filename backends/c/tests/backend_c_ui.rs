@@ -1,7 +1,15 @@
+use interoptopus::conformance::TestSuite;
+use interoptopus::lang::c::{
+    CType, CompositeType, Deprecation, Documentation, EnumType, Field, Function, FunctionSignature, Meta, OpaqueType, Parameter, Representation, Variant,
+};
 use interoptopus::testing::assert_file_matches_generated;
+use interoptopus::writer::OutputSet;
 use interoptopus::Error;
-use interoptopus::Interop;
-use interoptopus_backend_c::{compile_c_app_if_installed, CDocumentationStyle, CFunctionStyle, CIndentationStyle, CNamingStyle, Config, Generator};
+use interoptopus::{Interop, InventoryBuilder, Symbol};
+use interoptopus_backend_c::{
+    compile_c_app_if_installed, CDocumentationStyle, CFunctionStyle, CIndentationStyle, CNamingStyle, Config, ConformanceConfig, ConformanceGenerator, DocGenerator,
+    Generator,
+};
 use std::path::Path;
 
 fn nodocs_config() -> Config {
@@ -26,6 +34,15 @@ fn docs_inline_config(style: CFunctionStyle) -> Config {
     }
 }
 
+fn layout_asserts_config() -> Config {
+    Config {
+        prefix: "my_library_".to_string(),
+        documentation: CDocumentationStyle::None,
+        layout_asserts: true,
+        ..Config::default()
+    }
+}
+
 fn generate_bindings_multi(folder: impl AsRef<Path>, config: Option<Config>) -> Result<(), Error> {
     let config = config.unwrap_or_default();
 
@@ -39,15 +56,23 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, config: Option<Config>) ->
     Ok(())
 }
 
-// fn generate_documentation(output: &str, config: Option<Config>) -> Result<(), Error> {
-//     let config = config.unwrap_or(Config::default());
-//     let inventory = interoptopus_reference_project::ffi_inventory();
-//     let generator = Generator::new(config, inventory);
-//     let doc_gen = DocGenerator::new(library, generator);
-//     doc_gen.write_file(format!("{}/my_header.md", output))?;
-//
-//     Ok(())
-// }
+fn generate_conformance(folder: &str) -> Result<(), Error> {
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let suite = TestSuite::from_inventory(&inventory, &interoptopus_reference_project::ffi_recorder());
+    let generator = Generator::new(nodocs_config(), inventory);
+    let config = ConformanceConfig {
+        header: "my_header.h".to_string(),
+    };
+
+    ConformanceGenerator::new(&generator, &suite, config).write_file(format!("{}/conformance.c", folder))
+}
+
+fn generate_documentation(output: &str) -> Result<(), Error> {
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let generator = Generator::new(nodocs_config(), inventory.clone());
+
+    DocGenerator::new(inventory, generator).write_file(output)
+}
 
 #[test]
 #[cfg_attr(miri, ignore)]
@@ -55,10 +80,15 @@ fn bindings_match_reference() -> Result<(), Error> {
     generate_bindings_multi("tests/output_nodocs/", Some(nodocs_config()))?;
     generate_bindings_multi("tests/output_docs_inline/", Some(docs_inline_config(CFunctionStyle::ForwardDeclarations)))?;
     generate_bindings_multi("tests/output_typedefs/", Some(docs_inline_config(CFunctionStyle::Typedefs)))?;
+    generate_bindings_multi("tests/output_layout_asserts/", Some(layout_asserts_config()))?;
+    generate_conformance("tests/output_nodocs")?;
+    generate_documentation("tests/output_nodocs/my_header.md")?;
 
     assert_file_matches_generated("tests/output_nodocs/my_header.h");
+    assert_file_matches_generated("tests/output_nodocs/conformance.c");
     assert_file_matches_generated("tests/output_docs_inline/my_header.h");
     assert_file_matches_generated("tests/output_typedefs/my_header.h");
+    assert_file_matches_generated("tests/output_layout_asserts/my_header.h");
 
     Ok(())
 }
@@ -69,9 +99,47 @@ fn bindings_work() -> Result<(), Error> {
     generate_bindings_multi("tests/output_nodocs/", Some(nodocs_config()))?;
     generate_bindings_multi("tests/output_docs_inline/", Some(docs_inline_config(CFunctionStyle::ForwardDeclarations)))?;
     generate_bindings_multi("tests/output_typedefs/", Some(docs_inline_config(CFunctionStyle::Typedefs)))?;
+    generate_bindings_multi("tests/output_layout_asserts/", Some(layout_asserts_config()))?;
 
     compile_c_app_if_installed("tests/output_nodocs/", "tests/output_nodocs/app.c")?;
     compile_c_app_if_installed("tests/output_docs_inline/", "tests/output_docs_inline/app.c")?;
     compile_c_app_if_installed("tests/output_typedefs/", "tests/output_typedefs/app.c")?;
+    compile_c_app_if_installed("tests/output_layout_asserts/", "tests/output_layout_asserts/app.c")?;
+    Ok(())
+}
+
+#[test]
+fn deprecated_types_get_attribute() -> Result<(), Error> {
+    let deprecated = |note: &str| Meta::new().with_deprecation(Deprecation::Deprecated(Some(note.to_string())));
+
+    let opaque = OpaqueType::new("Opaque".to_string(), deprecated("Use `Other` instead."));
+    let composite = CompositeType::with_meta(
+        "Composite".to_string(),
+        vec![Field::new("x".to_string(), CType::ReadPointer(Box::new(CType::Opaque(opaque))))],
+        deprecated("Gone soon."),
+    );
+    let variants = vec![Variant::new("A".to_string(), 0, Documentation::new())];
+    let the_enum = EnumType::new("Enum".to_string(), variants, deprecated("Legacy."), Representation::default());
+    let params = vec![Parameter::new("x".to_string(), CType::Composite(composite))];
+    let function = Function::new("f".to_string(), FunctionSignature::new(params, CType::Enum(the_enum)), deprecated("Use `g` instead."));
+    let inventory = InventoryBuilder::new().register(Symbol::Function(function)).inventory();
+
+    let configs = [
+        nodocs_config(),
+        docs_inline_config(CFunctionStyle::ForwardDeclarations),
+        docs_inline_config(CFunctionStyle::Typedefs),
+    ];
+
+    for config in configs {
+        let mut output = OutputSet::new();
+        Generator::new(config, inventory.clone()).write_output_set(&mut output, "my_header.h")?;
+        let header = output.get("my_header.h").expect("header");
+
+        assert!(header.contains(r#"MY_LIBRARY_DEPRECATED("Use `Other` instead.");"#));
+        assert!(header.contains(r#"MY_LIBRARY_DEPRECATED("Gone soon.");"#));
+        assert!(header.contains(r#"MY_LIBRARY_DEPRECATED("Legacy.");"#));
+        assert!(header.contains(r#"MY_LIBRARY_DEPRECATED("Use `g` instead.");"#));
+    }
+
     Ok(())
 }
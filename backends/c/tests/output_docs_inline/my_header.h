@@ -9,12 +9,45 @@ extern "C" {
 
 #include <stdint.h>
 #include <stdbool.h>
+#include <stddef.h>
 
 
 
+#ifndef MY_LIBRARY_DEPRECATED
+#if defined(__GNUC__) || defined(__clang__)
+#define MY_LIBRARY_DEPRECATED(msg) __attribute__((deprecated(msg)))
+#else
+#define MY_LIBRARY_DEPRECATED(msg)
+#endif
+#endif
+
+#if defined(__SIZEOF_INT128__)
+typedef unsigned __int128 my_library_u128;
+typedef __int128 my_library_i128;
+#else
+#if defined(__cplusplus)
+#define MY_LIBRARY_ALIGN_16 alignas(16)
+#else
+#define MY_LIBRARY_ALIGN_16 _Alignas(16)
+#endif
+typedef struct my_library_u128 { MY_LIBRARY_ALIGN_16 uint64_t lo; uint64_t hi; } my_library_u128;
+typedef struct my_library_i128 { MY_LIBRARY_ALIGN_16 uint64_t lo; int64_t hi; } my_library_i128;
+#endif
+
 const uint8_t MY_LIBRARY_U8 = 255;
 const float MY_LIBRARY_F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944;
 const int32_t MY_LIBRARY_COMPUTED_I32 = -2147483647;
+/// Version string of the reference project.
+#define MY_LIBRARY_VERSION "1.2.3 \"reference\""
+const uint32_t MY_LIBRARY_NAMESPACED = 42;
+
+/// Enum with a deprecated variant.
+typedef enum my_library_enum_deprecated
+{
+    MY_LIBRARY_ENUM_DEPRECATED_CURRENT = 0,
+    /// @deprecated Use `Current` instead.
+    MY_LIBRARY_ENUM_DEPRECATED_LEGACY MY_LIBRARY_DEPRECATED("Use `Current` instead.") = 1,
+} my_library_enum_deprecated;
 
 /// Documented enum.
 typedef enum my_library_enum_documented
@@ -38,11 +71,29 @@ typedef struct my_library_generic3 my_library_generic3;
 
 typedef struct my_library_generic4 my_library_generic4;
 
+/// A reference counted service, instances of which can be shared between owners.
+/// Thread safe, instances may be used from multiple threads at the same time.
+typedef struct my_library_shared_service my_library_shared_service;
+
 /// Some struct we want to expose as a class.
+/// Thread safe, instances may be used from multiple threads at the same time.
 typedef struct my_library_simple_service my_library_simple_service;
 
+/// A service without constructors, only created by [`SimpleService::create_child`].
+/// Thread safe, instances may be used from multiple threads at the same time.
+typedef struct my_library_simple_service_child my_library_simple_service_child;
+
+/// Thread safe, instances may be used from multiple threads at the same time.
 typedef struct my_library_simple_service_lifetime my_library_simple_service_lifetime;
 
+/// A service whose functions and class are emitted into the `common` namespace.
+/// Thread safe, instances may be used from multiple threads at the same time.
+typedef struct my_library_simple_service_namespaced my_library_simple_service_namespaced;
+
+/// A service neither `Send` nor `Sync`, which must stay on the thread that created it.
+/// Instances must only be used from the thread which created them.
+typedef struct my_library_single_threaded_service my_library_single_threaded_service;
+
 typedef enum my_library_ffi_error
 {
     MY_LIBRARY_FFI_ERROR_OK = 0,
@@ -102,6 +153,14 @@ typedef struct my_library_phantomu8
     uint32_t x;
 } my_library_phantomu8;
 
+/// Struct with a deprecated field.
+typedef struct my_library_struct_deprecated
+{
+    uint32_t x;
+    /// @deprecated Use `x` instead.
+    uint32_t old_x MY_LIBRARY_DEPRECATED("Use `x` instead.");
+} my_library_struct_deprecated;
+
 /// Documented struct.
 typedef struct my_library_struct_documented
 {
@@ -119,6 +178,17 @@ typedef struct my_library_tupled
     uint8_t x0;
 } my_library_tupled;
 
+///Size and alignment of a single type as laid out by Rust.
+typedef struct my_library_type_layout
+{
+    ///Hash of the type's namespace and name.
+    uint64_t name_hash;
+    ///Size in bytes.
+    uint64_t size;
+    ///Alignment in bytes.
+    uint64_t align;
+} my_library_type_layout;
+
 typedef struct my_library_use_ascii_string_pattern
 {
     const char* ascii_string;
@@ -161,6 +231,11 @@ typedef struct my_library_visibility2
     uint8_t pblc2;
 } my_library_visibility2;
 
+typedef struct my_library_visibility_crate
+{
+    uint8_t x;
+} my_library_visibility_crate;
+
 typedef struct my_library_weird1u32
 {
     uint32_t x;
@@ -172,6 +247,8 @@ typedef uint8_t (*my_library_callback_u8)(uint8_t value);
 
 typedef uint32_t (*my_library_my_callback)(uint32_t value);
 
+typedef void (*my_library_my_callback_completion)(uint32_t value);
+
 typedef uint32_t (*my_library_my_callback_namespaced)(uint32_t value);
 
 typedef void (*my_library_sum_delegate1)();
@@ -182,6 +259,17 @@ typedef my_library_ffi_error (*my_library_sum_delegate_return)(int32_t x, int32_
 
 typedef void (*my_library_sum_delegate_return2)(int32_t x, int32_t y);
 
+typedef uint32_t (*my_library_fptr_fn_u32_ConstPtr_rval_u32)(uint32_t x0, const void* x1);
+
+typedef void (*my_library_fptr_fn_ConstPtr)(const void* x0);
+
+typedef struct my_library_my_closure
+{
+    my_library_fptr_fn_u32_ConstPtr_rval_u32 callback;
+    const void* context;
+    my_library_fptr_fn_ConstPtr destructor;
+} my_library_my_closure;
+
 typedef struct my_library_array
 {
     uint8_t data[16];
@@ -300,6 +388,15 @@ typedef struct my_library_delegate_callback_my_callback_contextual
     const void* context;
 } my_library_delegate_callback_my_callback_contextual;
 
+///A pointer to an array of data someone else owns which may not be modified.
+typedef struct my_library_slice_type_layout
+{
+    ///Pointer to start of immutable data.
+    const my_library_type_layout* data;
+    ///Number of elements.
+    uint64_t len;
+} my_library_slice_type_layout;
+
 ///A pointer to an array of data someone else owns which may not be modified.
 typedef struct my_library_slice_use_ascii_string_pattern
 {
@@ -343,6 +440,9 @@ typedef void (*my_library_callback_slice_mut)(my_library_slice_mut_u8 slice);
 typedef my_library_vec3f32 (*my_library_callback_huge_vec_slice)(my_library_slice_vec3f32 slice);
 
 
+static const uint8_t MY_LIBRARY_MAGIC[4] = { 73, 79, 80, 83 };
+static const my_library_vec3f32 MY_LIBRARY_UP = { 0, 1, 0 };
+
 void primitive_void();
 
 void primitive_void2();
@@ -365,6 +465,17 @@ int32_t primitive_i32(int32_t x);
 
 int64_t primitive_i64(int64_t x);
 
+size_t primitive_usize(size_t x);
+
+intptr_t primitive_isize(intptr_t x);
+
+my_library_u128 primitive_u128(my_library_u128 x);
+
+my_library_i128 primitive_i128(my_library_i128 x);
+
+/// Returns the Unicode scalar value of `x`, or `u32::MAX` if `x` is not a valid `char`.
+uint32_t primitive_char(uint32_t x);
+
 my_library_boolean_alignment boolean_alignment(my_library_boolean_alignment x);
 
 my_library_boolean_alignment boolean_alignment2(bool rval);
@@ -413,8 +524,14 @@ uint8_t generic_4(const my_library_generic4* x);
 uint8_t array_1(my_library_array x);
 
 /// This function has documentation.
+/// @param x A documented struct, its
+/// fields are documented as well.
 my_library_enum_documented documented(my_library_struct_documented x);
 
+/// Superseded by `documented`.
+/// @deprecated Use `documented` instead.
+my_library_enum_deprecated deprecated(my_library_struct_deprecated x) MY_LIBRARY_DEPRECATED("Use `documented` instead.");
+
 my_library_vec1 ambiguous_1(my_library_vec1 x);
 
 my_library_vec2 ambiguous_2(my_library_vec2 x);
@@ -429,6 +546,19 @@ my_library_slice_vec namespaced_inner_slice(my_library_slice_vec x);
 
 my_library_slice_mut_vec namespaced_inner_slice_mut(my_library_slice_mut_vec x);
 
+/// Emitted into the `common` namespace.
+double namespaced_function(my_library_vec x);
+
+/// Named after a C# keyword, which uses another name instead.
+uint32_t checked(uint32_t x);
+
+/// Exported as `renamed_symbol`, aliased in C and not emitted for Python.
+uint32_t renamed_symbol(uint32_t x);
+#define renamed_export renamed_symbol
+
+/// Not emitted for C#, whose API guard must still match the full inventory.
+uint32_t skipped_in_csharp(uint32_t x);
+
 my_library_ffi_error panics();
 
 my_library_enum_renamed renamed(my_library_struct_renamed x);
@@ -439,6 +569,8 @@ bool weird_1(my_library_weird1u32 x, my_library_weird2u8 y);
 
 void visibility(my_library_visibility1 x, my_library_visibility2 y);
 
+uint8_t visibility_crate(my_library_visibility_crate x);
+
 my_library_tupled repr_transparent(my_library_tupled x, const my_library_tupled* r);
 
 uint32_t pattern_ascii_pointer_1(const char* x);
@@ -483,6 +615,8 @@ char* pattern_ffi_cchar_mut_pointer(char* ffi_cchar);
 
 uint64_t pattern_api_guard();
 
+my_library_slice_type_layout pattern_layout_guard();
+
 uint32_t pattern_callback_1(my_library_my_callback callback, uint32_t x);
 
 my_library_my_callback_void pattern_callback_2(my_library_my_callback_void callback);
@@ -497,9 +631,15 @@ my_library_sum_delegate2 pattern_callback_6();
 
 my_library_ffi_error pattern_callback_7(my_library_sum_delegate_return c1, my_library_sum_delegate_return2 c2, int32_t x, int32_t i, int32_t* o);
 
+/// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+my_library_ffi_error pattern_callback_8(uint32_t x, my_library_my_callback_completion completion);
+
+/// Invokes `callback` once; its destructor runs when the callback is dropped on return.
+uint32_t pattern_callback_9(my_library_my_closure callback, uint32_t x);
+
 void pattern_surrogates_1(my_library_local s, my_library_container* c);
 
-/// Destroys the given instance.
+///Destroys the given instance.
 ///
 /// # Safety
 ///
@@ -508,6 +648,7 @@ void pattern_surrogates_1(my_library_local s, my_library_container* c);
 my_library_ffi_error simple_service_destroy(my_library_simple_service** context);
 
 /// The constructor must return a `Result<Self, Error>`.
+/// @param some_value Stored in the service.
 my_library_ffi_error simple_service_new_with(my_library_simple_service** context, uint32_t some_value);
 
 my_library_ffi_error simple_service_new_without(my_library_simple_service** context);
@@ -516,6 +657,12 @@ my_library_ffi_error simple_service_new_with_string(my_library_simple_service**
 
 my_library_ffi_error simple_service_new_failing(my_library_simple_service** context, uint8_t some_value);
 
+/// The value this service was created with.
+uint32_t simple_service_get_value(const my_library_simple_service* context);
+
+/// Setters may fail, which raises an exception in languages with properties.
+my_library_ffi_error simple_service_set_value(my_library_simple_service* context, uint32_t value);
+
 /// Methods returning a Result<(), _> are the default and do not
 /// need annotations.
 my_library_ffi_error simple_service_method_result(const my_library_simple_service* context, uint32_t anon1);
@@ -530,6 +677,9 @@ void simple_service_method_void(const my_library_simple_service* context);
 /// Regular void functions don't need an annotation.
 void simple_service_method_void2(const my_library_simple_service* context);
 
+/// @deprecated Use `method_void` instead.
+void simple_service_method_void_deprecated(const my_library_simple_service* context) MY_LIBRARY_DEPRECATED("Use `method_void` instead.");
+
 uint8_t simple_service_method_mut_self(my_library_simple_service* context, my_library_slice_u8 slice);
 
 /// Single line.
@@ -565,7 +715,30 @@ my_library_ffi_error simple_service_method_callback_ffi_return(my_library_simple
 
 my_library_ffi_error simple_service_method_callback_ffi_return_with_slice(my_library_simple_service* context, my_library_sum_delegate_return callback, my_library_slice_i32 input);
 
-/// Destroys the given instance.
+my_library_ffi_error simple_service_method_callback_completion(my_library_simple_service* context, uint32_t x, my_library_my_callback_completion completion);
+
+/// Doesn't need an instance, e.g., to query capabilities before construction.
+uint32_t simple_service_supported_formats();
+
+/// Static methods can fail like any other method.
+/// @param value Must not be `0`.
+my_library_ffi_error simple_service_validate(uint32_t value);
+
+/// Creates another service, which is returned as a fully wrapped object.
+/// @param offset Added to this service's value.
+my_library_ffi_error simple_service_create_child(const my_library_simple_service* context, my_library_simple_service_child** instance, uint32_t offset);
+
+///Destroys the given instance.
+///
+/// # Safety
+///
+/// The passed parameter MUST have been created with the corresponding init function;
+/// passing any other value results in undefined behavior.
+my_library_ffi_error simple_service_child_destroy(my_library_simple_service_child** context);
+
+uint32_t simple_service_child_value(const my_library_simple_service_child* context);
+
+///Destroys the given instance.
 ///
 /// # Safety
 ///
@@ -583,6 +756,52 @@ const char* simple_service_lifetime_return_string_accept_slice(my_library_simple
 
 my_library_ffi_error simple_service_lifetime_method_void_ffi_error(my_library_simple_service_lifetime* context);
 
+///Destroys the given instance.
+///
+/// # Safety
+///
+/// The passed parameter MUST have been created with the corresponding init function;
+/// passing any other value results in undefined behavior.
+my_library_ffi_error simple_service_namespaced_destroy(my_library_simple_service_namespaced** context);
+
+my_library_ffi_error simple_service_namespaced_new_with(my_library_simple_service_namespaced** context, uint32_t value);
+
+uint32_t simple_service_namespaced_value(const my_library_simple_service_namespaced* context);
+
+///Releases a reference to the given instance, destroying it once no references remain.
+///
+/// # Safety
+///
+/// The passed parameter MUST have been created with the corresponding init function;
+/// passing any other value results in undefined behavior.
+my_library_ffi_error shared_service_release(my_library_shared_service** context);
+
+my_library_ffi_error shared_service_new_with(my_library_shared_service** context, uint32_t value);
+
+uint32_t shared_service_value(const my_library_shared_service* context);
+
+/// Adds a reference to the given instance, which must be released separately.
+///
+/// # Safety
+///
+/// The passed parameter MUST have been created with the corresponding init function
+/// and not been released yet; passing any other value results in undefined behavior.
+my_library_ffi_error shared_service_retain(const my_library_shared_service* context);
+
+///Destroys the given instance.
+///
+/// # Safety
+///
+/// The passed parameter MUST have been created with the corresponding init function;
+/// passing any other value results in undefined behavior.
+my_library_ffi_error single_threaded_service_destroy(my_library_single_threaded_service** context);
+
+my_library_ffi_error single_threaded_service_new_with(my_library_single_threaded_service** context, uint32_t value);
+
+my_library_ffi_error single_threaded_service_increment(const my_library_single_threaded_service* context);
+
+uint32_t single_threaded_service_value(const my_library_single_threaded_service* context);
+
 
 #ifdef __cplusplus
 }
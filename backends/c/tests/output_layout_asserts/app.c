@@ -0,0 +1,7 @@
+#include <stdio.h>
+
+#include "my_header.h"
+
+int main(int argc, char *argv[]) {
+    printf("C compiled.\n");
+}
\ No newline at end of file
@@ -0,0 +1,639 @@
+// Automatically generated by Interoptopus.
+
+#ifndef interoptopus_generated
+#define interoptopus_generated
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#include <stdint.h>
+#include <stdbool.h>
+#include <stddef.h>
+
+
+
+#ifndef MY_LIBRARY_DEPRECATED
+#if defined(__GNUC__) || defined(__clang__)
+#define MY_LIBRARY_DEPRECATED(msg) __attribute__((deprecated(msg)))
+#else
+#define MY_LIBRARY_DEPRECATED(msg)
+#endif
+#endif
+
+#if defined(__SIZEOF_INT128__)
+typedef unsigned __int128 my_library_u128;
+typedef __int128 my_library_i128;
+#else
+#if defined(__cplusplus)
+#define MY_LIBRARY_ALIGN_16 alignas(16)
+#else
+#define MY_LIBRARY_ALIGN_16 _Alignas(16)
+#endif
+typedef struct my_library_u128 { MY_LIBRARY_ALIGN_16 uint64_t lo; uint64_t hi; } my_library_u128;
+typedef struct my_library_i128 { MY_LIBRARY_ALIGN_16 uint64_t lo; int64_t hi; } my_library_i128;
+#endif
+
+const uint8_t MY_LIBRARY_U8 = 255;
+const float MY_LIBRARY_F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944;
+const int32_t MY_LIBRARY_COMPUTED_I32 = -2147483647;
+#define MY_LIBRARY_VERSION "1.2.3 \"reference\""
+const uint32_t MY_LIBRARY_NAMESPACED = 42;
+
+typedef enum my_library_enumdeprecated
+    {
+    MY_LIBRARY_ENUMDEPRECATED_CURRENT = 0,
+    MY_LIBRARY_ENUMDEPRECATED_LEGACY MY_LIBRARY_DEPRECATED("Use `Current` instead.") = 1,
+    } my_library_enumdeprecated;
+
+typedef enum my_library_enumdocumented
+    {
+    MY_LIBRARY_ENUMDOCUMENTED_A = 0,
+    MY_LIBRARY_ENUMDOCUMENTED_B = 1,
+    MY_LIBRARY_ENUMDOCUMENTED_C = 2,
+    } my_library_enumdocumented;
+
+typedef enum my_library_enumrenamed
+    {
+    MY_LIBRARY_ENUMRENAMED_X = 0,
+    } my_library_enumrenamed;
+
+typedef struct my_library_generic2u8 my_library_generic2u8;
+typedef struct my_library_generic3 my_library_generic3;
+typedef struct my_library_generic4 my_library_generic4;
+typedef struct my_library_sharedservice my_library_sharedservice;
+typedef struct my_library_simpleservice my_library_simpleservice;
+typedef struct my_library_simpleservicechild my_library_simpleservicechild;
+typedef struct my_library_simpleservicelifetime my_library_simpleservicelifetime;
+typedef struct my_library_simpleservicenamespaced my_library_simpleservicenamespaced;
+typedef struct my_library_singlethreadedservice my_library_singlethreadedservice;
+typedef enum my_library_ffierror
+    {
+    MY_LIBRARY_FFIERROR_OK = 0,
+    MY_LIBRARY_FFIERROR_NULL = 100,
+    MY_LIBRARY_FFIERROR_PANIC = 200,
+    MY_LIBRARY_FFIERROR_DELEGATE = 300,
+    MY_LIBRARY_FFIERROR_FAIL = 400,
+    } my_library_ffierror;
+
+typedef struct my_library_booleanalignment
+    {
+    int32_t a;
+    int16_t b;
+    int16_t c;
+    uint8_t d;
+    uint8_t e;
+    uint8_t f;
+    uint8_t g;
+    uint8_t h;
+    uint8_t i;
+    uint8_t j;
+    uint8_t k;
+    uint64_t id;
+    bool is_valid;
+    uint64_t datum;
+    } my_library_booleanalignment;
+_Static_assert(sizeof(my_library_booleanalignment) == 40, "Size of my_library_booleanalignment differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, a) == 0, "Offset of my_library_booleanalignment.a differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, b) == 4, "Offset of my_library_booleanalignment.b differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, c) == 6, "Offset of my_library_booleanalignment.c differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, d) == 8, "Offset of my_library_booleanalignment.d differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, e) == 9, "Offset of my_library_booleanalignment.e differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, f) == 10, "Offset of my_library_booleanalignment.f differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, g) == 11, "Offset of my_library_booleanalignment.g differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, h) == 12, "Offset of my_library_booleanalignment.h differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, i) == 13, "Offset of my_library_booleanalignment.i differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, j) == 14, "Offset of my_library_booleanalignment.j differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, k) == 15, "Offset of my_library_booleanalignment.k differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, id) == 16, "Offset of my_library_booleanalignment.id differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, is_valid) == 24, "Offset of my_library_booleanalignment.is_valid differs from Rust.");
+_Static_assert(offsetof(my_library_booleanalignment, datum) == 32, "Offset of my_library_booleanalignment.datum differs from Rust.");
+
+typedef struct my_library_extratypef32
+    {
+    float x;
+    } my_library_extratypef32;
+_Static_assert(sizeof(my_library_extratypef32) == 4, "Size of my_library_extratypef32 differs from Rust.");
+_Static_assert(offsetof(my_library_extratypef32, x) == 0, "Offset of my_library_extratypef32.x differs from Rust.");
+
+typedef struct my_library_inner
+    {
+    float x;
+    } my_library_inner;
+_Static_assert(sizeof(my_library_inner) == 4, "Size of my_library_inner differs from Rust.");
+_Static_assert(offsetof(my_library_inner, x) == 0, "Offset of my_library_inner.x differs from Rust.");
+
+typedef struct my_library_local
+    {
+    uint32_t x;
+    } my_library_local;
+_Static_assert(sizeof(my_library_local) == 4, "Size of my_library_local differs from Rust.");
+_Static_assert(offsetof(my_library_local, x) == 0, "Offset of my_library_local.x differs from Rust.");
+
+typedef struct my_library_packed1
+    {
+    uint8_t x;
+    uint16_t y;
+    } my_library_packed1;
+
+typedef struct my_library_packed2
+    {
+    uint16_t y;
+    uint8_t x;
+    } my_library_packed2;
+
+typedef struct my_library_phantomu8
+    {
+    uint32_t x;
+    } my_library_phantomu8;
+_Static_assert(sizeof(my_library_phantomu8) == 4, "Size of my_library_phantomu8 differs from Rust.");
+_Static_assert(offsetof(my_library_phantomu8, x) == 0, "Offset of my_library_phantomu8.x differs from Rust.");
+
+typedef struct my_library_structdeprecated
+    {
+    uint32_t x;
+    uint32_t old_x MY_LIBRARY_DEPRECATED("Use `x` instead.");
+    } my_library_structdeprecated;
+#if defined(__GNUC__) || defined(__clang__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wdeprecated-declarations"
+#endif
+_Static_assert(sizeof(my_library_structdeprecated) == 8, "Size of my_library_structdeprecated differs from Rust.");
+_Static_assert(offsetof(my_library_structdeprecated, x) == 0, "Offset of my_library_structdeprecated.x differs from Rust.");
+_Static_assert(offsetof(my_library_structdeprecated, old_x) == 4, "Offset of my_library_structdeprecated.old_x differs from Rust.");
+#if defined(__GNUC__) || defined(__clang__)
+#pragma GCC diagnostic pop
+#endif
+
+typedef struct my_library_structdocumented
+    {
+    float x;
+    } my_library_structdocumented;
+_Static_assert(sizeof(my_library_structdocumented) == 4, "Size of my_library_structdocumented differs from Rust.");
+_Static_assert(offsetof(my_library_structdocumented, x) == 0, "Offset of my_library_structdocumented.x differs from Rust.");
+
+typedef struct my_library_structrenamed
+    {
+    my_library_enumrenamed e;
+    } my_library_structrenamed;
+_Static_assert(sizeof(my_library_structrenamed) == 4, "Size of my_library_structrenamed differs from Rust.");
+_Static_assert(offsetof(my_library_structrenamed, e) == 0, "Offset of my_library_structrenamed.e differs from Rust.");
+
+typedef struct my_library_tupled
+    {
+    uint8_t x0;
+    } my_library_tupled;
+_Static_assert(sizeof(my_library_tupled) == 1, "Size of my_library_tupled differs from Rust.");
+_Static_assert(offsetof(my_library_tupled, x0) == 0, "Offset of my_library_tupled.x0 differs from Rust.");
+
+typedef struct my_library_typelayout
+    {
+    uint64_t name_hash;
+    uint64_t size;
+    uint64_t align;
+    } my_library_typelayout;
+_Static_assert(sizeof(my_library_typelayout) == 24, "Size of my_library_typelayout differs from Rust.");
+_Static_assert(offsetof(my_library_typelayout, name_hash) == 0, "Offset of my_library_typelayout.name_hash differs from Rust.");
+_Static_assert(offsetof(my_library_typelayout, size) == 8, "Offset of my_library_typelayout.size differs from Rust.");
+_Static_assert(offsetof(my_library_typelayout, align) == 16, "Offset of my_library_typelayout.align differs from Rust.");
+
+typedef struct my_library_useasciistringpattern
+    {
+    const char* ascii_string;
+    } my_library_useasciistringpattern;
+_Static_assert(sizeof(my_library_useasciistringpattern) == 8, "Size of my_library_useasciistringpattern differs from Rust.");
+_Static_assert(offsetof(my_library_useasciistringpattern, ascii_string) == 0, "Offset of my_library_useasciistringpattern.ascii_string differs from Rust.");
+
+typedef struct my_library_vec
+    {
+    double x;
+    double z;
+    } my_library_vec;
+_Static_assert(sizeof(my_library_vec) == 16, "Size of my_library_vec differs from Rust.");
+_Static_assert(offsetof(my_library_vec, x) == 0, "Offset of my_library_vec.x differs from Rust.");
+_Static_assert(offsetof(my_library_vec, z) == 8, "Offset of my_library_vec.z differs from Rust.");
+
+typedef struct my_library_vec1
+    {
+    float x;
+    float y;
+    } my_library_vec1;
+_Static_assert(sizeof(my_library_vec1) == 8, "Size of my_library_vec1 differs from Rust.");
+_Static_assert(offsetof(my_library_vec1, x) == 0, "Offset of my_library_vec1.x differs from Rust.");
+_Static_assert(offsetof(my_library_vec1, y) == 4, "Offset of my_library_vec1.y differs from Rust.");
+
+typedef struct my_library_vec2
+    {
+    double x;
+    double z;
+    } my_library_vec2;
+_Static_assert(sizeof(my_library_vec2) == 16, "Size of my_library_vec2 differs from Rust.");
+_Static_assert(offsetof(my_library_vec2, x) == 0, "Offset of my_library_vec2.x differs from Rust.");
+_Static_assert(offsetof(my_library_vec2, z) == 8, "Offset of my_library_vec2.z differs from Rust.");
+
+typedef struct my_library_vec3f32
+    {
+    float x;
+    float y;
+    float z;
+    } my_library_vec3f32;
+_Static_assert(sizeof(my_library_vec3f32) == 12, "Size of my_library_vec3f32 differs from Rust.");
+_Static_assert(offsetof(my_library_vec3f32, x) == 0, "Offset of my_library_vec3f32.x differs from Rust.");
+_Static_assert(offsetof(my_library_vec3f32, y) == 4, "Offset of my_library_vec3f32.y differs from Rust.");
+_Static_assert(offsetof(my_library_vec3f32, z) == 8, "Offset of my_library_vec3f32.z differs from Rust.");
+
+typedef struct my_library_visibility1
+    {
+    uint8_t pblc;
+    uint8_t prvt;
+    } my_library_visibility1;
+_Static_assert(sizeof(my_library_visibility1) == 2, "Size of my_library_visibility1 differs from Rust.");
+_Static_assert(offsetof(my_library_visibility1, pblc) == 0, "Offset of my_library_visibility1.pblc differs from Rust.");
+_Static_assert(offsetof(my_library_visibility1, prvt) == 1, "Offset of my_library_visibility1.prvt differs from Rust.");
+
+typedef struct my_library_visibility2
+    {
+    uint8_t pblc1;
+    uint8_t pblc2;
+    } my_library_visibility2;
+_Static_assert(sizeof(my_library_visibility2) == 2, "Size of my_library_visibility2 differs from Rust.");
+_Static_assert(offsetof(my_library_visibility2, pblc1) == 0, "Offset of my_library_visibility2.pblc1 differs from Rust.");
+_Static_assert(offsetof(my_library_visibility2, pblc2) == 1, "Offset of my_library_visibility2.pblc2 differs from Rust.");
+
+typedef struct my_library_visibilitycrate
+    {
+    uint8_t x;
+    } my_library_visibilitycrate;
+_Static_assert(sizeof(my_library_visibilitycrate) == 1, "Size of my_library_visibilitycrate differs from Rust.");
+_Static_assert(offsetof(my_library_visibilitycrate, x) == 0, "Offset of my_library_visibilitycrate.x differs from Rust.");
+
+typedef struct my_library_weird1u32
+    {
+    uint32_t x;
+    } my_library_weird1u32;
+_Static_assert(sizeof(my_library_weird1u32) == 4, "Size of my_library_weird1u32 differs from Rust.");
+_Static_assert(offsetof(my_library_weird1u32, x) == 0, "Offset of my_library_weird1u32.x differs from Rust.");
+
+typedef uint8_t (*my_library_fptr_fn_u8_rval_u8)(uint8_t x0);
+
+typedef uint8_t (*my_library_callbacku8)(uint8_t value);
+
+typedef uint32_t (*my_library_mycallback)(uint32_t value);
+
+typedef void (*my_library_mycallbackcompletion)(uint32_t value);
+
+typedef uint32_t (*my_library_mycallbacknamespaced)(uint32_t value);
+
+typedef void (*my_library_sumdelegate1)();
+
+typedef int32_t (*my_library_sumdelegate2)(int32_t x, int32_t y);
+
+typedef my_library_ffierror (*my_library_sumdelegatereturn)(int32_t x, int32_t y);
+
+typedef void (*my_library_sumdelegatereturn2)(int32_t x, int32_t y);
+
+typedef uint32_t (*my_library_fptr_fn_u32_ConstPtr_rval_u32)(uint32_t x0, const void* x1);
+
+typedef void (*my_library_fptr_fn_ConstPtr)(const void* x0);
+
+typedef struct my_library_myclosure
+    {
+    my_library_fptr_fn_u32_ConstPtr_rval_u32 callback;
+    const void* context;
+    my_library_fptr_fn_ConstPtr destructor;
+    } my_library_myclosure;
+
+typedef struct my_library_array
+    {
+    uint8_t data[16];
+    } my_library_array;
+_Static_assert(sizeof(my_library_array) == 16, "Size of my_library_array differs from Rust.");
+_Static_assert(offsetof(my_library_array, data) == 0, "Offset of my_library_array.data differs from Rust.");
+
+typedef struct my_library_container
+    {
+    my_library_local foreign;
+    } my_library_container;
+_Static_assert(sizeof(my_library_container) == 4, "Size of my_library_container differs from Rust.");
+_Static_assert(offsetof(my_library_container, foreign) == 0, "Offset of my_library_container.foreign differs from Rust.");
+
+typedef struct my_library_genericu32
+    {
+    const uint32_t* x;
+    } my_library_genericu32;
+_Static_assert(sizeof(my_library_genericu32) == 8, "Size of my_library_genericu32 differs from Rust.");
+_Static_assert(offsetof(my_library_genericu32, x) == 0, "Offset of my_library_genericu32.x differs from Rust.");
+
+typedef struct my_library_genericu8
+    {
+    const uint8_t* x;
+    } my_library_genericu8;
+_Static_assert(sizeof(my_library_genericu8) == 8, "Size of my_library_genericu8 differs from Rust.");
+_Static_assert(offsetof(my_library_genericu8, x) == 0, "Offset of my_library_genericu8.x differs from Rust.");
+
+typedef struct my_library_weird2u8
+    {
+    uint8_t t;
+    uint8_t a[5];
+    const uint8_t* r;
+    } my_library_weird2u8;
+_Static_assert(sizeof(my_library_weird2u8) == 16, "Size of my_library_weird2u8 differs from Rust.");
+_Static_assert(offsetof(my_library_weird2u8, t) == 0, "Offset of my_library_weird2u8.t differs from Rust.");
+_Static_assert(offsetof(my_library_weird2u8, a) == 1, "Offset of my_library_weird2u8.a differs from Rust.");
+_Static_assert(offsetof(my_library_weird2u8, r) == 8, "Offset of my_library_weird2u8.r differs from Rust.");
+
+typedef struct my_library_slicebool
+    {
+    const uint8_t* data;
+    uint64_t len;
+    } my_library_slicebool;
+_Static_assert(sizeof(my_library_slicebool) == 16, "Size of my_library_slicebool differs from Rust.");
+_Static_assert(offsetof(my_library_slicebool, data) == 0, "Offset of my_library_slicebool.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicebool, len) == 8, "Offset of my_library_slicebool.len differs from Rust.");
+
+typedef struct my_library_slicei32
+    {
+    const int32_t* data;
+    uint64_t len;
+    } my_library_slicei32;
+_Static_assert(sizeof(my_library_slicei32) == 16, "Size of my_library_slicei32 differs from Rust.");
+_Static_assert(offsetof(my_library_slicei32, data) == 0, "Offset of my_library_slicei32.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicei32, len) == 8, "Offset of my_library_slicei32.len differs from Rust.");
+
+typedef struct my_library_sliceu32
+    {
+    const uint32_t* data;
+    uint64_t len;
+    } my_library_sliceu32;
+_Static_assert(sizeof(my_library_sliceu32) == 16, "Size of my_library_sliceu32 differs from Rust.");
+_Static_assert(offsetof(my_library_sliceu32, data) == 0, "Offset of my_library_sliceu32.data differs from Rust.");
+_Static_assert(offsetof(my_library_sliceu32, len) == 8, "Offset of my_library_sliceu32.len differs from Rust.");
+
+typedef struct my_library_sliceu8
+    {
+    const uint8_t* data;
+    uint64_t len;
+    } my_library_sliceu8;
+_Static_assert(sizeof(my_library_sliceu8) == 16, "Size of my_library_sliceu8 differs from Rust.");
+_Static_assert(offsetof(my_library_sliceu8, data) == 0, "Offset of my_library_sliceu8.data differs from Rust.");
+_Static_assert(offsetof(my_library_sliceu8, len) == 8, "Offset of my_library_sliceu8.len differs from Rust.");
+
+typedef struct my_library_slicemutconstptri8
+    {
+    const const char** data;
+    uint64_t len;
+    } my_library_slicemutconstptri8;
+_Static_assert(sizeof(my_library_slicemutconstptri8) == 16, "Size of my_library_slicemutconstptri8 differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutconstptri8, data) == 0, "Offset of my_library_slicemutconstptri8.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutconstptri8, len) == 8, "Offset of my_library_slicemutconstptri8.len differs from Rust.");
+
+typedef struct my_library_slicemutu32
+    {
+    const uint32_t* data;
+    uint64_t len;
+    } my_library_slicemutu32;
+_Static_assert(sizeof(my_library_slicemutu32) == 16, "Size of my_library_slicemutu32 differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutu32, data) == 0, "Offset of my_library_slicemutu32.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutu32, len) == 8, "Offset of my_library_slicemutu32.len differs from Rust.");
+
+typedef struct my_library_slicemutu8
+    {
+    const uint8_t* data;
+    uint64_t len;
+    } my_library_slicemutu8;
+_Static_assert(sizeof(my_library_slicemutu8) == 16, "Size of my_library_slicemutu8 differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutu8, data) == 0, "Offset of my_library_slicemutu8.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutu8, len) == 8, "Offset of my_library_slicemutu8.len differs from Rust.");
+
+typedef struct my_library_optioninner
+    {
+    my_library_inner t;
+    uint8_t is_some;
+    } my_library_optioninner;
+_Static_assert(sizeof(my_library_optioninner) == 8, "Size of my_library_optioninner differs from Rust.");
+_Static_assert(offsetof(my_library_optioninner, t) == 0, "Offset of my_library_optioninner.t differs from Rust.");
+_Static_assert(offsetof(my_library_optioninner, is_some) == 4, "Offset of my_library_optioninner.is_some differs from Rust.");
+
+typedef struct my_library_optionvec
+    {
+    my_library_vec t;
+    uint8_t is_some;
+    } my_library_optionvec;
+_Static_assert(sizeof(my_library_optionvec) == 24, "Size of my_library_optionvec differs from Rust.");
+_Static_assert(offsetof(my_library_optionvec, t) == 0, "Offset of my_library_optionvec.t differs from Rust.");
+_Static_assert(offsetof(my_library_optionvec, is_some) == 16, "Offset of my_library_optionvec.is_some differs from Rust.");
+
+typedef void (*my_library_mycallbackcontextual)(const void* context, uint32_t value);
+
+typedef void (*my_library_mycallbackvoid)(const void* ptr);
+
+typedef struct my_library_delegatecallbackmycallbackcontextual
+    {
+    my_library_mycallbackcontextual callback;
+    const void* context;
+    } my_library_delegatecallbackmycallbackcontextual;
+_Static_assert(sizeof(my_library_delegatecallbackmycallbackcontextual) == 16, "Size of my_library_delegatecallbackmycallbackcontextual differs from Rust.");
+_Static_assert(offsetof(my_library_delegatecallbackmycallbackcontextual, callback) == 0, "Offset of my_library_delegatecallbackmycallbackcontextual.callback differs from Rust.");
+_Static_assert(offsetof(my_library_delegatecallbackmycallbackcontextual, context) == 8, "Offset of my_library_delegatecallbackmycallbackcontextual.context differs from Rust.");
+
+typedef struct my_library_slicetypelayout
+    {
+    const my_library_typelayout* data;
+    uint64_t len;
+    } my_library_slicetypelayout;
+_Static_assert(sizeof(my_library_slicetypelayout) == 16, "Size of my_library_slicetypelayout differs from Rust.");
+_Static_assert(offsetof(my_library_slicetypelayout, data) == 0, "Offset of my_library_slicetypelayout.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicetypelayout, len) == 8, "Offset of my_library_slicetypelayout.len differs from Rust.");
+
+typedef struct my_library_sliceuseasciistringpattern
+    {
+    const my_library_useasciistringpattern* data;
+    uint64_t len;
+    } my_library_sliceuseasciistringpattern;
+_Static_assert(sizeof(my_library_sliceuseasciistringpattern) == 16, "Size of my_library_sliceuseasciistringpattern differs from Rust.");
+_Static_assert(offsetof(my_library_sliceuseasciistringpattern, data) == 0, "Offset of my_library_sliceuseasciistringpattern.data differs from Rust.");
+_Static_assert(offsetof(my_library_sliceuseasciistringpattern, len) == 8, "Offset of my_library_sliceuseasciistringpattern.len differs from Rust.");
+
+typedef struct my_library_slicevec
+    {
+    const my_library_vec* data;
+    uint64_t len;
+    } my_library_slicevec;
+_Static_assert(sizeof(my_library_slicevec) == 16, "Size of my_library_slicevec differs from Rust.");
+_Static_assert(offsetof(my_library_slicevec, data) == 0, "Offset of my_library_slicevec.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicevec, len) == 8, "Offset of my_library_slicevec.len differs from Rust.");
+
+typedef struct my_library_slicevec3f32
+    {
+    const my_library_vec3f32* data;
+    uint64_t len;
+    } my_library_slicevec3f32;
+_Static_assert(sizeof(my_library_slicevec3f32) == 16, "Size of my_library_slicevec3f32 differs from Rust.");
+_Static_assert(offsetof(my_library_slicevec3f32, data) == 0, "Offset of my_library_slicevec3f32.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicevec3f32, len) == 8, "Offset of my_library_slicevec3f32.len differs from Rust.");
+
+typedef struct my_library_slicemutvec
+    {
+    const my_library_vec* data;
+    uint64_t len;
+    } my_library_slicemutvec;
+_Static_assert(sizeof(my_library_slicemutvec) == 16, "Size of my_library_slicemutvec differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutvec, data) == 0, "Offset of my_library_slicemutvec.data differs from Rust.");
+_Static_assert(offsetof(my_library_slicemutvec, len) == 8, "Offset of my_library_slicemutvec.len differs from Rust.");
+
+typedef uint8_t (*my_library_callbackffislice)(my_library_sliceu8 slice);
+
+typedef void (*my_library_callbackslicemut)(my_library_slicemutu8 slice);
+
+typedef my_library_vec3f32 (*my_library_callbackhugevecslice)(my_library_slicevec3f32 slice);
+
+
+static const uint8_t MY_LIBRARY_MAGIC[4] = { 73, 79, 80, 83 };
+static const my_library_vec3f32 MY_LIBRARY_UP = { 0, 1, 0 };
+
+void primitive_void();
+void primitive_void2();
+bool primitive_bool(bool x);
+uint8_t primitive_u8(uint8_t x);
+uint16_t primitive_u16(uint16_t x);
+uint32_t primitive_u32(uint32_t x);
+uint64_t primitive_u64(uint64_t x);
+int8_t primitive_i8(int8_t x);
+int16_t primitive_i16(int16_t x);
+int32_t primitive_i32(int32_t x);
+int64_t primitive_i64(int64_t x);
+size_t primitive_usize(size_t x);
+intptr_t primitive_isize(intptr_t x);
+my_library_u128 primitive_u128(my_library_u128 x);
+my_library_i128 primitive_i128(my_library_i128 x);
+uint32_t primitive_char(uint32_t x);
+my_library_booleanalignment boolean_alignment(my_library_booleanalignment x);
+my_library_booleanalignment boolean_alignment2(bool rval);
+my_library_packed2 packed_to_packed1(my_library_packed1 a);
+int64_t many_args_5(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4);
+int64_t many_args_10(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4, int64_t x5, int64_t x6, int64_t x7, int64_t x8, int64_t x9);
+const int64_t* ptr(const int64_t* x);
+int64_t* ptr_mut(int64_t* x);
+const const int64_t** ptr_ptr(const const int64_t** x);
+const int64_t* ref_simple(const int64_t* x);
+int64_t* ref_mut_simple(int64_t* x);
+bool ref_option(const int64_t* x);
+bool ref_mut_option(int64_t* x);
+my_library_tupled tupled(my_library_tupled x);
+my_library_ffierror complex_args_1(my_library_vec3f32 a, const my_library_tupled* b);
+uint8_t callback(my_library_fptr_fn_u8_rval_u8 callback, uint8_t value);
+uint32_t generic_1a(my_library_genericu32 x, my_library_phantomu8 y);
+uint8_t generic_1b(my_library_genericu8 x, my_library_phantomu8 y);
+uint8_t generic_1c(const my_library_genericu8* x, const my_library_genericu8* y);
+uint8_t generic_2(const my_library_generic2u8* x);
+uint8_t generic_3(const my_library_generic3* x);
+uint8_t generic_4(const my_library_generic4* x);
+uint8_t array_1(my_library_array x);
+my_library_enumdocumented documented(my_library_structdocumented x);
+my_library_enumdeprecated deprecated(my_library_structdeprecated x) MY_LIBRARY_DEPRECATED("Use `documented` instead.");
+my_library_vec1 ambiguous_1(my_library_vec1 x);
+my_library_vec2 ambiguous_2(my_library_vec2 x);
+bool ambiguous_3(my_library_vec1 x, my_library_vec2 y);
+my_library_vec namespaced_type(my_library_vec x);
+my_library_optionvec namespaced_inner_option(my_library_optionvec x);
+my_library_slicevec namespaced_inner_slice(my_library_slicevec x);
+my_library_slicemutvec namespaced_inner_slice_mut(my_library_slicemutvec x);
+double namespaced_function(my_library_vec x);
+uint32_t checked(uint32_t x);
+uint32_t renamed_symbol(uint32_t x);
+#define renamed_export renamed_symbol
+uint32_t skipped_in_csharp(uint32_t x);
+my_library_ffierror panics();
+my_library_enumrenamed renamed(my_library_structrenamed x);
+void sleep(uint64_t millis);
+bool weird_1(my_library_weird1u32 x, my_library_weird2u8 y);
+void visibility(my_library_visibility1 x, my_library_visibility2 y);
+uint8_t visibility_crate(my_library_visibilitycrate x);
+my_library_tupled repr_transparent(my_library_tupled x, const my_library_tupled* r);
+uint32_t pattern_ascii_pointer_1(const char* x);
+const char* pattern_ascii_pointer_2();
+uint32_t pattern_ascii_pointer_len(const char* x, my_library_useasciistringpattern y);
+my_library_sliceuseasciistringpattern pattern_ascii_pointer_return_slice();
+uint32_t pattern_ffi_slice_1(my_library_sliceu32 ffi_slice);
+uint32_t pattern_ffi_slice_1b(my_library_slicemutu32 ffi_slice);
+my_library_vec3f32 pattern_ffi_slice_2(my_library_slicevec3f32 ffi_slice, int32_t i);
+void pattern_ffi_slice_3(my_library_slicemutu8 slice, my_library_callbackslicemut callback);
+void pattern_ffi_slice_4(my_library_sliceu8 slice, my_library_slicemutu8 slice2);
+void pattern_ffi_slice_5(const my_library_sliceu8* slice, my_library_slicemutu8* slice2);
+void pattern_ffi_slice_6(const my_library_slicemutu8* slice, my_library_callbacku8 callback);
+uint32_t pattern_ffi_slice_7(my_library_slicemutconstptri8 slices);
+uint8_t pattern_ffi_slice_delegate(my_library_callbackffislice callback);
+my_library_vec3f32 pattern_ffi_slice_delegate_huge(my_library_callbackhugevecslice callback);
+my_library_optioninner pattern_ffi_option_1(my_library_optioninner ffi_slice);
+my_library_inner pattern_ffi_option_2(my_library_optioninner ffi_slice);
+uint8_t pattern_ffi_bool(uint8_t ffi_bool);
+char pattern_ffi_cchar(char ffi_cchar);
+const char* pattern_ffi_cchar_const_pointer(const char* ffi_cchar);
+char* pattern_ffi_cchar_mut_pointer(char* ffi_cchar);
+uint64_t pattern_api_guard();
+my_library_slicetypelayout pattern_layout_guard();
+uint32_t pattern_callback_1(my_library_mycallback callback, uint32_t x);
+my_library_mycallbackvoid pattern_callback_2(my_library_mycallbackvoid callback);
+void pattern_callback_3(my_library_delegatecallbackmycallbackcontextual callback, uint32_t x);
+uint32_t pattern_callback_4(my_library_mycallbacknamespaced callback, uint32_t x);
+my_library_sumdelegate1 pattern_callback_5();
+my_library_sumdelegate2 pattern_callback_6();
+my_library_ffierror pattern_callback_7(my_library_sumdelegatereturn c1, my_library_sumdelegatereturn2 c2, int32_t x, int32_t i, int32_t* o);
+my_library_ffierror pattern_callback_8(uint32_t x, my_library_mycallbackcompletion completion);
+uint32_t pattern_callback_9(my_library_myclosure callback, uint32_t x);
+void pattern_surrogates_1(my_library_local s, my_library_container* c);
+my_library_ffierror simple_service_destroy(my_library_simpleservice** context);
+my_library_ffierror simple_service_new_with(my_library_simpleservice** context, uint32_t some_value);
+my_library_ffierror simple_service_new_without(my_library_simpleservice** context);
+my_library_ffierror simple_service_new_with_string(my_library_simpleservice** context, const char* ascii);
+my_library_ffierror simple_service_new_failing(my_library_simpleservice** context, uint8_t some_value);
+uint32_t simple_service_get_value(const my_library_simpleservice* context);
+my_library_ffierror simple_service_set_value(my_library_simpleservice* context, uint32_t value);
+my_library_ffierror simple_service_method_result(const my_library_simpleservice* context, uint32_t anon1);
+uint32_t simple_service_method_value(const my_library_simpleservice* context, uint32_t x);
+void simple_service_method_void(const my_library_simpleservice* context);
+void simple_service_method_void2(const my_library_simpleservice* context);
+void simple_service_method_void_deprecated(const my_library_simpleservice* context) MY_LIBRARY_DEPRECATED("Use `method_void` instead.");
+uint8_t simple_service_method_mut_self(my_library_simpleservice* context, my_library_sliceu8 slice);
+void simple_service_method_mut_self_void(my_library_simpleservice* context, my_library_slicebool slice);
+uint8_t simple_service_method_mut_self_ref(my_library_simpleservice* context, const uint8_t* x, uint8_t* y);
+uint8_t simple_service_method_mut_self_ref_slice(my_library_simpleservice* context, const uint8_t* x, uint8_t* y, my_library_sliceu8 slice);
+uint8_t simple_service_method_mut_self_ref_slice_limited(my_library_simpleservice* context, const uint8_t* x, uint8_t* y, my_library_sliceu8 slice, my_library_sliceu8 slice2);
+my_library_ffierror simple_service_method_mut_self_ffi_error(my_library_simpleservice* context, my_library_slicemutu8 slice);
+my_library_ffierror simple_service_method_mut_self_no_error(my_library_simpleservice* context, my_library_slicemutu8 slice);
+my_library_sliceu32 simple_service_return_slice(my_library_simpleservice* context);
+my_library_slicemutu32 simple_service_return_slice_mut(my_library_simpleservice* context);
+const char* simple_service_return_string(my_library_simpleservice* context);
+my_library_ffierror simple_service_method_void_ffi_error(my_library_simpleservice* context);
+my_library_ffierror simple_service_method_callback(my_library_simpleservice* context, my_library_mycallback callback);
+my_library_ffierror simple_service_method_callback_ffi_return(my_library_simpleservice* context, my_library_sumdelegatereturn callback);
+my_library_ffierror simple_service_method_callback_ffi_return_with_slice(my_library_simpleservice* context, my_library_sumdelegatereturn callback, my_library_slicei32 input);
+my_library_ffierror simple_service_method_callback_completion(my_library_simpleservice* context, uint32_t x, my_library_mycallbackcompletion completion);
+uint32_t simple_service_supported_formats();
+my_library_ffierror simple_service_validate(uint32_t value);
+my_library_ffierror simple_service_create_child(const my_library_simpleservice* context, my_library_simpleservicechild** instance, uint32_t offset);
+my_library_ffierror simple_service_child_destroy(my_library_simpleservicechild** context);
+uint32_t simple_service_child_value(const my_library_simpleservicechild* context);
+my_library_ffierror simple_service_lifetime_destroy(my_library_simpleservicelifetime** context);
+my_library_ffierror simple_service_lifetime_new_with(my_library_simpleservicelifetime** context, const uint32_t* some_value);
+void simple_service_lifetime_method_lt(my_library_simpleservicelifetime* context, my_library_slicebool slice);
+void simple_service_lifetime_method_lt2(my_library_simpleservicelifetime* context, my_library_slicebool slice);
+const char* simple_service_lifetime_return_string_accept_slice(my_library_simpleservicelifetime* anon0, my_library_sliceu8 anon1);
+my_library_ffierror simple_service_lifetime_method_void_ffi_error(my_library_simpleservicelifetime* context);
+my_library_ffierror simple_service_namespaced_destroy(my_library_simpleservicenamespaced** context);
+my_library_ffierror simple_service_namespaced_new_with(my_library_simpleservicenamespaced** context, uint32_t value);
+uint32_t simple_service_namespaced_value(const my_library_simpleservicenamespaced* context);
+my_library_ffierror shared_service_release(my_library_sharedservice** context);
+my_library_ffierror shared_service_new_with(my_library_sharedservice** context, uint32_t value);
+uint32_t shared_service_value(const my_library_sharedservice* context);
+my_library_ffierror shared_service_retain(const my_library_sharedservice* context);
+my_library_ffierror single_threaded_service_destroy(my_library_singlethreadedservice** context);
+my_library_ffierror single_threaded_service_new_with(my_library_singlethreadedservice** context, uint32_t value);
+my_library_ffierror single_threaded_service_increment(const my_library_singlethreadedservice* context);
+uint32_t single_threaded_service_value(const my_library_singlethreadedservice* context);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* interoptopus_generated */
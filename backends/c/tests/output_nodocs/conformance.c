@@ -0,0 +1,119 @@
+// Automatically generated by Interoptopus.
+#include <stdio.h>
+
+#include "my_header.h"
+
+static int failures = 0;
+
+static void check(int ok, const char* call)
+{
+    if (!ok)
+    {
+        printf("Conformance check failed: %s\n", call);
+        failures++;
+    }
+}
+
+int main(void)
+{
+    primitive_void();
+    primitive_void2();
+    check(primitive_bool(false) == true, "primitive_bool(false)");
+    check(primitive_bool(true) == false, "primitive_bool(true)");
+    check(primitive_u8(0) == 255, "primitive_u8(0)");
+    check(primitive_u8(1) == 254, "primitive_u8(1)");
+    check(primitive_u8(255) == 0, "primitive_u8(255)");
+    check(primitive_u16(0) == 65535, "primitive_u16(0)");
+    check(primitive_u16(1) == 65534, "primitive_u16(1)");
+    check(primitive_u16(65535) == 0, "primitive_u16(65535)");
+    check(primitive_u32(0U) == 4294967295U, "primitive_u32(0U)");
+    check(primitive_u32(1U) == 4294967294U, "primitive_u32(1U)");
+    check(primitive_u32(4294967295U) == 0U, "primitive_u32(4294967295U)");
+    check(primitive_u64(0ULL) == 18446744073709551615ULL, "primitive_u64(0ULL)");
+    check(primitive_u64(1ULL) == 18446744073709551614ULL, "primitive_u64(1ULL)");
+    check(primitive_u64(18446744073709551615ULL) == 0ULL, "primitive_u64(18446744073709551615ULL)");
+    check(primitive_i8(0) == 0, "primitive_i8(0)");
+    check(primitive_i8(1) == -1, "primitive_i8(1)");
+    check(primitive_i8(-1) == 1, "primitive_i8(-1)");
+    check(primitive_i8(-128) == -128, "primitive_i8(-128)");
+    check(primitive_i8(127) == -127, "primitive_i8(127)");
+    check(primitive_i16(0) == 0, "primitive_i16(0)");
+    check(primitive_i16(1) == -1, "primitive_i16(1)");
+    check(primitive_i16(-1) == 1, "primitive_i16(-1)");
+    check(primitive_i16(-32768) == -32768, "primitive_i16(-32768)");
+    check(primitive_i16(32767) == -32767, "primitive_i16(32767)");
+    check(primitive_i32(0) == 0, "primitive_i32(0)");
+    check(primitive_i32(1) == -1, "primitive_i32(1)");
+    check(primitive_i32(-1) == 1, "primitive_i32(-1)");
+    check(primitive_i32(INT32_MIN) == INT32_MIN, "primitive_i32(INT32_MIN)");
+    check(primitive_i32(2147483647) == -2147483647, "primitive_i32(2147483647)");
+    check(primitive_i64(0LL) == 0LL, "primitive_i64(0LL)");
+    check(primitive_i64(1LL) == -1LL, "primitive_i64(1LL)");
+    check(primitive_i64(-1LL) == 1LL, "primitive_i64(-1LL)");
+    check(primitive_i64(INT64_MIN) == INT64_MIN, "primitive_i64(INT64_MIN)");
+    check(primitive_i64(9223372036854775807LL) == -9223372036854775807LL, "primitive_i64(9223372036854775807LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 0LL, 0LL) == 0LL, "many_args_5(0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(1LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_5(1LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(-1LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_5(-1LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(INT64_MIN, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_5(INT64_MIN, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(9223372036854775807LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_5(9223372036854775807LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 1LL, 0LL, 0LL, 0LL) == 1LL, "many_args_5(0LL, 1LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(0LL, -1LL, 0LL, 0LL, 0LL) == -1LL, "many_args_5(0LL, -1LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(0LL, INT64_MIN, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_5(0LL, INT64_MIN, 0LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 9223372036854775807LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_5(0LL, 9223372036854775807LL, 0LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 1LL, 0LL, 0LL) == 1LL, "many_args_5(0LL, 0LL, 1LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 0LL, -1LL, 0LL, 0LL) == -1LL, "many_args_5(0LL, 0LL, -1LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 0LL, INT64_MIN, 0LL, 0LL) == INT64_MIN, "many_args_5(0LL, 0LL, INT64_MIN, 0LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 9223372036854775807LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_5(0LL, 0LL, 9223372036854775807LL, 0LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 1LL, 0LL) == 1LL, "many_args_5(0LL, 0LL, 0LL, 1LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 0LL, -1LL, 0LL) == -1LL, "many_args_5(0LL, 0LL, 0LL, -1LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 0LL, INT64_MIN, 0LL) == INT64_MIN, "many_args_5(0LL, 0LL, 0LL, INT64_MIN, 0LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 9223372036854775807LL, 0LL) == 9223372036854775807LL, "many_args_5(0LL, 0LL, 0LL, 9223372036854775807LL, 0LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 0LL, 1LL) == 1LL, "many_args_5(0LL, 0LL, 0LL, 0LL, 1LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 0LL, -1LL) == -1LL, "many_args_5(0LL, 0LL, 0LL, 0LL, -1LL)");
+    check(many_args_5(0LL, 0LL, 0LL, 0LL, INT64_MIN) == INT64_MIN, "many_args_5(0LL, 0LL, 0LL, 0LL, INT64_MIN)");
+    check(many_args_5(0LL, 0LL, 0LL, 0LL, 9223372036854775807LL) == 9223372036854775807LL, "many_args_5(0LL, 0LL, 0LL, 0LL, 9223372036854775807LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 0LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(-1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(-1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL, 0LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL) == 1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 1LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL) == -1LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, -1LL)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN) == INT64_MIN, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, INT64_MIN)");
+    check(many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL) == 9223372036854775807LL, "many_args_10(0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 0LL, 9223372036854775807LL)");
+
+    return failures != 0;
+}
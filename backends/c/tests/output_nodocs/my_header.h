@@ -9,12 +9,42 @@ extern "C" {
 
 #include <stdint.h>
 #include <stdbool.h>
+#include <stddef.h>
 
 
 
+#ifndef MY_LIBRARY_DEPRECATED
+#if defined(__GNUC__) || defined(__clang__)
+#define MY_LIBRARY_DEPRECATED(msg) __attribute__((deprecated(msg)))
+#else
+#define MY_LIBRARY_DEPRECATED(msg)
+#endif
+#endif
+
+#if defined(__SIZEOF_INT128__)
+typedef unsigned __int128 my_library_u128;
+typedef __int128 my_library_i128;
+#else
+#if defined(__cplusplus)
+#define MY_LIBRARY_ALIGN_16 alignas(16)
+#else
+#define MY_LIBRARY_ALIGN_16 _Alignas(16)
+#endif
+typedef struct my_library_u128 { MY_LIBRARY_ALIGN_16 uint64_t lo; uint64_t hi; } my_library_u128;
+typedef struct my_library_i128 { MY_LIBRARY_ALIGN_16 uint64_t lo; int64_t hi; } my_library_i128;
+#endif
+
 const uint8_t MY_LIBRARY_U8 = 255;
 const float MY_LIBRARY_F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944;
 const int32_t MY_LIBRARY_COMPUTED_I32 = -2147483647;
+#define MY_LIBRARY_VERSION "1.2.3 \"reference\""
+const uint32_t MY_LIBRARY_NAMESPACED = 42;
+
+typedef enum my_library_enumdeprecated
+    {
+    MY_LIBRARY_ENUMDEPRECATED_CURRENT = 0,
+    MY_LIBRARY_ENUMDEPRECATED_LEGACY MY_LIBRARY_DEPRECATED("Use `Current` instead.") = 1,
+    } my_library_enumdeprecated;
 
 typedef enum my_library_enumdocumented
     {
@@ -31,8 +61,12 @@ typedef enum my_library_enumrenamed
 typedef struct my_library_generic2u8 my_library_generic2u8;
 typedef struct my_library_generic3 my_library_generic3;
 typedef struct my_library_generic4 my_library_generic4;
+typedef struct my_library_sharedservice my_library_sharedservice;
 typedef struct my_library_simpleservice my_library_simpleservice;
+typedef struct my_library_simpleservicechild my_library_simpleservicechild;
 typedef struct my_library_simpleservicelifetime my_library_simpleservicelifetime;
+typedef struct my_library_simpleservicenamespaced my_library_simpleservicenamespaced;
+typedef struct my_library_singlethreadedservice my_library_singlethreadedservice;
 typedef enum my_library_ffierror
     {
     MY_LIBRARY_FFIERROR_OK = 0,
@@ -92,6 +126,12 @@ typedef struct my_library_phantomu8
     uint32_t x;
     } my_library_phantomu8;
 
+typedef struct my_library_structdeprecated
+    {
+    uint32_t x;
+    uint32_t old_x MY_LIBRARY_DEPRECATED("Use `x` instead.");
+    } my_library_structdeprecated;
+
 typedef struct my_library_structdocumented
     {
     float x;
@@ -107,6 +147,13 @@ typedef struct my_library_tupled
     uint8_t x0;
     } my_library_tupled;
 
+typedef struct my_library_typelayout
+    {
+    uint64_t name_hash;
+    uint64_t size;
+    uint64_t align;
+    } my_library_typelayout;
+
 typedef struct my_library_useasciistringpattern
     {
     const char* ascii_string;
@@ -149,6 +196,11 @@ typedef struct my_library_visibility2
     uint8_t pblc2;
     } my_library_visibility2;
 
+typedef struct my_library_visibilitycrate
+    {
+    uint8_t x;
+    } my_library_visibilitycrate;
+
 typedef struct my_library_weird1u32
     {
     uint32_t x;
@@ -160,6 +212,8 @@ typedef uint8_t (*my_library_callbacku8)(uint8_t value);
 
 typedef uint32_t (*my_library_mycallback)(uint32_t value);
 
+typedef void (*my_library_mycallbackcompletion)(uint32_t value);
+
 typedef uint32_t (*my_library_mycallbacknamespaced)(uint32_t value);
 
 typedef void (*my_library_sumdelegate1)();
@@ -170,6 +224,17 @@ typedef my_library_ffierror (*my_library_sumdelegatereturn)(int32_t x, int32_t y
 
 typedef void (*my_library_sumdelegatereturn2)(int32_t x, int32_t y);
 
+typedef uint32_t (*my_library_fptr_fn_u32_ConstPtr_rval_u32)(uint32_t x0, const void* x1);
+
+typedef void (*my_library_fptr_fn_ConstPtr)(const void* x0);
+
+typedef struct my_library_myclosure
+    {
+    my_library_fptr_fn_u32_ConstPtr_rval_u32 callback;
+    const void* context;
+    my_library_fptr_fn_ConstPtr destructor;
+    } my_library_myclosure;
+
 typedef struct my_library_array
     {
     uint8_t data[16];
@@ -261,6 +326,12 @@ typedef struct my_library_delegatecallbackmycallbackcontextual
     const void* context;
     } my_library_delegatecallbackmycallbackcontextual;
 
+typedef struct my_library_slicetypelayout
+    {
+    const my_library_typelayout* data;
+    uint64_t len;
+    } my_library_slicetypelayout;
+
 typedef struct my_library_sliceuseasciistringpattern
     {
     const my_library_useasciistringpattern* data;
@@ -292,6 +363,9 @@ typedef void (*my_library_callbackslicemut)(my_library_slicemutu8 slice);
 typedef my_library_vec3f32 (*my_library_callbackhugevecslice)(my_library_slicevec3f32 slice);
 
 
+static const uint8_t MY_LIBRARY_MAGIC[4] = { 73, 79, 80, 83 };
+static const my_library_vec3f32 MY_LIBRARY_UP = { 0, 1, 0 };
+
 void primitive_void();
 void primitive_void2();
 bool primitive_bool(bool x);
@@ -303,6 +377,11 @@ int8_t primitive_i8(int8_t x);
 int16_t primitive_i16(int16_t x);
 int32_t primitive_i32(int32_t x);
 int64_t primitive_i64(int64_t x);
+size_t primitive_usize(size_t x);
+intptr_t primitive_isize(intptr_t x);
+my_library_u128 primitive_u128(my_library_u128 x);
+my_library_i128 primitive_i128(my_library_i128 x);
+uint32_t primitive_char(uint32_t x);
 my_library_booleanalignment boolean_alignment(my_library_booleanalignment x);
 my_library_booleanalignment boolean_alignment2(bool rval);
 my_library_packed2 packed_to_packed1(my_library_packed1 a);
@@ -326,6 +405,7 @@ uint8_t generic_3(const my_library_generic3* x);
 uint8_t generic_4(const my_library_generic4* x);
 uint8_t array_1(my_library_array x);
 my_library_enumdocumented documented(my_library_structdocumented x);
+my_library_enumdeprecated deprecated(my_library_structdeprecated x) MY_LIBRARY_DEPRECATED("Use `documented` instead.");
 my_library_vec1 ambiguous_1(my_library_vec1 x);
 my_library_vec2 ambiguous_2(my_library_vec2 x);
 bool ambiguous_3(my_library_vec1 x, my_library_vec2 y);
@@ -333,11 +413,17 @@ my_library_vec namespaced_type(my_library_vec x);
 my_library_optionvec namespaced_inner_option(my_library_optionvec x);
 my_library_slicevec namespaced_inner_slice(my_library_slicevec x);
 my_library_slicemutvec namespaced_inner_slice_mut(my_library_slicemutvec x);
+double namespaced_function(my_library_vec x);
+uint32_t checked(uint32_t x);
+uint32_t renamed_symbol(uint32_t x);
+#define renamed_export renamed_symbol
+uint32_t skipped_in_csharp(uint32_t x);
 my_library_ffierror panics();
 my_library_enumrenamed renamed(my_library_structrenamed x);
 void sleep(uint64_t millis);
 bool weird_1(my_library_weird1u32 x, my_library_weird2u8 y);
 void visibility(my_library_visibility1 x, my_library_visibility2 y);
+uint8_t visibility_crate(my_library_visibilitycrate x);
 my_library_tupled repr_transparent(my_library_tupled x, const my_library_tupled* r);
 uint32_t pattern_ascii_pointer_1(const char* x);
 const char* pattern_ascii_pointer_2();
@@ -360,6 +446,7 @@ char pattern_ffi_cchar(char ffi_cchar);
 const char* pattern_ffi_cchar_const_pointer(const char* ffi_cchar);
 char* pattern_ffi_cchar_mut_pointer(char* ffi_cchar);
 uint64_t pattern_api_guard();
+my_library_slicetypelayout pattern_layout_guard();
 uint32_t pattern_callback_1(my_library_mycallback callback, uint32_t x);
 my_library_mycallbackvoid pattern_callback_2(my_library_mycallbackvoid callback);
 void pattern_callback_3(my_library_delegatecallbackmycallbackcontextual callback, uint32_t x);
@@ -367,16 +454,21 @@ uint32_t pattern_callback_4(my_library_mycallbacknamespaced callback, uint32_t x
 my_library_sumdelegate1 pattern_callback_5();
 my_library_sumdelegate2 pattern_callback_6();
 my_library_ffierror pattern_callback_7(my_library_sumdelegatereturn c1, my_library_sumdelegatereturn2 c2, int32_t x, int32_t i, int32_t* o);
+my_library_ffierror pattern_callback_8(uint32_t x, my_library_mycallbackcompletion completion);
+uint32_t pattern_callback_9(my_library_myclosure callback, uint32_t x);
 void pattern_surrogates_1(my_library_local s, my_library_container* c);
 my_library_ffierror simple_service_destroy(my_library_simpleservice** context);
 my_library_ffierror simple_service_new_with(my_library_simpleservice** context, uint32_t some_value);
 my_library_ffierror simple_service_new_without(my_library_simpleservice** context);
 my_library_ffierror simple_service_new_with_string(my_library_simpleservice** context, const char* ascii);
 my_library_ffierror simple_service_new_failing(my_library_simpleservice** context, uint8_t some_value);
+uint32_t simple_service_get_value(const my_library_simpleservice* context);
+my_library_ffierror simple_service_set_value(my_library_simpleservice* context, uint32_t value);
 my_library_ffierror simple_service_method_result(const my_library_simpleservice* context, uint32_t anon1);
 uint32_t simple_service_method_value(const my_library_simpleservice* context, uint32_t x);
 void simple_service_method_void(const my_library_simpleservice* context);
 void simple_service_method_void2(const my_library_simpleservice* context);
+void simple_service_method_void_deprecated(const my_library_simpleservice* context) MY_LIBRARY_DEPRECATED("Use `method_void` instead.");
 uint8_t simple_service_method_mut_self(my_library_simpleservice* context, my_library_sliceu8 slice);
 void simple_service_method_mut_self_void(my_library_simpleservice* context, my_library_slicebool slice);
 uint8_t simple_service_method_mut_self_ref(my_library_simpleservice* context, const uint8_t* x, uint8_t* y);
@@ -391,12 +483,29 @@ my_library_ffierror simple_service_method_void_ffi_error(my_library_simpleservic
 my_library_ffierror simple_service_method_callback(my_library_simpleservice* context, my_library_mycallback callback);
 my_library_ffierror simple_service_method_callback_ffi_return(my_library_simpleservice* context, my_library_sumdelegatereturn callback);
 my_library_ffierror simple_service_method_callback_ffi_return_with_slice(my_library_simpleservice* context, my_library_sumdelegatereturn callback, my_library_slicei32 input);
+my_library_ffierror simple_service_method_callback_completion(my_library_simpleservice* context, uint32_t x, my_library_mycallbackcompletion completion);
+uint32_t simple_service_supported_formats();
+my_library_ffierror simple_service_validate(uint32_t value);
+my_library_ffierror simple_service_create_child(const my_library_simpleservice* context, my_library_simpleservicechild** instance, uint32_t offset);
+my_library_ffierror simple_service_child_destroy(my_library_simpleservicechild** context);
+uint32_t simple_service_child_value(const my_library_simpleservicechild* context);
 my_library_ffierror simple_service_lifetime_destroy(my_library_simpleservicelifetime** context);
 my_library_ffierror simple_service_lifetime_new_with(my_library_simpleservicelifetime** context, const uint32_t* some_value);
 void simple_service_lifetime_method_lt(my_library_simpleservicelifetime* context, my_library_slicebool slice);
 void simple_service_lifetime_method_lt2(my_library_simpleservicelifetime* context, my_library_slicebool slice);
 const char* simple_service_lifetime_return_string_accept_slice(my_library_simpleservicelifetime* anon0, my_library_sliceu8 anon1);
 my_library_ffierror simple_service_lifetime_method_void_ffi_error(my_library_simpleservicelifetime* context);
+my_library_ffierror simple_service_namespaced_destroy(my_library_simpleservicenamespaced** context);
+my_library_ffierror simple_service_namespaced_new_with(my_library_simpleservicenamespaced** context, uint32_t value);
+uint32_t simple_service_namespaced_value(const my_library_simpleservicenamespaced* context);
+my_library_ffierror shared_service_release(my_library_sharedservice** context);
+my_library_ffierror shared_service_new_with(my_library_sharedservice** context, uint32_t value);
+uint32_t shared_service_value(const my_library_sharedservice* context);
+my_library_ffierror shared_service_retain(const my_library_sharedservice* context);
+my_library_ffierror single_threaded_service_destroy(my_library_singlethreadedservice** context);
+my_library_ffierror single_threaded_service_new_with(my_library_singlethreadedservice** context, uint32_t value);
+my_library_ffierror single_threaded_service_increment(const my_library_singlethreadedservice* context);
+uint32_t single_threaded_service_value(const my_library_singlethreadedservice* context);
 
 #ifdef __cplusplus
 }
@@ -1,5 +1,10 @@
+use interoptopus::patterns::service::Service;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Configures Python code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
 pub struct Config {
     /// How to name the function responsible for loading the DLL, e.g., `init_api`.
     pub init_api_function_name: String,
@@ -9,6 +14,15 @@ pub struct Config {
     pub raw_fn_namespace: String,
     /// Namespace for callback helpers, e.g., `callbacks`.
     pub callback_namespace: String,
+    /// Serialize calls on instances of services which are not `Sync` with a `threading.Lock`.
+    pub lock_non_sync_services: bool,
+}
+
+impl Config {
+    /// Whether calls on instances of `service` should take a lock, compare [`lock_non_sync_services`](Self::lock_non_sync_services).
+    pub fn locks_service(&self, service: &Service) -> bool {
+        self.lock_non_sync_services && !service.thread_safety().is_sync()
+    }
 }
 
 impl Default for Config {
@@ -18,6 +32,7 @@ impl Default for Config {
             ffi_attribute: "ffi".to_string(),
             raw_fn_namespace: "api".to_string(),
             callback_namespace: "callbacks".to_string(),
+            lock_non_sync_services: false,
         }
     }
 }
@@ -28,3 +43,12 @@ pub struct DocConfig {
     /// Header to append to the generated documentation.
     pub header: String,
 }
+
+/// Configures Python conformance test generation.
+#[derive(Clone, Debug, Default)]
+pub struct ConformanceConfig {
+    /// Module holding the generated bindings, e.g., `my_library` for `my_library.py`.
+    pub module: String,
+    /// Path of the native library passed to `init_lib()`.
+    pub library: String,
+}
@@ -0,0 +1,88 @@
+use crate::{ConformanceConfig, PythonWriter};
+use interoptopus::conformance::{ConformanceWriter, TestSuite};
+use interoptopus::lang::c::PrimitiveValue;
+use interoptopus::writer::IndentWriter;
+use interoptopus::{indented, Error, Interop, Inventory};
+
+/// Writes a `unittest` file calling functions with the vectors of a [`TestSuite`].
+pub struct ConformanceGenerator<'a, W> {
+    python_writer: &'a W,
+    suite: &'a TestSuite,
+    config: ConformanceConfig,
+}
+
+impl<'a, W: PythonWriter> ConformanceGenerator<'a, W> {
+    pub fn new(w: &'a W, suite: &'a TestSuite, config: ConformanceConfig) -> Self {
+        Self { python_writer: w, suite, config }
+    }
+
+    pub fn config(&self) -> &ConformanceConfig {
+        &self.config
+    }
+}
+
+impl<W: PythonWriter> ConformanceWriter for ConformanceGenerator<'_, W> {
+    fn inventory(&self) -> &Inventory {
+        self.python_writer.inventory()
+    }
+
+    fn suite(&self) -> &TestSuite {
+        self.suite
+    }
+
+    fn literal(&self, value: &PrimitiveValue) -> String {
+        match value {
+            PrimitiveValue::Bool(true) => "True".to_string(),
+            PrimitiveValue::Bool(false) => "False".to_string(),
+            PrimitiveValue::U8(x) => x.to_string(),
+            PrimitiveValue::U16(x) => x.to_string(),
+            PrimitiveValue::U32(x) => x.to_string(),
+            PrimitiveValue::U64(x) => x.to_string(),
+            PrimitiveValue::I8(x) => x.to_string(),
+            PrimitiveValue::I16(x) => x.to_string(),
+            PrimitiveValue::I32(x) => x.to_string(),
+            PrimitiveValue::I64(x) => x.to_string(),
+            // Python floats are doubles, so round expected values like `c_float` results are.
+            PrimitiveValue::F32(x) => format!("ctypes.c_float({:?}).value", x),
+            PrimitiveValue::F64(x) => format!("{:?}", x),
+        }
+    }
+}
+
+impl<W: PythonWriter> Interop for ConformanceGenerator<'_, W> {
+    fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let module = &self.config().module;
+
+        indented!(w, r#"# Automatically generated by Interoptopus."#)?;
+        indented!(w, r#"import ctypes"#)?;
+        indented!(w, r#"import unittest"#)?;
+        indented!(w, r#"import {}"#, module)?;
+        w.newline()?;
+        indented!(w, r#"{}.init_lib("{}")"#, module, self.config().library)?;
+        w.newline()?;
+        w.newline()?;
+        indented!(w, r#"class TestConformance(unittest.TestCase):"#)?;
+
+        for (function, vectors) in self.tested_functions() {
+            w.newline()?;
+            indented!(w, [_], r#"def test_{}(self):"#, function.name())?;
+            let name = self.python_writer.converter().function_name_to_python_name(function, "");
+
+            for vector in vectors {
+                let call = format!("{}.{}({})", module, name, self.arguments(vector));
+
+                match vector.expected() {
+                    Some(expected) => indented!(w, [_ _], r#"self.assertEqual({}, {})"#, self.literal(expected), call)?,
+                    None => indented!(w, [_ _], r#"{}"#, call)?,
+                }
+            }
+        }
+
+        w.newline()?;
+        w.newline()?;
+        indented!(w, r#"if __name__ == "__main__":"#)?;
+        indented!(w, [_], r#"unittest.main()"#)?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
-use interoptopus::lang::c::{CType, ConstantValue, Documentation, FnPointerType, PrimitiveType, PrimitiveValue};
+use interoptopus::lang::c::{CType, ConstantValue, Documentation, FnPointerType, Function, PrimitiveType, PrimitiveValue};
 use interoptopus::patterns::TypePattern;
+use interoptopus::util::{escape_string_literal, ControlEscape};
 use std::ops::Deref;
 
 /// Maps CType constructs to Pythonic constructs.
@@ -11,6 +12,31 @@ impl Converter {
         format!(r#""""{}""""#, docs)
     }
 
+    /// Documentation of a function followed by Sphinx `:param:` fields of its documented parameters.
+    pub fn function_documentation(&self, function: &Function) -> Documentation {
+        let mut lines = function.meta().documentation().lines().to_vec();
+        let params = function.signature().params().iter().filter(|x| !x.documentation().lines().is_empty());
+
+        for (i, param) in params.enumerate() {
+            if i == 0 && !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            let text = param.documentation().lines().iter().map(|x| x.trim()).collect::<Vec<_>>().join(" ");
+            lines.push(format!(" :param {}: {}", param.name(), text));
+        }
+
+        Documentation::from_lines(lines)
+    }
+
+    /// The Python name of a function, with the service's `common_prefix` removed, unless overridden via `name(python = "...")`.
+    pub fn function_name_to_python_name(&self, function: &Function, common_prefix: &str) -> String {
+        match function.meta().overrides().name("python") {
+            Some(name) => name.to_string(),
+            None => function.name().replace(common_prefix, ""),
+        }
+    }
+
     pub fn to_type_hint(&self, the_type: &CType, is_parameter: bool) -> String {
         match the_type {
             CType::Primitive(x) => match x {
@@ -26,6 +52,13 @@ impl Converter {
                 PrimitiveType::I64 => "int".to_string(),
                 PrimitiveType::F32 => "float".to_string(),
                 PrimitiveType::F64 => "float".to_string(),
+                PrimitiveType::Char => "int".to_string(),
+                PrimitiveType::USize => "int".to_string(),
+                PrimitiveType::ISize => "int".to_string(),
+                PrimitiveType::U128 if is_parameter => "int".to_string(),
+                PrimitiveType::I128 if is_parameter => "int".to_string(),
+                PrimitiveType::U128 => "c_uint128".to_string(),
+                PrimitiveType::I128 => "c_int128".to_string(),
             },
             CType::ReadPointer(x) => match x.deref() {
                 CType::Opaque(_) => "ctypes.c_void_p".to_string(),
@@ -60,6 +93,8 @@ impl Converter {
                     res
                 }
                 TypePattern::CChar => "ctypes.c_char".to_string(),
+                TypePattern::ContextCallback(c) if is_parameter => format!("{} | typing.Callable", c.name()),
+                TypePattern::ContextCallback(c) => c.name().to_string(),
                 _ => "".to_string(),
             },
             _ => "".to_string(),
@@ -82,6 +117,11 @@ impl Converter {
                 PrimitiveType::I64 => "ctypes.c_int64".to_string(),
                 PrimitiveType::F32 => "ctypes.c_float".to_string(),
                 PrimitiveType::F64 => "ctypes.c_double".to_string(),
+                PrimitiveType::Char => "ctypes.c_uint32".to_string(),
+                PrimitiveType::USize => "ctypes.c_size_t".to_string(),
+                PrimitiveType::ISize => "ctypes.c_ssize_t".to_string(),
+                PrimitiveType::U128 => "c_uint128".to_string(),
+                PrimitiveType::I128 => "c_int128".to_string(),
             },
             CType::Enum(_) => "ctypes.c_int".to_string(), // is this correct?
             CType::Composite(x) => x.rust_name().to_string(),
@@ -108,6 +148,7 @@ impl Converter {
                 TypePattern::Bool => "ctypes.c_uint8".to_string(),
                 TypePattern::CChar => "ctypes.c_char".to_string(),
                 TypePattern::NamedCallback(x) => self.fnpointer_to_typename(x.fnpointer()),
+                TypePattern::ContextCallback(x) => x.name().to_string(),
             },
         }
     }
@@ -147,6 +188,18 @@ impl Converter {
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => format!(r#""{}""#, escape_string_literal(x, ControlEscape::Hex)),
+            ConstantValue::Array(_, values) => {
+                let values = values.iter().map(|x| self.constant_value_to_value(x)).collect::<Vec<_>>();
+                format!("[{}]", values.join(", "))
+            }
+            ConstantValue::Composite(x, fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, x)| format!("{}={}", name, self.constant_value_to_value(x)))
+                    .collect::<Vec<_>>();
+                format!("{}({})", x.rust_name(), fields.join(", "))
+            }
         }
     }
 
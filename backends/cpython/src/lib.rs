@@ -137,12 +137,14 @@ use interoptopus::Interop;
 use interoptopus::{Error, Inventory};
 
 mod config;
+mod conformance;
 mod converter;
 mod docs;
 mod testing;
 mod writer;
 
-pub use config::{Config, DocConfig};
+pub use config::{Config, ConformanceConfig, DocConfig};
+pub use conformance::ConformanceGenerator;
 pub use converter::Converter;
 pub use docs::DocGenerator;
 pub use testing::run_python_if_installed;
@@ -159,7 +161,7 @@ impl Generator {
     pub fn new(config: Config, library: Inventory) -> Self {
         Self {
             config,
-            library,
+            library: library.without_skipped("python"),
             converter: Converter {},
         }
     }
@@ -1,9 +1,11 @@
 use crate::config::Config;
 use crate::converter::Converter;
-use interoptopus::lang::c::{CType, CompositeType, EnumType, Function, Layout, PrimitiveType};
-use interoptopus::patterns::service::Service;
+use interoptopus::lang::c::{CType, CompositeType, Deprecation, EnumType, Function, Layout, PrimitiveType};
+use interoptopus::patterns::api_guard::{is_layout_guard, layout_composites, layout_key};
+use interoptopus::patterns::callbacks::ContextCallback;
+use interoptopus::patterns::service::{is_created_by_service, Service, ServiceProperty};
 use interoptopus::patterns::{LibraryPattern, TypePattern};
-use interoptopus::util::{longest_common_prefix, safe_name, sort_types_by_dependencies};
+use interoptopus::util::{has_deprecations, longest_common_prefix, safe_name, sort_types_by_dependencies};
 use interoptopus::writer::{IndentWriter, WriteFor};
 use interoptopus::{indented, non_service_functions, Error, Inventory};
 
@@ -20,8 +22,17 @@ pub trait PythonWriter {
 
     fn write_imports(&self, w: &mut IndentWriter) -> Result<(), Error> {
         indented!(w, r#"from __future__ import annotations"#)?;
+        if self.inventory().functions().iter().any(|x| x.async_callback().is_some()) {
+            indented!(w, r#"import asyncio"#)?;
+        }
         indented!(w, r#"import ctypes"#)?;
+        if self.inventory().patterns().iter().any(|LibraryPattern::Service(x)| self.config().locks_service(x)) {
+            indented!(w, r#"import threading"#)?;
+        }
         indented!(w, r#"import typing"#)?;
+        if has_deprecations(self.inventory()) {
+            indented!(w, r#"import warnings"#)?;
+        }
         w.newline()?;
         indented!(w, r#"T = typing.TypeVar("T")"#)?;
         Ok(())
@@ -63,14 +74,70 @@ pub trait PythonWriter {
             }
         }
 
+        self.write_layout_guard(w)?;
+
         Ok(())
     }
 
+    fn write_layout_guard(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let Some(guard) = self.inventory().functions().iter().find(|x| is_layout_guard(x)) else {
+            return Ok(());
+        };
+
+        let mut composites = layout_composites(self.inventory());
+        composites.retain(|x| !x.is_empty());
+
+        w.newline()?;
+        indented!(w, [_], r#"type_layouts = {{"#)?;
+        for c in composites {
+            indented!(w, [_ _], r#"{}: ("{}", {}),"#, layout_key(c), c.rust_name(), c.rust_name())?;
+        }
+        indented!(w, [_], r#"}}"#)?;
+        indented!(w, [_], r#"layouts = c_lib.{}()"#, guard.name())?;
+        indented!(w, [_], r#"for i in range(len(layouts)):"#)?;
+        indented!(w, [_ _], r#"layout = layouts[i]"#)?;
+        indented!(w, [_ _], r#"if layout.name_hash not in type_layouts:"#)?;
+        indented!(w, [_ _ _], r#"continue"#)?;
+        indented!(w, [_ _], r#"name, t = type_layouts[layout.name_hash]"#)?;
+        indented!(w, [_ _], r#"if layout.size != ctypes.sizeof(t) or layout.align != ctypes.alignment(t):"#)?;
+        indented!(w, [_ _ _], r#"raise TypeError(f"Struct {{name}} has size {{layout.size}} and alignment {{layout.align}} in the library, but {{ctypes.sizeof(t)}} and {{ctypes.alignment(t)}} in the bindings. You probably forgot to update / copy either the bindings or the library.")"#)?;
+
+        Ok(())
+    }
+
+    /// Emits a `DeprecationWarning` pointing at the caller if the item is deprecated.
+    fn write_deprecation_warning(&self, w: &mut IndentWriter, name: &str, deprecation: &Deprecation) -> Result<(), Error> {
+        let message = match deprecation {
+            Deprecation::Deprecated(Some(note)) => format!("{} is deprecated: {}", name, note),
+            Deprecation::Deprecated(None) => format!("{} is deprecated.", name),
+            Deprecation::NotDeprecated => return Ok(()),
+        };
+
+        let message = message.replace('\\', "\\\\").replace('"', "\\\"");
+        indented!(w, r#"warnings.warn("{}", DeprecationWarning, stacklevel=2)"#, message)
+    }
+
     fn write_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        for c in self.inventory().constants() {
+        for c in self.inventory().constants().iter().filter(|x| !x.value().is_aggregate()) {
+            indented!(w, r#"{} = {}"#, c.name(), self.converter().constant_value_to_value(c.value()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes array and struct constants, which can only be defined after their types.
+    fn write_typed_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let constants = self.inventory().constants().iter().filter(|x| x.value().is_aggregate()).collect::<Vec<_>>();
+
+        for c in &constants {
             indented!(w, r#"{} = {}"#, c.name(), self.converter().constant_value_to_value(c.value()))?;
         }
 
+        if !constants.is_empty() {
+            w.newline()?;
+            w.newline()?;
+        }
+
         Ok(())
     }
 
@@ -89,6 +156,7 @@ pub trait PythonWriter {
                     TypePattern::Option(c) => {
                         self.write_option(w, c)?;
                     }
+                    TypePattern::ContextCallback(c) => self.write_context_callback(w, c)?,
                     _ => continue,
                 },
                 _ => continue,
@@ -147,6 +215,12 @@ pub trait PythonWriter {
             indented!(w, [_], r#"def __init__(self, {}):"#, extra_args)?;
 
             if write_for == WriteFor::Code {
+                w.indent();
+                w.indent();
+                self.write_deprecation_warning(w, c.rust_name(), c.meta().deprecation())?;
+                w.unindent();
+                w.unindent();
+
                 for field in c.fields().iter() {
                     indented!(w, [_ _], r#"if {} is not None:"#, field.name())?;
                     indented!(w, [_ _ _], r#"self.{} = {}"#, field.name(), field.name())?;
@@ -176,6 +250,12 @@ pub trait PythonWriter {
                 indented!(w, [_ _], r#""""{}""""#, documentation)?;
             }
 
+            w.indent();
+            w.indent();
+            self.write_deprecation_warning(w, &format!("{}.{}", c.rust_name(), f.name()), f.deprecation())?;
+            w.unindent();
+            w.unindent();
+
             match f.the_type() {
                 CType::Pattern(_) => indented!(w, [_ _], r#"return ctypes.Structure.__get__(self, "{}")"#, f.name())?,
                 _ => indented!(w, [_ _], r#"return ctypes.Structure.__get__(self, "{}")"#, f.name())?,
@@ -188,6 +268,11 @@ pub trait PythonWriter {
             if !documentation.is_empty() {
                 indented!(w, [_ _], r#""""{}""""#, documentation)?;
             }
+            w.indent();
+            w.indent();
+            self.write_deprecation_warning(w, &format!("{}.{}", c.rust_name(), f.name()), f.deprecation())?;
+            w.unindent();
+            w.unindent();
             indented!(w, [_ _], r#"return ctypes.Structure.__set__(self, "{}", value)"#, f.name())?;
         }
 
@@ -207,6 +292,11 @@ pub trait PythonWriter {
                 for line in v.documentation().lines() {
                     indented!(w, [_], r#"# {}"#, line)?;
                 }
+                match v.deprecation() {
+                    Deprecation::Deprecated(Some(note)) => indented!(w, [_], r#"# Deprecated: {}"#, note)?,
+                    Deprecation::Deprecated(None) => indented!(w, [_], r#"# Deprecated."#)?,
+                    Deprecation::NotDeprecated => {}
+                }
             }
             indented!(w, [_], r#"{} = {}"#, v.name(), v.value())?;
         }
@@ -246,15 +336,20 @@ pub trait PythonWriter {
     fn write_function(&self, w: &mut IndentWriter, function: &Function, write_for: WriteFor) -> Result<(), Error> {
         let rval_sig = self.converter().to_type_hint_out(function.signature().rval());
         let args = self.function_args_to_string(function, true, false);
-        let documentation = function.meta().documentation().lines().join("\n");
+        let documentation = self.converter().function_documentation(function).lines().join("\n");
+        let name = self.converter().function_name_to_python_name(function, "");
 
-        indented!(w, r#"def {}({}){}:"#, function.name(), args, rval_sig)?;
+        indented!(w, r#"def {}({}){}:"#, name, args, rval_sig)?;
 
         if write_for == WriteFor::Code {
             if !documentation.is_empty() {
                 indented!(w, [_], r#""""{}""""#, documentation)?;
             }
 
+            w.indent();
+            self.write_deprecation_warning(w, &name, function.meta().deprecation())?;
+            w.unindent();
+
             self.write_param_helpers(w, function)?;
             self.write_library_call(w, function, None)?;
             w.newline()?;
@@ -262,9 +357,117 @@ pub trait PythonWriter {
             indented!(w, [_], r#"..."#)?;
         }
 
+        if function.async_callback().is_some() {
+            self.write_async_function(w, function, write_for)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes an `async def` resolving once the function's `async_callback` fired.
+    fn write_async_function(&self, w: &mut IndentWriter, function: &Function, write_for: WriteFor) -> Result<(), Error> {
+        let args = self.async_function_args_to_string(function, false);
+        let documentation = self.converter().function_documentation(function).lines().join("\n");
+        let name = self.converter().function_name_to_python_name(function, "");
+
+        indented!(w, r#"async def {}_async({}){}:"#, name, args, self.async_function_type_hint_out(function))?;
+
+        if write_for == WriteFor::Code {
+            if !documentation.is_empty() {
+                indented!(w, [_], r#""""{}""""#, documentation)?;
+            }
+
+            w.indent();
+            self.write_deprecation_warning(w, &format!("{}_async", name), function.meta().deprecation())?;
+            w.unindent();
+
+            self.write_async_function_body(w, function, &name, false)?;
+            w.newline()?;
+        } else {
+            indented!(w, [_], r#"..."#)?;
+        }
+
+        Ok(())
+    }
+
+    /// Invokes `call` with a callback resolving an `asyncio` future.
+    ///
+    /// The callback is kept alive in a module-level dict until it fired, so cancelling the awaiting task
+    /// does not free it while Rust still holds the pointer. Errors returned through an `FFIError` raise
+    /// right away; any other return value is ignored, the function must then invoke the callback.
+    fn write_async_function_body(&self, w: &mut IndentWriter, function: &Function, call: &str, skip_first: bool) -> Result<(), Error> {
+        let async_param = function.async_callback().expect("Must be an async function.");
+        let callback = match async_param.the_type() {
+            CType::Pattern(TypePattern::NamedCallback(x)) => x.fnpointer(),
+            _ => panic!(
+                "Parameter `{}` of `{}` must be a named callback to be used as `async_callback`.",
+                async_param.name(),
+                function.name()
+            ),
+        };
+
+        let callback_params = (0..callback.signature().params().len()).map(|i| format!("x{}", i)).collect::<Vec<_>>();
+        let callback_result = match callback_params.len() {
+            0 => "None".to_string(),
+            1 => callback_params[0].clone(),
+            _ => format!("({})", callback_params.join(", ")),
+        };
+
+        let skip = if skip_first { 1 } else { 0 };
+        let args = function.signature().params().iter().skip(skip).map(|x| x.name()).collect::<Vec<_>>();
+
+        indented!(w, [_], r#"loop = asyncio.get_running_loop()"#)?;
+        indented!(w, [_], r#"future = loop.create_future()"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def _resolve(result):"#)?;
+        indented!(w, [_ _], r#"_pending_callbacks.pop(id({}), None)"#, async_param.name())?;
+        indented!(w, [_ _], r#"if not future.done():"#)?;
+        indented!(w, [_ _ _], r#"future.set_result(result)"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def _complete({}):"#, callback_params.join(", "))?;
+        indented!(w, [_ _], r#"loop.call_soon_threadsafe(_resolve, {})"#, callback_result)?;
+        match callback.signature().rval() {
+            CType::Primitive(PrimitiveType::Void) => {}
+            CType::Pattern(TypePattern::FFIErrorEnum(e)) => indented!(w, [_ _], r#"return {}"#, e.success_variant().value())?,
+            _ => indented!(w, [_ _], r#"return 0"#)?,
+        }
+        w.newline()?;
+        indented!(w, [_], r#"{} = callbacks.{}(_complete)"#, async_param.name(), safe_name(&callback.internal_name()))?;
+        indented!(w, [_], r#"_pending_callbacks[id({})] = {}"#, async_param.name(), async_param.name())?;
+        indented!(w, [_], r#"try:"#)?;
+        indented!(w, [_ _], r#"{}({})"#, call, args.join(", "))?;
+        indented!(w, [_], r#"except BaseException:"#)?;
+        indented!(w, [_ _], r#"_pending_callbacks.pop(id({}), None)"#, async_param.name())?;
+        indented!(w, [_ _], r#"raise"#)?;
+        indented!(w, [_], r#"return await future"#)?;
+
         Ok(())
     }
 
+    fn async_function_args_to_string(&self, function: &Function, skip_first: bool) -> String {
+        let async_param = function.async_callback().expect("Must be an async function.");
+        let skip = if skip_first { 1 } else { 0 };
+        function
+            .signature()
+            .params()
+            .iter()
+            .skip(skip)
+            .filter(|x| x.name() != async_param.name())
+            .map(|x| format!("{}{}", x.name(), self.converter().to_type_hint_in(x.the_type(), true)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn async_function_type_hint_out(&self, function: &Function) -> String {
+        match function.async_callback().map(|x| x.the_type()) {
+            Some(CType::Pattern(TypePattern::NamedCallback(x))) => match x.fnpointer().signature().params() {
+                [param] => self.converter().to_type_hint_out(param.the_type()),
+                _ => "".to_string(),
+            },
+            _ => "".to_string(),
+        }
+    }
+
     fn write_param_helpers(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
         for arg in function.signature().params() {
             match arg.the_type() {
@@ -280,6 +483,12 @@ pub trait PythonWriter {
                         indented!(w, [_ _], r#"{} = callbacks.{}({})"#, arg.name(), safe_name(&x.internal_name()), arg.name())?;
                         w.newline()?;
                     }
+                    TypePattern::ContextCallback(x) => {
+                        indented!(w, [_], r#"if not isinstance({}, {}):"#, arg.name(), x.name())?;
+                        indented!(w, [_ _], r#"{} = {}({})"#, arg.name(), x.name(), arg.name())?;
+                        indented!(w, [_], r#"{} = {}._take()"#, arg.name(), arg.name())?;
+                        w.newline()?;
+                    }
                     TypePattern::CStrPointer => {
                         indented!(w, [_], r#"if not hasattr({}, "__ctypes_from_outparam__"):"#, arg.name())?;
                         indented!(w, [_ _], r#"{} = ctypes.cast({}, ctypes.POINTER(ctypes.c_char))"#, arg.name(), arg.name())?;
@@ -446,6 +655,61 @@ pub trait PythonWriter {
         Ok(())
     }
 
+    fn write_context_callback(&self, w: &mut IndentWriter, c: &ContextCallback) -> Result<(), Error> {
+        let name = c.name();
+        let documentation = c.meta().documentation().lines().join("\n");
+        let params = c.signature().params().iter().map(|x| x.name()).collect::<Vec<_>>();
+
+        indented!(w, r#"class {}(ctypes.Structure):"#, name)?;
+        if !documentation.is_empty() {
+            indented!(w, [_], r#""""{}""""#, documentation)?;
+        } else {
+            indented!(w, [_], r#""""Wraps a Python callable so native code can call it back.""""#)?;
+        }
+        w.newline()?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        for field in c.composite().fields() {
+            indented!(w, [_ _], r#"("{}", {}),"#, field.name(), self.converter().to_ctypes_name(field.the_type(), true))?;
+        }
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"# Callables handed to native code, kept alive until native code calls the destructor."#)?;
+        indented!(w, [_], r#"_alive = {{}}"#)?;
+        indented!(w, [_], r#"_next_context = 1"#)?;
+        indented!(w, [_], r#"_taken = False"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __init__(self, f: typing.Callable = None):"#)?;
+        indented!(w, [_ _], r#"super().__init__()"#)?;
+        indented!(w, [_ _], r#"if f is not None:"#)?;
+        indented!(w, [_ _ _], r#"context = {}._next_context"#, name)?;
+        indented!(w, [_ _ _], r#"{}._next_context += 1"#, name)?;
+        indented!(w, [_ _ _], r#"{}._alive[context] = f"#, name)?;
+        indented!(w, [_ _ _], r#"self.callback = {}._callback_fn"#, name)?;
+        indented!(w, [_ _ _], r#"self.context = context"#)?;
+        indented!(w, [_ _ _], r#"self.destructor = {}._destructor_fn"#, name)?;
+        w.newline()?;
+        indented!(w, [_], r#"def _take(self):"#)?;
+        indented!(w, [_ _], r#""""Hands the context to native code, which releases it. Each instance can only be passed once.""""#)?;
+        indented!(w, [_ _], r#"if self._taken:"#)?;
+        indented!(w, [_ _ _], r#"raise ValueError("This {} was already passed to native code and cannot be passed again.")"#, name)?;
+        indented!(w, [_ _], r#"self._taken = True"#)?;
+        indented!(w, [_ _], r#"return self"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"@staticmethod"#)?;
+        indented!(w, [_], r#"def _call({}):"#, params.iter().copied().chain(["context"]).collect::<Vec<_>>().join(", "))?;
+        indented!(w, [_ _], r#"return {}._alive[context]({})"#, name, params.join(", "))?;
+        w.newline()?;
+        indented!(w, [_], r#"@staticmethod"#)?;
+        indented!(w, [_], r#"def _destroy(context):"#)?;
+        indented!(w, [_ _], r#"{}._alive.pop(context, None)"#, name)?;
+        w.newline()?;
+        w.newline()?;
+        indented!(w, r#"{}._callback_fn = {}._fields_[0][1]({}._call)"#, name, name, name)?;
+        indented!(w, r#"{}._destructor_fn = {}._fields_[2][1]({}._destroy)"#, name, name, name)?;
+
+        Ok(())
+    }
+
     fn write_patterns(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for pattern in self.inventory().patterns() {
             match pattern {
@@ -459,22 +723,24 @@ pub trait PythonWriter {
     fn write_pattern_class(&self, w: &mut IndentWriter, class: &Service) -> Result<(), Error> {
         let context_type_name = class.the_type().rust_name();
 
-        let mut all_functions = class.constructors().to_vec();
-        all_functions.extend_from_slice(class.methods());
-        all_functions.push(class.destructor().clone());
-
-        let _common_prefix = longest_common_prefix(&all_functions);
+        let _common_prefix = longest_common_prefix(&class.all_functions());
         let documentation = class.the_type().meta().documentation().lines().join("\n");
 
+        let docstring = match documentation.is_empty() {
+            true => format!(" {}", class.thread_safety().description()),
+            false => format!("{}\n\n {}", documentation, class.thread_safety().description()),
+        };
+
         indented!(w, r#"class {}:"#, context_type_name)?;
-        if !documentation.is_empty() {
-            indented!(w, [_], r#""""{}""""#, documentation)?;
-        }
+        indented!(w, [_], r#""""{}""""#, docstring)?;
         indented!(w, [_], r#"__api_lock = object()"#)?;
         w.newline()?;
         indented!(w, [_], r#"def __init__(self, api_lock, ctx):"#)?;
         indented!(w, [_ _], r#"assert(api_lock == {}.__api_lock), "You must create this with a static constructor." "#, context_type_name)?;
         indented!(w, [_ _], r#"self._ctx = ctx"#)?;
+        if self.config().locks_service(class) {
+            indented!(w, [_ _], r#"self._lock = threading.Lock()"#)?;
+        }
         w.newline()?;
         indented!(w, [_], r#"@property"#)?;
         indented!(w, [_], r#"def _as_parameter_(self):"#)?;
@@ -485,6 +751,10 @@ pub trait PythonWriter {
             self.write_pattern_class_ctor(w, class, ctor, WriteFor::Code)?;
         }
 
+        for function in class.static_methods() {
+            self.write_pattern_class_static_method(w, class, function, WriteFor::Code)?;
+        }
+
         // Dtor
         indented!(w, [_], r#"def __del__(self):"#)?;
         // indented!(w, [_ _], r#"global _api, ffi"#)?;
@@ -492,8 +762,36 @@ pub trait PythonWriter {
         self.write_success_enum_aware_rval(w, class.destructor(), &self.get_method_args(class.destructor(), "self._ctx"), false)?;
         w.unindent();
 
+        // Shared services hand out another reference on `copy.copy`, with a pointer of its own for `__del__` to clear.
+        if let Some(retain) = class.retain() {
+            w.newline()?;
+            indented!(w, [_], r#"def __copy__(self) -> {}:"#, context_type_name)?;
+            indented!(w, [_ _], r#"c_lib.{}(self._ctx)"#, retain.name())?;
+            indented!(w, [_ _], r#"rval = {}({}.__api_lock, ctypes.c_void_p(self._ctx.value))"#, context_type_name, context_type_name)?;
+            if is_created_by_service(self.inventory().patterns(), class.the_type()) {
+                indented!(w, [_ _], r#"rval._owner = self._owner"#)?;
+            }
+            // Copies share the instance, so they must also share its lock.
+            if self.config().locks_service(class) {
+                indented!(w, [_ _], r#"rval._lock = self._lock"#)?;
+            }
+            indented!(w, [_ _], r#"return rval"#)?;
+            w.newline()?;
+        }
+
+        for property in class.properties() {
+            self.write_pattern_class_property(w, class, property, WriteFor::Code)?;
+        }
+
         for function in class.methods() {
-            self.write_pattern_class_method(w, class, function, WriteFor::Code)?;
+            // Getters and setters were written as properties.
+            if class.property_of(function).is_none() {
+                self.write_pattern_class_method(w, class, function, WriteFor::Code)?;
+            }
+        }
+
+        for function in class.factories() {
+            self.write_pattern_class_factory(w, class, function, WriteFor::Code)?;
         }
 
         w.newline()?;
@@ -504,23 +802,25 @@ pub trait PythonWriter {
 
     fn write_pattern_class_ctor(&self, w: &mut IndentWriter, class: &Service, ctor: &Function, write_for: WriteFor) -> Result<(), Error> {
         let context_type_name = class.the_type().rust_name();
-        let mut all_functions = class.constructors().to_vec();
-        all_functions.extend_from_slice(class.methods());
-        all_functions.push(class.destructor().clone());
 
-        let common_prefix = longest_common_prefix(&all_functions);
+        let common_prefix = longest_common_prefix(&class.all_functions());
 
         let ctor_args = self.function_args_to_string(ctor, true, true);
+        let ctor_method_name = self.converter().function_name_to_python_name(ctor, &common_prefix);
         indented!(w, [_], r#"@staticmethod"#)?;
-        indented!(w, [_], r#"def {}({}) -> {}:"#, ctor.name().replace(&common_prefix, ""), ctor_args, context_type_name)?;
+        indented!(w, [_], r#"def {}({}) -> {}:"#, ctor_method_name, ctor_args, context_type_name)?;
 
         if write_for == WriteFor::Docs {
             return Ok(());
         }
 
-        indented!(w, [_ _], r#"{}"#, self.converter().documentation(ctor.meta().documentation()))?;
+        indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(ctor)))?;
         indented!(w, [_ _], r#"ctx = ctypes.c_void_p()"#)?;
         w.indent();
+        let ctor_name = format!("{}.{}", context_type_name, ctor_method_name);
+        w.indent();
+        self.write_deprecation_warning(w, &ctor_name, ctor.meta().deprecation())?;
+        w.unindent();
         self.write_param_helpers(w, ctor)?;
         self.write_success_enum_aware_rval(w, ctor, &self.get_method_args(ctor, "ctx"), false)?;
         w.unindent();
@@ -532,30 +832,204 @@ pub trait PythonWriter {
     }
 
     fn write_pattern_class_method(&self, w: &mut IndentWriter, class: &Service, function: &Function, write_for: WriteFor) -> Result<(), Error> {
-        let mut all_functions = class.constructors().to_vec();
-        all_functions.extend_from_slice(class.methods());
-        all_functions.push(class.destructor().clone());
-
-        let common_prefix = longest_common_prefix(&all_functions);
+        let common_prefix = longest_common_prefix(&class.all_functions());
 
         let args = self.function_args_to_string(function, true, true);
         let type_hint_out = self.converter().to_type_hint_out(function.signature().rval());
+        let method_name = self.converter().function_name_to_python_name(function, &common_prefix);
 
-        indented!(w, [_], r#"def {}(self, {}){}:"#, function.name().replace(&common_prefix, ""), &args, type_hint_out)?;
+        indented!(w, [_], r#"def {}(self, {}){}:"#, method_name, &args, type_hint_out)?;
 
         if write_for == WriteFor::Docs {
             return Ok(());
         }
 
-        indented!(w, [_ _], r#"{}"#, self.converter().documentation(function.meta().documentation()))?;
+        indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(function)))?;
+
+        let class_name = class.the_type().rust_name();
 
         w.indent();
+        w.indent();
+        self.write_deprecation_warning(w, &format!("{}.{}", class_name, method_name), function.meta().deprecation())?;
+        w.unindent();
         self.write_param_helpers(w, function)?;
         w.unindent();
 
-        self.write_library_call(w, function, Some("self._ctx"))?;
+        self.write_method_library_call(w, class, function)?;
         w.newline()?;
 
+        if function.async_callback().is_some() {
+            let args = self.async_function_args_to_string(function, true);
+            let separator = if args.is_empty() { "" } else { ", " };
+            let type_hint_out = self.async_function_type_hint_out(function);
+
+            indented!(w, [_], r#"async def {}_async(self{}{}){}:"#, method_name, separator, args, type_hint_out)?;
+            indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(function)))?;
+            w.indent();
+            w.indent();
+            self.write_deprecation_warning(w, &format!("{}.{}_async", class_name, method_name), function.meta().deprecation())?;
+            w.unindent();
+            self.write_async_function_body(w, function, &format!("self.{}", method_name), true)?;
+            w.unindent();
+            w.newline()?;
+        }
+
+        Ok(())
+    }
+
+    fn write_pattern_class_static_method(&self, w: &mut IndentWriter, class: &Service, function: &Function, write_for: WriteFor) -> Result<(), Error> {
+        let common_prefix = longest_common_prefix(&class.all_functions());
+
+        let args = self.function_args_to_string(function, true, false);
+        let type_hint_out = self.converter().to_type_hint_out(function.signature().rval());
+        let method_name = self.converter().function_name_to_python_name(function, &common_prefix);
+
+        indented!(w, [_], r#"@staticmethod"#)?;
+        indented!(w, [_], r#"def {}({}){}:"#, method_name, &args, type_hint_out)?;
+
+        if write_for == WriteFor::Docs {
+            return Ok(());
+        }
+
+        indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(function)))?;
+
+        let class_name = class.the_type().rust_name();
+
+        w.indent();
+        w.indent();
+        self.write_deprecation_warning(w, &format!("{}.{}", class_name, method_name), function.meta().deprecation())?;
+        w.unindent();
+        self.write_param_helpers(w, function)?;
+        self.write_library_call(w, function, None)?;
+        w.unindent();
+        w.newline()?;
+
+        if function.async_callback().is_some() {
+            let args = self.async_function_args_to_string(function, false);
+            let type_hint_out = self.async_function_type_hint_out(function);
+
+            indented!(w, [_], r#"@staticmethod"#)?;
+            indented!(w, [_], r#"async def {}_async({}){}:"#, method_name, args, type_hint_out)?;
+            indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(function)))?;
+            w.indent();
+            w.indent();
+            self.write_deprecation_warning(w, &format!("{}.{}_async", class_name, method_name), function.meta().deprecation())?;
+            w.unindent();
+            self.write_async_function_body(w, function, &format!("{}.{}", class_name, method_name), false)?;
+            w.unindent();
+            w.newline()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a method returning another service, which keeps `self` alive via `_owner`.
+    fn write_pattern_class_factory(&self, w: &mut IndentWriter, class: &Service, function: &Function, write_for: WriteFor) -> Result<(), Error> {
+        let common_prefix = longest_common_prefix(&class.all_functions());
+
+        // Skip the context and the out-parameter receiving the new service.
+        let params = function.signature().params().iter().skip(2).collect::<Vec<_>>();
+        let args = params
+            .iter()
+            .map(|x| format!("{}{}", x.name(), self.converter().to_type_hint_in(x.the_type(), true)))
+            .collect::<Vec<_>>();
+        let created_type_name = class.factory_type(function).expect("Must be a factory.").rust_name().to_string();
+        let method_name = self.converter().function_name_to_python_name(function, &common_prefix);
+        let separator = if args.is_empty() { "" } else { ", " };
+
+        indented!(w, [_], r#"def {}(self{}{}) -> {}:"#, method_name, separator, args.join(", "), created_type_name)?;
+
+        if write_for == WriteFor::Docs {
+            return Ok(());
+        }
+
+        indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(function)))?;
+
+        let class_name = class.the_type().rust_name();
+        let call_args = ["self._ctx", "ctx"].into_iter().chain(params.iter().map(|x| x.name())).collect::<Vec<_>>();
+
+        w.indent();
+        w.indent();
+        self.write_deprecation_warning(w, &format!("{}.{}", class_name, method_name), function.meta().deprecation())?;
+        w.unindent();
+        self.write_param_helpers(w, function)?;
+        indented!(w, [_], r#"ctx = ctypes.c_void_p()"#)?;
+        if self.config().locks_service(class) {
+            indented!(w, [_], r#"with self._lock:"#)?;
+            w.indent();
+            self.write_success_enum_aware_rval(w, function, &call_args.join(", "), false)?;
+            w.unindent();
+        } else {
+            self.write_success_enum_aware_rval(w, function, &call_args.join(", "), false)?;
+        }
+        indented!(w, [_], r#"rval = {}({}._{}__api_lock, ctx)"#, created_type_name, created_type_name, created_type_name)?;
+        indented!(w, [_], r#"rval._owner = self"#)?;
+        indented!(w, [_], r#"return rval"#)?;
+        w.unindent();
+        w.newline()?;
+
+        Ok(())
+    }
+
+    /// Writes a getter and setter pair as `@property`, a property without getter becomes `property(fset=...)`.
+    fn write_pattern_class_property(&self, w: &mut IndentWriter, class: &Service, property: &ServiceProperty, write_for: WriteFor) -> Result<(), Error> {
+        let class_name = class.the_type().rust_name();
+        let name = property.name();
+
+        if let Some(getter) = property.getter() {
+            let type_hint_out = self.converter().to_type_hint_out(getter.signature().rval());
+
+            indented!(w, [_], r#"@property"#)?;
+            indented!(w, [_], r#"def {}(self){}:"#, name, type_hint_out)?;
+
+            if write_for == WriteFor::Docs {
+                indented!(w, [_ _], r#"..."#)?;
+            } else {
+                indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(getter)))?;
+                w.indent();
+                w.indent();
+                self.write_deprecation_warning(w, &format!("{}.{}", class_name, name), getter.meta().deprecation())?;
+                w.unindent();
+                w.unindent();
+                self.write_method_library_call(w, class, getter)?;
+            }
+
+            w.newline()?;
+        }
+
+        if let Some(setter) = property.setter() {
+            let args = self.function_args_to_string(setter, true, true);
+            let setter_name = match property.getter() {
+                Some(_) => {
+                    indented!(w, [_], r#"@{}.setter"#, name)?;
+                    name.to_string()
+                }
+                None => format!("_set_{}", name),
+            };
+
+            indented!(w, [_], r#"def {}(self, {}):"#, setter_name, args)?;
+
+            if write_for == WriteFor::Docs {
+                indented!(w, [_ _], r#"..."#)?;
+            } else {
+                indented!(w, [_ _], r#"{}"#, self.converter().documentation(&self.converter().function_documentation(setter)))?;
+                w.indent();
+                w.indent();
+                self.write_deprecation_warning(w, &format!("{}.{}", class_name, name), setter.meta().deprecation())?;
+                w.unindent();
+                self.write_param_helpers(w, setter)?;
+                w.unindent();
+                self.write_setter_library_call(w, class, setter)?;
+            }
+
+            if property.getter().is_none() {
+                w.newline()?;
+                indented!(w, [_], r#"{} = property(fset={})"#, name, setter_name)?;
+            }
+
+            w.newline()?;
+        }
+
         Ok(())
     }
 
@@ -583,6 +1057,37 @@ pub trait PythonWriter {
         Ok(())
     }
 
+    /// Calls `function` on `self._ctx`, holding the instance's lock if the service has one.
+    fn write_method_library_call(&self, w: &mut IndentWriter, class: &Service, function: &Function) -> Result<(), Error> {
+        if !self.config().locks_service(class) {
+            return self.write_library_call(w, function, Some("self._ctx"));
+        }
+
+        indented!(w, [_ _], r#"with self._lock:"#)?;
+        w.indent();
+        self.write_library_call(w, function, Some("self._ctx"))?;
+        w.unindent();
+        Ok(())
+    }
+
+    /// Calls a property setter, whose return value Python discards.
+    ///
+    /// Failing `FFIError` setters therefore must raise, which the `errcheck` installed in `init_lib` does.
+    fn write_setter_library_call(&self, w: &mut IndentWriter, class: &Service, setter: &Function) -> Result<(), Error> {
+        let args = self.get_method_args(setter, "self._ctx");
+        let call = match setter.signature().rval() {
+            CType::Pattern(TypePattern::FFIErrorEnum(_)) => format!("c_lib.{}({})  # Raises if the setter fails.", setter.name(), args),
+            _ => format!("c_lib.{}({})", setter.name(), args),
+        };
+
+        if self.config().locks_service(class) {
+            indented!(w, [_ _], r#"with self._lock:"#)?;
+            indented!(w, [_ _ _], r#"{}"#, call)
+        } else {
+            indented!(w, [_ _], r#"{}"#, call)
+        }
+    }
+
     fn function_args_to_string(&self, function: &Function, type_hints: bool, skip_first: bool) -> String {
         let skip = if skip_first { 1 } else { 0 };
         function
@@ -654,6 +1159,11 @@ pub trait PythonWriter {
         w.newline()?;
         w.newline()?;
 
+        indented!(w, r#"# Completion callbacks of pending `async` calls, kept alive until native code invoked them."#)?;
+        indented!(w, r#"_pending_callbacks = {{}}"#)?;
+        w.newline()?;
+        w.newline()?;
+
         indented!(w, r#"class CallbackVars(object):"#)?;
         indented!(
             w,
@@ -687,10 +1197,48 @@ pub trait PythonWriter {
         w.newline()?;
         w.newline()?;
 
+        for primitive in [PrimitiveType::U128, PrimitiveType::I128] {
+            if self.inventory().ctypes().contains(&CType::Primitive(primitive)) {
+                self.write_utils_primitive(w, primitive)?;
+            }
+        }
+
         Ok(())
     }
 
-    fn write_utils_primitive(&self, _: &mut IndentWriter, _: PrimitiveType) -> Result<(), Error> {
+    fn write_utils_primitive(&self, w: &mut IndentWriter, primitive: PrimitiveType) -> Result<(), Error> {
+        let (name, signed) = match primitive {
+            PrimitiveType::U128 => ("c_uint128", false),
+            PrimitiveType::I128 => ("c_int128", true),
+            _ => return Ok(()),
+        };
+
+        indented!(w, r#"class {}(ctypes.Structure):"#, name)?;
+        indented!(w, [_], r#""""128-bit integer as two words, since ctypes has no native type for it.""""#)?;
+        indented!(w, [_], r#"# Matches the alignment of Rust's 128-bit integers; honored by ctypes since Python 3.13."#)?;
+        indented!(w, [_], r#"_align_ = 16"#)?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        indented!(w, [_], r#"    ("lo", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"    ("hi", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __init__(self, value: int = 0):"#)?;
+        indented!(w, [_ _], r#"super().__init__(value & 0xFFFFFFFFFFFFFFFF, (value >> 64) & 0xFFFFFFFFFFFFFFFF)"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __int__(self) -> int:"#)?;
+        indented!(w, [_ _], r#"value = self.lo | (self.hi << 64)"#)?;
+        if signed {
+            indented!(w, [_ _], r#"if value >= 1 << 127:"#)?;
+            indented!(w, [_ _ _], r#"value -= 1 << 128"#)?;
+        }
+        indented!(w, [_ _], r#"return value"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"@classmethod"#)?;
+        indented!(w, [_], r#"def from_param(cls, value):"#)?;
+        indented!(w, [_ _], r#"return value if isinstance(value, cls) else cls(value)"#)?;
+        w.newline()?;
+        w.newline()?;
+
         Ok(())
     }
 
@@ -713,6 +1261,8 @@ pub trait PythonWriter {
         w.newline()?;
         w.newline()?;
 
+        self.write_typed_constants(w)?;
+
         self.write_callback_helpers(w)?;
         w.newline()?;
         w.newline()?;
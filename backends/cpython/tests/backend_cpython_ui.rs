@@ -1,29 +1,66 @@
-use interoptopus::testing::assert_file_matches_generated;
+use interoptopus::conformance::TestSuite;
+use interoptopus::testing::assert_output_set_matches;
+use interoptopus::writer::OutputSet;
 use interoptopus::{Error, Interop};
-use interoptopus_backend_cpython::{run_python_if_installed, DocConfig, DocGenerator};
+use interoptopus_backend_cpython::{run_python_if_installed, ConformanceConfig, ConformanceGenerator, DocConfig, DocGenerator};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+
+/// Bindings for the reference project, with locks for services not `Sync`.
+fn bindings_config() -> interoptopus_backend_cpython::Config {
+    interoptopus_backend_cpython::Config {
+        lock_non_sync_services: true,
+        ..Default::default()
+    }
+}
 
 fn generate_bindings(output: &str) -> Result<(), Error> {
-    use interoptopus_backend_cpython::{Config, Generator};
+    use interoptopus_backend_cpython::Generator;
 
-    Generator::new(Config::default(), interoptopus_reference_project::ffi_inventory()).write_file(output)
+    Generator::new(bindings_config(), interoptopus_reference_project::ffi_inventory()).write_file(output)
 }
 
 fn generate_documentation(output: &str) -> Result<(), Error> {
-    use interoptopus_backend_cpython::{Config, Generator};
+    use interoptopus_backend_cpython::Generator;
 
     let inventory = interoptopus_reference_project::ffi_inventory();
-    let generator = Generator::new(Config::default(), interoptopus_reference_project::ffi_inventory());
+    let generator = Generator::new(bindings_config(), interoptopus_reference_project::ffi_inventory());
 
     DocGenerator::new(&inventory, &generator, DocConfig::default()).write_file(output)
 }
 
+/// The reference project as built by `cargo build`, relative to `tests/output/`.
+fn reference_library() -> String {
+    format!("../../../../target/debug/{}interoptopus_reference_project{}", DLL_PREFIX, DLL_SUFFIX)
+}
+
+fn generate_conformance(output: &mut OutputSet, library: &str) -> Result<(), Error> {
+    use interoptopus_backend_cpython::Generator;
+
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let suite = TestSuite::from_inventory(&inventory, &interoptopus_reference_project::ffi_recorder());
+    let generator = Generator::new(bindings_config(), inventory);
+    let config = ConformanceConfig {
+        module: "reference_project".to_string(),
+        library: library.to_string(),
+    };
+
+    output.write("conformance.py", |w| ConformanceGenerator::new(&generator, &suite, config).write_to(w))
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn bindings_match_reference() -> Result<(), Error> {
-    generate_bindings("tests/output/reference_project.py")?;
+    use interoptopus_backend_cpython::Generator;
+
+    let generator = Generator::new(bindings_config(), interoptopus_reference_project::ffi_inventory());
+    let mut output = OutputSet::new();
+    output.write("reference_project.py", |w| generator.write_to(w))?;
+    // The library only matters when running the tests, so keep the reference output platform independent.
+    generate_conformance(&mut output, "interoptopus_reference_project")?;
+
     generate_documentation("tests/output/reference_project.md")?;
 
-    assert_file_matches_generated("tests/output/reference_project.py");
+    assert_output_set_matches("tests/output/", &output);
 
     Ok(())
 }
@@ -33,7 +70,12 @@ fn bindings_match_reference() -> Result<(), Error> {
 fn bindings_work() -> Result<(), Error> {
     generate_bindings("tests/output/reference_project.py")?;
 
+    let mut conformance = OutputSet::new();
+    generate_conformance(&mut conformance, &reference_library())?;
+    conformance.write_to_directory("tests/output/")?;
+
     run_python_if_installed("tests/output/", "tests.py")?;
+    run_python_if_installed("tests/output/", "conformance.py")?;
 
     Ok(())
 }
@@ -1,6 +1,9 @@
 from __future__ import annotations
+import asyncio
 import ctypes
+import threading
 import typing
+import warnings
 
 T = typing.TypeVar("T")
 c_lib = None
@@ -21,6 +24,11 @@ def init_lib(path):
     c_lib.primitive_i16.argtypes = [ctypes.c_int16]
     c_lib.primitive_i32.argtypes = [ctypes.c_int32]
     c_lib.primitive_i64.argtypes = [ctypes.c_int64]
+    c_lib.primitive_usize.argtypes = [ctypes.c_size_t]
+    c_lib.primitive_isize.argtypes = [ctypes.c_ssize_t]
+    c_lib.primitive_u128.argtypes = [c_uint128]
+    c_lib.primitive_i128.argtypes = [c_int128]
+    c_lib.primitive_char.argtypes = [ctypes.c_uint32]
     c_lib.boolean_alignment.argtypes = [BooleanAlignment]
     c_lib.boolean_alignment2.argtypes = [ctypes.c_bool]
     c_lib.packed_to_packed1.argtypes = [Packed1]
@@ -44,6 +52,7 @@ def init_lib(path):
     c_lib.generic_4.argtypes = [ctypes.c_void_p]
     c_lib.array_1.argtypes = [Array]
     c_lib.documented.argtypes = [StructDocumented]
+    c_lib.deprecated.argtypes = [StructDeprecated]
     c_lib.ambiguous_1.argtypes = [Vec1]
     c_lib.ambiguous_2.argtypes = [Vec2]
     c_lib.ambiguous_3.argtypes = [Vec1, Vec2]
@@ -51,11 +60,15 @@ def init_lib(path):
     c_lib.namespaced_inner_option.argtypes = [OptionVec]
     c_lib.namespaced_inner_slice.argtypes = [SliceVec]
     c_lib.namespaced_inner_slice_mut.argtypes = [SliceMutVec]
+    c_lib.namespaced_function.argtypes = [Vec]
+    c_lib.checked.argtypes = [ctypes.c_uint32]
+    c_lib.skipped_in_csharp.argtypes = [ctypes.c_uint32]
     c_lib.panics.argtypes = []
     c_lib.renamed.argtypes = [StructRenamed]
     c_lib.sleep.argtypes = [ctypes.c_uint64]
     c_lib.weird_1.argtypes = [Weird1u32, Weird2u8]
     c_lib.visibility.argtypes = [Visibility1, Visibility2]
+    c_lib.visibility_crate.argtypes = [VisibilityCrate]
     c_lib.repr_transparent.argtypes = [Tupled, ctypes.POINTER(Tupled)]
     c_lib.pattern_ascii_pointer_1.argtypes = [ctypes.POINTER(ctypes.c_char)]
     c_lib.pattern_ascii_pointer_2.argtypes = []
@@ -78,6 +91,7 @@ def init_lib(path):
     c_lib.pattern_ffi_cchar_const_pointer.argtypes = [ctypes.POINTER(ctypes.c_char)]
     c_lib.pattern_ffi_cchar_mut_pointer.argtypes = [ctypes.POINTER(ctypes.c_char)]
     c_lib.pattern_api_guard.argtypes = []
+    c_lib.pattern_layout_guard.argtypes = []
     c_lib.pattern_callback_1.argtypes = [ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32), ctypes.c_uint32]
     c_lib.pattern_callback_2.argtypes = [ctypes.CFUNCTYPE(None, ctypes.c_void_p)]
     c_lib.pattern_callback_3.argtypes = [DelegateCallbackMyCallbackContextual, ctypes.c_uint32]
@@ -85,16 +99,22 @@ def init_lib(path):
     c_lib.pattern_callback_5.argtypes = []
     c_lib.pattern_callback_6.argtypes = []
     c_lib.pattern_callback_7.argtypes = [ctypes.CFUNCTYPE(ctypes.c_int, ctypes.c_int32, ctypes.c_int32), ctypes.CFUNCTYPE(None, ctypes.c_int32, ctypes.c_int32), ctypes.c_int32, ctypes.c_int32, ctypes.POINTER(ctypes.c_int32)]
+    c_lib.pattern_callback_8.argtypes = [ctypes.c_uint32, ctypes.CFUNCTYPE(None, ctypes.c_uint32)]
+    c_lib.pattern_callback_9.argtypes = [MyClosure, ctypes.c_uint32]
     c_lib.pattern_surrogates_1.argtypes = [Local, ctypes.POINTER(Container)]
     c_lib.simple_service_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
     c_lib.simple_service_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
     c_lib.simple_service_new_without.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
     c_lib.simple_service_new_with_string.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.POINTER(ctypes.c_char)]
     c_lib.simple_service_new_failing.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint8]
+    c_lib.simple_service_get_value.argtypes = [ctypes.c_void_p]
+    c_lib.simple_service_set_value.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
     c_lib.simple_service_method_result.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
     c_lib.simple_service_method_value.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
     c_lib.simple_service_method_void.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_method_void2.argtypes = [ctypes.c_void_p]
+    c_lib.simple_service_method_void_deprecated.argtypes = [ctypes.c_void_p]
+    c_lib.simple_service_lambda.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_method_mut_self.argtypes = [ctypes.c_void_p, SliceU8]
     c_lib.simple_service_method_mut_self_void.argtypes = [ctypes.c_void_p, SliceBool]
     c_lib.simple_service_method_mut_self_ref.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_uint8), ctypes.POINTER(ctypes.c_uint8)]
@@ -109,12 +129,29 @@ def init_lib(path):
     c_lib.simple_service_method_callback.argtypes = [ctypes.c_void_p, ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32)]
     c_lib.simple_service_method_callback_ffi_return.argtypes = [ctypes.c_void_p, ctypes.CFUNCTYPE(ctypes.c_int, ctypes.c_int32, ctypes.c_int32)]
     c_lib.simple_service_method_callback_ffi_return_with_slice.argtypes = [ctypes.c_void_p, ctypes.CFUNCTYPE(ctypes.c_int, ctypes.c_int32, ctypes.c_int32), SliceI32]
+    c_lib.simple_service_method_callback_completion.argtypes = [ctypes.c_void_p, ctypes.c_uint32, ctypes.CFUNCTYPE(None, ctypes.c_uint32)]
+    c_lib.simple_service_supported_formats.argtypes = []
+    c_lib.simple_service_validate.argtypes = [ctypes.c_uint32]
+    c_lib.simple_service_create_child.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
+    c_lib.simple_service_child_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
+    c_lib.simple_service_child_value.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_lifetime_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
     c_lib.simple_service_lifetime_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.POINTER(ctypes.c_uint32)]
     c_lib.simple_service_lifetime_method_lt.argtypes = [ctypes.c_void_p, SliceBool]
     c_lib.simple_service_lifetime_method_lt2.argtypes = [ctypes.c_void_p, SliceBool]
     c_lib.simple_service_lifetime_return_string_accept_slice.argtypes = [ctypes.c_void_p, SliceU8]
     c_lib.simple_service_lifetime_method_void_ffi_error.argtypes = [ctypes.c_void_p]
+    c_lib.simple_service_namespaced_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
+    c_lib.simple_service_namespaced_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
+    c_lib.simple_service_namespaced_value.argtypes = [ctypes.c_void_p]
+    c_lib.shared_service_release.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
+    c_lib.shared_service_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
+    c_lib.shared_service_value.argtypes = [ctypes.c_void_p]
+    c_lib.shared_service_retain.argtypes = [ctypes.c_void_p]
+    c_lib.single_threaded_service_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
+    c_lib.single_threaded_service_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
+    c_lib.single_threaded_service_increment.argtypes = [ctypes.c_void_p]
+    c_lib.single_threaded_service_value.argtypes = [ctypes.c_void_p]
 
     c_lib.primitive_bool.restype = ctypes.c_bool
     c_lib.primitive_u8.restype = ctypes.c_uint8
@@ -125,6 +162,11 @@ def init_lib(path):
     c_lib.primitive_i16.restype = ctypes.c_int16
     c_lib.primitive_i32.restype = ctypes.c_int32
     c_lib.primitive_i64.restype = ctypes.c_int64
+    c_lib.primitive_usize.restype = ctypes.c_size_t
+    c_lib.primitive_isize.restype = ctypes.c_ssize_t
+    c_lib.primitive_u128.restype = c_uint128
+    c_lib.primitive_i128.restype = c_int128
+    c_lib.primitive_char.restype = ctypes.c_uint32
     c_lib.boolean_alignment.restype = BooleanAlignment
     c_lib.boolean_alignment2.restype = BooleanAlignment
     c_lib.packed_to_packed1.restype = Packed2
@@ -148,6 +190,7 @@ def init_lib(path):
     c_lib.generic_4.restype = ctypes.c_uint8
     c_lib.array_1.restype = ctypes.c_uint8
     c_lib.documented.restype = ctypes.c_int
+    c_lib.deprecated.restype = ctypes.c_int
     c_lib.ambiguous_1.restype = Vec1
     c_lib.ambiguous_2.restype = Vec2
     c_lib.ambiguous_3.restype = ctypes.c_bool
@@ -155,9 +198,13 @@ def init_lib(path):
     c_lib.namespaced_inner_option.restype = OptionVec
     c_lib.namespaced_inner_slice.restype = SliceVec
     c_lib.namespaced_inner_slice_mut.restype = SliceMutVec
+    c_lib.namespaced_function.restype = ctypes.c_double
+    c_lib.checked.restype = ctypes.c_uint32
+    c_lib.skipped_in_csharp.restype = ctypes.c_uint32
     c_lib.panics.restype = ctypes.c_int
     c_lib.renamed.restype = ctypes.c_int
     c_lib.weird_1.restype = ctypes.c_bool
+    c_lib.visibility_crate.restype = ctypes.c_uint8
     c_lib.repr_transparent.restype = Tupled
     c_lib.pattern_ascii_pointer_1.restype = ctypes.c_uint32
     c_lib.pattern_ascii_pointer_2.restype = ctypes.POINTER(ctypes.c_char)
@@ -176,17 +223,22 @@ def init_lib(path):
     c_lib.pattern_ffi_cchar_const_pointer.restype = ctypes.POINTER(ctypes.c_char)
     c_lib.pattern_ffi_cchar_mut_pointer.restype = ctypes.POINTER(ctypes.c_char)
     c_lib.pattern_api_guard.restype = ctypes.c_uint64
+    c_lib.pattern_layout_guard.restype = SliceTypeLayout
     c_lib.pattern_callback_1.restype = ctypes.c_uint32
     c_lib.pattern_callback_2.restype = ctypes.CFUNCTYPE(None, ctypes.c_void_p)
     c_lib.pattern_callback_4.restype = ctypes.c_uint32
     c_lib.pattern_callback_5.restype = ctypes.CFUNCTYPE(None, )
     c_lib.pattern_callback_6.restype = ctypes.CFUNCTYPE(ctypes.c_int32, ctypes.c_int32, ctypes.c_int32)
     c_lib.pattern_callback_7.restype = ctypes.c_int
+    c_lib.pattern_callback_8.restype = ctypes.c_int
+    c_lib.pattern_callback_9.restype = ctypes.c_uint32
     c_lib.simple_service_destroy.restype = ctypes.c_int
     c_lib.simple_service_new_with.restype = ctypes.c_int
     c_lib.simple_service_new_without.restype = ctypes.c_int
     c_lib.simple_service_new_with_string.restype = ctypes.c_int
     c_lib.simple_service_new_failing.restype = ctypes.c_int
+    c_lib.simple_service_get_value.restype = ctypes.c_uint32
+    c_lib.simple_service_set_value.restype = ctypes.c_int
     c_lib.simple_service_method_result.restype = ctypes.c_int
     c_lib.simple_service_method_value.restype = ctypes.c_uint32
     c_lib.simple_service_method_mut_self.restype = ctypes.c_uint8
@@ -202,19 +254,38 @@ def init_lib(path):
     c_lib.simple_service_method_callback.restype = ctypes.c_int
     c_lib.simple_service_method_callback_ffi_return.restype = ctypes.c_int
     c_lib.simple_service_method_callback_ffi_return_with_slice.restype = ctypes.c_int
+    c_lib.simple_service_method_callback_completion.restype = ctypes.c_int
+    c_lib.simple_service_supported_formats.restype = ctypes.c_uint32
+    c_lib.simple_service_validate.restype = ctypes.c_int
+    c_lib.simple_service_create_child.restype = ctypes.c_int
+    c_lib.simple_service_child_destroy.restype = ctypes.c_int
+    c_lib.simple_service_child_value.restype = ctypes.c_uint32
     c_lib.simple_service_lifetime_destroy.restype = ctypes.c_int
     c_lib.simple_service_lifetime_new_with.restype = ctypes.c_int
     c_lib.simple_service_lifetime_return_string_accept_slice.restype = ctypes.POINTER(ctypes.c_char)
     c_lib.simple_service_lifetime_method_void_ffi_error.restype = ctypes.c_int
+    c_lib.simple_service_namespaced_destroy.restype = ctypes.c_int
+    c_lib.simple_service_namespaced_new_with.restype = ctypes.c_int
+    c_lib.simple_service_namespaced_value.restype = ctypes.c_uint32
+    c_lib.shared_service_release.restype = ctypes.c_int
+    c_lib.shared_service_new_with.restype = ctypes.c_int
+    c_lib.shared_service_value.restype = ctypes.c_uint32
+    c_lib.shared_service_retain.restype = ctypes.c_int
+    c_lib.single_threaded_service_destroy.restype = ctypes.c_int
+    c_lib.single_threaded_service_new_with.restype = ctypes.c_int
+    c_lib.single_threaded_service_increment.restype = ctypes.c_int
+    c_lib.single_threaded_service_value.restype = ctypes.c_uint32
 
     c_lib.complex_args_1.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.panics.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.pattern_callback_7.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.pattern_callback_8.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_without.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_with_string.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_failing.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_set_value.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_result.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_mut_self_ffi_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_mut_self_no_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
@@ -222,9 +293,73 @@ def init_lib(path):
     c_lib.simple_service_method_callback.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_callback_ffi_return.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_callback_ffi_return_with_slice.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_method_callback_completion.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_validate.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_create_child.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_child_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_lifetime_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_lifetime_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_lifetime_method_void_ffi_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_namespaced_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_namespaced_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.shared_service_release.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.shared_service_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.shared_service_retain.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.single_threaded_service_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.single_threaded_service_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.single_threaded_service_increment.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+
+    type_layouts = {
+        7725630223355528838: ("Array", Array),
+        13780230644173993384: ("BooleanAlignment", BooleanAlignment),
+        13908034248162013336: ("Container", Container),
+        7592086385043351515: ("DelegateCallbackMyCallbackContextual", DelegateCallbackMyCallbackContextual),
+        13883161942318069648: ("ExtraTypef32", ExtraTypef32),
+        5595264705378701256: ("Genericu32", Genericu32),
+        8975949700123536863: ("Genericu8", Genericu8),
+        17081007651772294375: ("Inner", Inner),
+        16839520613172088648: ("Local", Local),
+        11111868642027686448: ("Packed1", Packed1),
+        11111871940562571081: ("Packed2", Packed2),
+        8313648167006137431: ("Phantomu8", Phantomu8),
+        8480366299634365705: ("StructDeprecated", StructDeprecated),
+        2363003323943207424: ("StructDocumented", StructDocumented),
+        10152114614164664082: ("StructRenamed", StructRenamed),
+        4272519484055865455: ("Tupled", Tupled),
+        56007294078283895: ("TypeLayout", TypeLayout),
+        17668793176895495864: ("UseAsciiStringPattern", UseAsciiStringPattern),
+        1724911193198685794: ("Vec", Vec),
+        16480943761859895612: ("Vec1", Vec1),
+        16480947060394780245: ("Vec2", Vec2),
+        11529662329476111629: ("Vec3f32", Vec3f32),
+        2808206013834181724: ("Visibility1", Visibility1),
+        2808209312369066357: ("Visibility2", Visibility2),
+        5906906342198409056: ("VisibilityCrate", VisibilityCrate),
+        3463706654236366161: ("Weird1u32", Weird1u32),
+        10874643905297256825: ("Weird2u8", Weird2u8),
+        8449737672036434417: ("SliceBool", SliceBool),
+        5411056133711372521: ("SliceI32", SliceI32),
+        10038116113606839395: ("SliceTypeLayout", SliceTypeLayout),
+        11374494939819850637: ("SliceU32", SliceU32),
+        5419493245234256604: ("SliceU8", SliceU8),
+        1505421970813947124: ("SliceUseAsciiStringPattern", SliceUseAsciiStringPattern),
+        2104600942193532856: ("SliceVec", SliceVec),
+        2872124794895837801: ("SliceVec3f32", SliceVec3f32),
+        8033795909225755975: ("SliceMutConstPtrI8", SliceMutConstPtrI8),
+        3194354960619615953: ("SliceMutU32", SliceMutU32),
+        4368741217356678104: ("SliceMutU8", SliceMutU8),
+        9589520511416213518: ("SliceMutVec", SliceMutVec),
+        6182523805998915764: ("OptionInner", OptionInner),
+        17201604669416752257: ("OptionVec", OptionVec),
+    }
+    layouts = c_lib.pattern_layout_guard()
+    for i in range(len(layouts)):
+        layout = layouts[i]
+        if layout.name_hash not in type_layouts:
+            continue
+        name, t = type_layouts[layout.name_hash]
+        if layout.size != ctypes.sizeof(t) or layout.align != ctypes.alignment(t):
+            raise TypeError(f"Struct {name} has size {layout.size} and alignment {layout.align} in the library, but {ctypes.sizeof(t)} and {ctypes.alignment(t)} in the bindings. You probably forgot to update / copy either the bindings or the library.")
 
 
 def primitive_void():
@@ -260,6 +395,22 @@ def primitive_i32(x: int) -> int:
 def primitive_i64(x: int) -> int:
     return c_lib.primitive_i64(x)
 
+def primitive_usize(x: int) -> int:
+    return c_lib.primitive_usize(x)
+
+def primitive_isize(x: int) -> int:
+    return c_lib.primitive_isize(x)
+
+def primitive_u128(x: int) -> c_uint128:
+    return c_lib.primitive_u128(x)
+
+def primitive_i128(x: int) -> c_int128:
+    return c_lib.primitive_i128(x)
+
+def primitive_char(x: int) -> int:
+    """ Returns the Unicode scalar value of `x`, or `u32::MAX` if `x` is not a valid `char`."""
+    return c_lib.primitive_char(x)
+
 def boolean_alignment(x: BooleanAlignment) -> BooleanAlignment:
     return c_lib.boolean_alignment(x)
 
@@ -333,9 +484,16 @@ def array_1(x: Array) -> int:
     return c_lib.array_1(x)
 
 def documented(x: StructDocumented) -> ctypes.c_int:
-    """ This function has documentation."""
+    """ This function has documentation.
+
+ :param x: A documented struct, its fields are documented as well."""
     return c_lib.documented(x)
 
+def deprecated(x: StructDeprecated) -> ctypes.c_int:
+    """ Superseded by `documented`."""
+    warnings.warn("deprecated is deprecated: Use `documented` instead.", DeprecationWarning, stacklevel=2)
+    return c_lib.deprecated(x)
+
 def ambiguous_1(x: Vec1) -> Vec1:
     return c_lib.ambiguous_1(x)
 
@@ -363,6 +521,18 @@ def namespaced_inner_slice_mut(x: SliceMutVec | ctypes.Array[Vec]) -> SliceMutVe
 
     return c_lib.namespaced_inner_slice_mut(x)
 
+def namespaced_function(x: Vec) -> float:
+    """ Emitted into the `common` namespace."""
+    return c_lib.namespaced_function(x)
+
+def checked(x: int) -> int:
+    """ Named after a C# keyword, which uses another name instead."""
+    return c_lib.checked(x)
+
+def skipped_in_csharp(x: int) -> int:
+    """ Not emitted for C#, whose API guard must still match the full inventory."""
+    return c_lib.skipped_in_csharp(x)
+
 def panics():
     return c_lib.panics()
 
@@ -378,6 +548,9 @@ def weird_1(x: Weird1u32, y: Weird2u8) -> bool:
 def visibility(x: Visibility1, y: Visibility2):
     return c_lib.visibility(x, y)
 
+def visibility_crate(x: VisibilityCrate) -> int:
+    return c_lib.visibility_crate(x)
+
 def repr_transparent(x: Tupled, r: ctypes.POINTER(Tupled)) -> Tupled:
     return c_lib.repr_transparent(x, r)
 
@@ -482,6 +655,9 @@ def pattern_ffi_cchar_mut_pointer(ffi_cchar: ctypes.POINTER(ctypes.c_char)) -> c
 def pattern_api_guard():
     return c_lib.pattern_api_guard()
 
+def pattern_layout_guard() -> SliceTypeLayout:
+    return c_lib.pattern_layout_guard()
+
 def pattern_callback_1(callback, x: int) -> int:
     if not hasattr(callback, "__ctypes_from_outparam__"):
         callback = callbacks.fn_u32_rval_u32(callback)
@@ -518,6 +694,43 @@ def pattern_callback_7(c1, c2, x: int, i: int, o: ctypes.POINTER(ctypes.c_int32)
 
     return c_lib.pattern_callback_7(c1, c2, x, i, o)
 
+def pattern_callback_8(x: int, completion):
+    """ Completes by invoking `completion` exactly once, which bindings may expose as an `async` call."""
+    if not hasattr(completion, "__ctypes_from_outparam__"):
+        completion = callbacks.fn_u32(completion)
+
+    return c_lib.pattern_callback_8(x, completion)
+
+async def pattern_callback_8_async(x: int) -> int:
+    """ Completes by invoking `completion` exactly once, which bindings may expose as an `async` call."""
+    loop = asyncio.get_running_loop()
+    future = loop.create_future()
+
+    def _resolve(result):
+        _pending_callbacks.pop(id(completion), None)
+        if not future.done():
+            future.set_result(result)
+
+    def _complete(x0):
+        loop.call_soon_threadsafe(_resolve, x0)
+
+    completion = callbacks.fn_u32(_complete)
+    _pending_callbacks[id(completion)] = completion
+    try:
+        pattern_callback_8(x, completion)
+    except BaseException:
+        _pending_callbacks.pop(id(completion), None)
+        raise
+    return await future
+
+def pattern_callback_9(callback: MyClosure | typing.Callable, x: int) -> int:
+    """ Invokes `callback` once; its destructor runs when the callback is dropped on return."""
+    if not isinstance(callback, MyClosure):
+        callback = MyClosure(callback)
+    callback = callback._take()
+
+    return c_lib.pattern_callback_9(callback, x)
+
 def pattern_surrogates_1(s: Local, c: ctypes.POINTER(Container)):
     return c_lib.pattern_surrogates_1(s, c)
 
@@ -526,6 +739,8 @@ def pattern_surrogates_1(s: Local, c: ctypes.POINTER(Container)):
 U8 = 255
 F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944
 COMPUTED_I32 = -2147483647
+VERSION = "1.2.3 \"reference\""
+NAMESPACED = 42
 
 
 TRUE = ctypes.c_uint8(1)
@@ -538,6 +753,10 @@ def _errcheck(returned, success):
     else: raise Exception(f"Function returned error: {returned}")
 
 
+# Completion callbacks of pending `async` calls, kept alive until native code invoked them.
+_pending_callbacks = {}
+
+
 class CallbackVars(object):
     """Helper to be used `lambda x: setattr(cv, "x", x)` when getting values from callbacks."""
     def __str__(self):
@@ -565,6 +784,57 @@ class _Iter(object):
         return rval
 
 
+class c_uint128(ctypes.Structure):
+    """128-bit integer as two words, since ctypes has no native type for it."""
+    # Matches the alignment of Rust's 128-bit integers; honored by ctypes since Python 3.13.
+    _align_ = 16
+    _fields_ = [
+        ("lo", ctypes.c_uint64),
+        ("hi", ctypes.c_uint64),
+    ]
+
+    def __init__(self, value: int = 0):
+        super().__init__(value & 0xFFFFFFFFFFFFFFFF, (value >> 64) & 0xFFFFFFFFFFFFFFFF)
+
+    def __int__(self) -> int:
+        value = self.lo | (self.hi << 64)
+        return value
+
+    @classmethod
+    def from_param(cls, value):
+        return value if isinstance(value, cls) else cls(value)
+
+
+class c_int128(ctypes.Structure):
+    """128-bit integer as two words, since ctypes has no native type for it."""
+    # Matches the alignment of Rust's 128-bit integers; honored by ctypes since Python 3.13.
+    _align_ = 16
+    _fields_ = [
+        ("lo", ctypes.c_uint64),
+        ("hi", ctypes.c_uint64),
+    ]
+
+    def __init__(self, value: int = 0):
+        super().__init__(value & 0xFFFFFFFFFFFFFFFF, (value >> 64) & 0xFFFFFFFFFFFFFFFF)
+
+    def __int__(self) -> int:
+        value = self.lo | (self.hi << 64)
+        if value >= 1 << 127:
+            value -= 1 << 128
+        return value
+
+    @classmethod
+    def from_param(cls, value):
+        return value if isinstance(value, cls) else cls(value)
+
+
+class EnumDeprecated:
+    """ Enum with a deprecated variant."""
+    Current = 0
+    # Deprecated: Use `Current` instead.
+    Legacy = 1
+
+
 class EnumDocumented:
     """ Documented enum."""
     #  Variant A.
@@ -894,6 +1164,40 @@ class Phantomu8(ctypes.Structure):
         return ctypes.Structure.__set__(self, "x", value)
 
 
+class StructDeprecated(ctypes.Structure):
+    """ Struct with a deprecated field."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("x", ctypes.c_uint32),
+        ("old_x", ctypes.c_uint32),
+    ]
+
+    def __init__(self, x: int = None, old_x: int = None):
+        if x is not None:
+            self.x = x
+        if old_x is not None:
+            self.old_x = old_x
+
+    @property
+    def x(self) -> int:
+        return ctypes.Structure.__get__(self, "x")
+
+    @x.setter
+    def x(self, value: int):
+        return ctypes.Structure.__set__(self, "x", value)
+
+    @property
+    def old_x(self) -> int:
+        warnings.warn("StructDeprecated.old_x is deprecated: Use `x` instead.", DeprecationWarning, stacklevel=2)
+        return ctypes.Structure.__get__(self, "old_x")
+
+    @old_x.setter
+    def old_x(self, value: int):
+        warnings.warn("StructDeprecated.old_x is deprecated: Use `x` instead.", DeprecationWarning, stacklevel=2)
+        return ctypes.Structure.__set__(self, "old_x", value)
+
+
 class StructDocumented(ctypes.Structure):
     """ Documented struct."""
 
@@ -957,6 +1261,55 @@ class Tupled(ctypes.Structure):
         return ctypes.Structure.__set__(self, "x0", value)
 
 
+class TypeLayout(ctypes.Structure):
+    """Size and alignment of a single type as laid out by Rust."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("name_hash", ctypes.c_uint64),
+        ("size", ctypes.c_uint64),
+        ("align", ctypes.c_uint64),
+    ]
+
+    def __init__(self, name_hash: int = None, size: int = None, align: int = None):
+        if name_hash is not None:
+            self.name_hash = name_hash
+        if size is not None:
+            self.size = size
+        if align is not None:
+            self.align = align
+
+    @property
+    def name_hash(self) -> int:
+        """Hash of the type's namespace and name."""
+        return ctypes.Structure.__get__(self, "name_hash")
+
+    @name_hash.setter
+    def name_hash(self, value: int):
+        """Hash of the type's namespace and name."""
+        return ctypes.Structure.__set__(self, "name_hash", value)
+
+    @property
+    def size(self) -> int:
+        """Size in bytes."""
+        return ctypes.Structure.__get__(self, "size")
+
+    @size.setter
+    def size(self, value: int):
+        """Size in bytes."""
+        return ctypes.Structure.__set__(self, "size", value)
+
+    @property
+    def align(self) -> int:
+        """Alignment in bytes."""
+        return ctypes.Structure.__get__(self, "align")
+
+    @align.setter
+    def align(self, value: int):
+        """Alignment in bytes."""
+        return ctypes.Structure.__set__(self, "align", value)
+
+
 class UseAsciiStringPattern(ctypes.Structure):
 
     # These fields represent the underlying C data layout
@@ -1174,6 +1527,26 @@ class Visibility2(ctypes.Structure):
         return ctypes.Structure.__set__(self, "pblc2", value)
 
 
+class VisibilityCrate(ctypes.Structure):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("x", ctypes.c_uint8),
+    ]
+
+    def __init__(self, x: int = None):
+        if x is not None:
+            self.x = x
+
+    @property
+    def x(self) -> int:
+        return ctypes.Structure.__get__(self, "x")
+
+    @x.setter
+    def x(self, value: int):
+        return ctypes.Structure.__set__(self, "x", value)
+
+
 class Weird1u32(ctypes.Structure):
 
     # These fields represent the underlying C data layout
@@ -1194,6 +1567,50 @@ class Weird1u32(ctypes.Structure):
         return ctypes.Structure.__set__(self, "x", value)
 
 
+class MyClosure(ctypes.Structure):
+    """Wraps a Python callable so native code can call it back."""
+
+    _fields_ = [
+        ("callback", ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32, ctypes.c_void_p)),
+        ("context", ctypes.c_void_p),
+        ("destructor", ctypes.CFUNCTYPE(None, ctypes.c_void_p)),
+    ]
+
+    # Callables handed to native code, kept alive until native code calls the destructor.
+    _alive = {}
+    _next_context = 1
+    _taken = False
+
+    def __init__(self, f: typing.Callable = None):
+        super().__init__()
+        if f is not None:
+            context = MyClosure._next_context
+            MyClosure._next_context += 1
+            MyClosure._alive[context] = f
+            self.callback = MyClosure._callback_fn
+            self.context = context
+            self.destructor = MyClosure._destructor_fn
+
+    def _take(self):
+        """Hands the context to native code, which releases it. Each instance can only be passed once."""
+        if self._taken:
+            raise ValueError("This MyClosure was already passed to native code and cannot be passed again.")
+        self._taken = True
+        return self
+
+    @staticmethod
+    def _call(value, context):
+        return MyClosure._alive[context](value)
+
+    @staticmethod
+    def _destroy(context):
+        MyClosure._alive.pop(context, None)
+
+
+MyClosure._callback_fn = MyClosure._fields_[0][1](MyClosure._call)
+MyClosure._destructor_fn = MyClosure._fields_[2][1](MyClosure._destroy)
+
+
 class Array(ctypes.Structure):
 
     # These fields represent the underlying C data layout
@@ -1794,6 +2211,56 @@ class DelegateCallbackMyCallbackContextual(ctypes.Structure):
         return ctypes.Structure.__set__(self, "context", value)
 
 
+class SliceTypeLayout(ctypes.Structure):
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("data", ctypes.POINTER(TypeLayout)),
+        ("len", ctypes.c_uint64),
+    ]
+
+    def __len__(self):
+        return self.len
+
+    def __getitem__(self, i) -> TypeLayout:
+        if i < 0:
+            index = self.len+i
+        else:
+            index = i
+
+        if index >= self.len:
+            raise IndexError("Index out of range")
+
+        return self.data[index]
+
+    def copied(self) -> SliceTypeLayout:
+        """Returns a shallow, owned copy of the underlying slice.
+
+        The returned object owns the immediate data, but not the targets of any contained
+        pointers. In other words, if your struct contains any pointers the returned object
+        may only be used as long as these pointers are valid. If the struct did not contain
+        any pointers the returned object is valid indefinitely."""
+        array = (TypeLayout * len(self))()
+        ctypes.memmove(array, self.data, len(self) * ctypes.sizeof(TypeLayout))
+        rval = SliceTypeLayout(data=ctypes.cast(array, ctypes.POINTER(TypeLayout)), len=len(self))
+        rval.owned = array  # Store array in returned slice to prevent memory deallocation
+        return rval
+
+    def __iter__(self) -> typing.Iterable[TypeLayout]:
+        return _Iter(self)
+
+    def iter(self) -> typing.Iterable[TypeLayout]:
+        """Convenience method returning a value iterator."""
+        return iter(self)
+
+    def first(self) -> TypeLayout:
+        """Returns the first element of this slice."""
+        return self[0]
+
+    def last(self) -> TypeLayout:
+        """Returns the last element of this slice."""
+        return self[len(self)-1]
+
+
 class SliceUseAsciiStringPattern(ctypes.Structure):
     # These fields represent the underlying C data layout
     _fields_ = [
@@ -2007,6 +2474,10 @@ class SliceMutVec(ctypes.Structure):
 
 
 
+MAGIC = [73, 79, 80, 83]
+UP = Vec3f32(x=0, y=1, z=0)
+
+
 class callbacks:
     """Helpers to define callbacks."""
     fn_u8_rval_u8 = ctypes.CFUNCTYPE(ctypes.c_uint8, ctypes.c_uint8)
@@ -2015,6 +2486,7 @@ class callbacks:
     fn_SliceMutU8 = ctypes.CFUNCTYPE(None, SliceMutU8)
     fn_u8_rval_u8 = ctypes.CFUNCTYPE(ctypes.c_uint8, ctypes.c_uint8)
     fn_u32_rval_u32 = ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32)
+    fn_u32 = ctypes.CFUNCTYPE(None, ctypes.c_uint32)
     fn_ConstPtr_u32 = ctypes.CFUNCTYPE(None, ctypes.c_void_p, ctypes.c_uint32)
     fn_u32_rval_u32 = ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32)
     fn_ConstPtr = ctypes.CFUNCTYPE(None, ctypes.c_void_p)
@@ -2025,7 +2497,9 @@ class callbacks:
 
 
 class SimpleService:
-    """ Some struct we want to expose as a class."""
+    """ Some struct we want to expose as a class.
+
+ Thread safe, instances may be used from multiple threads at the same time."""
     __api_lock = object()
 
     def __init__(self, api_lock, ctx):
@@ -2038,7 +2512,9 @@ class SimpleService:
 
     @staticmethod
     def new_with(some_value: int) -> SimpleService:
-        """ The constructor must return a `Result<Self, Error>`."""
+        """ The constructor must return a `Result<Self, Error>`.
+
+ :param some_value: Stored in the service."""
         ctx = ctypes.c_void_p()
         c_lib.simple_service_new_with(ctx, some_value)
         self = SimpleService(SimpleService.__api_lock, ctx)
@@ -2070,8 +2546,30 @@ class SimpleService:
         self = SimpleService(SimpleService.__api_lock, ctx)
         return self
 
+    @staticmethod
+    def supported_formats() -> int:
+        """ Doesn't need an instance, e.g., to query capabilities before construction."""
+        return c_lib.simple_service_supported_formats()
+
+    @staticmethod
+    def validate(value: int):
+        """ Static methods can fail like any other method.
+
+ :param value: Must not be `0`."""
+        return c_lib.simple_service_validate(value)
+
     def __del__(self):
         c_lib.simple_service_destroy(self._ctx, )
+    @property
+    def value(self) -> int:
+        """ The value this service was created with."""
+        return c_lib.simple_service_get_value(self._ctx, )
+
+    @value.setter
+    def value(self, value: int):
+        """ Setters may fail, which raises an exception in languages with properties."""
+        c_lib.simple_service_set_value(self._ctx, value)  # Raises if the setter fails.
+
     def method_result(self, anon1: int):
         """ Methods returning a Result<(), _> are the default and do not
  need annotations."""
@@ -2091,6 +2589,15 @@ class SimpleService:
         """ Regular void functions don't need an annotation."""
         return c_lib.simple_service_method_void2(self._ctx, )
 
+    def method_void_deprecated(self, ):
+        """"""
+        warnings.warn("SimpleService.method_void_deprecated is deprecated: Use `method_void` instead.", DeprecationWarning, stacklevel=2)
+        return c_lib.simple_service_method_void_deprecated(self._ctx, )
+
+    def lambda_(self, ):
+        """ Named after a Python keyword, and not emitted for C."""
+        return c_lib.simple_service_lambda(self._ctx, )
+
     def method_mut_self(self, slice: SliceU8 | ctypes.Array[ctypes.c_uint8]) -> int:
         """"""
         if hasattr(slice, "_length_") and getattr(slice, "_type_", "") == ctypes.c_uint8:
@@ -2184,9 +2691,71 @@ class SimpleService:
 
         return c_lib.simple_service_method_callback_ffi_return_with_slice(self._ctx, callback, input)
 
+    def method_callback_completion(self, x: int, completion):
+        """"""
+        if not hasattr(completion, "__ctypes_from_outparam__"):
+            completion = callbacks.fn_u32(completion)
+
+        return c_lib.simple_service_method_callback_completion(self._ctx, x, completion)
+
+    async def method_callback_completion_async(self, x: int) -> int:
+        """"""
+        loop = asyncio.get_running_loop()
+        future = loop.create_future()
+
+        def _resolve(result):
+            _pending_callbacks.pop(id(completion), None)
+            if not future.done():
+                future.set_result(result)
+
+        def _complete(x0):
+            loop.call_soon_threadsafe(_resolve, x0)
+
+        completion = callbacks.fn_u32(_complete)
+        _pending_callbacks[id(completion)] = completion
+        try:
+            self.method_callback_completion(x, completion)
+        except BaseException:
+            _pending_callbacks.pop(id(completion), None)
+            raise
+        return await future
+
+    def create_child(self, offset: int) -> SimpleServiceChild:
+        """ Creates another service, which is returned as a fully wrapped object.
+
+ :param offset: Added to this service's value."""
+        ctx = ctypes.c_void_p()
+        c_lib.simple_service_create_child(self._ctx, ctx, offset)
+        rval = SimpleServiceChild(SimpleServiceChild._SimpleServiceChild__api_lock, ctx)
+        rval._owner = self
+        return rval
+
+
+
+class SimpleServiceChild:
+    """ A service without constructors, only created by [`SimpleService::create_child`].
+
+ Thread safe, instances may be used from multiple threads at the same time."""
+    __api_lock = object()
+
+    def __init__(self, api_lock, ctx):
+        assert(api_lock == SimpleServiceChild.__api_lock), "You must create this with a static constructor." 
+        self._ctx = ctx
+
+    @property
+    def _as_parameter_(self):
+        return self._ctx
+
+    def __del__(self):
+        c_lib.simple_service_child_destroy(self._ctx, )
+    def value(self, ) -> int:
+        """"""
+        return c_lib.simple_service_child_value(self._ctx, )
+
 
 
 class SimpleServiceLifetime:
+    """ Thread safe, instances may be used from multiple threads at the same time."""
     __api_lock = object()
 
     def __init__(self, api_lock, ctx):
@@ -2235,3 +2804,106 @@ class SimpleServiceLifetime:
 
 
 
+class SimpleServiceNamespaced:
+    """ A service whose functions and class are emitted into the `common` namespace.
+
+ Thread safe, instances may be used from multiple threads at the same time."""
+    __api_lock = object()
+
+    def __init__(self, api_lock, ctx):
+        assert(api_lock == SimpleServiceNamespaced.__api_lock), "You must create this with a static constructor." 
+        self._ctx = ctx
+
+    @property
+    def _as_parameter_(self):
+        return self._ctx
+
+    @staticmethod
+    def new_with(value: int) -> SimpleServiceNamespaced:
+        """"""
+        ctx = ctypes.c_void_p()
+        c_lib.simple_service_namespaced_new_with(ctx, value)
+        self = SimpleServiceNamespaced(SimpleServiceNamespaced.__api_lock, ctx)
+        return self
+
+    def __del__(self):
+        c_lib.simple_service_namespaced_destroy(self._ctx, )
+    def value(self, ) -> int:
+        """"""
+        return c_lib.simple_service_namespaced_value(self._ctx, )
+
+
+
+class SharedService:
+    """ A reference counted service, instances of which can be shared between owners.
+
+ Thread safe, instances may be used from multiple threads at the same time."""
+    __api_lock = object()
+
+    def __init__(self, api_lock, ctx):
+        assert(api_lock == SharedService.__api_lock), "You must create this with a static constructor." 
+        self._ctx = ctx
+
+    @property
+    def _as_parameter_(self):
+        return self._ctx
+
+    @staticmethod
+    def new_with(value: int) -> SharedService:
+        """"""
+        ctx = ctypes.c_void_p()
+        c_lib.shared_service_new_with(ctx, value)
+        self = SharedService(SharedService.__api_lock, ctx)
+        return self
+
+    def __del__(self):
+        c_lib.shared_service_release(self._ctx, )
+
+    def __copy__(self) -> SharedService:
+        c_lib.shared_service_retain(self._ctx)
+        rval = SharedService(SharedService.__api_lock, ctypes.c_void_p(self._ctx.value))
+        return rval
+
+    def value(self, ) -> int:
+        """"""
+        return c_lib.shared_service_value(self._ctx, )
+
+
+
+class SingleThreadedService:
+    """ A service neither `Send` nor `Sync`, which must stay on the thread that created it.
+
+ Instances must only be used from the thread which created them."""
+    __api_lock = object()
+
+    def __init__(self, api_lock, ctx):
+        assert(api_lock == SingleThreadedService.__api_lock), "You must create this with a static constructor." 
+        self._ctx = ctx
+        self._lock = threading.Lock()
+
+    @property
+    def _as_parameter_(self):
+        return self._ctx
+
+    @staticmethod
+    def new_with(value: int) -> SingleThreadedService:
+        """"""
+        ctx = ctypes.c_void_p()
+        c_lib.single_threaded_service_new_with(ctx, value)
+        self = SingleThreadedService(SingleThreadedService.__api_lock, ctx)
+        return self
+
+    def __del__(self):
+        c_lib.single_threaded_service_destroy(self._ctx, )
+    def increment(self, ):
+        """"""
+        with self._lock:
+            return c_lib.single_threaded_service_increment(self._ctx, )
+
+    def value(self, ) -> int:
+        """"""
+        with self._lock:
+            return c_lib.single_threaded_service_value(self._ctx, )
+
+
+
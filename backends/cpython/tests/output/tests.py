@@ -3,7 +3,9 @@ import reference_project as r
 import unittest
 import sys
 
-r.init_lib("../../../../target/debug/interoptopus_reference_project.dll")
+LIBRARY_NAMES = {"win32": "interoptopus_reference_project.dll", "darwin": "libinteroptopus_reference_project.dylib"}
+
+r.init_lib("../../../../target/debug/" + LIBRARY_NAMES.get(sys.platform, "libinteroptopus_reference_project.so"))
 
 
 # https://stackoverflow.com/questions/52475749/maximum-and-minimum-value-of-c-types-integers-from-python
@@ -99,6 +101,7 @@ class TestFunctions(unittest.TestCase):
             self.assertEqual(11.0, r.ambiguous_2(vec2).z)
             self.assertEqual(True, r.ambiguous_3(vec1, vec2))
 
+    @unittest.skipIf(sys.platform != "win32", "ctypes passes packed structs by value incorrectly outside Windows")
     def test_packed(self):
         p1 = r.Packed1(x=2, y=3)
         p2 = r.packed_to_packed1(p1)
@@ -149,6 +152,16 @@ class TestPatterns(unittest.TestCase):
         self.assertEqual(10, service.method_value(10))
         self.assertEqual(123, slice[0])
 
+    def test_service_failing_setter_raises(self):
+        service = r.SimpleService.new_with(123)
+        service.value = 5
+        self.assertEqual(5, service.value)
+
+        with self.assertRaises(Exception):
+            service.value = 0
+
+        self.assertEqual(5, service.value)
+
     def test_service_new_string(self):
         service = r.SimpleService.new_with_string(b"abc")
 
@@ -1,7 +1,12 @@
+use interoptopus::lang::c::Visibility;
+use interoptopus::patterns::service::Service;
 use interoptopus::util::NamespaceMappings;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// The types to write for the given recorder.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum WriteTypes {
     /// Only write items defined in the library for this namespace.
     Namespace,
@@ -13,6 +18,7 @@ pub enum WriteTypes {
 
 /// How to handle generation of unsupported elements
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Unsupported {
     /// Emit a panic during binding generation.
     Panic,
@@ -32,8 +38,9 @@ impl WriteTypes {
 
 /// The access modifiers for generated CSharp types
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CSharpVisibility {
-    /// Mimics Rust visibility.
+    /// Mimics Rust visibility, `pub` items become `public`, everything else `internal`.
     AsDeclared,
     /// Generates all types as `public class` / `public struct`.
     ForcePublic,
@@ -42,19 +49,38 @@ pub enum CSharpVisibility {
 }
 
 impl CSharpVisibility {
+    /// Access modifier for generated types without a Rust declaration of their own, e.g., helper classes.
     pub fn to_access_modifier(&self) -> &'static str {
         match self {
-            // TODO: `AsDeclared` should ultimately use the declared visibility but for now copy the previous
-            // behavior which is to make everything public.
             CSharpVisibility::AsDeclared => "public",
             CSharpVisibility::ForcePublic => "public",
             CSharpVisibility::ForceInternal => "internal",
         }
     }
+
+    /// Access modifier for a type declared in Rust with the given visibility.
+    pub fn to_access_modifier_for(&self, visibility: &Visibility) -> &'static str {
+        match (self, visibility) {
+            (CSharpVisibility::AsDeclared, Visibility::Public) => "public",
+            (CSharpVisibility::AsDeclared, Visibility::Private) => "internal",
+            _ => self.to_access_modifier(),
+        }
+    }
+
+    /// Access modifier for a member (e.g., a function) declared in Rust with the given visibility.
+    ///
+    /// Members live inside a class already carrying the forced modifier, so only `AsDeclared` restricts them.
+    pub fn to_member_access_modifier(&self, visibility: &Visibility) -> &'static str {
+        match (self, visibility) {
+            (CSharpVisibility::AsDeclared, Visibility::Private) => "internal",
+            _ => "public",
+        }
+    }
 }
 
 /// Whether and how `unsafe` in generated C# should be emitted.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Unsafe {
     /// Do not use C# `unsafe`.
     None,
@@ -74,8 +100,21 @@ impl Unsafe {
     }
 }
 
+/// How fixed-size arrays in struct fields, e.g., `x: [u8; 3]`, should be emitted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum StructArrays {
+    /// Unroll into individual fields `x0, x1, x2`; works everywhere but can be unwieldy for large arrays.
+    Unroll,
+    /// Wrap in a helper struct holding a `fixed` buffer; requires [`Unsafe`] and primitive element types.
+    FixedBuffer,
+    /// Wrap in a helper struct marked `[InlineArray(N)]`; requires .NET 8 or later.
+    InlineArray,
+}
+
 /// The kind of types to use when generating FFI method overloads.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ParamSliceType {
     /// Slices should be passed in as C# arrays.
     Array,
@@ -85,6 +124,7 @@ pub enum ParamSliceType {
 
 /// Configures C# code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
 pub struct Config {
     /// The file header, e.g., `// (c) My Company`.
     pub file_header_comment: String,
@@ -100,11 +140,11 @@ pub struct Config {
     pub namespace_id: String,
     /// Sets the visibility access modifiers for generated types.
     pub visibility_types: CSharpVisibility,
-    /// Whether, say, a `x: [u8; 3]` should become 3 `x0: u8, ...` instead.
+    /// How arrays in structs, say, a `x: [u8; 3]`, should be emitted.
     ///
-    /// If this is not set, interop generation with arrays in structs will fail. This is a somewhat
-    /// open issue w.r.t Unity-sans-unsafe support and feedback would be greatly welcome!
-    pub unroll_struct_arrays: bool,
+    /// Both helper struct variants come with a `this[int]` indexer and a `Length` property. This replaces
+    /// the former `unroll_struct_arrays: bool`, where `true` is now [`StructArrays::Unroll`].
+    pub struct_arrays: StructArrays,
     /// Which types to write.
     pub write_types: WriteTypes,
     /// If enabled bindings will use C# `unsafe` for increased performance; but will need to be enabled in C# project settings to work.
@@ -122,9 +162,20 @@ pub struct Config {
     pub param_slice_type: ParamSliceType,
     /// How to handle unsupported constructs.
     pub unsupported: Unsupported,
+    /// Serialize calls on instances of services which are not `Sync` with a lock in their class.
+    pub lock_non_sync_services: bool,
+    /// Map `u128` and `i128` to `UInt128` and `Int128`; requires .NET 8 or later and is not available in Unity.
+    ///
+    /// If disabled, items using them are handled according to [`unsupported`](Self::unsupported).
+    pub use_int128: bool,
 }
 
-impl Config {}
+impl Config {
+    /// Whether calls on instances of `service` should take a lock, compare [`lock_non_sync_services`](Self::lock_non_sync_services).
+    pub fn locks_service(&self, service: &Service) -> bool {
+        self.lock_non_sync_services && !service.thread_safety().is_sync()
+    }
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -136,7 +187,7 @@ impl Default for Config {
             namespace_mappings: NamespaceMappings::new("My.Company"),
             namespace_id: "".to_string(),
             visibility_types: CSharpVisibility::AsDeclared,
-            unroll_struct_arrays: true,
+            struct_arrays: StructArrays::Unroll,
             write_types: WriteTypes::NamespaceAndInteroptopusGlobal,
             use_unsafe: Unsafe::None,
             rename_symbols: false,
@@ -144,6 +195,8 @@ impl Default for Config {
             work_around_exception_in_callback_no_reentry: true,
             param_slice_type: ParamSliceType::Array,
             unsupported: Unsupported::Panic,
+            lock_non_sync_services: false,
+            use_int128: false,
         }
     }
 }
@@ -154,3 +207,10 @@ pub struct DocConfig {
     /// Header to append to the generated documentation.
     pub header: String,
 }
+
+/// Configures C# conformance test generation.
+#[derive(Clone, Debug, Default)]
+pub struct ConformanceConfig {
+    /// Namespace of the generated test class, e.g., `My.Company.Tests`.
+    pub namespace: String,
+}
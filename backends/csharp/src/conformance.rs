@@ -0,0 +1,100 @@
+use crate::converter::FunctionNameFlavor;
+use crate::{CSharpTypeConverter, CSharpWriter, ConformanceConfig};
+use interoptopus::conformance::{ConformanceWriter, TestSuite};
+use interoptopus::lang::c::PrimitiveValue;
+use interoptopus::writer::IndentWriter;
+use interoptopus::{indented, Error, Interop, Inventory};
+
+/// Writes an xUnit test class calling functions with the vectors of a [`TestSuite`].
+pub struct ConformanceGenerator<'a, W> {
+    csharp_writer: &'a W,
+    suite: &'a TestSuite,
+    config: ConformanceConfig,
+}
+
+impl<'a, W: CSharpWriter> ConformanceGenerator<'a, W> {
+    pub fn new(w: &'a W, suite: &'a TestSuite, config: ConformanceConfig) -> Self {
+        Self { csharp_writer: w, suite, config }
+    }
+
+    pub fn config(&self) -> &ConformanceConfig {
+        &self.config
+    }
+}
+
+impl<W: CSharpWriter> ConformanceWriter for ConformanceGenerator<'_, W> {
+    fn inventory(&self) -> &Inventory {
+        self.csharp_writer.inventory()
+    }
+
+    fn suite(&self) -> &TestSuite {
+        self.suite
+    }
+
+    fn literal(&self, value: &PrimitiveValue) -> String {
+        // Casts make `Assert.Equal` pick the right overload for narrow integers.
+        match value {
+            PrimitiveValue::Bool(x) => x.to_string(),
+            PrimitiveValue::U8(x) => format!("(byte) {}", x),
+            PrimitiveValue::U16(x) => format!("(ushort) {}", x),
+            PrimitiveValue::U32(x) => format!("{}u", x),
+            PrimitiveValue::U64(x) => format!("{}ul", x),
+            PrimitiveValue::I8(x) => format!("(sbyte) {}", x),
+            PrimitiveValue::I16(x) => format!("(short) {}", x),
+            PrimitiveValue::I32(i32::MIN) => "int.MinValue".to_string(),
+            PrimitiveValue::I32(x) => x.to_string(),
+            PrimitiveValue::I64(i64::MIN) => "long.MinValue".to_string(),
+            PrimitiveValue::I64(x) => format!("{}L", x),
+            PrimitiveValue::F32(x) => format!("{:?}f", x),
+            PrimitiveValue::F64(x) => format!("{:?}d", x),
+        }
+    }
+}
+
+impl<W: CSharpWriter> Interop for ConformanceGenerator<'_, W> {
+    fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let writer = self.csharp_writer;
+        let config = writer.config();
+
+        indented!(w, r#"// Automatically generated by Interoptopus."#)?;
+        indented!(w, r#"using Xunit;"#)?;
+        indented!(w, r#"using {};"#, writer.namespace_for_id(&config.namespace_id))?;
+        w.newline()?;
+        indented!(w, r#"namespace {}"#, self.config().namespace)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"public class ConformanceTests"#)?;
+        indented!(w, [_], r#"{{"#)?;
+
+        for (i, (function, vectors)) in self.tested_functions().into_iter().enumerate() {
+            let flavor = match config.rename_symbols {
+                true => FunctionNameFlavor::CSharpMethodNameWithClass,
+                false => FunctionNameFlavor::RawFFIName,
+            };
+            let name = writer.converter().function_name_to_csharp_name(function, flavor);
+
+            if i > 0 {
+                w.newline()?;
+            }
+
+            indented!(w, [_ _], r#"[Fact]"#)?;
+            indented!(w, [_ _], r#"public void {}()"#, function.name())?;
+            indented!(w, [_ _], r#"{{"#)?;
+
+            for vector in vectors {
+                let call = format!("{}.{}({})", config.class, name, self.arguments(vector));
+
+                match vector.expected() {
+                    Some(expected) => indented!(w, [_ _ _], r#"Assert.Equal({}, {});"#, self.literal(expected), call)?,
+                    None => indented!(w, [_ _ _], r#"{};"#, call)?,
+                }
+            }
+
+            indented!(w, [_ _], r#"}}"#)?;
+        }
+
+        indented!(w, [_], r#"}}"#)?;
+        indented!(w, r#"}}"#)?;
+
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use heck::{ToLowerCamelCase, ToUpperCamelCase};
 use interoptopus::lang::c::{
-    CType, CompositeType, ConstantValue, EnumType, Field, FnPointerType, Function, FunctionSignature, OpaqueType, Parameter, PrimitiveType, PrimitiveValue,
+    ArrayType, CType, CompositeType, ConstantValue, EnumType, Field, FnPointerType, Function, FunctionSignature, OpaqueType, Parameter, PrimitiveType, PrimitiveValue,
 };
-use interoptopus::patterns::callbacks::NamedCallback;
+use interoptopus::patterns::callbacks::{ContextCallback, NamedCallback};
 use interoptopus::patterns::TypePattern;
-use interoptopus::util::safe_name;
+use interoptopus::util::{escape_string_literal, safe_name, ControlEscape};
 
 /// Implements [`CSharpTypeConverter`].
 #[derive(Copy, Clone)]
@@ -37,6 +37,11 @@ pub trait CSharpTypeConverter {
             PrimitiveType::I64 => "long".to_string(),
             PrimitiveType::F32 => "float".to_string(),
             PrimitiveType::F64 => "double".to_string(),
+            PrimitiveType::Char => "uint".to_string(),
+            PrimitiveType::USize => "nuint".to_string(),
+            PrimitiveType::ISize => "nint".to_string(),
+            PrimitiveType::U128 => "UInt128".to_string(),
+            PrimitiveType::I128 => "Int128".to_string(),
         }
     }
 
@@ -75,6 +80,7 @@ pub trait CSharpTypeConverter {
                 TypePattern::Bool => true,
                 TypePattern::CChar => true,
                 TypePattern::NamedCallback(_) => false,
+                TypePattern::ContextCallback(_) => true,
             },
             CType::Array(_) => false, // TODO: should check inner and maybe return true
             CType::Enum(_) => true,
@@ -89,6 +95,16 @@ pub trait CSharpTypeConverter {
         x.name().to_string()
     }
 
+    /// Name of the blittable struct crossing the FFI boundary, nested in the managed class of the callback.
+    fn context_callback_to_typename(&self, x: &ContextCallback) -> String {
+        format!("{}.Native", x.name())
+    }
+
+    /// Converts a Rust array `[u8; 16]` to the name of its C# helper struct, e.g., `InteropArray_u8_16`.
+    fn array_to_typename(&self, x: &ArrayType) -> String {
+        format!("InteropArray_{}_{}", safe_name(&x.array_type().name_within_lib()), x.len())
+    }
+
     /// Converts an Rust `fn()` to a C# delegate name such as `InteropDelegate`.
     fn fnpointer_to_typename(&self, x: &FnPointerType) -> String {
         vec!["InteropDelegate".to_string(), safe_name(&x.internal_name())].join("_")
@@ -113,6 +129,7 @@ pub trait CSharpTypeConverter {
                 TypePattern::SliceMut(e) => self.composite_to_typename(e),
                 TypePattern::Option(e) => self.composite_to_typename(e),
                 TypePattern::NamedCallback(e) => self.named_callback_to_typename(e),
+                TypePattern::ContextCallback(e) => self.context_callback_to_typename(e),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_field(&x.fallback_type(), field, composite),
@@ -156,6 +173,7 @@ pub trait CSharpTypeConverter {
                 TypePattern::SliceMut(x) => self.composite_to_typename(x),
                 TypePattern::Option(x) => self.composite_to_typename(x),
                 TypePattern::NamedCallback(x) => self.named_callback_to_typename(x),
+                TypePattern::ContextCallback(x) => self.context_callback_to_typename(x),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_param(&x.fallback_type()),
@@ -180,6 +198,7 @@ pub trait CSharpTypeConverter {
                 TypePattern::SliceMut(x) => self.composite_to_typename(x),
                 TypePattern::Option(x) => self.composite_to_typename(x),
                 TypePattern::NamedCallback(x) => self.named_callback_to_typename(x),
+                TypePattern::ContextCallback(x) => self.context_callback_to_typename(x),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_rval(&x.fallback_type()),
@@ -202,6 +221,35 @@ pub trait CSharpTypeConverter {
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => format!(r#""{}""#, escape_string_literal(x, ControlEscape::Unicode)),
+            _ => self.constant_value_to_expression(value, false),
+        }
+    }
+
+    /// Converts a constant value to a C# expression, e.g., `new Vec2 { x = (float) 1 }`.
+    fn constant_value_to_expression(&self, value: &ConstantValue, rename_symbols: bool) -> String {
+        match value {
+            ConstantValue::Primitive(_) => format!("({}) {}", self.to_typespecifier_in_rval(&value.the_type()), self.constant_value_to_value(value)),
+            ConstantValue::String(_) => self.constant_value_to_value(value),
+            ConstantValue::Array(x, values) => {
+                let values = values.iter().map(|x| self.constant_value_to_expression(x, rename_symbols)).collect::<Vec<_>>();
+                format!("new {}[] {{ {} }}", self.to_typespecifier_in_rval(x.array_type()), values.join(", "))
+            }
+            ConstantValue::Composite(x, values) => {
+                let fields = x
+                    .fields()
+                    .iter()
+                    .zip(values)
+                    .map(|(field, (_, value))| {
+                        format!(
+                            "{} = {}",
+                            self.field_name_to_csharp_name(field, rename_symbols),
+                            self.constant_value_to_expression(value, rename_symbols)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("new {} {{ {} }}", self.composite_to_typename(x), fields.join(", "))
+            }
         }
     }
 
@@ -213,8 +261,12 @@ pub trait CSharpTypeConverter {
         self.to_typespecifier_in_rval(function.signature().rval())
     }
 
-    /// Gets the function name in a specific flavor
+    /// Gets the function name in a specific flavor, unless overridden via `name(csharp = "...")`.
     fn function_name_to_csharp_name(&self, function: &Function, flavor: FunctionNameFlavor) -> String {
+        if let Some(name) = function.meta().overrides().name("csharp") {
+            return name.to_string();
+        }
+
         match flavor {
             FunctionNameFlavor::RawFFIName => function.name().to_string(),
             FunctionNameFlavor::CSharpMethodNameWithClass => function.name().to_upper_camel_case(),
@@ -1,11 +1,14 @@
 use crate::config::DocConfig;
 use crate::converter::{CSharpTypeConverter, FunctionNameFlavor};
 use crate::CSharpWriter;
-use interoptopus::lang::c::{CType, CompositeType, Function};
-use interoptopus::patterns::{LibraryPattern, TypePattern};
-use interoptopus::writer::{IndentWriter, WriteFor};
-use interoptopus::{indented, non_service_functions};
-use interoptopus::{Error, Inventory};
+use heck::ToUpperCamelCase;
+use interoptopus::docs::html::HtmlConfig;
+use interoptopus::docs::{DocFormatter, DocModel};
+use interoptopus::lang::c::{CType, CompositeType, Field, Function};
+use interoptopus::patterns::service::{Service, ServiceProperty};
+use interoptopus::patterns::TypePattern;
+use interoptopus::writer::{IndentWriter, OutputSet, WriteFor};
+use interoptopus::{indented, Error, Inventory};
 use std::fs::File;
 use std::path::Path;
 
@@ -32,328 +35,146 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
         &self.doc_config
     }
 
-    pub fn write_toc(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"## API Overview"#)?;
-
-        w.newline()?;
-        indented!(w, r#"### Functions"#)?;
-        indented!(w, r#"Freestanding callables inside the module."#)?;
-
-        for the_type in non_service_functions(self.inventory) {
-            let doc = the_type.meta().documentation().lines().first().cloned().unwrap_or_default();
-
-            indented!(w, r#" - **[{}](#{})** - {}"#, the_type.name(), the_type.name(), doc)?;
-        }
-
-        w.newline()?;
-        indented!(w, r#"### Classes"#)?;
-        indented!(w, r#"Methods operating on common state."#)?;
-
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
-        }) {
-            let prefix = pattern.common_prefix();
-            let doc = pattern.the_type().meta().documentation().lines().first().cloned().unwrap_or_default();
-            let name = pattern.the_type().rust_name();
-
-            indented!(w, r#" - **[{}](#{})** - {}"#, name, name, doc)?;
+    pub fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        writeln!(w.writer(), "{}", self.config().header)?;
 
-            for x in pattern.constructors() {
-                let func_name = self
-                    .csharp_writer
-                    .converter()
-                    .function_name_to_csharp_name(x, FunctionNameFlavor::CSharpMethodNameWithoutClass(&prefix));
-                let target = format!("{}.{}", name, func_name);
-                let doc = x.meta().documentation().lines().first().cloned().unwrap_or_default();
-                indented!(w, r#"     - **[{}](#{})** <sup>**ctor**</sup> - {}"#, func_name, target, doc)?;
-            }
-            for x in pattern.methods() {
-                let func_name = self
-                    .csharp_writer
-                    .converter()
-                    .function_name_to_csharp_name(x, FunctionNameFlavor::CSharpMethodNameWithoutClass(&prefix));
-                let target = format!("{}.{}", name, func_name);
-                let doc = x.meta().documentation().lines().first().cloned().unwrap_or_default();
-                indented!(w, r#"     - **[{}](#{})** - {}"#, func_name, target, doc)?;
-            }
-        }
+        DocModel::from_inventory(self.inventory, self)?.write_markdown(w)?;
 
         w.newline()?;
-        indented!(w, r#"### Enums"#)?;
-        indented!(w, r#"Groups of related constants."#)?;
 
-        for the_type in self.inventory.ctypes().iter().filter_map(|x| match x {
-            CType::Enum(e) => Some(e),
-            _ => None,
-        }) {
-            let doc = the_type.meta().documentation().lines().first().cloned().unwrap_or_default();
-            indented!(w, r#" - **[{}](#{})** - {}"#, the_type.rust_name(), the_type.rust_name(), doc)?;
-        }
+        Ok(())
+    }
 
-        w.newline()?;
-        indented!(w, r#"### Data Structs"#)?;
-        indented!(w, r#"Composite data used by functions and methods."#)?;
+    /// Renders the documentation as a static HTML site into `output`.
+    pub fn write_html(&self, output: &mut OutputSet, config: &HtmlConfig) -> Result<(), Error> {
+        DocModel::from_inventory(self.inventory, self)?.write_html(output, config)
+    }
 
-        for the_type in self.inventory.ctypes().iter() {
-            match the_type {
-                CType::Composite(c) => {
-                    let doc = c.meta().documentation().lines().first().cloned().unwrap_or_default();
-                    indented!(w, r#" - **[{}](#{})** - {}"#, c.rust_name(), c.rust_name(), doc)?;
-                }
-                CType::Pattern(p @ TypePattern::Option(_)) => {
-                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
-                    indented!(w, r#" - **[{}](#{})** - A boolean flag and optionally data."#, c.rust_name(), c.rust_name())?;
-                }
-                CType::Pattern(p @ TypePattern::Slice(_)) => {
-                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
-                    indented!(w, r#" - **[{}](#{})** - A pointer and length of un-owned elements."#, c.rust_name(), c.rust_name())?;
-                }
-                _ => continue,
-            }
-        }
+    pub fn write_file<P: AsRef<Path>>(&self, file_name: P) -> Result<(), Error> {
+        let mut file = File::create(file_name)?;
+        let mut writer = IndentWriter::new(&mut file);
 
-        w.newline()?;
-        indented!(w, r#"---"#)?;
-        w.newline()?;
+        self.write_to(&mut writer)
+    }
 
-        Ok(())
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_toc`.")]
+    pub fn write_toc(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        DocModel::from_inventory(self.inventory, self)?.write_markdown_toc(w)
     }
 
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_composites`.")]
     pub fn write_types(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Types "#)?;
-
-        for the_type in self.inventory().ctypes() {
-            match the_type {
-                CType::Composite(e) => self.write_composite(w, e)?,
-                CType::Pattern(p @ TypePattern::Option(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
-                CType::Pattern(p @ TypePattern::Slice(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
-                _ => continue,
-            };
-
-            w.newline()?;
-            indented!(w, r#"---"#)?;
-            w.newline()?;
-        }
-
-        Ok(())
+        DocModel::from_inventory(self.inventory, self)?.write_markdown_composites(w)
     }
 
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_composite`.")]
     pub fn write_composite(&self, w: &mut IndentWriter, composite: &CompositeType) -> Result<(), Error> {
-        let meta = composite.meta();
-
-        w.newline()?;
-        w.newline()?;
-
-        indented!(w, r#" ### <a name="{}">**{}**</a>"#, composite.rust_name(), composite.rust_name())?;
-        w.newline()?;
-
-        for line in meta.documentation().lines() {
-            indented!(w, r#"{}"#, line.trim())?;
-        }
+        let model = DocModel::from_inventory(self.inventory, self)?;
 
-        w.newline()?;
-
-        indented!(w, r#"#### Fields "#)?;
-        for f in composite.fields() {
-            let doc = f.documentation().lines().join("\n");
-            let name = self
-                .csharp_writer
-                .converter()
-                .field_name_to_csharp_name(f, self.csharp_writer.config().rename_symbols);
-            indented!(w, r#"- **{}** - {} "#, name, doc)?;
+        match model.composite(composite) {
+            Some(x) => model.write_markdown_composite(w, x),
+            None => Ok(()),
         }
-
-        indented!(w, r#"#### Definition "#)?;
-        indented!(w, r#"```csharp"#)?;
-        self.csharp_writer.write_type_definition_composite_body(w, composite, WriteFor::Docs)?;
-        indented!(w, r#"```"#)?;
-
-        Ok(())
     }
 
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_enums`.")]
     pub fn write_enums(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Enums "#)?;
-
-        for the_type in self.inventory().ctypes() {
-            let the_enum = match the_type {
-                CType::Enum(e) => e,
-                _ => continue,
-            };
-
-            let meta = the_enum.meta();
-
-            w.newline()?;
-            w.newline()?;
-
-            indented!(w, r#" ### <a name="{}">**{}**</a>"#, the_type.name_within_lib(), the_type.name_within_lib())?;
-            w.newline()?;
-
-            for line in meta.documentation().lines() {
-                indented!(w, r#"{}"#, line.trim())?;
-            }
-            w.newline()?;
-
-            indented!(w, r#"#### Variants "#)?;
-            for v in the_enum.variants() {
-                let doc = v.documentation().lines().join("\n");
-                indented!(w, r#"- **{}** - {} "#, v.name(), doc)?;
-            }
-
-            indented!(w, r#"#### Definition "#)?;
-            indented!(w, r#"```csharp"#)?;
-            self.csharp_writer.write_type_definition_enum(w, the_enum, WriteFor::Docs)?;
-            indented!(w, r#"```"#)?;
-            w.newline()?;
-            indented!(w, r#"---"#)?;
-            w.newline()?;
-        }
-
-        Ok(())
+        DocModel::from_inventory(self.inventory, self)?.write_markdown_enums(w)
     }
 
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_functions`.")]
     pub fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Functions"#)?;
-
-        for the_type in non_service_functions(self.inventory()) {
-            self.write_function(w, the_type)?;
-        }
+        DocModel::from_inventory(self.inventory, self)?.write_markdown_functions(w)
+    }
 
-        Ok(())
+    #[deprecated(since = "0.14.26", note = "Use `write_to`, or `DocModel::write_markdown_services`.")]
+    pub fn write_services(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        DocModel::from_inventory(self.inventory, self)?.write_markdown_services(w)
     }
+}
 
-    fn write_function(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
-        indented!(w, r#"### <a name="{}">**{}**</a>"#, function.name(), function.name())?;
+impl<W: CSharpWriter> DocFormatter for DocGenerator<'_, W> {
+    fn language(&self) -> &str {
+        "csharp"
+    }
 
-        for line in function.meta().documentation().lines() {
-            if line.trim().starts_with('#') {
-                write!(w.writer(), "##")?;
-            }
-            indented!(w, r#"{}"#, line.trim())?;
+    fn type_name(&self, the_type: &CType) -> String {
+        match the_type {
+            CType::Array(x) => format!("{}[]", self.type_name(x.array_type())),
+            _ => self.csharp_writer.converter().to_typespecifier_in_param(the_type),
         }
-
-        indented!(w, r#"#### Definition "#)?;
-        indented!(w, r#"```csharp"#)?;
-        self.csharp_writer.write_function(w, function, WriteFor::Docs)?;
-        indented!(w, r#"```"#)?;
-        w.newline()?;
-        indented!(w, r#"---"#)?;
-        w.newline()?;
-
-        Ok(())
     }
 
-    pub fn write_services(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        indented!(w, r#"# Classes"#)?;
-
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
-        }) {
-            let prefix = pattern.common_prefix();
-            let doc = pattern.the_type().meta().documentation().lines();
-            let class_name = pattern.the_type().rust_name();
-
-            indented!(w, r#"## <a name="{}">**{}**</a>"#, class_name, class_name)?;
-
-            for line in doc {
-                let line = line.replace(" # ", " #### ");
-                let line = line.replace(" ## ", " ##### ");
-                let line = line.replace(" ### ", " ###### ");
-
-                indented!(w, r#"{}"#, line)?;
-            }
-
-            for x in pattern.constructors() {
-                let fname = self
-                    .csharp_writer
-                    .converter()
-                    .function_name_to_csharp_name(x, FunctionNameFlavor::CSharpMethodNameWithoutClass(&prefix));
-                let target = fname.to_string();
-                indented!(w, r#"### <a name="{}">**{}**</a> <sup>ctor</sup>"#, target, target)?;
-
-                let doc = x.meta().documentation().lines();
-                for line in doc {
-                    let line = line.replace(" # ", " #### ");
-                    let line = line.replace(" ## ", " ##### ");
-                    let line = line.replace(" ### ", " ###### ");
-                    indented!(w, r#"{}"#, line)?;
-                }
-                w.newline()?;
-                indented!(w, r#"#### Definition "#)?;
-                indented!(w, r#"```csharp"#)?;
-                self.csharp_writer
-                    .write_pattern_service_method(w, pattern, x, class_name, &fname, true, true, WriteFor::Docs)?;
-                indented!(w, r#"```"#)?;
-                w.newline()?;
-                indented!(w, r#"---"#)?;
-                w.newline()?;
-            }
-
-            for x in pattern.methods() {
-                let fname = self
-                    .csharp_writer
-                    .converter()
-                    .function_name_to_csharp_name(x, FunctionNameFlavor::CSharpMethodNameWithoutClass(&prefix));
-                let target = fname.to_string();
-
-                let rval = match x.signature().rval() {
-                    CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-                    CType::Pattern(TypePattern::CStrPointer) => "string".to_string(),
-                    _ => self.csharp_writer.converter().to_typespecifier_in_rval(x.signature().rval()),
-                };
+    fn function_name(&self, function: &Function, service: Option<&Service>) -> String {
+        match service {
+            Some(service) => self
+                .csharp_writer
+                .converter()
+                .function_name_to_csharp_name(function, FunctionNameFlavor::CSharpMethodNameWithoutClass(&service.common_prefix())),
+            None => self.csharp_writer.converter().function_name_to_csharp_name(function, FunctionNameFlavor::RawFFIName),
+        }
+    }
 
-                indented!(w, r#"### <a name="{}">**{}**</a>"#, target, target)?;
+    fn field_name(&self, _composite: &CompositeType, field: &Field) -> String {
+        self.csharp_writer
+            .converter()
+            .field_name_to_csharp_name(field, self.csharp_writer.config().rename_symbols)
+    }
 
-                let doc = x.meta().documentation().lines();
-                for line in doc {
-                    let line = line.replace(" # ", " #### ");
-                    let line = line.replace(" ## ", " ##### ");
-                    let line = line.replace(" ### ", " ###### ");
-                    indented!(w, r#"{}"#, line)?;
-                }
+    fn write_signature(&self, w: &mut IndentWriter, function: &Function, service: Option<&Service>) -> Result<(), Error> {
+        let Some(service) = service else {
+            return self.csharp_writer.write_function(w, function, WriteFor::Docs);
+        };
 
-                w.newline()?;
-                indented!(w, r#"#### Definition "#)?;
-                indented!(w, r#"```csharp"#)?;
-                indented!(w, r#"{} class {} {{"#, self.csharp_writer.config().visibility_types.to_access_modifier(), class_name)?;
-                w.indent();
-                self.csharp_writer
-                    .write_pattern_service_method(w, pattern, x, &rval, &fname, false, false, WriteFor::Docs)?;
-                for overload in self.csharp_writer.overloads() {
-                    overload.write_service_method_overload(w, self.csharp_writer.helper(), pattern, x, &fname, WriteFor::Docs)?;
-                }
-                w.unindent();
-                indented!(w, r#"}}"#)?;
-                indented!(w, r#"```"#)?;
-                w.newline()?;
-                indented!(w, r#"---"#)?;
-                w.newline()?;
-            }
+        let class_name = service.the_type().rust_name();
+        let fname = self.function_name(function, Some(service));
 
-            w.newline()?;
-            w.newline()?;
+        if service.constructors().contains(function) {
+            return self
+                .csharp_writer
+                .write_pattern_service_method(w, service, function, class_name, &fname, true, true, WriteFor::Docs);
         }
 
-        Ok(())
+        let rval = match function.signature().rval() {
+            _ if service.factories().contains(function) => service.factory_type(function).expect("Must be a factory.").rust_name().to_string(),
+            CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
+            CType::Pattern(TypePattern::CStrPointer) => "string".to_string(),
+            _ => self.csharp_writer.converter().to_typespecifier_in_rval(function.signature().rval()),
+        };
+        let is_static = service.static_methods().contains(function);
+        let rval = if is_static { format!("static {}", rval) } else { rval };
+
+        indented!(w, r#"{} class {} {{"#, self.csharp_writer.config().visibility_types.to_access_modifier(), class_name)?;
+        w.indent();
+        self.csharp_writer
+            .write_pattern_service_method(w, service, function, &rval, &fname, false, false, WriteFor::Docs)?;
+        let is_factory = service.factories().contains(function);
+        for overload in self.csharp_writer.overloads().iter().filter(|_| !is_static && !is_factory) {
+            overload.write_service_method_overload(w, self.csharp_writer.helper(), service, function, &fname, WriteFor::Docs)?;
+        }
+        w.unindent();
+        indented!(w, r#"}}"#)
     }
 
-    pub fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        writeln!(w.writer(), "{}", self.config().header)?;
-
-        self.write_toc(w)?;
-        self.write_types(w)?;
-        self.write_enums(w)?;
-        self.write_functions(w)?;
-        self.write_services(w)?;
+    fn property_name(&self, property: &ServiceProperty) -> Option<String> {
+        Some(property.name().to_upper_camel_case())
+    }
 
-        w.newline()?;
+    fn write_property_signature(&self, w: &mut IndentWriter, property: &ServiceProperty, service: &Service) -> Result<(), Error> {
+        let class_name = service.the_type().rust_name();
 
-        Ok(())
+        indented!(w, r#"{} class {} {{"#, self.csharp_writer.config().visibility_types.to_access_modifier(), class_name)?;
+        w.indent();
+        self.csharp_writer.write_pattern_service_property(w, service, property, WriteFor::Docs)?;
+        w.unindent();
+        indented!(w, r#"}}"#)
     }
 
-    pub fn write_file<P: AsRef<Path>>(&self, file_name: P) -> Result<(), Error> {
-        let mut file = File::create(file_name)?;
-        let mut writer = IndentWriter::new(&mut file);
-
-        self.write_to(&mut writer)
+    fn write_type_definition(&self, w: &mut IndentWriter, the_type: &CType) -> Result<(), Error> {
+        match the_type {
+            CType::Composite(x) => self.csharp_writer.write_type_definition_composite_body(w, x, WriteFor::Docs),
+            CType::Enum(x) => self.csharp_writer.write_type_definition_enum(w, x, WriteFor::Docs),
+            _ => Ok(()),
+        }
     }
 }
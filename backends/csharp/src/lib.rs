@@ -113,21 +113,26 @@
 //! }
 //! ```
 
-use interoptopus::writer::IndentWriter;
+use interoptopus::patterns::api_guard::inventory_hash;
+use interoptopus::writer::{IndentWriter, OutputSet};
 use interoptopus::Interop;
 use interoptopus::{Error, Inventory};
 use overloads::OverloadWriter;
 
 mod config;
+mod conformance;
 mod converter;
 mod docs;
+mod multifile;
 pub mod overloads;
 mod testing;
 mod writer;
 
-pub use config::{CSharpVisibility, Config, DocConfig, ParamSliceType, Unsafe, Unsupported, WriteTypes};
+pub use config::{CSharpVisibility, Config, ConformanceConfig, DocConfig, ParamSliceType, StructArrays, Unsafe, Unsupported, WriteTypes};
+pub use conformance::ConformanceGenerator;
 pub use converter::{CSharpTypeConverter, Converter};
 pub use docs::DocGenerator;
+pub use multifile::{FileKind, Manifest, ManifestEntry, Part};
 pub use testing::run_dotnet_command_if_installed;
 pub use writer::CSharpWriter;
 
@@ -135,6 +140,7 @@ pub use writer::CSharpWriter;
 pub struct Generator {
     config: Config,
     library: Inventory,
+    api_hash: u64,
     converter: Converter,
     overload_writer: Vec<Box<dyn OverloadWriter>>,
 }
@@ -143,7 +149,8 @@ impl Generator {
     pub fn new(config: Config, library: Inventory) -> Self {
         Self {
             config,
-            library,
+            library: library.without_skipped("csharp"),
+            api_hash: inventory_hash(&library),
             converter: Converter {},
             overload_writer: vec![],
         }
@@ -159,6 +166,12 @@ impl Interop for Generator {
     fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
         self.write_all(w)
     }
+
+    /// Splits bindings like [`Generator::write_files`] does, `file_name` (sans `.cs`) prefixes all but service files.
+    fn write_output_set(&self, output: &mut OutputSet, file_name: &str) -> Result<(), Error> {
+        self.write_split(output, file_name.trim_end_matches(".cs"))?;
+        Ok(())
+    }
 }
 
 impl CSharpWriter for Generator {
@@ -170,6 +183,10 @@ impl CSharpWriter for Generator {
         &self.library
     }
 
+    fn api_hash(&self) -> u64 {
+        self.api_hash
+    }
+
     fn converter(&self) -> &Converter {
         &self.converter
     }
@@ -0,0 +1,198 @@
+//! Writes bindings split into multiple files, see [`Generator::write_files`].
+use crate::converter::Converter;
+use crate::overloads::OverloadWriter;
+use crate::{CSharpWriter, Config, Generator, WriteTypes};
+use interoptopus::patterns::service::Service;
+use interoptopus::patterns::LibraryPattern;
+use interoptopus::writer::{IndentWriter, OutputSet};
+use interoptopus::{Error, Inventory};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Which part of the bindings to write, see [`CSharpWriter::write_part`].
+#[derive(Copy, Clone, Debug)]
+pub enum Part<'a> {
+    /// The static interop class holding constants and functions.
+    Interop,
+    /// All type definitions and builtins.
+    Types,
+    /// A single service class.
+    Service(&'a Service),
+}
+
+/// What a file listed in a [`Manifest`] contains.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FileKind {
+    Interop,
+    Types,
+    /// The service class of the given name.
+    Service(String),
+}
+
+/// A single file written by [`Generator::write_files`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ManifestEntry {
+    /// File name relative to the output folder.
+    pub file_name: String,
+    /// Namespace ID the file was written for.
+    pub namespace_id: String,
+    pub kind: FileKind,
+}
+
+/// Lists all files written by [`Generator::write_files`].
+///
+/// Its [`Display`] output, one file name per line, is also written next to the bindings so build
+/// scripts can include or clean up generated files.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    fn write(&mut self, output: &mut OutputSet, entry: ManifestEntry, f: impl FnOnce(&mut IndentWriter) -> Result<(), Error>) -> Result<(), Error> {
+        output.write(&entry.file_name, f)?;
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry.file_name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Views a [`Generator`] with the config adjusted to a single namespace.
+struct NamespaceWriter<'a> {
+    config: Config,
+    generator: &'a Generator,
+}
+
+impl CSharpWriter for NamespaceWriter<'_> {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn inventory(&self) -> &Inventory {
+        self.generator.inventory()
+    }
+
+    fn api_hash(&self) -> u64 {
+        self.generator.api_hash()
+    }
+
+    fn converter(&self) -> &Converter {
+        self.generator.converter()
+    }
+
+    fn overloads(&self) -> &[Box<dyn OverloadWriter>] {
+        self.generator.overloads()
+    }
+}
+
+impl NamespaceWriter<'_> {
+    /// Checks whether [`Part::Types`] has any content by writing it to a scratch buffer.
+    fn has_emittable_types(&self) -> Result<bool, Error> {
+        let mut buffer = Vec::new();
+        let mut w = IndentWriter::new(&mut buffer);
+
+        self.write_type_definitions(&mut w)?;
+        self.write_builtins(&mut w)?;
+
+        Ok(!buffer.is_empty())
+    }
+}
+
+impl Generator {
+    /// Writes bindings for all namespaces into `folder` in one pass.
+    ///
+    /// Each namespace gets its own interop class and types file, and each service its own file, e.g.,
+    /// `Interop.cs`, `Interop.Types.cs`, `Interop.common.Types.cs`, `SimpleService.cs` and `SimpleServiceNamespaced.common.cs`;
+    /// files without content are skipped. Interoptopus globals go into the files of the configured `namespace_id`, unless `write_types` is [`WriteTypes::Namespace`].
+    /// The returned [`Manifest`] is also written to `<class>.manifest`. Files whose content did not change are not rewritten.
+    pub fn write_files(&self, folder: impl AsRef<Path>) -> Result<Manifest, Error> {
+        let mut output = OutputSet::new();
+        let manifest = self.write_split(&mut output, &self.config.class)?;
+
+        output.insert(&format!("{}.manifest", self.config.class), manifest.to_string());
+        output.write_to_directory(folder)?;
+
+        Ok(manifest)
+    }
+
+    /// Splits bindings by namespace and [`Part`] into `output`, file names start with `prefix`, except for services.
+    /// Non-default namespaces add their ID as infix, e.g., `Interop.common.cs` or `SimpleServiceNamespaced.common.cs`.
+    pub(crate) fn write_split(&self, output: &mut OutputSet, prefix: &str) -> Result<Manifest, Error> {
+        let mut manifest = Manifest::default();
+
+        let mut namespace_ids = self.library.namespaces().to_vec();
+        if !namespace_ids.contains(&self.config.namespace_id) {
+            namespace_ids.insert(0, self.config.namespace_id.clone());
+        }
+
+        for namespace_id in namespace_ids {
+            let write_types = match (namespace_id == self.config.namespace_id, self.config.write_types) {
+                (true, WriteTypes::All) => WriteTypes::NamespaceAndInteroptopusGlobal,
+                (true, x) => x,
+                (false, _) => WriteTypes::Namespace,
+            };
+
+            let writer = NamespaceWriter {
+                config: Config {
+                    namespace_id: namespace_id.clone(),
+                    write_types,
+                    ..self.config.clone()
+                },
+                generator: self,
+            };
+
+            let infix = if namespace_id.is_empty() { String::new() } else { format!(".{}", namespace_id) };
+
+            if writer.has_emittable_functions(self.library.functions()) || writer.has_emittable_constants(self.library.constants()) {
+                let entry = ManifestEntry {
+                    file_name: format!("{}{}.cs", prefix, infix),
+                    namespace_id: namespace_id.clone(),
+                    kind: FileKind::Interop,
+                };
+                manifest.write(output, entry, |w| writer.write_part(w, Part::Interop))?;
+            }
+
+            if writer.has_emittable_types()? {
+                let entry = ManifestEntry {
+                    file_name: format!("{}{}.Types.cs", prefix, infix),
+                    namespace_id: namespace_id.clone(),
+                    kind: FileKind::Types,
+                };
+                manifest.write(output, entry, |w| writer.write_part(w, Part::Types))?;
+            }
+
+            for pattern in self.library.patterns() {
+                match pattern {
+                    LibraryPattern::Service(service) => {
+                        if !writer.should_emit_service(service) {
+                            continue;
+                        }
+
+                        let name = service.the_type().rust_name().to_string();
+                        let entry = ManifestEntry {
+                            file_name: format!("{}{}.cs", name, infix),
+                            namespace_id: namespace_id.clone(),
+                            kind: FileKind::Service(name),
+                        };
+                        manifest.write(output, entry, |w| writer.write_part(w, Part::Service(service)))?;
+                    }
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+}
@@ -1,9 +1,9 @@
 use crate::config::ParamSliceType;
 use crate::converter::FunctionNameFlavor;
-use crate::overloads::{write_common_service_method_overload, write_function_overloaded_invoke_with_error_handling, Helper};
+use crate::overloads::{write_common_service_method_overload, write_function_overloaded_invoke_with_error_handling, write_obsolete_annotation, Helper};
 use crate::{OverloadWriter, Unsafe};
 use core::panic;
-use interoptopus::lang::c::{CType, CompositeType, Field, Function, FunctionSignature, Parameter};
+use interoptopus::lang::c::{CType, CompositeType, Field, Function, FunctionSignature, Parameter, PrimitiveType};
 use interoptopus::patterns::callbacks::NamedCallback;
 use interoptopus::patterns::service::Service;
 use interoptopus::patterns::TypePattern;
@@ -38,6 +38,167 @@ impl DotNet {
         })
     }
 
+    /// Name of the `Task` returning wrapper generated for a function marked `async_callback`.
+    fn async_name(&self, h: &Helper, function: &Function) -> String {
+        match h.config.rename_symbols {
+            true => format!(
+                "{}Async",
+                h.converter.function_name_to_csharp_name(function, FunctionNameFlavor::CSharpMethodNameWithClass)
+            ),
+            false => format!("{}_async", h.converter.function_name_to_csharp_name(function, FunctionNameFlavor::RawFFIName)),
+        }
+    }
+
+    /// Returns the callback of a function marked `async_callback` and the C# type its `Task` resolves to.
+    fn async_callback_and_result<'a>(&self, h: &Helper, function: &'a Function) -> Option<(&'a Parameter, &'a NamedCallback, String)> {
+        let param = function.async_callback()?;
+        let callback = match param.the_type() {
+            CType::Pattern(TypePattern::NamedCallback(x)) => x,
+            _ => panic!(
+                "Parameter `{}` of `{}` must be a named callback to be used as `async_callback`.",
+                param.name(),
+                function.name()
+            ),
+        };
+
+        let results = callback
+            .fnpointer()
+            .signature()
+            .params()
+            .iter()
+            .map(|x| h.converter.to_typespecifier_in_param(x.the_type()))
+            .collect::<Vec<_>>();
+
+        let result = match results.len() {
+            0 => "bool".to_string(),
+            1 => results[0].clone(),
+            _ => format!("({})", results.join(", ")),
+        };
+
+        Some((param, callback, result))
+    }
+
+    fn async_task_type(&self, callback: &NamedCallback, result: &str) -> String {
+        match callback.fnpointer().signature().params().len() {
+            0 => "System.Threading.Tasks.Task".to_string(),
+            _ => format!("System.Threading.Tasks.Task<{}>", result),
+        }
+    }
+
+    /// Writes an `async` wrapper resolving a `Task` once the completion callback fired.
+    ///
+    /// The delegate handed to Rust is kept alive by a `GCHandle` until it was invoked, or the call
+    /// failed with an error. The handle is released under a lock in whichever happens first.
+    fn write_function_async_overload(&self, w: &mut IndentWriter, h: &Helper, function: &Function, write_for: WriteFor) -> Result<(), Error> {
+        let Some((async_param, callback, result)) = self.async_callback_and_result(h, function) else {
+            return Ok(());
+        };
+
+        let raw_name = h.converter.function_name_to_csharp_name(
+            function,
+            match h.config.rename_symbols {
+                true => FunctionNameFlavor::CSharpMethodNameWithClass,
+                false => FunctionNameFlavor::RawFFIName,
+            },
+        );
+
+        let mut params = Vec::new();
+        let mut to_invoke = Vec::new();
+        for p in function.signature().params() {
+            if p.name() == async_param.name() {
+                to_invoke.push(p.name().to_string());
+                continue;
+            }
+
+            let native = h.converter.function_parameter_to_csharp_typename(p);
+            if native.contains("out ") {
+                to_invoke.push(format!("out {}", p.name()));
+            } else if native.contains("ref ") {
+                to_invoke.push(format!("ref {}", p.name()));
+            } else {
+                to_invoke.push(p.name().to_string());
+            }
+
+            params.push(format!("{} {}", native, p.name()));
+        }
+
+        let task = self.async_task_type(callback, &result);
+        let visibility = h.config.visibility_types.to_member_access_modifier(function.meta().visibility());
+        let signature = format!(r#"{} static {} {}({})"#, visibility, task, self.async_name(h, function), params.join(", "));
+        if write_for == WriteFor::Docs {
+            indented!(w, r#"{};"#, signature)?;
+            return Ok(());
+        }
+
+        let callback_params = (0..callback.fnpointer().signature().params().len()).map(|i| format!("x{}", i)).collect::<Vec<_>>();
+        let callback_result = match callback_params.len() {
+            0 => "true".to_string(),
+            1 => callback_params[0].clone(),
+            _ => format!("({})", callback_params.join(", ")),
+        };
+
+        w.newline()?;
+        self.write_documentation(w, function.meta().documentation())?;
+        write_obsolete_annotation(w, function.meta().deprecation())?;
+        indented!(w, "{}", signature)?;
+        indented!(w, r#"{{"#)?;
+        indented!(
+            w,
+            [_],
+            r#"var tcs = new System.Threading.Tasks.TaskCompletionSource<{}>(System.Threading.Tasks.TaskCreationOptions.RunContinuationsAsynchronously);"#,
+            result
+        )?;
+        indented!(w, [_], r#"var handle = default(GCHandle);"#)?;
+        indented!(
+            w,
+            [_],
+            r#"{} {} = ({}) =>"#,
+            h.converter.named_callback_to_typename(callback),
+            async_param.name(),
+            callback_params.join(", ")
+        )?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"lock (tcs)"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"if (handle.IsAllocated)"#)?;
+        indented!(w, [_ _ _], r#"{{"#)?;
+        indented!(w, [_ _ _ _], r#"handle.Free();"#)?;
+        indented!(w, [_ _ _], r#"}}"#)?;
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_ _], r#"tcs.TrySetResult({});"#, callback_result)?;
+        match callback.fnpointer().signature().rval() {
+            CType::Primitive(PrimitiveType::Void) => {}
+            CType::Pattern(TypePattern::FFIErrorEnum(e)) => {
+                indented!(w, [_ _], r#"return {}.{};"#, e.the_enum().rust_name(), e.success_variant().name())?;
+            }
+            _ => indented!(w, [_ _], r#"return default;"#)?,
+        }
+        indented!(w, [_], r#"}};"#)?;
+        indented!(w, [_], r#"handle = GCHandle.Alloc({});"#, async_param.name())?;
+
+        let call = format!(r#"{}({})"#, raw_name, to_invoke.join(", "));
+        match function.signature().rval() {
+            CType::Pattern(TypePattern::FFIErrorEnum(e)) => {
+                indented!(w, [_], r#"var rval = {};"#, call)?;
+                indented!(w, [_], r#"if (rval != {}.{})"#, e.the_enum().rust_name(), e.success_variant().name())?;
+                indented!(w, [_], r#"{{"#)?;
+                indented!(w, [_ _], r#"lock (tcs)"#)?;
+                indented!(w, [_ _], r#"{{"#)?;
+                indented!(w, [_ _ _], r#"if (handle.IsAllocated)"#)?;
+                indented!(w, [_ _ _], r#"{{"#)?;
+                indented!(w, [_ _ _ _], r#"handle.Free();"#)?;
+                indented!(w, [_ _ _], r#"}}"#)?;
+                indented!(w, [_ _], r#"}}"#)?;
+                indented!(w, [_ _], r#"tcs.TrySetException(new InteropException<{}>(rval));"#, e.the_enum().rust_name())?;
+                indented!(w, [_], r#"}}"#)?;
+            }
+            _ => indented!(w, [_], r#"{};"#, call)?,
+        }
+
+        indented!(w, [_], r#"return tcs.Task;"#)?;
+        indented!(w, r#"}}"#)
+    }
+
     fn pattern_to_native_in_signature(&self, h: &Helper, param: &Parameter) -> String {
         if h.config.use_unsafe == Unsafe::None && h.config.param_slice_type == ParamSliceType::Span {
             panic!("param_slice_type: Span requires unsafe support (use_unsafe must be anything other than None)");
@@ -155,6 +316,8 @@ impl OverloadWriter for DotNet {
     }
 
     fn write_function_overload(&self, w: &mut IndentWriter, h: Helper, function: &Function, write_for: WriteFor) -> Result<(), Error> {
+        self.write_function_async_overload(w, &h, function, write_for)?;
+
         let has_overload = self.has_overloadable(function.signature());
         let has_error_enum = h.converter.has_ffi_error_rval(function.signature());
 
@@ -240,7 +403,8 @@ impl OverloadWriter for DotNet {
             params.push(format!("{} {}", native, name));
         }
 
-        let signature = format!(r#"public static {} {}({})"#, rval, this_name, params.join(", "));
+        let visibility = h.config.visibility_types.to_member_access_modifier(function.meta().visibility());
+        let signature = format!(r#"{} static {} {}({})"#, visibility, rval, this_name, params.join(", "));
         if write_for == WriteFor::Docs {
             indented!(w, r#"{};"#, signature)?;
             return Ok(());
@@ -250,6 +414,7 @@ impl OverloadWriter for DotNet {
 
         if write_for == WriteFor::Code {
             self.write_documentation(w, function.meta().documentation())?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
         }
 
         indented!(w, "{}", signature)?;
@@ -348,11 +513,41 @@ impl OverloadWriter for DotNet {
         &self,
         w: &mut IndentWriter,
         h: Helper,
-        _class: &Service,
+        class: &Service,
         function: &Function,
         fn_pretty: &str,
         write_for: WriteFor,
     ) -> Result<(), Error> {
+        if let Some((async_param, callback, result)) = self.async_callback_and_result(&h, function) {
+            let task = self.async_task_type(callback, &result);
+            let params = function.signature().params().iter().skip(1).filter(|p| p.name() != async_param.name());
+            let arg_tokens = params
+                .clone()
+                .map(|p| format!("{} {}", h.converter.function_parameter_to_csharp_typename(p), p.name()));
+            let to_invoke = params.map(|p| p.name().to_string());
+            let signature = format!(r#"public {} {}Async({})"#, task, fn_pretty, arg_tokens.collect::<Vec<_>>().join(", "));
+
+            if write_for == WriteFor::Docs {
+                indented!(w, r#"{};"#, signature)?;
+            } else {
+                w.newline()?;
+                self.write_documentation(w, function.meta().documentation())?;
+                write_obsolete_annotation(w, function.meta().deprecation())?;
+                let args = std::iter::once("_context".to_string()).chain(to_invoke).collect::<Vec<_>>();
+                indented!(w, r#"{}"#, signature)?;
+                indented!(w, r#"{{"#)?;
+                if h.config.locks_service(class) {
+                    indented!(w, [_], r#"lock (_lock)"#)?;
+                    indented!(w, [_], r#"{{"#)?;
+                    indented!(w, [_ _], r#"return {}.{}({});"#, h.config.class, self.async_name(&h, function), args.join(", "))?;
+                    indented!(w, [_], r#"}}"#)?;
+                } else {
+                    indented!(w, [_], r#"return {}.{}({});"#, h.config.class, self.async_name(&h, function), args.join(", "))?;
+                }
+                indented!(w, r#"}}"#)?;
+            }
+        }
+
         if !self.has_overloadable(function.signature()) {
             return Ok(());
         }
@@ -360,9 +555,10 @@ impl OverloadWriter for DotNet {
         if write_for == WriteFor::Code {
             w.newline()?;
             self.write_documentation(w, function.meta().documentation())?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
         }
 
-        write_common_service_method_overload(w, h, function, fn_pretty, |h, p| self.pattern_to_native_in_signature(h, p), write_for)?;
+        write_common_service_method_overload(w, h, class, function, fn_pretty, |h, p| self.pattern_to_native_in_signature(h, p), write_for)?;
 
         Ok(())
     }
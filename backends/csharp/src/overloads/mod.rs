@@ -50,7 +50,7 @@
 //! }
 //!
 
-use interoptopus::lang::c::{CType, CompositeType, Documentation, Field, Function, Parameter, PrimitiveType};
+use interoptopus::lang::c::{CType, CompositeType, Deprecation, Documentation, Field, Function, Parameter, PrimitiveType};
 use interoptopus::patterns::service::Service;
 use interoptopus::patterns::TypePattern;
 use interoptopus::writer::{IndentWriter, WriteFor};
@@ -106,6 +106,15 @@ pub trait OverloadWriter {
     }
 }
 
+/// Writes `[Obsolete]` if the item is deprecated.
+pub(crate) fn write_obsolete_annotation(w: &mut IndentWriter, deprecation: &Deprecation) -> Result<(), Error> {
+    match deprecation {
+        Deprecation::Deprecated(Some(note)) => indented!(w, r#"[Obsolete("{}")]"#, note.replace('\\', "\\\\").replace('"', "\\\"")),
+        Deprecation::Deprecated(None) => indented!(w, r#"[Obsolete]"#),
+        Deprecation::NotDeprecated => Ok(()),
+    }
+}
+
 /// Writes common error handling based on a call's return type.
 #[rustfmt::skip]
 fn write_function_overloaded_invoke_with_error_handling(w: &mut IndentWriter, function: &Function, fn_call: &str, rethrow_delegates: &[&str]) -> Result<(), Error> {
@@ -140,6 +149,7 @@ fn write_function_overloaded_invoke_with_error_handling(w: &mut IndentWriter, fu
 fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) -> String>(
     w: &mut IndentWriter,
     h: Helper,
+    class: &Service,
     function: &Function,
     fn_pretty: &str,
     f_pattern: FPatternMap,
@@ -208,6 +218,13 @@ fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) ->
     indented!(w, "{}", signature)?;
     indented!(w, r#"{{"#)?;
 
+    let locks = h.config.locks_service(class);
+    if locks {
+        indented!(w, [_], r#"lock (_lock)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        w.indent();
+    }
+
     match function.signature().rval() {
         CType::Pattern(TypePattern::FFIErrorEnum(_)) => {
             indented!(w, [_], r#"{};"#, fn_call)?;
@@ -220,6 +237,11 @@ fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) ->
         }
     }
 
+    if locks {
+        w.unindent();
+        indented!(w, [_], r#"}}"#)?;
+    }
+
     indented!(w, r#"}}"#)?;
 
     Ok(())
@@ -1,5 +1,5 @@
 use crate::converter::FunctionNameFlavor;
-use crate::overloads::{write_common_service_method_overload, write_function_overloaded_invoke_with_error_handling, Helper};
+use crate::overloads::{write_common_service_method_overload, write_function_overloaded_invoke_with_error_handling, write_obsolete_annotation, Helper};
 use crate::OverloadWriter;
 use interoptopus::lang::c::{CType, CompositeType, Field, Function, FunctionSignature, Parameter};
 use interoptopus::patterns::callbacks::NamedCallback;
@@ -138,7 +138,8 @@ impl Unity {
             )?;
         }
 
-        indented!(w, r#"public static extern {} {}({});"#, rval, name, params.join(", "))?;
+        let visibility = h.config.visibility_types.to_member_access_modifier(function.meta().visibility());
+        indented!(w, r#"{} static extern {} {}({});"#, visibility, rval, name, params.join(", "))?;
 
         if write_for == WriteFor::Code {
             w.newline()?;
@@ -197,6 +198,7 @@ impl OverloadWriter for Unity {
         if write_for == WriteFor::Code {
             w.newline()?;
             self.write_documentation(w, function.meta().documentation())?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
         }
 
         // If we have delegates we need to write a version with IntPtr only
@@ -281,7 +283,8 @@ impl OverloadWriter for Unity {
             params.push(format!("{} {}", native, name));
         }
 
-        let signature = format!(r#"public static {} {}({})"#, rval, this_name, params.join(", "));
+        let visibility = h.config.visibility_types.to_member_access_modifier(function.meta().visibility());
+        let signature = format!(r#"{} static {} {}({})"#, visibility, rval, this_name, params.join(", "));
         if write_for == WriteFor::Docs {
             indented!(w, "{};", signature)?;
             unindented!(w, r#"#endif"#)?;
@@ -317,7 +320,7 @@ impl OverloadWriter for Unity {
         &self,
         w: &mut IndentWriter,
         h: Helper,
-        _class: &Service,
+        class: &Service,
         function: &Function,
         fn_pretty: &str,
         write_for: WriteFor,
@@ -333,11 +336,13 @@ impl OverloadWriter for Unity {
         }
         if write_for == WriteFor::Code {
             self.write_documentation(w, function.meta().documentation())?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
         }
 
         write_common_service_method_overload(
             w,
             h,
+            class,
             function,
             fn_pretty,
             |h, p| self.pattern_to_native_in_signature(h, p, function.signature()),
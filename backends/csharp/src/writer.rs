@@ -1,12 +1,17 @@
-use crate::config::{Config, Unsafe, Unsupported, WriteTypes};
+use crate::config::{Config, StructArrays, Unsafe, Unsupported, WriteTypes};
 use crate::converter::{CSharpTypeConverter, Converter, FunctionNameFlavor};
-use crate::overloads::{Helper, OverloadWriter};
-use interoptopus::lang::c::{CType, CompositeType, Constant, Documentation, EnumType, Field, FnPointerType, Function, Layout, Meta, PrimitiveType, Variant, Visibility};
-use interoptopus::patterns::api_guard::inventory_hash;
-use interoptopus::patterns::callbacks::NamedCallback;
-use interoptopus::patterns::service::Service;
+use crate::multifile::Part;
+use crate::overloads::{write_obsolete_annotation, Helper, OverloadWriter};
+use heck::ToUpperCamelCase;
+use interoptopus::lang::c::{
+    ArrayType, CType, CompositeType, Constant, ConstantValue, Documentation, EnumType, Field, FnPointerType, Function, Layout, Meta, PrimitiveType, Variant, Visibility,
+};
+use interoptopus::lang::rust::CTypeInfo;
+use interoptopus::patterns::api_guard::{inventory_hash, is_layout_guard, layout_composites, layout_key, TypeLayout};
+use interoptopus::patterns::callbacks::{ContextCallback, NamedCallback};
+use interoptopus::patterns::service::{is_created_by_service, Service, ServiceProperty};
 use interoptopus::patterns::{LibraryPattern, TypePattern};
-use interoptopus::util::{is_global_type, longest_common_prefix};
+use interoptopus::util::{has_deprecations, is_global_type, longest_common_prefix};
 use interoptopus::writer::{IndentWriter, WriteFor};
 use interoptopus::{indented, Error, Inventory};
 use std::iter::zip;
@@ -21,6 +26,11 @@ pub trait CSharpWriter {
 
     fn converter(&self) -> &Converter;
 
+    /// Returns the hash the library's API guard reports, computed before skipped functions were removed.
+    fn api_hash(&self) -> u64 {
+        inventory_hash(self.inventory())
+    }
+
     fn overloads(&self) -> &[Box<dyn OverloadWriter>];
 
     fn helper(&self) -> Helper {
@@ -67,6 +77,11 @@ pub trait CSharpWriter {
         }
         indented!(w, r#"#pragma warning restore 0105"#)?;
 
+        // The bindings themselves call into obsolete items, e.g., from overloads.
+        if has_deprecations(self.inventory()) {
+            indented!(w, r#"#pragma warning disable CS0612, CS0618"#)?;
+        }
+
         Ok(())
     }
 
@@ -78,17 +93,21 @@ pub trait CSharpWriter {
     fn write_abi_guard(&self, w: &mut IndentWriter) -> Result<(), Error> {
         self.debug(w, "write_abi_guard")?;
 
+        // Guards are only checked by the class emitting the guard functions.
+        let emitted = self.inventory().functions().iter().filter(|x| self.should_emit_by_meta(x.meta())).collect::<Vec<_>>();
+        let api_guard = emitted.iter().find(|x| matches!(x.signature().rval(), CType::Pattern(TypePattern::APIVersion)));
+        let layout_guard = emitted.iter().find(|x| is_layout_guard(x));
+
+        if api_guard.is_none() && layout_guard.is_none() {
+            return Ok(());
+        }
+
         indented!(w, r#"static {}()"#, self.config().class)?;
         indented!(w, r#"{{"#)?;
 
         // Check if there is a API version marker for us to write
-        if let Some(api_guard) = self
-            .inventory()
-            .functions()
-            .iter()
-            .find(|x| matches!(x.signature().rval(), CType::Pattern(TypePattern::APIVersion)))
-        {
-            let version = inventory_hash(self.inventory());
+        if let Some(api_guard) = api_guard {
+            let version = self.api_hash();
             let flavor = match self.config().rename_symbols {
                 true => FunctionNameFlavor::CSharpMethodNameWithClass,
                 false => FunctionNameFlavor::RawFFIName,
@@ -101,6 +120,102 @@ pub trait CSharpWriter {
             indented!(w, [_], r#"}}"#)?;
         }
 
+        if let Some(layout_guard) = layout_guard {
+            self.write_layout_guard(w, layout_guard)?;
+        }
+
+        indented!(w, r#"}}"#)?;
+
+        if layout_guard.is_some() {
+            w.newline()?;
+            self.write_layout_guard_helper(w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes code verifying each struct's marshalled size and alignment match what the library's layout guard reports.
+    fn write_layout_guard(&self, w: &mut IndentWriter, layout_guard: &Function) -> Result<(), Error> {
+        self.debug(w, "write_layout_guard")?;
+
+        let flavor = match self.config().rename_symbols {
+            true => FunctionNameFlavor::CSharpMethodNameWithClass,
+            false => FunctionNameFlavor::RawFFIName,
+        };
+        let fn_call = self.converter().function_name_to_csharp_name(layout_guard, flavor);
+
+        let mut composites = layout_composites(self.inventory());
+        composites.retain(|x| !x.is_empty());
+
+        indented!(w, [_], r#"var type_layouts = {}.{}();"#, self.config().class, fn_call)?;
+        indented!(w, [_], r#"for (var i = 0; i < type_layouts.Count; i++)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"var layout = type_layouts[i];"#)?;
+        indented!(w, [_ _], r#"switch (layout.name_hash)"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        for c in composites {
+            let name = self.converter().composite_to_typename(c);
+            indented!(w, [_ _ _], r#"case {}ul: CheckTypeLayout(layout, "{}", typeof({})); break;"#, layout_key(c), name, name)?;
+        }
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        Ok(())
+    }
+
+    fn write_layout_guard_helper(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        self.debug(w, "write_layout_guard_helper")?;
+
+        let type_layout = match TypeLayout::type_info() {
+            CType::Composite(c) => self.converter().composite_to_typename(&c),
+            _ => unreachable!("TypeLayout must be a composite"),
+        };
+
+        indented!(w, r#"static void CheckTypeLayout({} layout, string name, Type type)"#, type_layout)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"var size = Marshal.SizeOf(type);"#)?;
+        indented!(w, [_], r#"if (layout.size != (ulong) size)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"throw new TypeLoadException($"Struct {{name}} has size {{layout.size}} in the library, but {{size}} in the bindings. You probably forgot to update / copy either the bindings or the library.");"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"var align = AlignOf(type);"#)?;
+        indented!(w, [_], r#"if (layout.align != (ulong) align)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"throw new TypeLoadException($"Struct {{name}} has alignment {{layout.align}} in the library, but {{align}} in the bindings. You probably forgot to update / copy either the bindings or the library.");"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(
+            w,
+            r#"// Marshal has no AlignOf, so this follows its rules: the largest field alignment, capped by Pack."#
+        )?;
+        indented!(w, r#"static int AlignOf(Type type)"#)?;
+        indented!(w, r#"{{"#)?;
+        indented!(
+            w,
+            [_],
+            r#"if (type.FullName == "System.Int128" || type.FullName == "System.UInt128") {{ return 16; }}"#
+        )?;
+        indented!(w, [_], r#"if (type.IsEnum) {{ return AlignOf(Enum.GetUnderlyingType(type)); }}"#)?;
+        indented!(w, [_], r#"if (type.IsPointer || !type.IsValueType) {{ return IntPtr.Size; }}"#)?;
+        indented!(w, [_], r#"if (type.IsPrimitive) {{ return Marshal.SizeOf(type); }}"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"var align = 1;"#)?;
+        indented!(
+            w,
+            [_],
+            r#"foreach (var field in type.GetFields(System.Reflection.BindingFlags.Instance | System.Reflection.BindingFlags.Public | System.Reflection.BindingFlags.NonPublic))"#
+        )?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"var marshal = (MarshalAsAttribute) Attribute.GetCustomAttribute(field, typeof(MarshalAsAttribute));"#)?;
+        indented!(w, [_ _], r#"var is_byte = marshal != null && (marshal.Value == UnmanagedType.I1 || marshal.Value == UnmanagedType.U1);"#)?;
+        indented!(w, [_ _], r#"var field_type = field.FieldType.IsArray ? field.FieldType.GetElementType() : field.FieldType;"#)?;
+        indented!(w, [_ _], r#"align = Math.Max(align, is_byte ? 1 : AlignOf(field_type));"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"var pack = type.StructLayoutAttribute != null ? type.StructLayoutAttribute.Pack : 0;"#)?;
+        indented!(w, [_], r#"return pack > 0 ? Math.Min(align, pack) : align;"#)?;
         indented!(w, r#"}}"#)?;
 
         Ok(())
@@ -119,12 +234,28 @@ pub trait CSharpWriter {
 
     fn write_constant(&self, w: &mut IndentWriter, constant: &Constant) -> Result<(), Error> {
         self.debug(w, "write_constant")?;
-        let rval = self.converter().to_typespecifier_in_rval(&constant.the_type());
         let name = constant.name();
         let value = self.converter().constant_value_to_value(constant.value());
+        let expression = self.converter().constant_value_to_expression(constant.value(), self.config().rename_symbols);
 
         self.write_documentation(w, constant.meta().documentation())?;
-        indented!(w, r#"public const {} {} = ({}) {};"#, rval, name, rval, value)
+        write_obsolete_annotation(w, constant.meta().deprecation())?;
+
+        match constant.value() {
+            ConstantValue::Primitive(_) => {
+                let rval = self.converter().to_typespecifier_in_rval(&constant.the_type());
+                indented!(w, r#"public const {} {} = ({}) {};"#, rval, name, rval, value)
+            }
+            ConstantValue::String(_) => indented!(w, r#"public const string {} = {};"#, name, value),
+            ConstantValue::Array(x, _) => {
+                let element = self.converter().to_typespecifier_in_rval(x.array_type());
+                indented!(w, r#"public static readonly {}[] {} = {};"#, element, name, expression)
+            }
+            ConstantValue::Composite(x, _) => {
+                let rval = self.converter().composite_to_typename(x);
+                indented!(w, r#"public static readonly {} {} = {};"#, rval, name, expression)
+            }
+        }
     }
 
     fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
@@ -140,8 +271,23 @@ pub trait CSharpWriter {
 
     fn write_function(&self, w: &mut IndentWriter, function: &Function, write_for: WriteFor) -> Result<(), Error> {
         self.debug(w, "write_function")?;
+
+        let mut signature_types = function.signature().params().iter().map(|x| x.the_type()).chain([function.signature().rval()]);
+        if write_for == WriteFor::Code && !self.config().use_int128 && signature_types.any(|x| self.needs_int128(x)) {
+            let comment = format!(
+                r#"// THIS FUNCTION IS NOT EMITTED - `{}` uses 128-bit integers, which require .NET 8 and `use_int128`."#,
+                function.name()
+            );
+            return match self.config().unsupported {
+                Unsupported::Panic => panic!("{}", comment),
+                Unsupported::Comment => indented!(w, "{}", comment),
+            };
+        }
+
         if write_for == WriteFor::Code {
             self.write_documentation(w, function.meta().documentation())?;
+            self.write_parameter_documentation(w, function)?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
             self.write_function_annotation(w, function)?;
         }
         self.write_function_declaration(w, function)?;
@@ -153,6 +299,12 @@ pub trait CSharpWriter {
         Ok(())
     }
 
+    /// Whether `the_type` is or contains `u128` / `i128`, see [`Config::use_int128`].
+    fn needs_int128(&self, the_type: &CType) -> bool {
+        let is_int128 = |x: &CType| matches!(x, CType::Primitive(PrimitiveType::U128 | PrimitiveType::I128));
+        is_int128(the_type) || the_type.embedded_types().iter().any(is_int128)
+    }
+
     fn write_documentation(&self, w: &mut IndentWriter, documentation: &Documentation) -> Result<(), Error> {
         for line in documentation.lines() {
             indented!(w, r#"///{}"#, line)?;
@@ -161,6 +313,21 @@ pub trait CSharpWriter {
         Ok(())
     }
 
+    /// Writes `<param>` tags for all documented parameters.
+    fn write_parameter_documentation(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
+        for param in function.signature().params() {
+            let lines = param.documentation().lines();
+
+            if !lines.is_empty() {
+                let text = lines.iter().map(|x| x.trim()).collect::<Vec<_>>().join(" ");
+                let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                indented!(w, r#"/// <param name="{}">{}</param>"#, param.name(), text)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn write_function_annotation(&self, w: &mut IndentWriter, function: &Function) -> Result<(), Error> {
         indented!(
             w,
@@ -187,7 +354,9 @@ pub trait CSharpWriter {
             params.push(format!("{} {}", the_type, name));
         }
 
-        indented!(w, r#"public static extern {} {}({});"#, rval, name, params.join(", "))
+        let visibility = self.config().visibility_types.to_member_access_modifier(function.meta().visibility());
+
+        indented!(w, r#"{} static extern {} {}({});"#, visibility, rval, name, params.join(", "))
     }
 
     fn write_type_definitions(&self, w: &mut IndentWriter) -> Result<(), Error> {
@@ -205,7 +374,12 @@ pub trait CSharpWriter {
 
         match the_type {
             CType::Primitive(_) => {}
-            CType::Array(_) => {}
+            CType::Array(a) => {
+                if self.config().struct_arrays != StructArrays::Unroll {
+                    self.write_type_definition_array(w, a)?;
+                    w.newline()?;
+                }
+            }
             CType::Enum(e) => {
                 self.write_type_definition_enum(w, e, WriteFor::Code)?;
                 w.newline()?;
@@ -250,6 +424,10 @@ pub trait CSharpWriter {
                     self.write_type_definition_named_callback(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::ContextCallback(x) => {
+                    self.write_type_definition_context_callback(w, x)?;
+                    w.newline()?;
+                }
                 TypePattern::Bool => {
                     self.write_type_definition_ffibool(w)?;
                     w.newline()?;
@@ -311,7 +489,7 @@ pub trait CSharpWriter {
     fn write_type_definition_named_callback_body(&self, w: &mut IndentWriter, the_type: &NamedCallback) -> Result<(), Error> {
         let rval = self.converter().to_typespecifier_in_rval(the_type.fnpointer().signature().rval());
         let name = self.converter().named_callback_to_typename(the_type);
-        let visibility = self.config().visibility_types.to_access_modifier();
+        let visibility = self.config().visibility_types.to_access_modifier_for(the_type.meta().visibility());
 
         let mut params = Vec::new();
         for param in the_type.fnpointer().signature().params().iter() {
@@ -321,6 +499,101 @@ pub trait CSharpWriter {
         indented!(w, r#"{} delegate {} {}({});"#, visibility, rval, name, params.join(", "))
     }
 
+    /// Writes the class of a [`ContextCallback`], constructed from a managed delegate which is kept
+    /// alive by a `GCHandle` until the library invokes the destructor.
+    ///
+    /// Each instance hands its context to native code exactly once, converting it to the nested `Native`
+    /// struct a second time throws instead of passing an already released handle.
+    fn write_type_definition_context_callback(&self, w: &mut IndentWriter, the_type: &ContextCallback) -> Result<(), Error> {
+        self.debug(w, "write_type_definition_context_callback")?;
+
+        let name = the_type.name();
+        let delegate = format!("{}Delegate", name);
+        let visibility = self.config().visibility_types.to_access_modifier_for(the_type.meta().visibility());
+        let rval = self.converter().to_typespecifier_in_rval(the_type.signature().rval());
+        let returns = if the_type.signature().rval().is_void() { "" } else { "return " };
+
+        let mut params = Vec::new();
+        let mut args = Vec::new();
+        for param in the_type.signature().params() {
+            params.push(format!("{} {}", self.converter().to_typespecifier_in_param(param.the_type()), param.name()));
+            args.push(param.name().to_string());
+        }
+
+        let native_params = params.iter().cloned().chain(std::iter::once("IntPtr context".to_string())).collect::<Vec<_>>();
+
+        indented!(w, r#"{} delegate {} {}({});"#, visibility, rval, delegate, params.join(", "))?;
+        w.newline()?;
+        self.write_documentation(w, the_type.meta().documentation())?;
+        indented!(w, r#"{} partial class {}"#, visibility, name)?;
+        indented!(w, r#"{{"#)?;
+        w.indent();
+        indented!(w, r#"[Serializable]"#)?;
+        indented!(w, r#"[StructLayout(LayoutKind.Sequential)]"#)?;
+        indented!(w, r#"public partial struct Native"#)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"internal IntPtr callback;"#)?;
+        indented!(w, [_], r#"internal IntPtr context;"#)?;
+        indented!(w, [_], r#"internal IntPtr destructor;"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"public static implicit operator Native({} f) => new {}(f).Consume();"#, delegate, name)?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(w, r#"[UnmanagedFunctionPointer(CallingConvention.Cdecl)]"#)?;
+        indented!(w, r#"private delegate {} NativeCallback({});"#, rval, native_params.join(", "))?;
+        w.newline()?;
+        indented!(w, r#"[UnmanagedFunctionPointer(CallingConvention.Cdecl)]"#)?;
+        indented!(w, r#"private delegate void NativeDestructor(IntPtr context);"#)?;
+        w.newline()?;
+        indented!(w, r#"private static readonly NativeCallback _callback = Invoke;"#)?;
+        indented!(w, r#"private static readonly NativeDestructor _destructor = Release;"#)?;
+        w.newline()?;
+        indented!(w, r#"private readonly {} _f;"#, delegate)?;
+        indented!(w, r#"private int _consumed;"#)?;
+        w.newline()?;
+        indented!(w, r#"public {}({} f)"#, name, delegate)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"_f = f;"#)?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(w, r#"public static implicit operator {}({} f) => new {}(f);"#, name, delegate, name)?;
+        w.newline()?;
+        indented!(w, r#"public static implicit operator Native({} c) => c.Consume();"#, name)?;
+        w.newline()?;
+        indented!(w, r#"/// Hands the delegate to native code, which releases it once done. Can only be called once."#)?;
+        indented!(w, r#"public Native Consume()"#)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"if (System.Threading.Interlocked.Exchange(ref _consumed, 1) != 0)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(
+            w,
+            [_ _],
+            r#"throw new InvalidOperationException("This {} was already passed to native code and cannot be passed again.");"#,
+            name
+        )?;
+        indented!(w, [_], r#"}}"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"var native = new Native();"#)?;
+        indented!(w, [_], r#"native.callback = Marshal.GetFunctionPointerForDelegate(_callback);"#)?;
+        indented!(w, [_], r#"native.context = GCHandle.ToIntPtr(GCHandle.Alloc(_f));"#)?;
+        indented!(w, [_], r#"native.destructor = Marshal.GetFunctionPointerForDelegate(_destructor);"#)?;
+        indented!(w, [_], r#"return native;"#)?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(w, r#"private static {} Invoke({})"#, rval, native_params.join(", "))?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"var f = ({}) GCHandle.FromIntPtr(context).Target;"#, delegate)?;
+        indented!(w, [_], r#"{}f({});"#, returns, args.join(", "))?;
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        indented!(w, r#"private static void Release(IntPtr context)"#)?;
+        indented!(w, r#"{{"#)?;
+        indented!(w, [_], r#"GCHandle.FromIntPtr(context).Free();"#)?;
+        indented!(w, r#"}}"#)?;
+        w.unindent();
+        indented!(w, r#"}}"#)
+    }
+
     fn write_type_definition_fn_pointer_annotation(&self, w: &mut IndentWriter, _the_type: &FnPointerType) -> Result<(), Error> {
         indented!(w, r#"[UnmanagedFunctionPointer(CallingConvention.Cdecl)]"#)
     }
@@ -342,8 +615,14 @@ pub trait CSharpWriter {
         self.debug(w, "write_type_definition_enum")?;
         if write_for == WriteFor::Code {
             self.write_documentation(w, the_type.meta().documentation())?;
+            write_obsolete_annotation(w, the_type.meta().deprecation())?;
         }
-        indented!(w, r#"public enum {}"#, the_type.rust_name())?;
+        indented!(
+            w,
+            r#"{} enum {}"#,
+            self.config().visibility_types.to_access_modifier_for(the_type.meta().visibility()),
+            the_type.rust_name()
+        )?;
         indented!(w, r#"{{"#)?;
         w.indent();
 
@@ -360,13 +639,77 @@ pub trait CSharpWriter {
         let variant_value = variant.value();
         if write_for == WriteFor::Code {
             self.write_documentation(w, variant.documentation())?;
+            write_obsolete_annotation(w, variant.deprecation())?;
         }
         indented!(w, r#"{} = {},"#, variant_name, variant_value)
     }
 
+    /// Writes the helper struct standing in for a `[T; N]` struct field, see [`StructArrays`].
+    fn write_type_definition_array(&self, w: &mut IndentWriter, the_type: &ArrayType) -> Result<(), Error> {
+        self.debug(w, "write_type_definition_array")?;
+
+        let type_name = self.converter().array_to_typename(the_type);
+        let element_type = self.converter().to_typespecifier_in_rval(the_type.array_type());
+        let visibility = self.config().visibility_types.to_access_modifier();
+        let len = the_type.len();
+
+        match self.config().struct_arrays {
+            StructArrays::Unroll => return Ok(()),
+            StructArrays::FixedBuffer => {
+                if !self.config().use_unsafe.any_unsafe() {
+                    panic!("Emitting arrays in structs as `fixed` buffers requires `use_unsafe` to be enabled.");
+                }
+
+                if !matches!(the_type.array_type(), CType::Primitive(_)) {
+                    let comment = r#"// THIS STRUCT IS BROKEN - C# `fixed` buffers only support primitive element types, use `StructArrays::InlineArray` instead."#;
+                    match self.config().unsupported {
+                        Unsupported::Panic => panic!("{}", comment),
+                        Unsupported::Comment => indented!(w, "{}", comment)?,
+                    }
+                }
+
+                indented!(w, r#"[Serializable]"#)?;
+                indented!(w, r#"[StructLayout(LayoutKind.Sequential)]"#)?;
+                indented!(w, r#"{} unsafe partial struct {}"#, visibility, type_name)?;
+                indented!(w, r#"{{"#)?;
+                indented!(w, [_], r#"fixed {} data[{}];"#, element_type, len)?;
+            }
+            StructArrays::InlineArray => {
+                indented!(w, r#"[System.Runtime.CompilerServices.InlineArray({})]"#, len)?;
+                indented!(w, r#"{} partial struct {}"#, visibility, type_name)?;
+                indented!(w, r#"{{"#)?;
+                indented!(w, [_], r#"{} element0;"#, element_type)?;
+            }
+        }
+
+        let element = match self.config().struct_arrays {
+            StructArrays::InlineArray => format!("MemoryMarshal.CreateSpan(ref element0, {})[i]", len),
+            _ => "data[i]".to_string(),
+        };
+
+        w.newline()?;
+        indented!(w, [_], r#"public int Length => {};"#, len)?;
+        w.newline()?;
+        indented!(w, [_], r#"public {} this[int i]"#, element_type)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"get"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"if (i < 0 || i >= {}) throw new IndexOutOfRangeException();"#, len)?;
+        indented!(w, [_ _ _], r#"return {};"#, element)?;
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_ _], r#"set"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"if (i < 0 || i >= {}) throw new IndexOutOfRangeException();"#, len)?;
+        indented!(w, [_ _ _], r#"{} = value;"#, element)?;
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        indented!(w, r#"}}"#)
+    }
+
     fn write_type_definition_composite(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
         self.debug(w, "write_type_definition_composite")?;
         self.write_documentation(w, the_type.meta().documentation())?;
+        write_obsolete_annotation(w, the_type.meta().deprecation())?;
         self.write_type_definition_composite_annotation(w, the_type)?;
         self.write_type_definition_composite_body(w, the_type, WriteFor::Code)
     }
@@ -382,6 +725,14 @@ pub trait CSharpWriter {
             }
         };
 
+        if !self.config().use_int128 && the_type.fields().iter().any(|x| self.needs_int128(x.the_type())) {
+            let comment = r#"// THIS STRUCT IS BROKEN - 128-bit integer fields require .NET 8 and `use_int128`."#;
+            match self.config().unsupported {
+                Unsupported::Panic => panic!("{}", comment),
+                Unsupported::Comment => indented!(w, "{}", comment)?,
+            }
+        }
+
         match the_type.repr().layout() {
             Layout::C | Layout::Transparent | Layout::Opaque => indented!(w, r#"[StructLayout(LayoutKind.Sequential)]"#),
             Layout::Packed => indented!(w, r#"[StructLayout(LayoutKind.Sequential, Pack = 1)]"#),
@@ -393,7 +744,7 @@ pub trait CSharpWriter {
         indented!(
             w,
             r#"{} partial struct {}"#,
-            self.config().visibility_types.to_access_modifier(),
+            self.config().visibility_types.to_access_modifier_for(the_type.meta().visibility()),
             the_type.rust_name()
         )?;
         indented!(w, r#"{{"#)?;
@@ -402,6 +753,7 @@ pub trait CSharpWriter {
         for field in the_type.fields() {
             if write_for == WriteFor::Code {
                 self.write_documentation(w, field.documentation())?;
+                write_obsolete_annotation(w, field.deprecation())?;
 
                 for overload in self.overloads() {
                     overload.write_field_decorators(w, self.helper(), field, the_type)?;
@@ -424,8 +776,9 @@ pub trait CSharpWriter {
 
         match field.the_type() {
             CType::Array(a) => {
-                if !self.config().unroll_struct_arrays {
-                    panic!("Unable to generate bindings for arrays in fields if `unroll_struct_arrays` is not enabled.");
+                if self.config().struct_arrays != StructArrays::Unroll {
+                    let type_name = self.converter().array_to_typename(a);
+                    return indented!(w, r#"{}{} {};"#, visibility, type_name, field_name);
                 }
 
                 let type_name = self.converter().to_typespecifier_in_field(a.array_type(), field, the_type);
@@ -501,6 +854,11 @@ pub trait CSharpWriter {
         rval
     }
 
+    /// Checks whether the given service belongs into the current file.
+    fn should_emit_service(&self, service: &Service) -> bool {
+        service.namespace() == self.config().namespace_id
+    }
+
     /// Checks whether for the given type and the current file a type definition should be emitted.
     fn should_emit_by_type(&self, t: &CType) -> bool {
         if self.config().write_types == WriteTypes::All {
@@ -513,7 +871,7 @@ pub trait CSharpWriter {
 
         match t {
             CType::Primitive(_) => self.config().write_types == WriteTypes::NamespaceAndInteroptopusGlobal,
-            CType::Array(_) => false,
+            CType::Array(x) => self.should_emit_by_type(x.array_type()),
             CType::Enum(x) => self.should_emit_by_meta(x.meta()),
             CType::Opaque(x) => self.should_emit_by_meta(x.meta()),
             CType::Composite(x) => self.should_emit_by_meta(x.meta()),
@@ -530,6 +888,7 @@ pub trait CSharpWriter {
                 TypePattern::Bool => self.config().write_types == WriteTypes::NamespaceAndInteroptopusGlobal,
                 TypePattern::CChar => false,
                 TypePattern::NamedCallback(x) => self.should_emit_by_meta(x.meta()),
+                TypePattern::ContextCallback(x) => self.should_emit_by_meta(x.meta()),
             },
         }
     }
@@ -538,7 +897,7 @@ pub trait CSharpWriter {
         for pattern in self.inventory().patterns() {
             match pattern {
                 LibraryPattern::Service(cls) => {
-                    if self.should_emit_by_meta(cls.the_type().meta()) {
+                    if self.should_emit_service(cls) {
                         self.write_pattern_service(w, cls)?
                     }
                 }
@@ -854,27 +1213,44 @@ pub trait CSharpWriter {
 
     fn write_pattern_service(&self, w: &mut IndentWriter, class: &Service) -> Result<(), Error> {
         self.debug(w, "write_pattern_service")?;
-        let mut all_functions = class.constructors().to_vec();
-        all_functions.extend_from_slice(class.methods());
-        all_functions.push(class.destructor().clone());
-
         let context_type_name = class.the_type().rust_name();
-        let common_prefix = longest_common_prefix(&all_functions);
+        let common_prefix = longest_common_prefix(&class.all_functions());
 
         self.write_documentation(w, class.the_type().meta().documentation())?;
+        if !class.the_type().meta().documentation().lines().is_empty() {
+            indented!(w, r#"///"#)?;
+        }
+        indented!(w, r#"/// {}"#, class.thread_safety().description())?;
+        write_obsolete_annotation(w, class.the_type().meta().deprecation())?;
         indented!(
             w,
             r#"{} partial class {} : IDisposable"#,
-            self.config().visibility_types.to_access_modifier(),
+            self.config().visibility_types.to_access_modifier_for(class.the_type().meta().visibility()),
             context_type_name
         )?;
         indented!(w, r#"{{"#)?;
         w.indent();
         indented!(w, r#"private IntPtr _context;"#)?;
+        if self.config().locks_service(class) {
+            indented!(w, r#"private object _lock = new object();"#)?;
+        }
         w.newline()?;
         indented!(w, r#"private {}() {{}}"#, context_type_name)?;
         w.newline()?;
 
+        // Instances returned by other services keep their creator alive.
+        let is_created_by_service = is_created_by_service(self.inventory().patterns(), class.the_type());
+        if is_created_by_service {
+            indented!(w, r#"internal object Owner {{ get; }}"#)?;
+            w.newline()?;
+            indented!(w, r#"internal {}(IntPtr context, object owner)"#, context_type_name)?;
+            indented!(w, r#"{{"#)?;
+            indented!(w, [_], r#"_context = context;"#)?;
+            indented!(w, [_], r#"Owner = owner;"#)?;
+            indented!(w, r#"}}"#)?;
+            w.newline()?;
+        }
+
         for ctor in class.constructors() {
             // Ctor
             let fn_name = self
@@ -883,6 +1259,8 @@ pub trait CSharpWriter {
             let rval = format!("static {}", context_type_name);
 
             self.write_documentation(w, ctor.meta().documentation())?;
+            self.write_parameter_documentation(w, ctor)?;
+            write_obsolete_annotation(w, ctor.meta().deprecation())?;
             self.write_pattern_service_method(w, class, ctor, &rval, &fn_name, true, true, WriteFor::Code)?;
             w.newline()?;
         }
@@ -891,7 +1269,35 @@ pub trait CSharpWriter {
         self.write_pattern_service_method(w, class, class.destructor(), "void", "Dispose", true, false, WriteFor::Code)?;
         w.newline()?;
 
+        if let Some(retain) = class.retain() {
+            self.write_pattern_service_clone(w, class, retain, is_created_by_service)?;
+            w.newline()?;
+        }
+
+        for function in class.static_methods() {
+            let fn_name = self
+                .converter()
+                .function_name_to_csharp_name(function, FunctionNameFlavor::CSharpMethodNameWithoutClass(&common_prefix));
+
+            // Static methods don't have a context, they forward to the raw function as-is.
+            let rval = match function.signature().rval() {
+                CType::Pattern(TypePattern::FFIErrorEnum(_)) => "static void".to_string(),
+                CType::Pattern(TypePattern::CStrPointer) => "static string".to_string(),
+                _ => format!("static {}", self.converter().to_typespecifier_in_rval(function.signature().rval())),
+            };
+            self.write_documentation(w, function.meta().documentation())?;
+            self.write_parameter_documentation(w, function)?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
+            self.write_pattern_service_method(w, class, function, &rval, &fn_name, false, false, WriteFor::Code)?;
+            w.newline()?;
+        }
+
         for function in class.methods() {
+            // Getters and setters are written as properties below.
+            if class.property_of(function).is_some() {
+                continue;
+            }
+
             // Main function
             let fn_name = self
                 .converter()
@@ -905,6 +1311,8 @@ pub trait CSharpWriter {
                 _ => self.converter().to_typespecifier_in_rval(function.signature().rval()),
             };
             self.write_documentation(w, function.meta().documentation())?;
+            self.write_parameter_documentation(w, function)?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
             self.write_pattern_service_method(w, class, function, &rval, &fn_name, false, false, WriteFor::Code)?;
 
             for overload in self.overloads() {
@@ -914,6 +1322,27 @@ pub trait CSharpWriter {
             w.newline()?;
         }
 
+        for function in class.factories() {
+            let fn_name = self
+                .converter()
+                .function_name_to_csharp_name(function, FunctionNameFlavor::CSharpMethodNameWithoutClass(&common_prefix));
+            let created = class.factory_type(function).expect("Must be a factory.");
+
+            self.write_documentation(w, function.meta().documentation())?;
+            self.write_parameter_documentation(w, function)?;
+            write_obsolete_annotation(w, function.meta().deprecation())?;
+            self.write_pattern_service_method(w, class, function, created.rust_name(), &fn_name, false, false, WriteFor::Code)?;
+            w.newline()?;
+        }
+
+        for property in class.properties() {
+            let accessor = property.getter().or(property.setter()).expect("Property must have a getter or setter.");
+            self.write_documentation(w, accessor.meta().documentation())?;
+            write_obsolete_annotation(w, accessor.meta().deprecation())?;
+            self.write_pattern_service_property(w, class, property, WriteFor::Code)?;
+            w.newline()?;
+        }
+
         indented!(w, r#"public IntPtr Context => _context;"#)?;
 
         w.unindent();
@@ -924,6 +1353,139 @@ pub trait CSharpWriter {
         Ok(())
     }
 
+    /// Writes `Clone()` for shared services, returning a new wrapper holding another reference.
+    fn write_pattern_service_clone(&self, w: &mut IndentWriter, class: &Service, retain: &Function, keep_owner: bool) -> Result<(), Error> {
+        self.debug(w, "write_pattern_service_clone")?;
+
+        let class_name = class.the_type().rust_name();
+        let method_to_invoke = self.converter().function_name_to_csharp_name(
+            retain,
+            match self.config().rename_symbols {
+                true => FunctionNameFlavor::CSharpMethodNameWithClass,
+                false => FunctionNameFlavor::RawFFIName,
+            },
+        );
+
+        indented!(w, r#"/// Returns another reference to this instance, which must be disposed separately."#)?;
+        let locks = self.config().locks_service(class);
+
+        indented!(w, r#"public {} Clone()"#, class_name)?;
+        indented!(w, r#"{{"#)?;
+        if locks {
+            indented!(w, [_], r#"lock (_lock)"#)?;
+            indented!(w, [_], r#"{{"#)?;
+            w.indent();
+        }
+        indented!(w, [_], r#"var rval = {}.{}(_context);"#, self.config().class, method_to_invoke)?;
+        if let CType::Pattern(TypePattern::FFIErrorEnum(e)) = retain.signature().rval() {
+            indented!(w, [_], r#"if (rval != {}.{})"#, e.the_enum().rust_name(), e.success_variant().name())?;
+            indented!(w, [_], r#"{{"#)?;
+            indented!(w, [_ _], r#"throw new InteropException<{}>(rval);"#, e.the_enum().rust_name())?;
+            indented!(w, [_], r#"}}"#)?;
+        }
+        if keep_owner {
+            indented!(w, [_], r#"var clone = new {}(_context, Owner);"#, class_name)?;
+        } else {
+            indented!(w, [_], r#"var clone = new {}();"#, class_name)?;
+            indented!(w, [_], r#"clone._context = _context;"#)?;
+        }
+        // Clones share the instance, so they must also share its lock.
+        if locks {
+            indented!(w, [_], r#"clone._lock = _lock;"#)?;
+        }
+        indented!(w, [_], r#"return clone;"#)?;
+        if locks {
+            w.unindent();
+            indented!(w, [_], r#"}}"#)?;
+        }
+        indented!(w, r#"}}"#)
+    }
+
+    /// Writes a getter and setter pair as C# property, forwarding to the flat functions.
+    fn write_pattern_service_property(&self, w: &mut IndentWriter, class: &Service, property: &ServiceProperty, write_for: WriteFor) -> Result<(), Error> {
+        self.debug(w, "write_pattern_service_property")?;
+
+        let name = property.name().to_upper_camel_case();
+        let the_type = match property.getter().map(|x| x.signature().rval()) {
+            Some(CType::Pattern(TypePattern::CStrPointer)) => "string".to_string(),
+            Some(rval) => self.converter().to_typespecifier_in_rval(rval),
+            None => self.converter().to_typespecifier_in_param(property.the_type()),
+        };
+
+        if write_for == WriteFor::Docs {
+            let getter = property.getter().map(|_| " get;").unwrap_or_default();
+            let setter = property.setter().map(|_| " set;").unwrap_or_default();
+            return indented!(w, r#"public {} {} {{{}{} }}"#, the_type, name, getter, setter);
+        }
+
+        let ffi_name = |function: &Function| {
+            let flavor = match self.config().rename_symbols {
+                true => FunctionNameFlavor::CSharpMethodNameWithClass,
+                false => FunctionNameFlavor::RawFFIName,
+            };
+            self.converter().function_name_to_csharp_name(function, flavor)
+        };
+
+        let locks = self.config().locks_service(class);
+        let lock = |w: &mut IndentWriter| -> Result<(), Error> {
+            if locks {
+                indented!(w, [_ _], r#"lock (_lock)"#)?;
+                indented!(w, [_ _], r#"{{"#)?;
+                w.indent();
+            }
+            Ok(())
+        };
+        let unlock = |w: &mut IndentWriter| -> Result<(), Error> {
+            if locks {
+                w.unindent();
+                indented!(w, [_ _], r#"}}"#)?;
+            }
+            Ok(())
+        };
+
+        indented!(w, r#"public {} {}"#, the_type, name)?;
+        indented!(w, r#"{{"#)?;
+
+        if let Some(getter) = property.getter() {
+            let fn_call = format!(r#"{}.{}(_context)"#, self.config().class, ffi_name(getter));
+
+            indented!(w, [_], r#"get"#)?;
+            indented!(w, [_], r#"{{"#)?;
+            lock(w)?;
+            match getter.signature().rval() {
+                CType::Pattern(TypePattern::CStrPointer) => {
+                    indented!(w, [_ _], r#"var s = {};"#, fn_call)?;
+                    indented!(w, [_ _], r#"return Marshal.PtrToStringAnsi(s);"#)?;
+                }
+                _ => indented!(w, [_ _], r#"return {};"#, fn_call)?,
+            }
+            unlock(w)?;
+            indented!(w, [_], r#"}}"#)?;
+        }
+
+        if let Some(setter) = property.setter() {
+            let fn_call = format!(r#"{}.{}(_context, value)"#, self.config().class, ffi_name(setter));
+
+            indented!(w, [_], r#"set"#)?;
+            indented!(w, [_], r#"{{"#)?;
+            lock(w)?;
+            match setter.signature().rval() {
+                CType::Pattern(TypePattern::FFIErrorEnum(e)) => {
+                    indented!(w, [_ _], r#"var rval = {};"#, fn_call)?;
+                    indented!(w, [_ _], r#"if (rval != {}.{})"#, e.the_enum().rust_name(), e.success_variant().name())?;
+                    indented!(w, [_ _], r#"{{"#)?;
+                    indented!(w, [_ _ _], r#"throw new InteropException<{}>(rval);"#, e.the_enum().rust_name())?;
+                    indented!(w, [_ _], r#"}}"#)?;
+                }
+                _ => indented!(w, [_ _], r#"{};"#, fn_call)?,
+            }
+            unlock(w)?;
+            indented!(w, [_], r#"}}"#)?;
+        }
+
+        indented!(w, r#"}}"#)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn write_pattern_service_method(
         &self,
@@ -943,10 +1505,12 @@ pub trait CSharpWriter {
         let mut types = Vec::new();
         let mut to_wrap_delegates = Vec::new();
         let mut to_wrap_delegate_types = Vec::new();
+        let is_static = class.static_methods().contains(function);
+        let created = class.factory_type(function);
 
-        // For every parameter except the first, figure out how we should forward
-        // it to the invocation we perform.
-        for p in function.signature().params().iter().skip(1) {
+        // For every parameter except the first (unless static), figure out how we should forward
+        // it to the invocation we perform. Methods returning a service also skip its out-parameter.
+        for p in function.signature().params().iter().skip(usize::from(!is_static) + usize::from(created.is_some())) {
             let name = p.name();
 
             // If we call the checked function we want to resolve a `SliceU8` to a `byte[]`,
@@ -995,14 +1559,18 @@ pub trait CSharpWriter {
                 false => FunctionNameFlavor::RawFFIName,
             },
         );
-        let extra_args = if to_invoke.is_empty() {
-            "".to_string()
+        let extra_args = if to_invoke.is_empty() || is_static {
+            to_invoke.join(", ")
         } else {
             format!(", {}", to_invoke.join(", "))
         };
 
         // Assemble actual function call.
-        let context = if write_contxt_by_ref {
+        let context = if is_static {
+            ""
+        } else if created.is_some() {
+            "_context, ref context"
+        } else if write_contxt_by_ref {
             if is_ctor {
                 "ref self._context"
             } else {
@@ -1028,6 +1596,18 @@ pub trait CSharpWriter {
             indented!(w, [_], r#"var self = new {}();"#, class.the_type().rust_name())?;
         }
 
+        // Constructors and static methods don't have an instance to lock yet.
+        let locks = self.config().locks_service(class) && !is_ctor && !is_static;
+        if locks {
+            indented!(w, [_], r#"lock (_lock)"#)?;
+            indented!(w, [_], r#"{{"#)?;
+            w.indent();
+        }
+
+        if created.is_some() {
+            indented!(w, [_], r#"var context = IntPtr.Zero;"#)?;
+        }
+
         for (name, ty) in zip(&to_wrap_delegates, &to_wrap_delegate_types) {
             indented!(w, [_], r#"var {}_safe_delegate = new {}ExceptionSafe({});"#, name, ty, name)?;
         }
@@ -1060,6 +1640,15 @@ pub trait CSharpWriter {
             indented!(w, [_], r#"return self;"#)?;
         }
 
+        if let Some(created) = created {
+            indented!(w, [_], r#"return new {}(context, this);"#, created.rust_name())?;
+        }
+
+        if locks {
+            w.unindent();
+            indented!(w, [_], r#"}}"#)?;
+        }
+
         indented!(w, r#"}}"#)?;
 
         Ok(())
@@ -1082,54 +1671,82 @@ pub trait CSharpWriter {
         Ok(())
     }
 
-    fn write_all(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        self.write_file_header_comments(w)?;
-        w.newline()?;
-
-        self.write_imports(w)?;
-        w.newline()?;
+    /// Writes the static interop class(es) holding constants and functions.
+    fn write_interop_classes(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        if self.config().class_constants.is_none() || self.config().class_constants == Some(self.config().clone().class) {
+            if self.has_emittable_functions(self.inventory().functions()) || self.has_emittable_constants(self.inventory().constants()) {
+                self.write_class_context(&self.config().class, w, |w| {
+                    self.write_native_lib_string(w)?;
+                    w.newline()?;
 
-        self.write_namespace_context(w, |w| {
-            if self.config().class_constants.is_none() || self.config().class_constants == Some(self.config().clone().class) {
-                if self.has_emittable_functions(self.inventory().functions()) || self.has_emittable_constants(self.inventory().constants()) {
-                    self.write_class_context(&self.config().class, w, |w| {
-                        self.write_native_lib_string(w)?;
-                        w.newline()?;
+                    self.write_abi_guard(w)?;
+                    w.newline()?;
 
-                        self.write_abi_guard(w)?;
-                        w.newline()?;
+                    self.write_constants(w)?;
+                    w.newline()?;
 
-                        self.write_constants(w)?;
-                        w.newline()?;
+                    self.write_functions(w)?;
+                    Ok(())
+                })?;
+            }
+        } else {
+            if self.has_emittable_constants(self.inventory().constants()) {
+                self.write_class_context(self.config().class_constants.as_ref().unwrap(), w, |w| {
+                    self.write_constants(w)?;
+                    w.newline()?;
 
-                        self.write_functions(w)?;
-                        Ok(())
-                    })?;
-                }
-            } else {
-                if self.has_emittable_constants(self.inventory().constants()) {
-                    self.write_class_context(self.config().class_constants.as_ref().unwrap(), w, |w| {
-                        self.write_constants(w)?;
-                        w.newline()?;
+                    Ok(())
+                })?;
+            }
 
-                        Ok(())
-                    })?;
-                }
+            if self.has_emittable_functions(self.inventory().functions()) {
+                w.newline()?;
+                self.write_class_context(&self.config().class, w, |w| {
+                    self.write_native_lib_string(w)?;
+                    w.newline()?;
 
-                if self.has_emittable_functions(self.inventory().functions()) {
+                    self.write_abi_guard(w)?;
                     w.newline()?;
-                    self.write_class_context(&self.config().class, w, |w| {
-                        self.write_native_lib_string(w)?;
-                        w.newline()?;
 
-                        self.write_abi_guard(w)?;
-                        w.newline()?;
+                    self.write_functions(w)?;
+                    Ok(())
+                })?;
+            }
+        }
+
+        Ok(())
+    }
 
-                        self.write_functions(w)?;
-                        Ok(())
-                    })?;
-                }
+    /// Writes a self-contained file holding only the given [`Part`] of the bindings.
+    fn write_part(&self, w: &mut IndentWriter, part: Part) -> Result<(), Error> {
+        self.write_file_header_comments(w)?;
+        w.newline()?;
+
+        self.write_imports(w)?;
+        w.newline()?;
+
+        self.write_namespace_context(w, |w| match part {
+            Part::Interop => self.write_interop_classes(w),
+            Part::Types => {
+                self.write_type_definitions(w)?;
+                w.newline()?;
+                self.write_builtins(w)
             }
+            Part::Service(service) => self.write_pattern_service(w, service),
+        })?;
+
+        Ok(())
+    }
+
+    fn write_all(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        self.write_file_header_comments(w)?;
+        w.newline()?;
+
+        self.write_imports(w)?;
+        w.newline()?;
+
+        self.write_namespace_context(w, |w| {
+            self.write_interop_classes(w)?;
 
             w.newline()?;
             self.write_type_definitions(w)?;
@@ -1,25 +1,38 @@
+use interoptopus::conformance::TestSuite;
+use interoptopus::docs::html::HtmlConfig;
+use interoptopus::lang::c::{CType, Documentation, Function, FunctionSignature, Meta, PrimitiveType};
+use interoptopus::patterns::api_guard::inventory_hash;
 use interoptopus::testing::assert_file_matches_generated;
 use interoptopus::util::NamespaceMappings;
-use interoptopus::{Error, Interop};
+use interoptopus::writer::OutputSet;
+use interoptopus::{Error, Interop, InventoryBuilder, Symbol};
 use interoptopus_backend_csharp::overloads::{DotNet, Unity};
 use interoptopus_backend_csharp::{
-    run_dotnet_command_if_installed, CSharpVisibility, Config, DocConfig, DocGenerator, Generator, ParamSliceType, Unsafe, Unsupported, WriteTypes,
+    run_dotnet_command_if_installed, CSharpVisibility, Config, ConformanceConfig, ConformanceGenerator, DocConfig, DocGenerator, FileKind, Generator, ParamSliceType,
+    StructArrays, Unsafe, Unsupported, WriteTypes,
 };
 use std::path::{Path, PathBuf};
 use tempdir::TempDir;
 
 /// Writes a simple project config so `dotnet build` works.
 pub fn write_simple_project_file(path: impl AsRef<Path>) -> Result<(), Error> {
-    let csprj = r#"
+    write_project_file(path, "netcoreapp3.1")
+}
+
+/// Writes a project config targeting `framework`, e.g., `net8.0`.
+pub fn write_project_file(path: impl AsRef<Path>, framework: &str) -> Result<(), Error> {
+    let csprj = format!(
+        r#"
 <Project Sdk="Microsoft.NET.Sdk">
   <PropertyGroup>
-    <TargetFramework>netcoreapp3.1</TargetFramework>
+    <TargetFramework>{framework}</TargetFramework>
     <AllowUnsafeBlocks>true</AllowUnsafeBlocks>
   </PropertyGroup>
   <ItemGroup>
     <PackageReference Include="Microsoft.NET.Test.Sdk" Version="15.9.0" />
   </ItemGroup>
-</Project>"#;
+</Project>"#
+    );
 
     let mut project_path = PathBuf::new();
     project_path.push(path);
@@ -40,6 +53,7 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, param_s
         visibility_types: CSharpVisibility::ForceInternal,
         param_slice_type,
         use_unsafe,
+        use_int128: true,
         ..Config::default()
     });
 
@@ -72,6 +86,22 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, param_s
     Ok(())
 }
 
+/// Generates conformance tests calling into the bindings written by [`generate_bindings_multi`].
+fn generate_conformance(folder: &str) -> Result<(), Error> {
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let suite = TestSuite::from_inventory(&inventory, &interoptopus_reference_project::ffi_recorder());
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        ..Config::default()
+    };
+    let generator = Generator::new(config, inventory);
+    let config = ConformanceConfig {
+        namespace: "interop_test".to_string(),
+    };
+
+    ConformanceGenerator::new(&generator, &suite, config).write_file(format!("{}/Conformance.cs", folder))
+}
+
 fn generate_documentation(output: &str) -> Result<(), Error> {
     let inventory = interoptopus_reference_project::ffi_inventory();
     let mut generator = Generator::new(
@@ -92,7 +122,34 @@ fn generate_safe() -> Result<(), Error> {
 }
 
 fn generate_unsafe() -> Result<(), Error> {
-    generate_bindings_multi("tests/output_unsafe", Unsafe::UnsafePlatformMemCpy, ParamSliceType::Span, None)
+    let config = Config {
+        dll_name: "interoptopus_reference_project".to_string(),
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        unsupported: Unsupported::Comment,
+        visibility_types: CSharpVisibility::ForceInternal,
+        param_slice_type: ParamSliceType::Span,
+        use_unsafe: Unsafe::UnsafePlatformMemCpy,
+        lock_non_sync_services: true,
+        use_int128: true,
+        ..Config::default()
+    };
+
+    generate_bindings_multi("tests/output_unsafe", Unsafe::UnsafePlatformMemCpy, ParamSliceType::Span, Some(config))
+}
+
+/// Unity has no `Int128`, so functions using them must be left out.
+fn generate_unity() -> Result<(), Error> {
+    let config = Config {
+        dll_name: "interoptopus_reference_project".to_string(),
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        unsupported: Unsupported::Comment,
+        visibility_types: CSharpVisibility::ForceInternal,
+        use_unsafe: Unsafe::UnsafePlatformMemCpy,
+        use_int128: false,
+        ..Config::default()
+    };
+
+    generate_bindings_multi("tests/output_unity/Assets", Unsafe::UnsafePlatformMemCpy, ParamSliceType::Array, Some(config))
 }
 
 #[test]
@@ -100,12 +157,16 @@ fn generate_unsafe() -> Result<(), Error> {
 fn bindings_match_reference() -> Result<(), Error> {
     generate_safe()?;
     generate_unsafe()?;
-    generate_bindings_multi("tests/output_unity/Assets", Unsafe::UnsafePlatformMemCpy, ParamSliceType::Array, None)?;
+    generate_unity()?;
+    generate_conformance("tests/output_safe")?;
+    generate_conformance("tests/output_unsafe")?;
 
     assert_file_matches_generated("tests/output_safe/Interop.cs");
+    assert_file_matches_generated("tests/output_safe/Conformance.cs");
     assert_file_matches_generated("tests/output_safe/Interop.common.cs");
 
     assert_file_matches_generated("tests/output_unsafe/Interop.cs");
+    assert_file_matches_generated("tests/output_unsafe/Conformance.cs");
     assert_file_matches_generated("tests/output_unsafe/Interop.common.cs");
 
     assert_file_matches_generated("tests/output_unity/Assets/Interop.cs");
@@ -116,6 +177,45 @@ fn bindings_match_reference() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn api_guard_hashes_unfiltered_inventory() -> Result<(), Error> {
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let mut output = OutputSet::new();
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        use_int128: true,
+        ..Config::default()
+    };
+
+    Generator::new(config, inventory.clone()).write_output_set(&mut output, "Interop.cs")?;
+    let generated = output.get("Interop.cs").expect("interop file");
+
+    assert!(inventory.functions().iter().any(|x| x.name() == "skipped_in_csharp"));
+    assert!(!generated.contains("skipped_in_csharp"));
+    assert!(generated.contains(&format!("if (api_version != {}ul)", inventory_hash(&inventory))));
+
+    Ok(())
+}
+
+#[test]
+fn html_documentation_has_pages_and_search() -> Result<(), Error> {
+    let inventory = interoptopus_reference_project::ffi_inventory();
+    let generator = Generator::new(Config::default(), inventory.clone());
+    let mut output = OutputSet::new();
+
+    DocGenerator::new(&inventory, &generator, DocConfig::default()).write_html(&mut output, &HtmlConfig::default())?;
+
+    let service = output.get("service.SimpleService.html").expect("service page");
+    let search_index = output.get("search-index.js").expect("search index");
+
+    assert!(service.contains(r#"<h2 id="SimpleService.NewWith">NewWith</h2>"#));
+    assert!(service.contains("public SimpleService NewWith(uint some_value);"));
+    assert!(output.get("type.Vec3f32.html").is_some_and(|x| x.contains("<code>float</code>")));
+    assert!(search_index.contains(r#""name":"SimpleService.MethodVoid","kind":"method","url":"service.SimpleService.html#SimpleService.MethodVoid""#));
+
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn bindings_work() -> Result<(), Error> {
@@ -212,6 +312,165 @@ fn config_visibility_force_visibility_internal() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_write_files() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let config = Config {
+        dll_name: "interoptopus_reference_project".to_string(),
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        unsupported: Unsupported::Comment,
+        ..Config::default()
+    };
+
+    let manifest = Generator::new(config, interoptopus_reference_project::ffi_inventory())
+        .add_overload_writer(DotNet::new())
+        .write_files(temp.path())?;
+
+    let file_names = manifest.entries().iter().map(|x| x.file_name.as_str()).collect::<Vec<_>>();
+    assert!(file_names.contains(&"Interop.cs"));
+    assert!(file_names.contains(&"Interop.Types.cs"));
+    assert!(file_names.contains(&"Interop.common.Types.cs"));
+    assert!(file_names.contains(&"SimpleService.cs"));
+    assert!(file_names.contains(&"SimpleServiceNamespaced.common.cs"));
+    assert!(manifest.entries().iter().any(|x| x.kind == FileKind::Service("SimpleService".to_string())));
+
+    for file_name in file_names {
+        assert!(temp.path().join(file_name).exists());
+    }
+
+    assert_eq!(std::fs::read_to_string(temp.path().join("Interop.manifest"))?, manifest.to_string());
+
+    write_simple_project_file(temp.path())?;
+    run_dotnet_command_if_installed(temp.path(), "build")?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_write_files_skips_empty_parts() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let signature = FunctionSignature::new(vec![], CType::Primitive(PrimitiveType::Void));
+    let meta = Meta::with_namespace_documentation("other".to_string(), Documentation::new());
+    let inventory = InventoryBuilder::new()
+        .register(Symbol::Function(Function::new("f".to_string(), signature, meta)))
+        .inventory();
+
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("other", "My.Company.Other"),
+        ..Config::default()
+    };
+
+    let manifest = Generator::new(config, inventory).write_files(temp.path())?;
+
+    let file_names = manifest.entries().iter().map(|x| x.file_name.as_str()).collect::<Vec<_>>();
+    assert!(file_names.contains(&"Interop.Types.cs"));
+    assert!(file_names.contains(&"Interop.other.cs"));
+    assert!(!file_names.contains(&"Interop.other.Types.cs"));
+    assert!(!temp.path().join("Interop.other.Types.cs").exists());
+
+    Ok(())
+}
+
+/// Generates bindings with the given `struct_arrays` into `folder`, returning the `Interop.cs` and `Interop.common.cs` contents.
+fn generate_struct_arrays(folder: &Path, struct_arrays: StructArrays) -> Result<(String, String), Error> {
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        use_unsafe: Unsafe::UnsafeKeyword,
+        unsupported: Unsupported::Comment,
+        use_int128: true,
+        struct_arrays,
+        ..Config::default()
+    };
+
+    generate_bindings_multi(folder, Unsafe::None, ParamSliceType::Array, Some(config))?;
+
+    Ok((
+        std::fs::read_to_string(folder.join("Interop.cs"))?,
+        std::fs::read_to_string(folder.join("Interop.common.cs"))?,
+    ))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_struct_arrays_unroll() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let (interop, common) = generate_struct_arrays(temp.path(), StructArrays::Unroll)?;
+    assert!(interop.contains("public partial struct Array\n    {\n        public byte data0;\n        public byte data1;"));
+    assert!(interop.contains("        public byte data15;\n    }"));
+    assert!(!interop.contains("InteropArray_") && !common.contains("InteropArray_"));
+
+    write_simple_project_file(temp.path())?;
+    run_dotnet_command_if_installed(temp.path(), "build")?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_struct_arrays_fixed_buffer() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let (interop, common) = generate_struct_arrays(temp.path(), StructArrays::FixedBuffer)?;
+    assert!(interop.contains("public partial struct Array\n    {\n        public InteropArray_u8_16 data;\n    }"));
+    assert!(common.contains("public unsafe partial struct InteropArray_u8_16\n    {\n        fixed byte data[16];\n\n        public int Length => 16;"));
+    assert!(common.contains("                return data[i];"));
+    assert!(common.contains("                data[i] = value;"));
+
+    write_simple_project_file(temp.path())?;
+    run_dotnet_command_if_installed(temp.path(), "build")?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_struct_arrays_inline_array() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let (interop, common) = generate_struct_arrays(temp.path(), StructArrays::InlineArray)?;
+    assert!(interop.contains("public partial struct Array\n    {\n        public InteropArray_u8_16 data;\n    }"));
+    assert!(common.contains("[System.Runtime.CompilerServices.InlineArray(16)]\n    public partial struct InteropArray_u8_16\n    {\n        byte element0;"));
+    assert!(common.contains("                return MemoryMarshal.CreateSpan(ref element0, 16)[i];"));
+    assert!(common.contains("                MemoryMarshal.CreateSpan(ref element0, 16)[i] = value;"));
+
+    // `[InlineArray]` needs .NET 8.
+    write_project_file(temp.path(), "net8.0")?;
+    run_dotnet_command_if_installed(temp.path(), "build")?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn config_visibility_as_declared() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_csharp")?;
+
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        use_unsafe: Unsafe::UnsafeKeyword,
+        unsupported: Unsupported::Comment,
+        visibility_types: CSharpVisibility::AsDeclared,
+        ..Config::default()
+    };
+
+    generate_bindings_multi(temp.path(), Unsafe::None, ParamSliceType::Array, Some(config))?;
+
+    let generated = std::fs::read_to_string(temp.path().join("Interop.cs"))?;
+    assert!(generated.contains("internal partial struct VisibilityCrate"));
+    assert!(generated.contains("public partial struct Visibility1"));
+    assert!(generated.contains("internal static extern byte visibility_crate("));
+
+    write_simple_project_file(temp.path())?;
+    run_dotnet_command_if_installed(temp.path(), "build")?;
+
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn config_visibility_force_visibility_public() -> Result<(), Error> {
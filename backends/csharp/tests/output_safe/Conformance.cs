@@ -0,0 +1,172 @@
+// Automatically generated by Interoptopus.
+using Xunit;
+using My.Company;
+
+namespace interop_test
+{
+    public class ConformanceTests
+    {
+        [Fact]
+        public void primitive_void()
+        {
+            Interop.primitive_void();
+        }
+
+        [Fact]
+        public void primitive_void2()
+        {
+            Interop.primitive_void2();
+        }
+
+        [Fact]
+        public void primitive_bool()
+        {
+            Assert.Equal(true, Interop.primitive_bool(false));
+            Assert.Equal(false, Interop.primitive_bool(true));
+        }
+
+        [Fact]
+        public void primitive_u8()
+        {
+            Assert.Equal((byte) 255, Interop.primitive_u8((byte) 0));
+            Assert.Equal((byte) 254, Interop.primitive_u8((byte) 1));
+            Assert.Equal((byte) 0, Interop.primitive_u8((byte) 255));
+        }
+
+        [Fact]
+        public void primitive_u16()
+        {
+            Assert.Equal((ushort) 65535, Interop.primitive_u16((ushort) 0));
+            Assert.Equal((ushort) 65534, Interop.primitive_u16((ushort) 1));
+            Assert.Equal((ushort) 0, Interop.primitive_u16((ushort) 65535));
+        }
+
+        [Fact]
+        public void primitive_u32()
+        {
+            Assert.Equal(4294967295u, Interop.primitive_u32(0u));
+            Assert.Equal(4294967294u, Interop.primitive_u32(1u));
+            Assert.Equal(0u, Interop.primitive_u32(4294967295u));
+        }
+
+        [Fact]
+        public void primitive_u64()
+        {
+            Assert.Equal(18446744073709551615ul, Interop.primitive_u64(0ul));
+            Assert.Equal(18446744073709551614ul, Interop.primitive_u64(1ul));
+            Assert.Equal(0ul, Interop.primitive_u64(18446744073709551615ul));
+        }
+
+        [Fact]
+        public void primitive_i8()
+        {
+            Assert.Equal((sbyte) 0, Interop.primitive_i8((sbyte) 0));
+            Assert.Equal((sbyte) -1, Interop.primitive_i8((sbyte) 1));
+            Assert.Equal((sbyte) 1, Interop.primitive_i8((sbyte) -1));
+            Assert.Equal((sbyte) -128, Interop.primitive_i8((sbyte) -128));
+            Assert.Equal((sbyte) -127, Interop.primitive_i8((sbyte) 127));
+        }
+
+        [Fact]
+        public void primitive_i16()
+        {
+            Assert.Equal((short) 0, Interop.primitive_i16((short) 0));
+            Assert.Equal((short) -1, Interop.primitive_i16((short) 1));
+            Assert.Equal((short) 1, Interop.primitive_i16((short) -1));
+            Assert.Equal((short) -32768, Interop.primitive_i16((short) -32768));
+            Assert.Equal((short) -32767, Interop.primitive_i16((short) 32767));
+        }
+
+        [Fact]
+        public void primitive_i32()
+        {
+            Assert.Equal(0, Interop.primitive_i32(0));
+            Assert.Equal(-1, Interop.primitive_i32(1));
+            Assert.Equal(1, Interop.primitive_i32(-1));
+            Assert.Equal(int.MinValue, Interop.primitive_i32(int.MinValue));
+            Assert.Equal(-2147483647, Interop.primitive_i32(2147483647));
+        }
+
+        [Fact]
+        public void primitive_i64()
+        {
+            Assert.Equal(0L, Interop.primitive_i64(0L));
+            Assert.Equal(-1L, Interop.primitive_i64(1L));
+            Assert.Equal(1L, Interop.primitive_i64(-1L));
+            Assert.Equal(long.MinValue, Interop.primitive_i64(long.MinValue));
+            Assert.Equal(-9223372036854775807L, Interop.primitive_i64(9223372036854775807L));
+        }
+
+        [Fact]
+        public void many_args_5()
+        {
+            Assert.Equal(0L, Interop.many_args_5(0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_5(1L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_5(-1L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_5(long.MinValue, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_5(9223372036854775807L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_5(0L, 1L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_5(0L, -1L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_5(0L, long.MinValue, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_5(0L, 9223372036854775807L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_5(0L, 0L, 1L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_5(0L, 0L, -1L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_5(0L, 0L, long.MinValue, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_5(0L, 0L, 9223372036854775807L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_5(0L, 0L, 0L, 1L, 0L));
+            Assert.Equal(-1L, Interop.many_args_5(0L, 0L, 0L, -1L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_5(0L, 0L, 0L, long.MinValue, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_5(0L, 0L, 0L, 9223372036854775807L, 0L));
+            Assert.Equal(1L, Interop.many_args_5(0L, 0L, 0L, 0L, 1L));
+            Assert.Equal(-1L, Interop.many_args_5(0L, 0L, 0L, 0L, -1L));
+            Assert.Equal(long.MinValue, Interop.many_args_5(0L, 0L, 0L, 0L, long.MinValue));
+            Assert.Equal(9223372036854775807L, Interop.many_args_5(0L, 0L, 0L, 0L, 9223372036854775807L));
+        }
+
+        [Fact]
+        public void many_args_10()
+        {
+            Assert.Equal(0L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(-1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(long.MinValue, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(9223372036854775807L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, -1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, long.MinValue, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 9223372036854775807L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, -1L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, long.MinValue, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 9223372036854775807L, 0L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 1L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, -1L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, long.MinValue, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 9223372036854775807L, 0L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 1L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, -1L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, long.MinValue, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 9223372036854775807L, 0L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 1L, 0L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, -1L, 0L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, long.MinValue, 0L, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 9223372036854775807L, 0L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 1L, 0L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, -1L, 0L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, long.MinValue, 0L, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 9223372036854775807L, 0L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 1L, 0L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, -1L, 0L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, long.MinValue, 0L, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 9223372036854775807L, 0L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 1L, 0L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, -1L, 0L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, long.MinValue, 0L));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 9223372036854775807L, 0L));
+            Assert.Equal(1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 1L));
+            Assert.Equal(-1L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, -1L));
+            Assert.Equal(long.MinValue, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, long.MinValue));
+            Assert.Equal(9223372036854775807L, Interop.many_args_10(0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 0L, 9223372036854775807L));
+        }
+    }
+}
@@ -8,9 +8,62 @@ using System.Runtime.InteropServices;
 using My.Company;
 using My.Company.Common;
 #pragma warning restore 0105
+#pragma warning disable CS0612, CS0618
 
 namespace My.Company.Common
 {
+    internal static partial class Interop
+    {
+        public const string NativeLib = "interoptopus_reference_project";
+
+
+        public const uint NAMESPACED = (uint) 42;
+
+
+        /// Emitted into the `common` namespace.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "namespaced_function")]
+        public static extern double namespaced_function(Vec x);
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_namespaced_destroy")]
+        public static extern FFIError simple_service_namespaced_destroy(ref IntPtr context);
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        public static void simple_service_namespaced_destroy_checked(ref IntPtr context)
+        {
+            var rval = simple_service_namespaced_destroy(ref context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_namespaced_new_with")]
+        public static extern FFIError simple_service_namespaced_new_with(ref IntPtr context, uint value);
+
+        public static void simple_service_namespaced_new_with_checked(ref IntPtr context, uint value)
+        {
+            var rval = simple_service_namespaced_new_with(ref context, value);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_namespaced_value")]
+        public static extern uint simple_service_namespaced_value(IntPtr context);
+
+    }
 
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
@@ -637,6 +690,44 @@ namespace My.Company.Common
     internal delegate uint MyCallbackNamespaced(uint value);
 
 
+    /// A service whose functions and class are emitted into the `common` namespace.
+    ///
+    /// Thread safe, instances may be used from multiple threads at the same time.
+    internal partial class SimpleServiceNamespaced : IDisposable
+    {
+        private IntPtr _context;
+
+        private SimpleServiceNamespaced() {}
+
+        public static SimpleServiceNamespaced NewWith(uint value)
+        {
+            var self = new SimpleServiceNamespaced();
+            var rval = Interop.simple_service_namespaced_new_with(ref self._context, value);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+            return self;
+        }
+
+        public void Dispose()
+        {
+            var rval = Interop.simple_service_namespaced_destroy(ref _context);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        public uint Value()
+        {
+            return Interop.simple_service_namespaced_value(_context);
+        }
+
+        public IntPtr Context => _context;
+    }
+
+
 
     public class InteropException<T> : Exception
     {
@@ -13,6 +13,7 @@ using Unity.Collections;
 using My.Company;
 using My.Company.Common;
 #pragma warning restore 0105
+#pragma warning disable CS0612, CS0618
 
 namespace My.Company
 {
@@ -23,18 +24,110 @@ namespace My.Company
         static Interop()
         {
             var api_version = Interop.pattern_api_guard();
-            if (api_version != 4570136964495247137ul)
+            if (api_version != 9353674647693334944ul)
             {
-                throw new TypeLoadException($"API reports hash {api_version} which differs from hash in bindings (4570136964495247137). You probably forgot to update / copy either the bindings or the library.");
+                throw new TypeLoadException($"API reports hash {api_version} which differs from hash in bindings (9353674647693334944). You probably forgot to update / copy either the bindings or the library.");
+            }
+            var type_layouts = Interop.pattern_layout_guard();
+            for (var i = 0; i < type_layouts.Count; i++)
+            {
+                var layout = type_layouts[i];
+                switch (layout.name_hash)
+                {
+                    case 7725630223355528838ul: CheckTypeLayout(layout, "Array", typeof(Array)); break;
+                    case 13780230644173993384ul: CheckTypeLayout(layout, "BooleanAlignment", typeof(BooleanAlignment)); break;
+                    case 13908034248162013336ul: CheckTypeLayout(layout, "Container", typeof(Container)); break;
+                    case 7592086385043351515ul: CheckTypeLayout(layout, "DelegateCallbackMyCallbackContextual", typeof(DelegateCallbackMyCallbackContextual)); break;
+                    case 13883161942318069648ul: CheckTypeLayout(layout, "ExtraTypef32", typeof(ExtraTypef32)); break;
+                    case 5595264705378701256ul: CheckTypeLayout(layout, "Genericu32", typeof(Genericu32)); break;
+                    case 8975949700123536863ul: CheckTypeLayout(layout, "Genericu8", typeof(Genericu8)); break;
+                    case 17081007651772294375ul: CheckTypeLayout(layout, "Inner", typeof(Inner)); break;
+                    case 16839520613172088648ul: CheckTypeLayout(layout, "Local", typeof(Local)); break;
+                    case 11111868642027686448ul: CheckTypeLayout(layout, "Packed1", typeof(Packed1)); break;
+                    case 11111871940562571081ul: CheckTypeLayout(layout, "Packed2", typeof(Packed2)); break;
+                    case 8313648167006137431ul: CheckTypeLayout(layout, "Phantomu8", typeof(Phantomu8)); break;
+                    case 8480366299634365705ul: CheckTypeLayout(layout, "StructDeprecated", typeof(StructDeprecated)); break;
+                    case 2363003323943207424ul: CheckTypeLayout(layout, "StructDocumented", typeof(StructDocumented)); break;
+                    case 10152114614164664082ul: CheckTypeLayout(layout, "StructRenamed", typeof(StructRenamed)); break;
+                    case 4272519484055865455ul: CheckTypeLayout(layout, "Tupled", typeof(Tupled)); break;
+                    case 56007294078283895ul: CheckTypeLayout(layout, "TypeLayout", typeof(TypeLayout)); break;
+                    case 17668793176895495864ul: CheckTypeLayout(layout, "UseAsciiStringPattern", typeof(UseAsciiStringPattern)); break;
+                    case 1724911193198685794ul: CheckTypeLayout(layout, "Vec", typeof(Vec)); break;
+                    case 16480943761859895612ul: CheckTypeLayout(layout, "Vec1", typeof(Vec1)); break;
+                    case 16480947060394780245ul: CheckTypeLayout(layout, "Vec2", typeof(Vec2)); break;
+                    case 11529662329476111629ul: CheckTypeLayout(layout, "Vec3f32", typeof(Vec3f32)); break;
+                    case 2808206013834181724ul: CheckTypeLayout(layout, "Visibility1", typeof(Visibility1)); break;
+                    case 2808209312369066357ul: CheckTypeLayout(layout, "Visibility2", typeof(Visibility2)); break;
+                    case 5906906342198409056ul: CheckTypeLayout(layout, "VisibilityCrate", typeof(VisibilityCrate)); break;
+                    case 3463706654236366161ul: CheckTypeLayout(layout, "Weird1u32", typeof(Weird1u32)); break;
+                    case 10874643905297256825ul: CheckTypeLayout(layout, "Weird2u8", typeof(Weird2u8)); break;
+                    case 8449737672036434417ul: CheckTypeLayout(layout, "SliceBool", typeof(SliceBool)); break;
+                    case 5411056133711372521ul: CheckTypeLayout(layout, "SliceI32", typeof(SliceI32)); break;
+                    case 10038116113606839395ul: CheckTypeLayout(layout, "SliceTypeLayout", typeof(SliceTypeLayout)); break;
+                    case 11374494939819850637ul: CheckTypeLayout(layout, "SliceU32", typeof(SliceU32)); break;
+                    case 5419493245234256604ul: CheckTypeLayout(layout, "SliceU8", typeof(SliceU8)); break;
+                    case 1505421970813947124ul: CheckTypeLayout(layout, "SliceUseAsciiStringPattern", typeof(SliceUseAsciiStringPattern)); break;
+                    case 2104600942193532856ul: CheckTypeLayout(layout, "SliceVec", typeof(SliceVec)); break;
+                    case 2872124794895837801ul: CheckTypeLayout(layout, "SliceVec3f32", typeof(SliceVec3f32)); break;
+                    case 8033795909225755975ul: CheckTypeLayout(layout, "SliceMutConstPtrI8", typeof(SliceMutConstPtrI8)); break;
+                    case 3194354960619615953ul: CheckTypeLayout(layout, "SliceMutU32", typeof(SliceMutU32)); break;
+                    case 4368741217356678104ul: CheckTypeLayout(layout, "SliceMutU8", typeof(SliceMutU8)); break;
+                    case 9589520511416213518ul: CheckTypeLayout(layout, "SliceMutVec", typeof(SliceMutVec)); break;
+                    case 6182523805998915764ul: CheckTypeLayout(layout, "OptionInner", typeof(OptionInner)); break;
+                    case 17201604669416752257ul: CheckTypeLayout(layout, "OptionVec", typeof(OptionVec)); break;
+                }
+            }
+        }
+
+        static void CheckTypeLayout(TypeLayout layout, string name, Type type)
+        {
+            var size = Marshal.SizeOf(type);
+            if (layout.size != (ulong) size)
+            {
+                throw new TypeLoadException($"Struct {name} has size {layout.size} in the library, but {size} in the bindings. You probably forgot to update / copy either the bindings or the library.");
+            }
+
+            var align = AlignOf(type);
+            if (layout.align != (ulong) align)
+            {
+                throw new TypeLoadException($"Struct {name} has alignment {layout.align} in the library, but {align} in the bindings. You probably forgot to update / copy either the bindings or the library.");
             }
         }
 
+        // Marshal has no AlignOf, so this follows its rules: the largest field alignment, capped by Pack.
+        static int AlignOf(Type type)
+        {
+            if (type.FullName == "System.Int128" || type.FullName == "System.UInt128") { return 16; }
+            if (type.IsEnum) { return AlignOf(Enum.GetUnderlyingType(type)); }
+            if (type.IsPointer || !type.IsValueType) { return IntPtr.Size; }
+            if (type.IsPrimitive) { return Marshal.SizeOf(type); }
+
+            var align = 1;
+            foreach (var field in type.GetFields(System.Reflection.BindingFlags.Instance | System.Reflection.BindingFlags.Public | System.Reflection.BindingFlags.NonPublic))
+            {
+                var marshal = (MarshalAsAttribute) Attribute.GetCustomAttribute(field, typeof(MarshalAsAttribute));
+                var is_byte = marshal != null && (marshal.Value == UnmanagedType.I1 || marshal.Value == UnmanagedType.U1);
+                var field_type = field.FieldType.IsArray ? field.FieldType.GetElementType() : field.FieldType;
+                align = Math.Max(align, is_byte ? 1 : AlignOf(field_type));
+            }
+
+            var pack = type.StructLayoutAttribute != null ? type.StructLayoutAttribute.Pack : 0;
+            return pack > 0 ? Math.Min(align, pack) : align;
+        }
+
         public const byte U8 = (byte) 255;
 
         public const float F32_MIN_POSITIVE = (float) 0.000000000000000000000000000000000000011754944;
 
         public const int COMPUTED_I32 = (int) -2147483647;
 
+        /// Version string of the reference project.
+        public const string VERSION = "1.2.3 \"reference\"";
+
+        public static readonly byte[] MAGIC = new byte[] { (byte) 73, (byte) 79, (byte) 80, (byte) 83 };
+
+        public static readonly Vec3f32 UP = new Vec3f32 { x = (float) 0, y = (float) 1, z = (float) 0 };
+
 
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_void")]
         public static extern void primitive_void();
@@ -69,6 +162,22 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_i64")]
         public static extern long primitive_i64(long x);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_usize")]
+        public static extern nuint primitive_usize(nuint x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_isize")]
+        public static extern nint primitive_isize(nint x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_u128")]
+        public static extern UInt128 primitive_u128(UInt128 x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_i128")]
+        public static extern Int128 primitive_i128(Int128 x);
+
+        /// Returns the Unicode scalar value of `x`, or `u32::MAX` if `x` is not a valid `char`.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_char")]
+        public static extern uint primitive_char(uint x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "boolean_alignment")]
         public static extern BooleanAlignment boolean_alignment(BooleanAlignment x);
 
@@ -152,9 +261,15 @@ namespace My.Company
         public static extern byte array_1(Array x);
 
         /// This function has documentation.
+        /// <param name="x">A documented struct, its fields are documented as well.</param>
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "documented")]
         public static extern EnumDocumented documented(StructDocumented x);
 
+        /// Superseded by `documented`.
+        [Obsolete("Use `documented` instead.")]
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "deprecated")]
+        public static extern EnumDeprecated deprecated(StructDeprecated x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "ambiguous_1")]
         public static extern Vec1 ambiguous_1(Vec1 x);
 
@@ -216,6 +331,14 @@ namespace My.Company
         }
         #endif
 
+        /// Named after a C# keyword, which uses another name instead.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "checked")]
+        public static extern uint CheckedValue(uint x);
+
+        /// Exported as `renamed_symbol`, aliased in C and not emitted for Python.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "renamed_symbol")]
+        public static extern uint renamed_symbol(uint x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "panics")]
         public static extern FFIError panics();
 
@@ -240,6 +363,9 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "visibility")]
         public static extern void visibility(Visibility1 x, Visibility2 y);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "visibility_crate")]
+        public static extern byte visibility_crate(VisibilityCrate x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "repr_transparent")]
         public static extern Tupled repr_transparent(Tupled x, ref Tupled r);
 
@@ -490,6 +616,9 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_api_guard")]
         public static extern ulong pattern_api_guard();
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_layout_guard")]
+        public static extern SliceTypeLayout pattern_layout_guard();
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_1")]
         public static extern uint pattern_callback_1(MyCallback callback, uint x);
 
@@ -538,10 +667,65 @@ namespace My.Company
         public static extern FFIError pattern_callback_7(IntPtr c1, IntPtr c2, int x, int i, out int o);
 
 
+        /// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_8")]
+        public static extern FFIError pattern_callback_8(uint x, MyCallbackCompletion completion);
+
+        /// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+        public static System.Threading.Tasks.Task<uint> pattern_callback_8_async(uint x)
+        {
+            var tcs = new System.Threading.Tasks.TaskCompletionSource<uint>(System.Threading.Tasks.TaskCreationOptions.RunContinuationsAsynchronously);
+            var handle = default(GCHandle);
+            MyCallbackCompletion completion = (x0) =>
+            {
+                lock (tcs)
+                {
+                    if (handle.IsAllocated)
+                    {
+                        handle.Free();
+                    }
+                }
+                tcs.TrySetResult(x0);
+            };
+            handle = GCHandle.Alloc(completion);
+            var rval = pattern_callback_8(x, completion);
+            if (rval != FFIError.Ok)
+            {
+                lock (tcs)
+                {
+                    if (handle.IsAllocated)
+                    {
+                        handle.Free();
+                    }
+                }
+                tcs.TrySetException(new InteropException<FFIError>(rval));
+            }
+            return tcs.Task;
+        }
+
+        /// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+        public static void pattern_callback_8_checked(uint x, MyCallbackCompletion completion)
+        {
+            var rval = pattern_callback_8(x, completion);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        /// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_8")]
+        public static extern FFIError pattern_callback_8(uint x, IntPtr completion);
+
+
+        /// Invokes `callback` once; its destructor runs when the callback is dropped on return.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_9")]
+        public static extern uint pattern_callback_9(MyClosure.Native callback, uint x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_surrogates_1")]
         public static extern void pattern_surrogates_1(Local s, out Container c);
 
-        /// Destroys the given instance.
+        ///Destroys the given instance.
         ///
         /// # Safety
         ///
@@ -550,7 +734,7 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_destroy")]
         public static extern FFIError simple_service_destroy(ref IntPtr context);
 
-        /// Destroys the given instance.
+        ///Destroys the given instance.
         ///
         /// # Safety
         ///
@@ -566,6 +750,7 @@ namespace My.Company
         }
 
         /// The constructor must return a `Result<Self, Error>`.
+        /// <param name="some_value">Stored in the service.</param>
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_new_with")]
         public static extern FFIError simple_service_new_with(ref IntPtr context, uint some_value);
 
@@ -615,6 +800,24 @@ namespace My.Company
             }
         }
 
+        /// The value this service was created with.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_get_value")]
+        public static extern uint simple_service_get_value(IntPtr context);
+
+        /// Setters may fail, which raises an exception in languages with properties.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_set_value")]
+        public static extern FFIError simple_service_set_value(IntPtr context, uint value);
+
+        /// Setters may fail, which raises an exception in languages with properties.
+        public static void simple_service_set_value_checked(IntPtr context, uint value)
+        {
+            var rval = simple_service_set_value(context, value);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
         /// Methods returning a Result<(), _> are the default and do not
         /// need annotations.
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_result")]
@@ -644,6 +847,14 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_void2")]
         public static extern void simple_service_method_void2(IntPtr context);
 
+        [Obsolete("Use `method_void` instead.")]
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_void_deprecated")]
+        public static extern void simple_service_method_void_deprecated(IntPtr context);
+
+        /// Named after a Python keyword, and not emitted for C.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_lambda")]
+        public static extern void simple_service_lambda(IntPtr context);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_mut_self")]
         public static extern byte simple_service_method_mut_self(IntPtr context, SliceU8 slice);
 
@@ -906,7 +1117,115 @@ namespace My.Company
         }
         #endif
 
-        /// Destroys the given instance.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_callback_completion")]
+        public static extern FFIError simple_service_method_callback_completion(IntPtr context, uint x, MyCallbackCompletion completion);
+
+        public static System.Threading.Tasks.Task<uint> simple_service_method_callback_completion_async(IntPtr context, uint x)
+        {
+            var tcs = new System.Threading.Tasks.TaskCompletionSource<uint>(System.Threading.Tasks.TaskCreationOptions.RunContinuationsAsynchronously);
+            var handle = default(GCHandle);
+            MyCallbackCompletion completion = (x0) =>
+            {
+                lock (tcs)
+                {
+                    if (handle.IsAllocated)
+                    {
+                        handle.Free();
+                    }
+                }
+                tcs.TrySetResult(x0);
+            };
+            handle = GCHandle.Alloc(completion);
+            var rval = simple_service_method_callback_completion(context, x, completion);
+            if (rval != FFIError.Ok)
+            {
+                lock (tcs)
+                {
+                    if (handle.IsAllocated)
+                    {
+                        handle.Free();
+                    }
+                }
+                tcs.TrySetException(new InteropException<FFIError>(rval));
+            }
+            return tcs.Task;
+        }
+
+        public static void simple_service_method_callback_completion_checked(IntPtr context, uint x, MyCallbackCompletion completion)
+        {
+            var rval = simple_service_method_callback_completion(context, x, completion);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_callback_completion")]
+        public static extern FFIError simple_service_method_callback_completion(IntPtr context, uint x, IntPtr completion);
+
+
+        /// Doesn't need an instance, e.g., to query capabilities before construction.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_supported_formats")]
+        public static extern uint simple_service_supported_formats();
+
+        /// Static methods can fail like any other method.
+        /// <param name="value">Must not be `0`.</param>
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_validate")]
+        public static extern FFIError simple_service_validate(uint value);
+
+        /// Static methods can fail like any other method.
+        public static void simple_service_validate_checked(uint value)
+        {
+            var rval = simple_service_validate(value);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        /// Creates another service, which is returned as a fully wrapped object.
+        /// <param name="offset">Added to this service's value.</param>
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_create_child")]
+        public static extern FFIError simple_service_create_child(IntPtr context, ref IntPtr instance, uint offset);
+
+        /// Creates another service, which is returned as a fully wrapped object.
+        public static void simple_service_create_child_checked(IntPtr context, ref IntPtr instance, uint offset)
+        {
+            var rval = simple_service_create_child(context, ref instance, offset);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_child_destroy")]
+        public static extern FFIError simple_service_child_destroy(ref IntPtr context);
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        public static void simple_service_child_destroy_checked(ref IntPtr context)
+        {
+            var rval = simple_service_child_destroy(ref context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_child_value")]
+        public static extern uint simple_service_child_value(IntPtr context);
+
+        ///Destroys the given instance.
         ///
         /// # Safety
         ///
@@ -915,7 +1234,7 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_lifetime_destroy")]
         public static extern FFIError simple_service_lifetime_destroy(ref IntPtr context);
 
-        /// Destroys the given instance.
+        ///Destroys the given instance.
         ///
         /// # Safety
         ///
@@ -1025,10 +1344,132 @@ namespace My.Company
             }
         }
 
+        ///Releases a reference to the given instance, destroying it once no references remain.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "shared_service_release")]
+        public static extern FFIError shared_service_release(ref IntPtr context);
+
+        ///Releases a reference to the given instance, destroying it once no references remain.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        public static void shared_service_release_checked(ref IntPtr context)
+        {
+            var rval = shared_service_release(ref context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "shared_service_new_with")]
+        public static extern FFIError shared_service_new_with(ref IntPtr context, uint value);
+
+        public static void shared_service_new_with_checked(ref IntPtr context, uint value)
+        {
+            var rval = shared_service_new_with(ref context, value);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "shared_service_value")]
+        public static extern uint shared_service_value(IntPtr context);
+
+        /// Adds a reference to the given instance, which must be released separately.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function
+        /// and not been released yet; passing any other value results in undefined behavior.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "shared_service_retain")]
+        public static extern FFIError shared_service_retain(IntPtr context);
+
+        /// Adds a reference to the given instance, which must be released separately.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function
+        /// and not been released yet; passing any other value results in undefined behavior.
+        public static void shared_service_retain_checked(IntPtr context)
+        {
+            var rval = shared_service_retain(context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "single_threaded_service_destroy")]
+        public static extern FFIError single_threaded_service_destroy(ref IntPtr context);
+
+        ///Destroys the given instance.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function;
+        /// passing any other value results in undefined behavior.
+        public static void single_threaded_service_destroy_checked(ref IntPtr context)
+        {
+            var rval = single_threaded_service_destroy(ref context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "single_threaded_service_new_with")]
+        public static extern FFIError single_threaded_service_new_with(ref IntPtr context, uint value);
+
+        public static void single_threaded_service_new_with_checked(ref IntPtr context, uint value)
+        {
+            var rval = single_threaded_service_new_with(ref context, value);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "single_threaded_service_increment")]
+        public static extern FFIError single_threaded_service_increment(IntPtr context);
+
+        public static void single_threaded_service_increment_checked(IntPtr context)
+        {
+            var rval = single_threaded_service_increment(context);;
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "single_threaded_service_value")]
+        public static extern uint single_threaded_service_value(IntPtr context);
+
+    }
+
+    /// Enum with a deprecated variant.
+    internal enum EnumDeprecated
+    {
+        Current = 0,
+        [Obsolete("Use `Current` instead.")]
+        Legacy = 1,
     }
 
     /// Documented enum.
-    public enum EnumDocumented
+    internal enum EnumDocumented
     {
         /// Variant A.
         A = 0,
@@ -1038,7 +1479,7 @@ namespace My.Company
         C = 2,
     }
 
-    public enum EnumRenamed
+    internal enum EnumRenamed
     {
         X = 0,
     }
@@ -1168,6 +1609,16 @@ namespace My.Company
         public uint x;
     }
 
+    /// Struct with a deprecated field.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    internal partial struct StructDeprecated
+    {
+        public uint x;
+        [Obsolete("Use `x` instead.")]
+        public uint old_x;
+    }
+
     /// Documented struct.
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
@@ -1191,6 +1642,19 @@ namespace My.Company
         public byte x0;
     }
 
+    ///Size and alignment of a single type as laid out by Rust.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    internal partial struct TypeLayout
+    {
+        ///Hash of the type's namespace and name.
+        public ulong name_hash;
+        ///Size in bytes.
+        public ulong size;
+        ///Alignment in bytes.
+        public ulong align;
+    }
+
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
     internal partial struct UseAsciiStringPattern
@@ -1239,6 +1703,13 @@ namespace My.Company
         public byte pblc2;
     }
 
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    internal partial struct VisibilityCrate
+    {
+        public byte x;
+    }
+
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
     internal partial struct Weird1u32
@@ -1265,7 +1736,7 @@ namespace My.Company
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     internal delegate byte InteropDelegate_fn_u8_rval_u8(byte x0);
 
-    public enum FFIError
+    internal enum FFIError
     {
         Ok = 0,
         Null = 100,
@@ -1274,6 +1745,105 @@ namespace My.Company
         Fail = 400,
     }
 
+    ///A pointer to an array of data someone else owns which may not be modified.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    internal partial struct SliceTypeLayout
+    {
+        ///Pointer to start of immutable data.
+        #if UNITY_2018_1_OR_NEWER
+        [NativeDisableUnsafePtrRestriction]
+        #endif
+        IntPtr data;
+        ///Number of elements.
+        ulong len;
+    }
+
+    internal partial struct SliceTypeLayout : IEnumerable<TypeLayout>
+    {
+        public SliceTypeLayout(GCHandle handle, ulong count)
+        {
+            this.data = handle.AddrOfPinnedObject();
+            this.len = count;
+        }
+        public SliceTypeLayout(IntPtr handle, ulong count)
+        {
+            this.data = handle;
+            this.len = count;
+        }
+        #if (NETSTANDARD2_1_OR_GREATER || NET5_0_OR_GREATER || NETCOREAPP2_1_OR_GREATER)
+        public ReadOnlySpan<TypeLayout> ReadOnlySpan
+        {
+            get
+            {
+                unsafe
+                {
+                    return new ReadOnlySpan<TypeLayout>(this.data.ToPointer(), (int) this.len);
+                }
+            }
+        }
+        #endif
+        #if UNITY_2018_1_OR_NEWER
+        public SliceTypeLayout(NativeArray<TypeLayout> handle)
+        {
+            unsafe
+            {
+                this.data = new IntPtr(NativeArrayUnsafeUtility.GetUnsafeReadOnlyPtr(handle));
+                this.len = (ulong) handle.Length;
+            }
+        }
+        #endif
+        public TypeLayout this[int i]
+        {
+            get
+            {
+                if (i >= Count) throw new IndexOutOfRangeException();
+                unsafe
+                {
+                    var d = (TypeLayout*) data.ToPointer();
+                    return d[i];
+                }
+            }
+        }
+        public TypeLayout[] Copied
+        {
+            get
+            {
+                var rval = new TypeLayout[len];
+                unsafe
+                {
+                    fixed (void* dst = rval)
+                    {
+                        #if __INTEROPTOPUS_NEVER
+                        #elif NETCOREAPP
+                        Unsafe.CopyBlock(dst, data.ToPointer(), (uint) len * (uint) sizeof(TypeLayout));
+                        #elif UNITY_2018_1_OR_NEWER
+                        UnsafeUtility.MemCpy(dst, data.ToPointer(), (long) (len * (ulong) sizeof(TypeLayout)));
+                        #else
+                        for (var i = 0; i < (int) len; i++) {
+                            rval[i] = this[i];
+                        }
+                        #endif
+                    }
+                }
+                return rval;
+            }
+        }
+        public int Count => (int) len;
+        public IEnumerator<TypeLayout> GetEnumerator()
+        {
+            for (var i = 0; i < (int)len; ++i)
+            {
+                yield return this[i];
+            }
+        }
+        IEnumerator IEnumerable.GetEnumerator()
+        {
+            return this.GetEnumerator();
+        }
+    }
+
+
     ///A pointer to an array of data someone else owns which may not be modified.
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
@@ -1504,6 +2074,9 @@ namespace My.Company
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     internal delegate uint MyCallback(uint value);
 
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    internal delegate void MyCallbackCompletion(uint value);
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     internal delegate void MyCallbackContextual(IntPtr context, uint value);
 
@@ -1554,8 +2127,73 @@ namespace My.Company
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     internal delegate void SumDelegateReturn2(int x, int y);
 
+    internal delegate uint MyClosureDelegate(uint value);
+
+    internal partial class MyClosure
+    {
+        [Serializable]
+        [StructLayout(LayoutKind.Sequential)]
+        public partial struct Native
+        {
+            internal IntPtr callback;
+            internal IntPtr context;
+            internal IntPtr destructor;
+
+            public static implicit operator Native(MyClosureDelegate f) => new MyClosure(f).Consume();
+        }
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        private delegate uint NativeCallback(uint value, IntPtr context);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        private delegate void NativeDestructor(IntPtr context);
+
+        private static readonly NativeCallback _callback = Invoke;
+        private static readonly NativeDestructor _destructor = Release;
+
+        private readonly MyClosureDelegate _f;
+        private int _consumed;
+
+        public MyClosure(MyClosureDelegate f)
+        {
+            _f = f;
+        }
+
+        public static implicit operator MyClosure(MyClosureDelegate f) => new MyClosure(f);
+
+        public static implicit operator Native(MyClosure c) => c.Consume();
+
+        /// Hands the delegate to native code, which releases it once done. Can only be called once.
+        public Native Consume()
+        {
+            if (System.Threading.Interlocked.Exchange(ref _consumed, 1) != 0)
+            {
+                throw new InvalidOperationException("This MyClosure was already passed to native code and cannot be passed again.");
+            }
+
+            var native = new Native();
+            native.callback = Marshal.GetFunctionPointerForDelegate(_callback);
+            native.context = GCHandle.ToIntPtr(GCHandle.Alloc(_f));
+            native.destructor = Marshal.GetFunctionPointerForDelegate(_destructor);
+            return native;
+        }
+
+        private static uint Invoke(uint value, IntPtr context)
+        {
+            var f = (MyClosureDelegate) GCHandle.FromIntPtr(context).Target;
+            return f(value);
+        }
+
+        private static void Release(IntPtr context)
+        {
+            GCHandle.FromIntPtr(context).Free();
+        }
+    }
+
 
     /// Some struct we want to expose as a class.
+    ///
+    /// Thread safe, instances may be used from multiple threads at the same time.
     internal partial class SimpleService : IDisposable
     {
         private IntPtr _context;
@@ -1563,6 +2201,7 @@ namespace My.Company
         private SimpleService() {}
 
         /// The constructor must return a `Result<Self, Error>`.
+        /// <param name="some_value">Stored in the service.</param>
         public static SimpleService NewWith(uint some_value)
         {
             var self = new SimpleService();
@@ -1616,6 +2255,23 @@ namespace My.Company
             }
         }
 
+        /// Doesn't need an instance, e.g., to query capabilities before construction.
+        public static uint SupportedFormats()
+        {
+            return Interop.simple_service_supported_formats();
+        }
+
+        /// Static methods can fail like any other method.
+        /// <param name="value">Must not be `0`.</param>
+        public static void Validate(uint value)
+        {
+            var rval = Interop.simple_service_validate(value);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
         /// Methods returning a Result<(), _> are the default and do not
         /// need annotations.
         public void MethodResult(uint anon1)
@@ -1646,6 +2302,18 @@ namespace My.Company
             Interop.simple_service_method_void2(_context);
         }
 
+        [Obsolete("Use `method_void` instead.")]
+        public void MethodVoidDeprecated()
+        {
+            Interop.simple_service_method_void_deprecated(_context);
+        }
+
+        /// Named after a Python keyword, and not emitted for C.
+        public void Lambda()
+        {
+            Interop.simple_service_lambda(_context);
+        }
+
         public byte MethodMutSelf(SliceU8 slice)
         {
             return Interop.simple_service_method_mut_self(_context, slice);
@@ -1852,10 +2520,97 @@ namespace My.Company
         }
         #endif
 
+        public void MethodCallbackCompletion(uint x, MyCallbackCompletion completion)
+        {
+            var rval = Interop.simple_service_method_callback_completion(_context, x, completion);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        public System.Threading.Tasks.Task<uint> MethodCallbackCompletionAsync(uint x)
+        {
+            return Interop.simple_service_method_callback_completion_async(_context, x);
+        }
+
+        #if UNITY_2018_1_OR_NEWER
+        public void MethodCallbackCompletion(uint x, IntPtr completion)
+        {
+            Interop.simple_service_method_callback_completion(_context, x, completion);
+        }
+        #endif
+
+        /// Creates another service, which is returned as a fully wrapped object.
+        /// <param name="offset">Added to this service's value.</param>
+        public SimpleServiceChild CreateChild(uint offset)
+        {
+            var context = IntPtr.Zero;
+            var rval = Interop.simple_service_create_child(_context, ref context, offset);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+            return new SimpleServiceChild(context, this);
+        }
+
+        /// The value this service was created with.
+        public uint Value
+        {
+            get
+            {
+                return Interop.simple_service_get_value(_context);
+            }
+            set
+            {
+                var rval = Interop.simple_service_set_value(_context, value);
+                if (rval != FFIError.Ok)
+                {
+                    throw new InteropException<FFIError>(rval);
+                }
+            }
+        }
+
+        public IntPtr Context => _context;
+    }
+
+
+    /// A service without constructors, only created by [`SimpleService::create_child`].
+    ///
+    /// Thread safe, instances may be used from multiple threads at the same time.
+    internal partial class SimpleServiceChild : IDisposable
+    {
+        private IntPtr _context;
+
+        private SimpleServiceChild() {}
+
+        internal object Owner { get; }
+
+        internal SimpleServiceChild(IntPtr context, object owner)
+        {
+            _context = context;
+            Owner = owner;
+        }
+
+        public void Dispose()
+        {
+            var rval = Interop.simple_service_child_destroy(ref _context);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        public uint Value()
+        {
+            return Interop.simple_service_child_value(_context);
+        }
+
         public IntPtr Context => _context;
     }
 
 
+    /// Thread safe, instances may be used from multiple threads at the same time.
     internal partial class SimpleServiceLifetime : IDisposable
     {
         private IntPtr _context;
@@ -1947,5 +2702,113 @@ namespace My.Company
     }
 
 
+    /// A reference counted service, instances of which can be shared between owners.
+    ///
+    /// Thread safe, instances may be used from multiple threads at the same time.
+    internal partial class SharedService : IDisposable
+    {
+        private IntPtr _context;
+
+        private SharedService() {}
+
+        public static SharedService NewWith(uint value)
+        {
+            var self = new SharedService();
+            var rval = Interop.shared_service_new_with(ref self._context, value);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+            return self;
+        }
+
+        public void Dispose()
+        {
+            var rval = Interop.shared_service_release(ref _context);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+        }
+
+        /// Returns another reference to this instance, which must be disposed separately.
+        public SharedService Clone()
+        {
+            var rval = Interop.shared_service_retain(_context);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+            var clone = new SharedService();
+            clone._context = _context;
+            return clone;
+        }
+
+        public uint Value()
+        {
+            return Interop.shared_service_value(_context);
+        }
+
+        public IntPtr Context => _context;
+    }
+
+
+    /// A service neither `Send` nor `Sync`, which must stay on the thread that created it.
+    ///
+    /// Instances must only be used from the thread which created them.
+    internal partial class SingleThreadedService : IDisposable
+    {
+        private IntPtr _context;
+        private object _lock = new object();
+
+        private SingleThreadedService() {}
+
+        public static SingleThreadedService NewWith(uint value)
+        {
+            var self = new SingleThreadedService();
+            var rval = Interop.single_threaded_service_new_with(ref self._context, value);
+            if (rval != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(rval);
+            }
+            return self;
+        }
+
+        public void Dispose()
+        {
+            lock (_lock)
+            {
+                var rval = Interop.single_threaded_service_destroy(ref _context);
+                if (rval != FFIError.Ok)
+                {
+                    throw new InteropException<FFIError>(rval);
+                }
+            }
+        }
+
+        public void Increment()
+        {
+            lock (_lock)
+            {
+                var rval = Interop.single_threaded_service_increment(_context);
+                if (rval != FFIError.Ok)
+                {
+                    throw new InteropException<FFIError>(rval);
+                }
+            }
+        }
+
+        public uint Value()
+        {
+            lock (_lock)
+            {
+                return Interop.single_threaded_service_value(_context);
+            }
+        }
+
+        public IntPtr Context => _context;
+    }
+
+
 
 }
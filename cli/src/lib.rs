@@ -0,0 +1,227 @@
+//! Generates bindings from a built library embedding its [Interoptopus](https://github.com/ralfbiedert/interoptopus) inventory.
+//!
+//! Bindings are usually generated from unit tests calling your inventory function. If your library
+//! also exports its inventory via [`inventory_export!`](interoptopus::inventory_export), this crate can
+//! regenerate bindings from the shipped `.dll` / `.so` alone, without the source tree.
+//!
+//! # Usage
+//!
+//! Enable the `inventory_export` feature in your library and export the inventory:
+//!
+//! ```rust
+//! use interoptopus::{inventory_export, Inventory, InventoryBuilder};
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new().inventory()
+//! }
+//!
+//! inventory_export!(my_inventory);
+//! ```
+//!
+//! Then describe which backends to run in a config file, e.g., `bindings.toml`. Each section is
+//! optional, `output` paths are relative to the config file, all other keys are the respective
+//! backend's `Config` fields and default as usual:
+//!
+//! ```toml
+//! [c]
+//! output = "bindings/c/my_library.h"
+//! ifndef = "my_library"
+//!
+//! [csharp]
+//! output = "bindings/csharp/Interop.cs"
+//! dll_name = "my_library"
+//! namespace_mappings = { "" = "My.Company", "_global" = "My.Company" }
+//! overloads = ["DotNet"]
+//!
+//! [cpython]
+//! output = "bindings/python/my_library.py"
+//! ```
+//!
+//! Finally run
+//!
+//! ```text
+//! interoptopus-bindgen target/release/libmy_library.so bindings.toml
+//! ```
+//!
+//! Files whose content did not change are not rewritten.
+use interoptopus::patterns::api_guard::{inventory_from_ptr, INVENTORY_SYMBOL};
+use interoptopus::writer::OutputSet;
+use interoptopus::{Interop, Inventory};
+use serde::Deserialize;
+use std::ffi::c_char;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Can be observed if generating bindings fails.
+#[derive(Debug)]
+pub enum Error {
+    /// Loading the library or its inventory symbol failed.
+    Library(libloading::Error),
+
+    /// The config file is malformed.
+    Config(toml::de::Error),
+
+    /// Reading the inventory or writing bindings failed.
+    Interoptopus(interoptopus::Error),
+}
+
+impl From<libloading::Error> for Error {
+    fn from(e: libloading::Error) -> Self {
+        Self::Library(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Self::Config(e)
+    }
+}
+
+impl From<interoptopus::Error> for Error {
+    fn from(e: interoptopus::Error) -> Self {
+        Self::Interoptopus(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Interoptopus(e.into())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Library(e) => write!(f, "Failed to load library: {}", e),
+            Error::Config(e) => write!(f, "Invalid config: {}", e),
+            Error::Interoptopus(e) => write!(f, "Failed to generate bindings: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// C# overload writers to add, see `interoptopus_backend_csharp::overloads`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize)]
+pub enum CSharpOverload {
+    DotNet,
+    Unity,
+}
+
+/// Generates C bindings into `output`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CTarget {
+    pub output: PathBuf,
+    #[serde(flatten)]
+    pub config: interoptopus_backend_c::Config,
+}
+
+/// Generates C# bindings into `output`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CSharpTarget {
+    pub output: PathBuf,
+    #[serde(default)]
+    pub overloads: Vec<CSharpOverload>,
+    #[serde(flatten)]
+    pub config: interoptopus_backend_csharp::Config,
+}
+
+/// Generates Python bindings into `output`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CPythonTarget {
+    pub output: PathBuf,
+    #[serde(flatten)]
+    pub config: interoptopus_backend_cpython::Config,
+}
+
+/// Which backends to run, usually read from a TOML file.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Config {
+    pub c: Option<CTarget>,
+    pub csharp: Option<CSharpTarget>,
+    pub cpython: Option<CPythonTarget>,
+}
+
+impl Config {
+    /// Parses a config, `output` paths are kept as they are.
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Reads a config file, resolving `output` paths relative to the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut config = Self::from_toml(&fs::read_to_string(path)?)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+
+        let outputs = [
+            config.c.as_mut().map(|x| &mut x.output),
+            config.csharp.as_mut().map(|x| &mut x.output),
+            config.cpython.as_mut().map(|x| &mut x.output),
+        ];
+
+        for output in outputs.into_iter().flatten() {
+            *output = base.join(&*output);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Loads the library at `path` and reads the inventory it exports via [`INVENTORY_SYMBOL`].
+///
+/// # Safety
+///
+/// Loading a library runs its initialization code. The library's [`INVENTORY_SYMBOL`] must have been
+/// generated by [`inventory_export!`](interoptopus::inventory_export).
+pub unsafe fn load_inventory(path: impl AsRef<Path>) -> Result<Inventory, Error> {
+    let library = unsafe { libloading::Library::new(path.as_ref())? };
+    let symbol = unsafe { library.get::<extern "C" fn() -> *const c_char>(INVENTORY_SYMBOL.as_bytes())? };
+
+    // The string is owned by the library, so parse it before unloading.
+    let inventory = unsafe { inventory_from_ptr(symbol()) }?;
+
+    Ok(inventory)
+}
+
+/// Runs all configured backends, returning the paths of files actually written.
+pub fn generate(config: &Config, inventory: &Inventory) -> Result<Vec<PathBuf>, Error> {
+    let mut written = Vec::new();
+
+    if let Some(target) = &config.c {
+        let generator = interoptopus_backend_c::Generator::new(target.config.clone(), inventory.clone());
+        written.extend(write_target(&generator, &target.output)?);
+    }
+
+    if let Some(target) = &config.csharp {
+        let mut generator = interoptopus_backend_csharp::Generator::new(target.config.clone(), inventory.clone());
+
+        for overload in &target.overloads {
+            match overload {
+                CSharpOverload::DotNet => generator.add_overload_writer(interoptopus_backend_csharp::overloads::DotNet::new()),
+                CSharpOverload::Unity => generator.add_overload_writer(interoptopus_backend_csharp::overloads::Unity::new()),
+            };
+        }
+
+        written.extend(write_target(&generator, &target.output)?);
+    }
+
+    if let Some(target) = &config.cpython {
+        let generator = interoptopus_backend_cpython::Generator::new(target.config.clone(), inventory.clone());
+        written.extend(write_target(&generator, &target.output)?);
+    }
+
+    Ok(written)
+}
+
+fn write_target(generator: &impl Interop, output: &Path) -> Result<Vec<PathBuf>, Error> {
+    let folder = output.parent().unwrap_or_else(|| Path::new(""));
+    let file_name = output.file_name().and_then(|x| x.to_str()).ok_or(interoptopus::Error::FileNotFound)?;
+
+    let mut files = OutputSet::new();
+    files.write(file_name, |w| generator.write_to(w))?;
+
+    let written = files.write_to_directory(folder)?;
+    Ok(written.into_iter().map(|x| folder.join(x)).collect())
+}
@@ -0,0 +1,32 @@
+use interoptopus_cli::{generate, load_inventory, Config, Error};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn run(library: &str, config: &str) -> Result<Vec<PathBuf>, Error> {
+    let config = Config::from_file(config)?;
+    let inventory = unsafe { load_inventory(library)? };
+
+    generate(&config, &inventory)
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    let [_, library, config] = args.as_slice() else {
+        eprintln!("Usage: interoptopus-bindgen <library> <config.toml>");
+        return ExitCode::FAILURE;
+    };
+
+    match run(library, config) {
+        Ok(written) => {
+            for file in written {
+                println!("Wrote {}", file.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
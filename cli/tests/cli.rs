@@ -0,0 +1,61 @@
+use interoptopus::patterns::api_guard::inventory_from_ptr;
+use interoptopus_cli::{generate, load_inventory, Config, Error};
+use interoptopus_reference_project::patterns::api_guard::interoptopus_inventory;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use tempdir::TempDir;
+
+#[test]
+fn generates_all_backends_from_embedded_inventory() -> Result<(), Error> {
+    let temp = TempDir::new("interoptopus_cli").unwrap();
+    let config_file = temp.path().join("bindings.toml");
+
+    fs::write(
+        &config_file,
+        r#"
+        [c]
+        output = "c/reference_project.h"
+        ifndef = "reference_project"
+
+        [csharp]
+        output = "csharp/Interop.cs"
+        dll_name = "interoptopus_reference_project"
+        namespace_mappings = { "" = "My.Company", "_global" = "My.Company", "common" = "My.Company.Common" }
+        visibility_types = "ForceInternal"
+        overloads = ["DotNet"]
+        use_int128 = true
+
+        [cpython]
+        output = "reference_project.py"
+        "#,
+    )?;
+
+    let config = Config::from_file(&config_file)?;
+    let inventory = unsafe { inventory_from_ptr(interoptopus_inventory()) }?;
+
+    let written = generate(&config, &inventory)?;
+    let header = fs::read_to_string(temp.path().join("c/reference_project.h"))?;
+
+    assert_eq!(written.len(), 3);
+    assert!(header.contains("#ifndef reference_project"));
+    assert!(fs::read_to_string(temp.path().join("csharp/Interop.cs"))?.contains("internal static partial class Interop"));
+    assert!(generate(&config, &inventory)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn loads_inventory_from_built_library() -> Result<(), Error> {
+    // Test binaries live in `target/<profile>/deps`, next to the reference project built as a dependency.
+    let exe = std::env::current_exe()?;
+    let library = exe.with_file_name(format!("{}interoptopus_reference_project{}", DLL_PREFIX, DLL_SUFFIX));
+
+    assert!(library.exists(), "{} not found, build the reference project first", library.display());
+
+    let inventory = unsafe { load_inventory(&library) }?;
+
+    assert_eq!(inventory, interoptopus_reference_project::ffi_inventory());
+
+    Ok(())
+}
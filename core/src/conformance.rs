@@ -0,0 +1,398 @@
+//! Derives cross-language conformance tests from an [`Inventory`].
+//!
+//! Hand-written tests in each backend only cover what someone remembered to test. Instead, a
+//! [`TestSuite`] calls every function with boundary values (`0`, `MIN`, `MAX`, ...) on the Rust side
+//! and records the results. Backends then emit test files performing the same calls through their
+//! bindings and comparing against the recorded values, so any backend gets conformance coverage for
+//! free.
+//!
+//! Since Rust can't call a function from its signature alone, functions must be registered with a
+//! [`Recorder`]. Only functions whose parameters and return value are primitives are supported.
+//!
+//! # Example
+//!
+//! ```
+//! use interoptopus::conformance::{Recorder, TestSuite};
+//! use interoptopus::{ffi_function, function, Inventory, InventoryBuilder};
+//!
+//! #[ffi_function]
+//! pub fn my_add(x: u32, y: u32) -> u32 {
+//!     x.wrapping_add(y)
+//! }
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new().register(function!(my_add)).inventory()
+//! }
+//!
+//! let recorder = Recorder::new().record("my_add", |x, y| my_add(x, y));
+//! let suite = TestSuite::from_inventory(&my_inventory(), &recorder);
+//!
+//! assert_eq!(suite.vectors().len(), 5);
+//! ```
+//!
+//! Parameters are varied one at a time while all others stay `0`, which keeps functions like sums
+//! from overflowing. Recorded functions must not panic for any boundary value though, as panics
+//! can't unwind out of `extern "C"` functions and abort the process.
+use crate::lang::c::{CType, Function, PrimitiveType, PrimitiveValue};
+use crate::{non_service_functions, Inventory};
+use std::collections::HashMap;
+
+/// A Rust type usable as parameter or return value of recorded functions.
+pub trait ConformanceValue: Sized {
+    /// Converts `self` into a value, `None` for `()`.
+    fn into_value(self) -> Option<PrimitiveValue>;
+
+    /// Converts a value back, `None` if the types don't match.
+    fn from_value(value: &PrimitiveValue) -> Option<Self>;
+}
+
+impl ConformanceValue for () {
+    fn into_value(self) -> Option<PrimitiveValue> {
+        None
+    }
+
+    fn from_value(_: &PrimitiveValue) -> Option<Self> {
+        None
+    }
+}
+
+macro_rules! impl_conformance_value {
+    ($t:ty, $variant:ident) => {
+        impl ConformanceValue for $t {
+            fn into_value(self) -> Option<PrimitiveValue> {
+                Some(PrimitiveValue::$variant(self))
+            }
+
+            fn from_value(value: &PrimitiveValue) -> Option<Self> {
+                match value {
+                    PrimitiveValue::$variant(x) => Some(*x),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_conformance_value!(bool, Bool);
+impl_conformance_value!(u8, U8);
+impl_conformance_value!(u16, U16);
+impl_conformance_value!(u32, U32);
+impl_conformance_value!(u64, U64);
+impl_conformance_value!(i8, I8);
+impl_conformance_value!(i16, I16);
+impl_conformance_value!(i32, I32);
+impl_conformance_value!(i64, I64);
+impl_conformance_value!(f32, F32);
+impl_conformance_value!(f64, F64);
+
+/// Implemented for closures taking and returning [`ConformanceValue`]s, see [`Recorder::record`].
+pub trait Recordable<Args> {
+    /// Invokes the closure, `None` if the arguments don't fit its signature.
+    fn call(&self, args: &[PrimitiveValue]) -> Option<Option<PrimitiveValue>>;
+}
+
+macro_rules! impl_recordable {
+    ($($arg:ident),*) => {
+        impl<F, R, $($arg),*> Recordable<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> R,
+            R: ConformanceValue,
+            $($arg: ConformanceValue),*
+        {
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn call(&self, args: &[PrimitiveValue]) -> Option<Option<PrimitiveValue>> {
+                let mut args = args.iter();
+                $(let $arg = $arg::from_value(args.next()?)?;)*
+
+                if args.next().is_some() {
+                    return None;
+                }
+
+                Some(self($($arg),*).into_value())
+            }
+        }
+    };
+}
+
+impl_recordable!();
+impl_recordable!(A0);
+impl_recordable!(A0, A1);
+impl_recordable!(A0, A1, A2);
+impl_recordable!(A0, A1, A2, A3);
+impl_recordable!(A0, A1, A2, A3, A4);
+impl_recordable!(A0, A1, A2, A3, A4, A5);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6, A7);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6, A7, A8);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_recordable!(A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+
+type RecordedFn = Box<dyn Fn(&[PrimitiveValue]) -> Option<Option<PrimitiveValue>>>;
+
+/// Calls functions on the Rust side to obtain expected results of a [`TestSuite`].
+#[derive(Default)]
+pub struct Recorder {
+    functions: HashMap<String, RecordedFn>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a closure invoking the FFI function `name`, usually `|x| name(x)`.
+    pub fn record<Args: 'static>(mut self, name: &str, f: impl Recordable<Args> + 'static) -> Self {
+        self.functions.insert(name.to_string(), Box::new(move |args| f.call(args)));
+        self
+    }
+
+    /// Invokes the function `name`, `None` if it was not recorded or the arguments don't match.
+    pub fn call(&self, name: &str, args: &[PrimitiveValue]) -> Option<Option<PrimitiveValue>> {
+        self.functions.get(name)?(args)
+    }
+}
+
+/// A single call and its expected outcome.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestVector {
+    function: Function,
+    args: Vec<PrimitiveValue>,
+    expected: Option<PrimitiveValue>,
+}
+
+impl TestVector {
+    /// The function to call.
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+
+    /// Arguments in parameter order.
+    pub fn args(&self) -> &[PrimitiveValue] {
+        &self.args
+    }
+
+    /// The value Rust returned, `None` for functions returning `()`.
+    pub fn expected(&self) -> Option<&PrimitiveValue> {
+        self.expected.as_ref()
+    }
+}
+
+/// Test vectors derived from an inventory, emitted by backends as test files.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TestSuite {
+    vectors: Vec<TestVector>,
+}
+
+impl TestSuite {
+    /// Derives vectors for all functions of the inventory which have a primitive signature and were recorded.
+    ///
+    /// Vectors producing a non-finite float are skipped, as they can't be compared portably.
+    pub fn from_inventory(inventory: &Inventory, recorder: &Recorder) -> Self {
+        let mut vectors = Vec::new();
+
+        for function in inventory.functions() {
+            let Some(params) = primitive_params(function) else {
+                continue;
+            };
+
+            for args in argument_sets(&params) {
+                let Some(expected) = recorder.call(function.name(), &args) else {
+                    continue;
+                };
+
+                if expected.as_ref().is_some_and(|x| !is_finite(x)) {
+                    continue;
+                }
+
+                vectors.push(TestVector {
+                    function: function.clone(),
+                    args,
+                    expected,
+                });
+            }
+        }
+
+        Self { vectors }
+    }
+
+    pub fn vectors(&self) -> &[TestVector] {
+        &self.vectors
+    }
+
+    /// Returns the tested functions in inventory order, each with its vectors.
+    pub fn by_function(&self) -> Vec<(&Function, Vec<&TestVector>)> {
+        let mut rval: Vec<(&Function, Vec<&TestVector>)> = Vec::new();
+
+        for vector in &self.vectors {
+            match rval.last_mut() {
+                Some((f, vectors)) if f.name() == vector.function.name() => vectors.push(vector),
+                _ => rval.push((&vector.function, vec![vector])),
+            }
+        }
+
+        rval
+    }
+}
+
+/// Shared parts of backend generators emitting a [`TestSuite`] as test file.
+///
+/// Backends spell literals and lay out the file in their [`Interop::write_to`](crate::Interop::write_to),
+/// using the vectors and arguments provided here.
+pub trait ConformanceWriter {
+    /// The inventory the tested bindings were generated from.
+    fn inventory(&self) -> &Inventory;
+
+    /// The suite to emit.
+    fn suite(&self) -> &TestSuite;
+
+    /// Returns `value` as a literal of the target language.
+    fn literal(&self, value: &PrimitiveValue) -> String;
+
+    /// Returns the tested functions with their vectors, skipping those not exported as free functions.
+    fn tested_functions(&self) -> Vec<(&Function, Vec<&TestVector>)> {
+        let functions = non_service_functions(self.inventory());
+
+        self.suite()
+            .by_function()
+            .into_iter()
+            .filter(|(f, _)| functions.iter().any(|x| x.name() == f.name()))
+            .collect()
+    }
+
+    /// Returns the arguments of `vector` as comma separated literals.
+    fn arguments(&self, vector: &TestVector) -> String {
+        vector.args().iter().map(|x| self.literal(x)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Values worth testing for a primitive, the first one being neutral.
+pub fn boundary_values(primitive: &PrimitiveType) -> Vec<PrimitiveValue> {
+    match primitive {
+        // No recordable values exist for these, functions using them aren't tested.
+        PrimitiveType::Void | PrimitiveType::Char | PrimitiveType::USize | PrimitiveType::ISize | PrimitiveType::U128 | PrimitiveType::I128 => vec![],
+        PrimitiveType::Bool => vec![PrimitiveValue::Bool(false), PrimitiveValue::Bool(true)],
+        PrimitiveType::U8 => vec![PrimitiveValue::U8(0), PrimitiveValue::U8(1), PrimitiveValue::U8(u8::MAX)],
+        PrimitiveType::U16 => vec![PrimitiveValue::U16(0), PrimitiveValue::U16(1), PrimitiveValue::U16(u16::MAX)],
+        PrimitiveType::U32 => vec![PrimitiveValue::U32(0), PrimitiveValue::U32(1), PrimitiveValue::U32(u32::MAX)],
+        PrimitiveType::U64 => vec![PrimitiveValue::U64(0), PrimitiveValue::U64(1), PrimitiveValue::U64(u64::MAX)],
+        PrimitiveType::I8 => vec![
+            PrimitiveValue::I8(0),
+            PrimitiveValue::I8(1),
+            PrimitiveValue::I8(-1),
+            PrimitiveValue::I8(i8::MIN),
+            PrimitiveValue::I8(i8::MAX),
+        ],
+        PrimitiveType::I16 => vec![
+            PrimitiveValue::I16(0),
+            PrimitiveValue::I16(1),
+            PrimitiveValue::I16(-1),
+            PrimitiveValue::I16(i16::MIN),
+            PrimitiveValue::I16(i16::MAX),
+        ],
+        PrimitiveType::I32 => vec![
+            PrimitiveValue::I32(0),
+            PrimitiveValue::I32(1),
+            PrimitiveValue::I32(-1),
+            PrimitiveValue::I32(i32::MIN),
+            PrimitiveValue::I32(i32::MAX),
+        ],
+        PrimitiveType::I64 => vec![
+            PrimitiveValue::I64(0),
+            PrimitiveValue::I64(1),
+            PrimitiveValue::I64(-1),
+            PrimitiveValue::I64(i64::MIN),
+            PrimitiveValue::I64(i64::MAX),
+        ],
+        PrimitiveType::F32 => vec![
+            PrimitiveValue::F32(0.0),
+            PrimitiveValue::F32(1.5),
+            PrimitiveValue::F32(-1.5),
+            PrimitiveValue::F32(f32::MAX),
+        ],
+        PrimitiveType::F64 => vec![
+            PrimitiveValue::F64(0.0),
+            PrimitiveValue::F64(1.5),
+            PrimitiveValue::F64(-1.5),
+            PrimitiveValue::F64(f64::MAX),
+        ],
+    }
+}
+
+fn is_finite(value: &PrimitiveValue) -> bool {
+    match value {
+        PrimitiveValue::F32(x) => x.is_finite(),
+        PrimitiveValue::F64(x) => x.is_finite(),
+        _ => true,
+    }
+}
+
+// Parameter types if all parameters and the return value are primitives.
+fn primitive_params(function: &Function) -> Option<Vec<PrimitiveType>> {
+    if !matches!(function.signature().rval(), CType::Primitive(_)) {
+        return None;
+    }
+
+    function
+        .signature()
+        .params()
+        .iter()
+        .map(|x| match x.the_type() {
+            CType::Primitive(p) if !boundary_values(p).is_empty() => Some(*p),
+            _ => None,
+        })
+        .collect()
+}
+
+// All-neutral arguments, then each parameter stepping through its boundary values.
+fn argument_sets(params: &[PrimitiveType]) -> Vec<Vec<PrimitiveValue>> {
+    let values = params.iter().map(boundary_values).collect::<Vec<_>>();
+    let neutral = values.iter().map(|x| x[0].clone()).collect::<Vec<_>>();
+    let mut rval = vec![neutral.clone()];
+
+    for (i, values) in values.iter().enumerate() {
+        for value in values.iter().skip(1) {
+            let mut args = neutral.clone();
+            args[i] = value.clone();
+            rval.push(args);
+        }
+    }
+
+    rval
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conformance::{Recorder, TestSuite};
+    use crate::lang::c::{CType, Function, FunctionSignature, Meta, Parameter, PrimitiveType, PrimitiveValue};
+    use crate::{InventoryBuilder, Symbol};
+
+    #[test]
+    fn varies_one_parameter_at_a_time() {
+        let u8_type = CType::Primitive(PrimitiveType::U8);
+        let params = vec![Parameter::new("x".to_string(), u8_type.clone()), Parameter::new("y".to_string(), u8_type.clone())];
+        let function = Function::new("add".to_string(), FunctionSignature::new(params, u8_type), Meta::new());
+        let inventory = InventoryBuilder::new().register(Symbol::Function(function)).inventory();
+
+        let recorder = Recorder::new().record("add", |x: u8, y: u8| x.wrapping_add(y));
+        let suite = TestSuite::from_inventory(&inventory, &recorder);
+        let calls = suite.vectors().iter().map(|x| (x.args().to_vec(), x.expected().cloned())).collect::<Vec<_>>();
+
+        assert_eq!(calls.len(), 5);
+        assert_eq!(calls[0], (vec![PrimitiveValue::U8(0), PrimitiveValue::U8(0)], Some(PrimitiveValue::U8(0))));
+        assert_eq!(calls[4], (vec![PrimitiveValue::U8(0), PrimitiveValue::U8(255)], Some(PrimitiveValue::U8(255))));
+    }
+
+    #[test]
+    fn skips_unrecorded_and_mismatched() {
+        let u8_type = CType::Primitive(PrimitiveType::U8);
+        let params = vec![Parameter::new("x".to_string(), u8_type.clone())];
+        let function = |name: &str| Symbol::Function(Function::new(name.to_string(), FunctionSignature::new(params.clone(), u8_type.clone()), Meta::new()));
+        let inventory = InventoryBuilder::new().register(function("a")).register(function("b")).inventory();
+
+        let recorder = Recorder::new().record("a", |x: u16| x);
+        let suite = TestSuite::from_inventory(&inventory, &recorder);
+
+        assert!(suite.vectors().is_empty());
+    }
+}
@@ -1,12 +1,15 @@
 use crate::lang::c::{CType, Constant, Function};
 use crate::patterns::LibraryPattern;
 use crate::util::{ctypes_from_functions_types, extract_namespaces_from_types};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Tells the [`InventoryBuilder`] what to register.
 ///
 /// Most users won't need to touch this enum directly, as its variants are usually created via the [`function`](crate::function), [`constant`](crate::constant), [`extra_type`](crate::extra_type) and [`pattern`](crate::pattern) macros.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Symbol {
     Function(Function),
     Constant(Constant),
@@ -83,6 +86,9 @@ impl InventoryBuilder {
                         self.functions.push(x.destructor().clone());
                         self.functions.extend(x.constructors().iter().cloned());
                         self.functions.extend(x.methods().iter().cloned());
+                        self.functions.extend(x.static_methods().iter().cloned());
+                        self.functions.extend(x.factories().iter().cloned());
+                        self.functions.extend(x.retain().cloned());
                     }
                 }
                 self.patterns.push(x)
@@ -122,6 +128,7 @@ impl InventoryBuilder {
 
 /// Holds FFI-relevant items, produced via [`InventoryBuilder`], ingested by backends.
 #[derive(Clone, Debug, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Inventory {
     functions: Vec<Function>,
     ctypes: Vec<CType>,
@@ -147,7 +154,10 @@ impl Inventory {
     /// Produce a new inventory for the given functions, constants and patterns.
     ///
     /// Type information will be automatically derived from the used fields and parameters.
-    fn new(functions: Vec<Function>, constants: Vec<Constant>, patterns: Vec<LibraryPattern>, extra_types: Vec<CType>) -> Self {
+    fn new(functions: Vec<Function>, constants: Vec<Constant>, patterns: Vec<LibraryPattern>, mut extra_types: Vec<CType>) -> Self {
+        // Arrays and structs of constants need their types emitted, even if no function uses them.
+        extra_types.extend(constants.iter().map(|x| x.the_type()).filter(|x| matches!(x, CType::Array(_) | CType::Composite(_))));
+
         let mut ctypes = ctypes_from_functions_types(&functions, &extra_types);
         let mut namespaces = HashSet::new();
 
@@ -236,6 +246,24 @@ impl Inventory {
             namespaces,
         }
     }
+
+    /// Return a new [`Inventory`] without the functions marked `skip(backend)`, e.g., via `#[ffi_function(skip(python))]`.
+    ///
+    /// Skipped constructors and methods are also removed from their [`Service`](crate::patterns::service::Service).
+    pub fn without_skipped(&self, backend: &str) -> Inventory {
+        let mut rval = self.filter(|x| match x {
+            InventoryItem::Function(x) => !x.meta().overrides().is_skipped(backend),
+            _ => true,
+        });
+
+        for pattern in &mut rval.patterns {
+            match pattern {
+                LibraryPattern::Service(x) => *x = x.without_skipped(backend),
+            }
+        }
+
+        rval
+    }
 }
 
 /// Returns all functions not belonging to a [`service`](crate::patterns::service) pattern.
@@ -249,6 +277,9 @@ pub fn non_service_functions(inventory: &Inventory) -> Vec<&Function> {
             LibraryPattern::Service(service) => {
                 service_methods.extend_from_slice(service.methods());
                 service_methods.extend_from_slice(service.constructors());
+                service_methods.extend_from_slice(service.static_methods());
+                service_methods.extend_from_slice(service.factories());
+                service_methods.extend(service.retain().cloned());
                 service_methods.push(service.destructor().clone());
             }
         }
@@ -0,0 +1,553 @@
+//! Renders a [`DocModel`] as a static HTML site.
+//!
+//! The site consists of an `index.html` overview, a `functions.html` page, one `service.*.html`
+//! page per service and one `type.*.html` page per struct or enum. Every page has a search box
+//! backed by `search-index.js`, so the site works offline and straight from the file system,
+//! without a web server.
+//!
+//! ```
+//! use interoptopus::docs::html::HtmlConfig;
+//! use interoptopus::docs::DocModel;
+//! use interoptopus::writer::OutputSet;
+//!
+//! let mut output = OutputSet::new();
+//! DocModel::default().write_html(&mut output, &HtmlConfig::default())?;
+//!
+//! assert!(output.get("index.html").is_some());
+//! # Ok::<(), interoptopus::Error>(())
+//! ```
+use crate::docs::{DocField, DocFunction, DocModel, DocTypeRef, ItemKind, TocEntry};
+use crate::writer::OutputSet;
+use crate::Error;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const STYLE: &str = r#"body { margin: 0; font-family: sans-serif; line-height: 1.5; color: #222; }
+nav { position: fixed; top: 0; bottom: 0; width: 16rem; overflow-y: auto; padding: 1rem; background: #f4f4f4; box-sizing: border-box; }
+nav ul { padding-left: 1rem; }
+main { margin-left: 16rem; padding: 1rem 2rem; max-width: 60rem; }
+pre { background: #f4f4f4; padding: 0.5rem; overflow-x: auto; }
+code { font-family: monospace; }
+table { border-collapse: collapse; }
+td, th { border: 1px solid #ddd; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; }
+#search { width: 100%; box-sizing: border-box; }
+#search-results { padding-left: 1rem; }
+.kind { color: #888; font-size: 0.8em; }
+"#;
+
+const SEARCH: &str = r#"(function () {
+    var input = document.getElementById("search");
+    var results = document.getElementById("search-results");
+
+    input.addEventListener("input", function () {
+        var query = input.value.trim().toLowerCase();
+        results.innerHTML = "";
+
+        if (query.length === 0) {
+            return;
+        }
+
+        var matches = window.searchIndex.filter(function (x) {
+            return x.name.toLowerCase().indexOf(query) >= 0 || x.summary.toLowerCase().indexOf(query) >= 0;
+        });
+
+        matches.slice(0, 50).forEach(function (x) {
+            var item = document.createElement("li");
+            var link = document.createElement("a");
+            link.href = x.url;
+            link.textContent = x.name;
+            item.appendChild(link);
+            item.appendChild(document.createTextNode(" " + x.kind));
+            results.appendChild(item);
+        });
+    });
+})();
+"#;
+
+/// Configures HTML documentation.
+#[derive(Clone, Debug)]
+pub struct HtmlConfig {
+    /// Title shown on every page.
+    pub title: String,
+}
+
+impl Default for HtmlConfig {
+    fn default() -> Self {
+        Self {
+            title: "API Reference".to_string(),
+        }
+    }
+}
+
+impl DocModel {
+    /// Renders the documentation as HTML pages into `output`.
+    pub fn write_html(&self, output: &mut OutputSet, config: &HtmlConfig) -> Result<(), Error> {
+        let site = Site::new(self, config);
+
+        output.insert("style.css", STYLE.to_string());
+        output.insert("search.js", SEARCH.to_string());
+        output.insert("search-index.js", site.search_index());
+        output.insert("index.html", site.index()?);
+        output.insert("functions.html", site.functions()?);
+
+        for service in &self.services {
+            let mut content = String::new();
+            writeln!(content, r#"<h1>{}</h1>"#, escape(&service.name))?;
+            content.push_str(&render_docs(&service.docs));
+
+            for function in service
+                .constructors
+                .iter()
+                .chain(&service.static_methods)
+                .chain(&service.properties)
+                .chain(&service.methods)
+            {
+                content.push_str(&site.function(function)?);
+            }
+
+            output.insert(&page_for_service(&service.anchor), site.page(&service.name, &content)?);
+        }
+
+        for composite in &self.composites {
+            let mut content = String::new();
+            writeln!(content, r#"<h1>{}</h1>"#, escape(&composite.name))?;
+            content.push_str(&render_docs(&composite.docs));
+            content.push_str(&site.fields("Fields", &composite.fields)?);
+            content.push_str(&site.code(&composite.definition)?);
+
+            output.insert(&page_for_type(&composite.anchor), site.page(&composite.name, &content)?);
+        }
+
+        for the_enum in &self.enums {
+            let mut content = String::new();
+            writeln!(content, r#"<h1>{}</h1>"#, escape(&the_enum.name))?;
+            content.push_str(&render_docs(&the_enum.docs));
+
+            writeln!(content, r#"<h2>Variants</h2>"#)?;
+            writeln!(content, r#"<table>"#)?;
+            for variant in &the_enum.variants {
+                let docs = variant.docs.join(" ");
+                writeln!(
+                    content,
+                    r#"<tr><td><code>{}</code></td><td><code>{}</code></td><td>{}</td></tr>"#,
+                    escape(&variant.name),
+                    variant.value,
+                    render_inline(docs.trim())
+                )?;
+            }
+            writeln!(content, r#"</table>"#)?;
+            content.push_str(&site.code(&the_enum.definition)?);
+
+            output.insert(&page_for_type(&the_enum.anchor), site.page(&the_enum.name, &content)?);
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves anchors of the model to page URLs.
+struct Site<'a> {
+    model: &'a DocModel,
+    config: &'a HtmlConfig,
+    urls: HashMap<(ItemKind, String), String>,
+}
+
+impl<'a> Site<'a> {
+    fn new(model: &'a DocModel, config: &'a HtmlConfig) -> Self {
+        let mut urls = HashMap::new();
+
+        for function in &model.functions {
+            urls.insert((ItemKind::Function, function.anchor.clone()), format!("functions.html#{}", function.anchor));
+        }
+
+        for service in &model.services {
+            let page = page_for_service(&service.anchor);
+            for function in service
+                .constructors
+                .iter()
+                .chain(&service.static_methods)
+                .chain(&service.properties)
+                .chain(&service.methods)
+            {
+                urls.insert((ItemKind::ServiceMember, function.anchor.clone()), format!("{}#{}", page, function.anchor));
+            }
+            urls.insert((ItemKind::Service, service.anchor.clone()), page);
+        }
+
+        for anchor in model.enums.iter().map(|x| &x.anchor).chain(model.composites.iter().map(|x| &x.anchor)) {
+            urls.insert((ItemKind::Type, anchor.clone()), page_for_type(anchor));
+        }
+
+        Self { model, config, urls }
+    }
+
+    fn url(&self, kind: ItemKind, anchor: &str) -> &str {
+        self.urls.get(&(kind, anchor.to_string())).map(|x| x.as_str()).unwrap_or("index.html")
+    }
+
+    /// Types link to their struct or enum, or to the service of an opaque type.
+    fn type_url(&self, anchor: &str) -> &str {
+        match self.urls.get(&(ItemKind::Type, anchor.to_string())) {
+            Some(x) => x,
+            None => self.url(ItemKind::Service, anchor),
+        }
+    }
+
+    /// Wraps `content` into a page with navigation and search.
+    fn page(&self, title: &str, content: &str) -> Result<String, Error> {
+        let mut rval = String::new();
+
+        writeln!(rval, r#"<!DOCTYPE html>"#)?;
+        writeln!(rval, r#"<html lang="en">"#)?;
+        writeln!(rval, r#"<head>"#)?;
+        writeln!(rval, r#"<meta charset="utf-8">"#)?;
+        writeln!(rval, r#"<title>{} - {}</title>"#, escape(title), escape(&self.config.title))?;
+        writeln!(rval, r#"<link rel="stylesheet" href="style.css">"#)?;
+        writeln!(rval, r#"</head>"#)?;
+        writeln!(rval, r#"<body>"#)?;
+        writeln!(rval, r#"<nav>"#)?;
+        writeln!(rval, r#"<a href="index.html"><strong>{}</strong></a>"#, escape(&self.config.title))?;
+        writeln!(rval, r#"<input id="search" type="search" placeholder="Search..." autocomplete="off">"#)?;
+        writeln!(rval, r#"<ul id="search-results"></ul>"#)?;
+        writeln!(rval, r#"<ul>"#)?;
+        writeln!(rval, r#"<li><a href="functions.html">Functions</a></li>"#)?;
+        for service in &self.model.services {
+            writeln!(
+                rval,
+                r#"<li><a href="{}">{}</a></li>"#,
+                self.url(ItemKind::Service, &service.anchor),
+                escape(&service.name)
+            )?;
+        }
+        writeln!(rval, r#"</ul>"#)?;
+        writeln!(rval, r#"</nav>"#)?;
+        writeln!(rval, r#"<main>"#)?;
+        rval.push_str(content);
+        writeln!(rval, r#"</main>"#)?;
+        writeln!(rval, r#"<script src="search-index.js"></script>"#)?;
+        writeln!(rval, r#"<script src="search.js"></script>"#)?;
+        writeln!(rval, r#"</body>"#)?;
+        writeln!(rval, r#"</html>"#)?;
+
+        Ok(rval)
+    }
+
+    fn index(&self) -> Result<String, Error> {
+        let mut content = String::new();
+        writeln!(content, r#"<h1>{}</h1>"#, escape(&self.config.title))?;
+
+        for section in self.model.toc() {
+            if section.entries.is_empty() {
+                continue;
+            }
+
+            writeln!(content, r#"<h2>{}</h2>"#, escape(section.title))?;
+            writeln!(content, r#"<p>{}</p>"#, escape(section.description))?;
+            writeln!(content, r#"<ul>"#)?;
+            for entry in &section.entries {
+                content.push_str(&self.toc_entry(entry)?);
+            }
+            writeln!(content, r#"</ul>"#)?;
+        }
+
+        self.page("Overview", &content)
+    }
+
+    fn toc_entry(&self, entry: &TocEntry) -> Result<String, Error> {
+        let mut rval = String::new();
+        let ctor = if entry.constructor { r#" <span class="kind">ctor</span>"# } else { "" };
+        let summary = if entry.summary.is_empty() {
+            String::new()
+        } else {
+            format!(" - {}", render_inline(&entry.summary))
+        };
+
+        write!(
+            rval,
+            r#"<li><a href="{}">{}</a>{}{}"#,
+            self.url(entry.kind(), &entry.anchor),
+            escape(&entry.name),
+            ctor,
+            summary
+        )?;
+
+        if !entry.children.is_empty() {
+            writeln!(rval, r#"<ul>"#)?;
+            for child in &entry.children {
+                rval.push_str(&self.toc_entry(child)?);
+            }
+            write!(rval, r#"</ul>"#)?;
+        }
+
+        writeln!(rval, r#"</li>"#)?;
+        Ok(rval)
+    }
+
+    fn functions(&self) -> Result<String, Error> {
+        let mut content = String::new();
+        writeln!(content, r#"<h1>Functions</h1>"#)?;
+
+        for function in &self.model.functions {
+            content.push_str(&self.function(function)?);
+        }
+
+        self.page("Functions", &content)
+    }
+
+    fn function(&self, function: &DocFunction) -> Result<String, Error> {
+        let mut rval = String::new();
+
+        writeln!(rval, r#"<h2 id="{}">{}</h2>"#, escape(&function.anchor), escape(&function.name))?;
+        rval.push_str(&self.code(&function.signature)?);
+        rval.push_str(&render_docs(&function.docs));
+        rval.push_str(&self.fields("Parameters", &function.params)?);
+
+        Ok(rval)
+    }
+
+    fn fields(&self, title: &str, fields: &[DocField]) -> Result<String, Error> {
+        let mut rval = String::new();
+
+        if fields.is_empty() {
+            return Ok(rval);
+        }
+
+        writeln!(rval, r#"<h3>{}</h3>"#, escape(title))?;
+        writeln!(rval, r#"<table>"#)?;
+        for field in fields {
+            let docs = field.docs.join(" ");
+            writeln!(
+                rval,
+                r#"<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>"#,
+                escape(&field.name),
+                self.type_ref(&field.the_type),
+                render_inline(docs.trim())
+            )?;
+        }
+        writeln!(rval, r#"</table>"#)?;
+
+        Ok(rval)
+    }
+
+    fn type_ref(&self, type_ref: &DocTypeRef) -> String {
+        match &type_ref.link {
+            Some(link) => format!(r#"<a href="{}"><code>{}</code></a>"#, self.type_url(link), escape(&type_ref.name)),
+            None => format!(r#"<code>{}</code>"#, escape(&type_ref.name)),
+        }
+    }
+
+    fn code(&self, code: &str) -> Result<String, Error> {
+        let mut rval = String::new();
+
+        if !code.trim().is_empty() {
+            writeln!(
+                rval,
+                r#"<pre><code class="language-{}">{}</code></pre>"#,
+                escape(&self.model.language),
+                escape(code.trim_end())
+            )?;
+        }
+
+        Ok(rval)
+    }
+
+    /// Renders all items as a script assigning `window.searchIndex`.
+    fn search_index(&self) -> String {
+        let mut entries = Vec::new();
+        let mut push = |name: &str, kind: &str, item: ItemKind, anchor: &str, docs: &[String]| {
+            let summary = docs.first().map(|x| x.trim()).unwrap_or_default();
+            entries.push(format!(
+                r#"{{"name":{},"kind":{},"url":{},"summary":{}}}"#,
+                json_string(name),
+                json_string(kind),
+                json_string(self.url(item, anchor)),
+                json_string(summary)
+            ));
+        };
+
+        for x in &self.model.functions {
+            push(&x.name, "function", ItemKind::Function, &x.anchor, &x.docs);
+        }
+
+        for x in &self.model.services {
+            push(&x.name, "service", ItemKind::Service, &x.anchor, &x.docs);
+
+            for f in &x.constructors {
+                push(&format!("{}.{}", x.name, f.name), "constructor", ItemKind::ServiceMember, &f.anchor, &f.docs);
+            }
+
+            for f in &x.static_methods {
+                push(&format!("{}.{}", x.name, f.name), "static method", ItemKind::ServiceMember, &f.anchor, &f.docs);
+            }
+
+            for f in &x.properties {
+                push(&format!("{}.{}", x.name, f.name), "property", ItemKind::ServiceMember, &f.anchor, &f.docs);
+            }
+
+            for f in &x.methods {
+                push(&format!("{}.{}", x.name, f.name), "method", ItemKind::ServiceMember, &f.anchor, &f.docs);
+            }
+        }
+
+        for x in &self.model.enums {
+            push(&x.name, "enum", ItemKind::Type, &x.anchor, &x.docs);
+        }
+
+        for x in &self.model.composites {
+            push(&x.name, "struct", ItemKind::Type, &x.anchor, &x.docs);
+        }
+
+        format!("window.searchIndex = [\n{}\n];\n", entries.join(",\n"))
+    }
+}
+
+fn page_for_service(anchor: &str) -> String {
+    format!("service.{}.html", anchor)
+}
+
+fn page_for_type(anchor: &str) -> String {
+    format!("type.{}.html", anchor)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn json_string(text: &str) -> String {
+    let mut rval = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => rval.push_str("\\\""),
+            '\\' => rval.push_str("\\\\"),
+            '<' => rval.push_str("\\u003c"),
+            c if (c as u32) < 0x20 => rval.push_str(&format!("\\u{:04x}", c as u32)),
+            c => rval.push(c),
+        }
+    }
+
+    rval.push('"');
+    rval
+}
+
+/// Escapes `text`, rendering `` `code` `` spans.
+fn render_inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, x)| if i % 2 == 1 { format!("<code>{}</code>", escape(x)) } else { escape(x) })
+        .collect()
+}
+
+/// Renders the Markdown subset used in doc comments: paragraphs, headings, lists and code blocks.
+fn render_docs(docs: &[String]) -> String {
+    let mut rval = String::new();
+    let mut paragraph = Vec::new();
+    let mut list: Vec<String> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    let flush = |rval: &mut String, paragraph: &mut Vec<&str>, list: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            rval.push_str(&format!("<p>{}</p>\n", render_inline(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+
+        if !list.is_empty() {
+            let items = list.iter().map(|x| format!("<li>{}</li>", render_inline(x))).collect::<String>();
+            rval.push_str(&format!("<ul>{}</ul>\n", items));
+            list.clear();
+        }
+    };
+
+    for line in docs {
+        let trimmed = line.trim();
+
+        if let Some(lines) = &mut code {
+            if trimmed.starts_with("```") {
+                rval.push_str(&format!("<pre><code>{}</code></pre>\n", escape(&lines.join("\n"))));
+                code = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") {
+            flush(&mut rval, &mut paragraph, &mut list);
+            code = Some(Vec::new());
+        } else if let Some(heading) = trimmed.strip_prefix('#') {
+            flush(&mut rval, &mut paragraph, &mut list);
+            rval.push_str(&format!("<h4>{}</h4>\n", render_inline(heading.trim_start_matches('#').trim())));
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            if !paragraph.is_empty() {
+                flush(&mut rval, &mut paragraph, &mut list);
+            }
+            list.push(item.to_string());
+        } else if trimmed.is_empty() {
+            flush(&mut rval, &mut paragraph, &mut list);
+        } else if let Some(last) = list.last_mut() {
+            // Continuation of the previous list item.
+            last.push(' ');
+            last.push_str(trimmed);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    if let Some(lines) = code {
+        rval.push_str(&format!("<pre><code>{}</code></pre>\n", escape(&lines.join("\n"))));
+    }
+
+    flush(&mut rval, &mut paragraph, &mut list);
+    rval
+}
+
+#[cfg(test)]
+mod test {
+    use super::{render_docs, HtmlConfig};
+    use crate::docs::{DocFormatter, DocModel};
+    use crate::lang::c::{CType, CompositeType, Field, Function, FunctionSignature, Meta, Parameter, PrimitiveType};
+    use crate::writer::OutputSet;
+    use crate::{InventoryBuilder, Symbol};
+
+    struct Names;
+
+    impl DocFormatter for Names {
+        fn language(&self) -> &str {
+            "c"
+        }
+
+        fn type_name(&self, the_type: &CType) -> String {
+            the_type.name_within_lib()
+        }
+    }
+
+    #[test]
+    fn same_named_items_link_to_their_own_page() {
+        let vec = CompositeType::new("Vec".to_string(), vec![Field::new("x".to_string(), CType::Primitive(PrimitiveType::F32))]);
+        let params = vec![Parameter::new("v".to_string(), CType::Composite(vec))];
+        let function = Function::new("Vec".to_string(), FunctionSignature::new(params, CType::Primitive(PrimitiveType::Void)), Meta::new());
+        let inventory = InventoryBuilder::new().register(Symbol::Function(function)).inventory();
+
+        let mut output = OutputSet::new();
+        DocModel::from_inventory(&inventory, &Names)
+            .unwrap()
+            .write_html(&mut output, &HtmlConfig::default())
+            .unwrap();
+        let index = output.get("index.html").unwrap();
+
+        assert!(index.contains(r#"<a href="functions.html#Vec">Vec</a>"#));
+        assert!(index.contains(r#"<a href="type.Vec.html">Vec</a>"#));
+    }
+
+    #[test]
+    fn docs_render_paragraphs_lists_and_code() {
+        let docs = ["Some `code` & text", "", "# Safety", "- one", "- two", "```", "let x = 1 < 2;", "```"].map(String::from);
+
+        let html = render_docs(&docs);
+
+        assert_eq!(
+            html,
+            "<p>Some <code>code</code> &amp; text</p>\n<h4>Safety</h4>\n<ul><li>one</li><li>two</li></ul>\n<pre><code>let x = 1 &lt; 2;</code></pre>\n"
+        );
+    }
+}
@@ -0,0 +1,1020 @@
+//! Language-neutral documentation shared by all backends.
+//!
+//! A [`DocModel`] collects the functions, services, enums and data structs of an [`Inventory`]
+//! together with their documentation, and resolves cross-links between them. Backends only
+//! implement [`DocFormatter`] to render names and signatures in their language; the model can
+//! then be written as Markdown via [`DocModel::write_markdown`], or as a static HTML site via
+//! [`DocModel::write_html`], so each language gets a reference with the same structure.
+//!
+//! # Example
+//!
+//! ```
+//! use interoptopus::docs::{DocFormatter, DocModel};
+//! use interoptopus::lang::c::CType;
+//! use interoptopus::writer::IndentWriter;
+//! use interoptopus::{ffi_function, function, InventoryBuilder};
+//!
+//! /// Adds two numbers.
+//! #[ffi_function]
+//! pub fn my_add(x: u32, y: u32) -> u32 {
+//!     x.wrapping_add(y)
+//! }
+//!
+//! struct RustNames;
+//!
+//! impl DocFormatter for RustNames {
+//!     fn language(&self) -> &str {
+//!         "rust"
+//!     }
+//!
+//!     fn type_name(&self, the_type: &CType) -> String {
+//!         the_type.name_within_lib()
+//!     }
+//! }
+//!
+//! let inventory = InventoryBuilder::new().register(function!(my_add)).inventory();
+//! let model = DocModel::from_inventory(&inventory, &RustNames)?;
+//!
+//! let mut markdown = Vec::new();
+//! model.write_markdown(&mut IndentWriter::new(&mut markdown))?;
+//!
+//! assert!(String::from_utf8(markdown).unwrap().contains("my_add(x: u32, y: u32) -> u32"));
+//! # Ok::<(), interoptopus::Error>(())
+//! ```
+use crate::lang::c::{CType, CompositeType, Deprecation, Documentation, EnumType, Field, Function, Meta};
+use crate::patterns::service::{Service, ServiceProperty};
+use crate::patterns::{LibraryPattern, TypePattern};
+use crate::writer::IndentWriter;
+use crate::{indented, non_service_functions, Error, Inventory};
+use std::collections::HashSet;
+
+pub mod html;
+
+/// Renders names and signatures of a target language, implemented by backends.
+pub trait DocFormatter {
+    /// Language of code blocks, e.g., `csharp`.
+    fn language(&self) -> &str;
+
+    /// Name of a type as seen by users of the bindings.
+    fn type_name(&self, the_type: &CType) -> String;
+
+    /// Name of a function, or of a method if `service` is set.
+    fn function_name(&self, function: &Function, service: Option<&Service>) -> String {
+        match service {
+            Some(service) => function.name().replace(&service.common_prefix(), ""),
+            None => function.name().to_string(),
+        }
+    }
+
+    /// Name of a field as seen by users of the bindings.
+    fn field_name(&self, _composite: &CompositeType, field: &Field) -> String {
+        field.name().to_string()
+    }
+
+    /// Writes the declaration of a function, or of a method if `service` is set.
+    fn write_signature(&self, w: &mut IndentWriter, function: &Function, service: Option<&Service>) -> Result<(), Error> {
+        let skip = usize::from(service.is_some());
+        let params = function
+            .signature()
+            .params()
+            .iter()
+            .skip(skip)
+            .map(|x| format!("{}: {}", x.name(), self.type_name(x.the_type())))
+            .collect::<Vec<_>>();
+
+        let rval = match function.signature().rval() {
+            x if x.is_void() => String::new(),
+            x => format!(" -> {}", self.type_name(x)),
+        };
+
+        indented!(w, r#"{}({}){}"#, self.function_name(function, service), params.join(", "), rval)
+    }
+
+    /// Name of a service property, or `None` if getters and setters are documented as methods.
+    fn property_name(&self, _property: &ServiceProperty) -> Option<String> {
+        None
+    }
+
+    /// Writes the declaration of a property, called if [`property_name`](Self::property_name) returns a name.
+    fn write_property_signature(&self, _w: &mut IndentWriter, _property: &ServiceProperty, _service: &Service) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Writes the definition of a struct or enum, nothing by default.
+    fn write_type_definition(&self, _w: &mut IndentWriter, _the_type: &CType) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A type referenced from a signature or field, linked if it is documented.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocTypeRef {
+    name: String,
+    link: Option<String>,
+}
+
+impl DocTypeRef {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Anchor of the documented type, if any.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+}
+
+/// A function parameter or struct field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocField {
+    name: String,
+    the_type: DocTypeRef,
+    docs: Vec<String>,
+}
+
+impl DocField {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn the_type(&self) -> &DocTypeRef {
+        &self.the_type
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// A variant of a [`DocEnum`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocVariant {
+    name: String,
+    value: usize,
+    docs: Vec<String>,
+}
+
+impl DocVariant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// A freestanding function, service constructor or method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocFunction {
+    name: String,
+    anchor: String,
+    docs: Vec<String>,
+    signature: String,
+    params: Vec<DocField>,
+    rval: Option<DocTypeRef>,
+}
+
+impl DocFunction {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    /// Declaration rendered by [`DocFormatter::write_signature`].
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Parameters as seen by users, i.e., without the service context of methods.
+    pub fn params(&self) -> &[DocField] {
+        &self.params
+    }
+
+    /// Return type, `None` for `void`.
+    pub fn rval(&self) -> Option<&DocTypeRef> {
+        self.rval.as_ref()
+    }
+}
+
+/// A service and its constructors and methods.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocService {
+    name: String,
+    anchor: String,
+    docs: Vec<String>,
+    constructors: Vec<DocFunction>,
+    static_methods: Vec<DocFunction>,
+    properties: Vec<DocFunction>,
+    methods: Vec<DocFunction>,
+}
+
+impl DocService {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    pub fn constructors(&self) -> &[DocFunction] {
+        &self.constructors
+    }
+
+    pub fn static_methods(&self) -> &[DocFunction] {
+        &self.static_methods
+    }
+
+    /// Getter and setter pairs, if the language has properties.
+    pub fn properties(&self) -> &[DocFunction] {
+        &self.properties
+    }
+
+    pub fn methods(&self) -> &[DocFunction] {
+        &self.methods
+    }
+}
+
+/// An enum and its variants.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocEnum {
+    name: String,
+    anchor: String,
+    docs: Vec<String>,
+    variants: Vec<DocVariant>,
+    definition: String,
+}
+
+impl DocEnum {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    pub fn variants(&self) -> &[DocVariant] {
+        &self.variants
+    }
+
+    /// Definition rendered by [`DocFormatter::write_type_definition`], possibly empty.
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+}
+
+/// A struct, including the structs backing options and slices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocComposite {
+    name: String,
+    anchor: String,
+    docs: Vec<String>,
+    fields: Vec<DocField>,
+    definition: String,
+}
+
+impl DocComposite {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    pub fn fields(&self) -> &[DocField] {
+        &self.fields
+    }
+
+    /// Definition rendered by [`DocFormatter::write_type_definition`], possibly empty.
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+}
+
+/// Kind of a documented item, items of different kinds may share a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    Function,
+    Service,
+    /// Constructor, method or property of a service.
+    ServiceMember,
+    /// Enum or data struct.
+    Type,
+}
+
+/// An entry of the table of contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TocEntry {
+    name: String,
+    anchor: String,
+    kind: ItemKind,
+    summary: String,
+    constructor: bool,
+    children: Vec<TocEntry>,
+}
+
+impl TocEntry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn anchor(&self) -> &str {
+        &self.anchor
+    }
+
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// First line of the documentation.
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub fn is_constructor(&self) -> bool {
+        self.constructor
+    }
+
+    /// Constructors and methods of services.
+    pub fn children(&self) -> &[TocEntry] {
+        &self.children
+    }
+}
+
+/// A section of the table of contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TocSection {
+    title: &'static str,
+    description: &'static str,
+    entries: Vec<TocEntry>,
+}
+
+impl TocSection {
+    pub fn title(&self) -> &str {
+        self.title
+    }
+
+    pub fn description(&self) -> &str {
+        self.description
+    }
+
+    pub fn entries(&self) -> &[TocEntry] {
+        &self.entries
+    }
+}
+
+/// Documentation of an inventory, independent of any output format.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DocModel {
+    language: String,
+    functions: Vec<DocFunction>,
+    services: Vec<DocService>,
+    enums: Vec<DocEnum>,
+    composites: Vec<DocComposite>,
+}
+
+impl DocModel {
+    /// Extracts the documentation of all items in the inventory, using `formatter` for names and signatures.
+    pub fn from_inventory(inventory: &Inventory, formatter: &impl DocFormatter) -> Result<Self, Error> {
+        let services = inventory
+            .patterns()
+            .iter()
+            .map(|x| match x {
+                LibraryPattern::Service(s) => s,
+            })
+            .collect::<Vec<_>>();
+
+        let enums = inventory
+            .ctypes()
+            .iter()
+            .filter_map(|x| match x {
+                CType::Enum(e) => Some(e),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let composites = inventory.ctypes().iter().filter_map(documented_composite).collect::<Vec<_>>();
+
+        let mut anchors = HashSet::new();
+        anchors.extend(services.iter().map(|x| type_anchor(x.the_type().rust_name(), x.the_type().meta())));
+        anchors.extend(enums.iter().map(|x| type_anchor(x.rust_name(), x.meta())));
+        anchors.extend(composites.iter().map(|x| type_anchor(x.rust_name(), x.meta())));
+
+        let linker = Linker { formatter, anchors };
+
+        let mut rval = Self {
+            language: formatter.language().to_string(),
+            ..Self::default()
+        };
+
+        for function in non_service_functions(inventory) {
+            rval.functions.push(linker.function(function, None, function.name())?);
+        }
+
+        for service in services {
+            let name = service.the_type().rust_name();
+            let anchor = type_anchor(name, service.the_type().meta());
+            let mut doc_service = DocService {
+                name: name.to_string(),
+                anchor: anchor.clone(),
+                docs: service_doc_lines(service),
+                constructors: Vec::new(),
+                static_methods: Vec::new(),
+                properties: Vec::new(),
+                methods: Vec::new(),
+            };
+
+            for x in service.constructors() {
+                let method_name = formatter.function_name(x, Some(service));
+                doc_service
+                    .constructors
+                    .push(linker.function(x, Some(service), &format!("{}.{}", anchor, method_name))?);
+            }
+
+            for x in service.static_methods() {
+                let method_name = formatter.function_name(x, Some(service));
+                doc_service
+                    .static_methods
+                    .push(linker.function(x, Some(service), &format!("{}.{}", anchor, method_name))?);
+            }
+
+            for x in service.properties() {
+                if let Some(property_name) = formatter.property_name(x) {
+                    doc_service
+                        .properties
+                        .push(linker.property(x, service, &property_name, &format!("{}.{}", anchor, property_name))?);
+                }
+            }
+
+            for x in service.methods() {
+                if service.property_of(x).is_some_and(|x| formatter.property_name(x).is_some()) {
+                    continue;
+                }
+
+                let method_name = formatter.function_name(x, Some(service));
+                doc_service.methods.push(linker.function(x, Some(service), &format!("{}.{}", anchor, method_name))?);
+            }
+
+            for x in service.factories() {
+                let method_name = formatter.function_name(x, Some(service));
+                doc_service.methods.push(linker.function(x, Some(service), &format!("{}.{}", anchor, method_name))?);
+            }
+
+            rval.services.push(doc_service);
+        }
+
+        for the_enum in enums {
+            rval.enums.push(linker.enumeration(the_enum)?);
+        }
+
+        for composite in composites {
+            rval.composites.push(linker.composite(composite)?);
+        }
+
+        Ok(rval)
+    }
+
+    /// Language of code blocks.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn functions(&self) -> &[DocFunction] {
+        &self.functions
+    }
+
+    pub fn services(&self) -> &[DocService] {
+        &self.services
+    }
+
+    pub fn enums(&self) -> &[DocEnum] {
+        &self.enums
+    }
+
+    pub fn composites(&self) -> &[DocComposite] {
+        &self.composites
+    }
+
+    /// Returns the documented enum for `the_enum`, if any.
+    pub fn enumeration(&self, the_enum: &EnumType) -> Option<&DocEnum> {
+        let anchor = type_anchor(the_enum.rust_name(), the_enum.meta());
+        self.enums.iter().find(|x| x.anchor == anchor)
+    }
+
+    /// Returns the documented data struct for `composite`, if any.
+    pub fn composite(&self, composite: &CompositeType) -> Option<&DocComposite> {
+        let anchor = type_anchor(composite.rust_name(), composite.meta());
+        self.composites.iter().find(|x| x.anchor == anchor)
+    }
+
+    /// Returns the table of contents, one section per kind of item.
+    pub fn toc(&self) -> Vec<TocSection> {
+        let entry = |name: &str, anchor: &str, kind: ItemKind, docs: &[String], constructor: bool| TocEntry {
+            name: name.to_string(),
+            anchor: anchor.to_string(),
+            kind,
+            summary: docs.first().map(|x| x.trim().to_string()).unwrap_or_default(),
+            constructor,
+            children: Vec::new(),
+        };
+
+        let services = self.services.iter().map(|x| {
+            let member = |f: &DocFunction, constructor: bool| entry(&f.name, &f.anchor, ItemKind::ServiceMember, &f.docs, constructor);
+            let mut rval = entry(&x.name, &x.anchor, ItemKind::Service, &x.docs, false);
+            rval.children.extend(x.constructors.iter().map(|f| member(f, true)));
+            rval.children.extend(x.static_methods.iter().map(|f| member(f, false)));
+            rval.children.extend(x.properties.iter().map(|f| member(f, false)));
+            rval.children.extend(x.methods.iter().map(|f| member(f, false)));
+            rval
+        });
+
+        vec![
+            TocSection {
+                title: "Functions",
+                description: "Freestanding callables inside the module.",
+                entries: self.functions.iter().map(|x| entry(&x.name, &x.anchor, ItemKind::Function, &x.docs, false)).collect(),
+            },
+            TocSection {
+                title: "Services",
+                description: "Methods operating on common state.",
+                entries: services.collect(),
+            },
+            TocSection {
+                title: "Enums",
+                description: "Groups of related constants.",
+                entries: self.enums.iter().map(|x| entry(&x.name, &x.anchor, ItemKind::Type, &x.docs, false)).collect(),
+            },
+            TocSection {
+                title: "Data Structs",
+                description: "Composite data used by functions and methods.",
+                entries: self.composites.iter().map(|x| entry(&x.name, &x.anchor, ItemKind::Type, &x.docs, false)).collect(),
+            },
+        ]
+    }
+
+    /// Writes the documentation as a single Markdown file.
+    pub fn write_markdown(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        self.write_markdown_toc(w)?;
+        self.write_markdown_composites(w)?;
+        self.write_markdown_enums(w)?;
+        self.write_markdown_functions(w)?;
+        self.write_markdown_services(w)
+    }
+
+    /// Writes the table of contents of [`write_markdown`](Self::write_markdown).
+    pub fn write_markdown_toc(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        indented!(w, r#"## API Overview"#)?;
+
+        for section in self.toc() {
+            w.newline()?;
+            indented!(w, r#"### {}"#, section.title)?;
+            indented!(w, r#"{}"#, section.description)?;
+
+            for entry in &section.entries {
+                indented!(w, r#" - **[{}](#{})**{}"#, entry.name, entry.anchor, markdown_summary(&entry.summary))?;
+
+                for child in &entry.children {
+                    let ctor = if child.constructor { " <sup>**ctor**</sup>" } else { "" };
+                    indented!(w, r#"     - **[{}](#{})**{}{}"#, child.name, child.anchor, ctor, markdown_summary(&child.summary))?;
+                }
+            }
+        }
+
+        w.newline()?;
+        indented!(w, r#"---"#)
+    }
+
+    /// Writes the `# Types` section of [`write_markdown`](Self::write_markdown).
+    pub fn write_markdown_composites(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        w.newline()?;
+        indented!(w, r#"# Types"#)?;
+
+        for composite in &self.composites {
+            w.newline()?;
+            self.write_markdown_composite(w, composite)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single data struct.
+    pub fn write_markdown_composite(&self, w: &mut IndentWriter, composite: &DocComposite) -> Result<(), Error> {
+        write_markdown_heading(w, &composite.anchor, &composite.name, "")?;
+        write_markdown_docs(w, &composite.docs)?;
+        write_markdown_fields(w, "Fields", &composite.fields)?;
+        self.write_markdown_code(w, &composite.definition)?;
+        write_markdown_rule(w)
+    }
+
+    /// Writes the `# Enums` section of [`write_markdown`](Self::write_markdown).
+    pub fn write_markdown_enums(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        w.newline()?;
+        indented!(w, r#"# Enums"#)?;
+
+        for the_enum in &self.enums {
+            w.newline()?;
+            self.write_markdown_enum(w, the_enum)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single enum.
+    pub fn write_markdown_enum(&self, w: &mut IndentWriter, the_enum: &DocEnum) -> Result<(), Error> {
+        write_markdown_heading(w, &the_enum.anchor, &the_enum.name, "")?;
+        write_markdown_docs(w, &the_enum.docs)?;
+
+        w.newline()?;
+        indented!(w, r#"#### Variants"#)?;
+        for variant in &the_enum.variants {
+            indented!(
+                w,
+                r#"- **{}** = `{}`{}"#,
+                variant.name,
+                variant.value,
+                markdown_summary(variant.docs.join(" ").trim())
+            )?;
+        }
+
+        self.write_markdown_code(w, &the_enum.definition)?;
+        write_markdown_rule(w)
+    }
+
+    /// Writes the `# Functions` section of [`write_markdown`](Self::write_markdown).
+    pub fn write_markdown_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        w.newline()?;
+        indented!(w, r#"# Functions"#)?;
+
+        for function in &self.functions {
+            w.newline()?;
+            self.write_markdown_function(w, function, "")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the `# Services` section of [`write_markdown`](Self::write_markdown).
+    pub fn write_markdown_services(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        w.newline()?;
+        indented!(w, r#"# Services"#)?;
+
+        for service in &self.services {
+            w.newline()?;
+            indented!(w, r#"## <a name="{}">**{}**</a>"#, service.anchor, service.name)?;
+            write_markdown_docs(w, &service.docs)?;
+
+            for function in &service.constructors {
+                w.newline()?;
+                self.write_markdown_function(w, function, " <sup>ctor</sup>")?;
+            }
+
+            for function in &service.static_methods {
+                w.newline()?;
+                self.write_markdown_function(w, function, " <sup>static</sup>")?;
+            }
+
+            for function in &service.properties {
+                w.newline()?;
+                self.write_markdown_function(w, function, " <sup>property</sup>")?;
+            }
+
+            for function in &service.methods {
+                w.newline()?;
+                self.write_markdown_function(w, function, "")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_markdown_function(&self, w: &mut IndentWriter, function: &DocFunction, suffix: &str) -> Result<(), Error> {
+        write_markdown_heading(w, &function.anchor, &function.name, suffix)?;
+        write_markdown_docs(w, &function.docs)?;
+        write_markdown_fields(w, "Parameters", &function.params)?;
+        self.write_markdown_code(w, &function.signature)?;
+        write_markdown_rule(w)
+    }
+
+    fn write_markdown_code(&self, w: &mut IndentWriter, code: &str) -> Result<(), Error> {
+        if code.trim().is_empty() {
+            return Ok(());
+        }
+
+        w.newline()?;
+        indented!(w, r#"#### Definition"#)?;
+        indented!(w, r#"```{}"#, self.language)?;
+        writeln!(w.writer(), "{}", code.trim_end())?;
+        indented!(w, r#"```"#)
+    }
+}
+
+/// Resolves names and links while building a [`DocModel`].
+struct Linker<'a, F> {
+    formatter: &'a F,
+    anchors: HashSet<String>,
+}
+
+impl<F: DocFormatter> Linker<'_, F> {
+    fn type_ref(&self, the_type: &CType) -> DocTypeRef {
+        DocTypeRef {
+            name: self.formatter.type_name(the_type),
+            link: documented_anchor(the_type).filter(|x| self.anchors.contains(x)),
+        }
+    }
+
+    fn function(&self, function: &Function, service: Option<&Service>, anchor: &str) -> Result<DocFunction, Error> {
+        // The first parameter of service constructors and methods is the context, static methods have none.
+        // Methods returning a service are followed by its out-parameter, which becomes the return value.
+        let created_service = service.and_then(|x| x.factory_type(function));
+        let skip = usize::from(service.is_some_and(|x| !x.static_methods().contains(function))) + usize::from(created_service.is_some());
+        let params = function
+            .signature()
+            .params()
+            .iter()
+            .skip(skip)
+            .map(|x| DocField {
+                name: x.name().to_string(),
+                the_type: self.type_ref(x.the_type()),
+                docs: doc_lines(x.documentation()),
+            })
+            .collect();
+
+        let rval = match (created_service, function.signature().rval()) {
+            (Some(x), _) => Some(self.type_ref(&CType::Opaque(x))),
+            (None, x) if x.is_void() => None,
+            (None, x) => Some(self.type_ref(x)),
+        };
+
+        Ok(DocFunction {
+            name: self.formatter.function_name(function, service),
+            anchor: anchor.to_string(),
+            docs: item_doc_lines(function.meta().documentation(), function.meta().deprecation()),
+            signature: capture(|w| self.formatter.write_signature(w, function, service))?,
+            params,
+            rval,
+        })
+    }
+
+    fn property(&self, property: &ServiceProperty, service: &Service, name: &str, anchor: &str) -> Result<DocFunction, Error> {
+        let accessor = property.getter().or(property.setter()).expect("Property must have a getter or setter.");
+
+        Ok(DocFunction {
+            name: name.to_string(),
+            anchor: anchor.to_string(),
+            docs: item_doc_lines(accessor.meta().documentation(), accessor.meta().deprecation()),
+            signature: capture(|w| self.formatter.write_property_signature(w, property, service))?,
+            params: Vec::new(),
+            rval: Some(self.type_ref(property.the_type())),
+        })
+    }
+
+    fn enumeration(&self, the_enum: &EnumType) -> Result<DocEnum, Error> {
+        let the_type = CType::Enum(the_enum.clone());
+
+        Ok(DocEnum {
+            name: the_enum.rust_name().to_string(),
+            anchor: type_anchor(the_enum.rust_name(), the_enum.meta()),
+            docs: item_doc_lines(the_enum.meta().documentation(), the_enum.meta().deprecation()),
+            variants: the_enum
+                .variants()
+                .iter()
+                .map(|x| DocVariant {
+                    name: x.name().to_string(),
+                    value: x.value(),
+                    docs: item_doc_lines(x.documentation(), x.deprecation()),
+                })
+                .collect(),
+            definition: capture(|w| self.formatter.write_type_definition(w, &the_type))?,
+        })
+    }
+
+    fn composite(&self, composite: &CompositeType) -> Result<DocComposite, Error> {
+        let the_type = CType::Composite(composite.clone());
+
+        Ok(DocComposite {
+            name: composite.rust_name().to_string(),
+            anchor: type_anchor(composite.rust_name(), composite.meta()),
+            docs: item_doc_lines(composite.meta().documentation(), composite.meta().deprecation()),
+            fields: composite
+                .fields()
+                .iter()
+                .map(|x| DocField {
+                    name: self.formatter.field_name(composite, x),
+                    the_type: self.type_ref(x.the_type()),
+                    docs: item_doc_lines(x.documentation(), x.deprecation()),
+                })
+                .collect(),
+            definition: capture(|w| self.formatter.write_type_definition(w, &the_type))?,
+        })
+    }
+}
+
+/// Returns the struct documented for a type, if it is documented as one.
+fn documented_composite(the_type: &CType) -> Option<&CompositeType> {
+    match the_type {
+        CType::Composite(x) => Some(x),
+        CType::Pattern(TypePattern::Option(x) | TypePattern::Slice(x) | TypePattern::SliceMut(x)) => Some(x),
+        _ => None,
+    }
+}
+
+/// Returns the anchor of the item documenting a type, looking through pointers and arrays.
+fn documented_anchor(the_type: &CType) -> Option<String> {
+    match the_type {
+        CType::Enum(x) => Some(type_anchor(x.rust_name(), x.meta())),
+        CType::Opaque(x) => Some(type_anchor(x.rust_name(), x.meta())),
+        CType::Array(x) => documented_anchor(x.array_type()),
+        CType::ReadPointer(x) | CType::ReadWritePointer(x) => documented_anchor(x),
+        CType::Pattern(TypePattern::FFIErrorEnum(x)) => Some(type_anchor(x.the_enum().rust_name(), x.the_enum().meta())),
+        x => documented_composite(x).map(|x| type_anchor(x.rust_name(), x.meta())),
+    }
+}
+
+/// Anchor of a type or service, qualified by its namespace so same-named items don't collide.
+fn type_anchor(name: &str, meta: &Meta) -> String {
+    match meta.namespace() {
+        "" => name.to_string(),
+        namespace => format!("{}.{}", namespace, name),
+    }
+}
+
+/// Documentation lines without the space following `///`.
+fn doc_lines(documentation: &Documentation) -> Vec<String> {
+    documentation.lines().iter().map(|x| x.strip_prefix(' ').unwrap_or(x).to_string()).collect()
+}
+
+/// Documentation lines of a service, followed by notes on deprecation and thread safety.
+fn service_doc_lines(service: &Service) -> Vec<String> {
+    let mut rval = item_doc_lines(service.the_type().meta().documentation(), service.the_type().meta().deprecation());
+
+    if !rval.is_empty() {
+        rval.push(String::new());
+    }
+
+    rval.push(service.thread_safety().description().to_string());
+    rval
+}
+
+/// Documentation lines of an item, followed by a note if the item is deprecated.
+fn item_doc_lines(documentation: &Documentation, deprecation: &Deprecation) -> Vec<String> {
+    let mut rval = doc_lines(documentation);
+
+    if deprecation.is_deprecated() {
+        if !rval.is_empty() {
+            rval.push(String::new());
+        }
+
+        match deprecation.note() {
+            Some(note) => rval.push(format!("Deprecated: {}", note)),
+            None => rval.push("Deprecated.".to_string()),
+        }
+    }
+
+    rval
+}
+
+fn capture(f: impl FnOnce(&mut IndentWriter) -> Result<(), Error>) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    f(&mut IndentWriter::new(&mut buffer))?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+fn markdown_type_ref(type_ref: &DocTypeRef) -> String {
+    match &type_ref.link {
+        Some(link) => format!("[`{}`](#{})", type_ref.name, link),
+        None => format!("`{}`", type_ref.name),
+    }
+}
+
+/// Separates a non-empty summary from the item it describes.
+fn markdown_summary(summary: &str) -> String {
+    if summary.is_empty() {
+        String::new()
+    } else {
+        format!(" - {}", summary)
+    }
+}
+
+fn write_markdown_heading(w: &mut IndentWriter, anchor: &str, name: &str, suffix: &str) -> Result<(), Error> {
+    indented!(w, r#"### <a name="{}">**{}**</a>{}"#, anchor, name, suffix)
+}
+
+/// Writes documentation, demoting its headings below the item heading.
+fn write_markdown_docs(w: &mut IndentWriter, docs: &[String]) -> Result<(), Error> {
+    let mut in_code = false;
+
+    for line in docs {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+
+        if !in_code && line.starts_with('#') {
+            indented!(w, r#"###{}"#, line)?;
+        } else {
+            indented!(w, r#"{}"#, line)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_markdown_fields(w: &mut IndentWriter, title: &str, fields: &[DocField]) -> Result<(), Error> {
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    w.newline()?;
+    indented!(w, r#"#### {}"#, title)?;
+
+    for field in fields {
+        let doc = field.docs.join(" ");
+        indented!(w, r#"- **{}** {}{}"#, field.name, markdown_type_ref(&field.the_type), markdown_summary(doc.trim()))?;
+    }
+
+    Ok(())
+}
+
+fn write_markdown_rule(w: &mut IndentWriter) -> Result<(), Error> {
+    w.newline()?;
+    indented!(w, r#"---"#)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DocFormatter, DocModel};
+    use crate::lang::c::{CType, CompositeType, Documentation, Field, Function, FunctionSignature, Meta, Parameter, PrimitiveType};
+    use crate::{InventoryBuilder, Symbol};
+
+    struct Names;
+
+    impl DocFormatter for Names {
+        fn language(&self) -> &str {
+            "c"
+        }
+
+        fn type_name(&self, the_type: &CType) -> String {
+            the_type.name_within_lib()
+        }
+    }
+
+    #[test]
+    fn fields_and_params_link_documented_types() {
+        let vec3 = CompositeType::new("Vec".to_string(), vec![Field::new("x".to_string(), CType::Primitive(PrimitiveType::F32))]);
+        let param = Parameter::new("v".to_string(), CType::ReadPointer(Box::new(CType::Composite(vec3))));
+        let signature = FunctionSignature::new(vec![param], CType::Primitive(PrimitiveType::F32));
+        let function = Function::new("length".to_string(), signature, Meta::new());
+        let inventory = InventoryBuilder::new().register(Symbol::Function(function)).inventory();
+
+        let model = DocModel::from_inventory(&inventory, &Names).unwrap();
+
+        assert_eq!(model.composites()[0].fields()[0].the_type().link(), None);
+        assert_eq!(model.functions()[0].params()[0].the_type().link(), Some("Vec"));
+        assert_eq!(model.functions()[0].signature(), "length(v: ConstPtrVec) -> f32\n");
+    }
+
+    #[test]
+    fn anchors_include_namespace() {
+        let vec = |namespace: &str| {
+            let meta = Meta::with_namespace_documentation(namespace.to_string(), Documentation::new());
+            let fields = vec![Field::new("x".to_string(), CType::Primitive(PrimitiveType::F32))];
+            CType::Composite(CompositeType::with_meta("Vec".to_string(), fields, meta))
+        };
+        let params = vec![Parameter::new("a".to_string(), vec("")), Parameter::new("b".to_string(), vec("math"))];
+        let signature = FunctionSignature::new(params, CType::Primitive(PrimitiveType::Void));
+        let function = Function::new("f".to_string(), signature, Meta::new());
+        let inventory = InventoryBuilder::new().register(Symbol::Function(function)).inventory();
+
+        let model = DocModel::from_inventory(&inventory, &Names).unwrap();
+        let anchors = model.composites().iter().map(|x| x.anchor()).collect::<Vec<_>>();
+
+        assert_eq!(anchors.len(), 2);
+        assert!(anchors.contains(&"Vec") && anchors.contains(&"math.Vec"));
+        assert_eq!(model.functions()[0].params()[1].the_type().link(), Some("math.Vec"));
+    }
+}
@@ -32,6 +32,13 @@ pub enum Error {
 
     /// A specified file was not found.
     FileNotFound,
+
+    /// A binary, e.g., a shared library, could not be parsed.
+    MalformedBinary,
+
+    /// An inventory could not be (de)serialized.
+    #[cfg(feature = "inventory_export")]
+    Json(serde_json::Error),
 }
 
 impl From<std::fmt::Error> for Error {
@@ -58,6 +65,13 @@ impl From<std::string::FromUtf8Error> for Error {
     }
 }
 
+#[cfg(feature = "inventory_export")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
 impl Display for Error {
     // TODO: This should be nicer.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,301 @@
+//! Verifies a built library actually exports the functions of an [`Inventory`].
+//!
+//! Bindings are generated from the inventory, not from the library itself. If both disagree, e.g.,
+//! because a function lost its `#[no_mangle]` or was never registered, the mismatch usually only shows
+//! up at runtime, say, as an `EntryPointNotFoundException` in C#. Running [`check_elf_exports`] as part
+//! of your tests catches this early.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use interoptopus::exports::check_elf_exports;
+//! # use interoptopus::{Inventory, InventoryBuilder};
+//! # fn my_inventory() -> Inventory { InventoryBuilder::new().inventory() }
+//!
+//! let report = check_elf_exports(&my_inventory(), "target/debug/libmy_library.so")?;
+//!
+//! assert!(report.is_ok(), "{}", report);
+//! # Ok::<(), interoptopus::Error>(())
+//! ```
+use crate::{Error, Inventory};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const SHT_DYNSYM: u32 = 11;
+const STT_FUNC: u8 = 2;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+const STV_HIDDEN: u8 = 2;
+const STV_INTERNAL: u8 = 1;
+const SHN_UNDEF: u16 = 0;
+
+/// Differences between an [`Inventory`] and the functions a library exports, see [`compare_exports`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportReport {
+    not_exported: Vec<String>,
+    not_in_inventory: Vec<String>,
+}
+
+impl ExportReport {
+    /// Functions declared in the inventory the library does not export, e.g., a forgotten `#[no_mangle]`.
+    pub fn not_exported(&self) -> &[String] {
+        &self.not_exported
+    }
+
+    /// Functions exported by the library not present in the inventory, e.g., a forgotten `register()`.
+    pub fn not_in_inventory(&self) -> &[String] {
+        &self.not_in_inventory
+    }
+
+    /// Returns `true` if inventory and library agree.
+    pub fn is_ok(&self) -> bool {
+        self.not_exported.is_empty() && self.not_in_inventory.is_empty()
+    }
+}
+
+impl Display for ExportReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return write!(f, "Library exports match inventory.");
+        }
+
+        for name in &self.not_exported {
+            writeln!(f, "Declared in inventory but not exported: {}", name)?;
+        }
+
+        for name in &self.not_in_inventory {
+            writeln!(f, "Exported but missing from inventory: {}", name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares the functions of an inventory with the given exported symbol names.
+pub fn compare_exports(inventory: &Inventory, exported: &[String]) -> ExportReport {
+    let declared = inventory.functions().iter().map(|x| x.name()).collect::<Vec<_>>();
+
+    let mut not_exported = declared
+        .iter()
+        .filter(|x| !exported.iter().any(|e| e == *x))
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    let mut not_in_inventory = exported
+        .iter()
+        .filter(|x| !declared.contains(&x.as_str()))
+        .filter(|x| !is_interoptopus_symbol(x))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    not_exported.sort();
+    not_exported.dedup();
+    not_in_inventory.sort();
+    not_in_inventory.dedup();
+
+    ExportReport { not_exported, not_in_inventory }
+}
+
+/// Reads the ELF shared object at `path` and compares its exported functions with the inventory.
+pub fn check_elf_exports(inventory: &Inventory, path: impl AsRef<Path>) -> Result<ExportReport, Error> {
+    let exported = elf_exported_functions(path)?;
+    Ok(compare_exports(inventory, &exported))
+}
+
+/// Returns the names of all functions an ELF shared object exports through its dynamic symbol table.
+///
+/// Only defined, global or weak, visible function symbols are returned; both 32 and 64 bit files of
+/// either endianness are supported.
+pub fn elf_exported_functions(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
+    let data = std::fs::read(path)?;
+    let elf = Elf::parse(&data)?;
+
+    elf.exported_functions()
+}
+
+// Symbols Interoptopus itself exports that are never part of an inventory.
+fn is_interoptopus_symbol(name: &str) -> bool {
+    #[cfg(feature = "inventory_export")]
+    {
+        name == crate::patterns::api_guard::INVENTORY_SYMBOL
+    }
+
+    #[cfg(not(feature = "inventory_export"))]
+    {
+        let _ = name;
+        false
+    }
+}
+
+/// Minimal, bounds checked view of an ELF file.
+struct Elf<'a> {
+    data: &'a [u8],
+    is_64: bool,
+    is_le: bool,
+}
+
+struct Section {
+    kind: u32,
+    offset: usize,
+    size: usize,
+    link: usize,
+    entry_size: usize,
+}
+
+impl<'a> Elf<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        if data.get(0..4) != Some(b"\x7fELF".as_slice()) {
+            return Err(Error::MalformedBinary);
+        }
+
+        let is_64 = match data.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err(Error::MalformedBinary),
+        };
+
+        let is_le = match data.get(5) {
+            Some(1) => true,
+            Some(2) => false,
+            _ => return Err(Error::MalformedBinary),
+        };
+
+        Ok(Self { data, is_64, is_le })
+    }
+
+    fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        let end = offset.checked_add(N).ok_or(Error::MalformedBinary)?;
+        let slice = self.data.get(offset..end).ok_or(Error::MalformedBinary)?;
+
+        Ok(slice.try_into().expect("Length was checked above"))
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8, Error> {
+        self.data.get(offset).copied().ok_or(Error::MalformedBinary)
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, Error> {
+        let b = self.bytes(offset)?;
+        Ok(if self.is_le { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, Error> {
+        let b = self.bytes(offset)?;
+        Ok(if self.is_le { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    }
+
+    /// Reads a word, 32 or 64 bit depending on the file class.
+    fn word(&self, offset: usize) -> Result<usize, Error> {
+        let value = if self.is_64 {
+            let b = self.bytes(offset)?;
+            if self.is_le {
+                u64::from_le_bytes(b)
+            } else {
+                u64::from_be_bytes(b)
+            }
+        } else {
+            self.u32(offset)? as u64
+        };
+
+        usize::try_from(value).map_err(|_| Error::MalformedBinary)
+    }
+
+    fn sections(&self) -> Result<Vec<Section>, Error> {
+        let (offset, entry_size, count) = if self.is_64 {
+            (self.word(0x28)?, self.u16(0x3A)? as usize, self.u16(0x3C)? as usize)
+        } else {
+            (self.word(0x20)?, self.u16(0x2E)? as usize, self.u16(0x30)? as usize)
+        };
+
+        (0..count)
+            .map(|i| {
+                let base = i.checked_mul(entry_size).and_then(|x| x.checked_add(offset)).ok_or(Error::MalformedBinary)?;
+
+                Ok(if self.is_64 {
+                    Section {
+                        kind: self.u32(base + 4)?,
+                        offset: self.word(base + 24)?,
+                        size: self.word(base + 32)?,
+                        link: self.u32(base + 40)? as usize,
+                        entry_size: self.word(base + 56)?,
+                    }
+                } else {
+                    Section {
+                        kind: self.u32(base + 4)?,
+                        offset: self.word(base + 16)?,
+                        size: self.word(base + 20)?,
+                        link: self.u32(base + 24)? as usize,
+                        entry_size: self.word(base + 36)?,
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn string(&self, table: &Section, offset: usize) -> Result<String, Error> {
+        let start = table.offset.checked_add(offset).ok_or(Error::MalformedBinary)?;
+        let bytes = self.data.get(start..).ok_or(Error::MalformedBinary)?;
+        let len = bytes.iter().position(|x| *x == 0).ok_or(Error::MalformedBinary)?;
+
+        Ok(std::str::from_utf8(&bytes[..len])?.to_string())
+    }
+
+    fn exported_functions(&self) -> Result<Vec<String>, Error> {
+        let sections = self.sections()?;
+        let mut names = Vec::new();
+
+        for symbols in sections.iter().filter(|x| x.kind == SHT_DYNSYM) {
+            let strings = sections.get(symbols.link).ok_or(Error::MalformedBinary)?;
+
+            if symbols.entry_size == 0 {
+                return Err(Error::MalformedBinary);
+            }
+
+            for i in 0..symbols.size / symbols.entry_size {
+                let base = symbols.offset.checked_add(i * symbols.entry_size).ok_or(Error::MalformedBinary)?;
+
+                // Elf32_Sym and Elf64_Sym order their fields differently.
+                let (name, info, other, section) = if self.is_64 {
+                    (self.u32(base)?, self.u8(base + 4)?, self.u8(base + 5)?, self.u16(base + 6)?)
+                } else {
+                    (self.u32(base)?, self.u8(base + 12)?, self.u8(base + 13)?, self.u16(base + 14)?)
+                };
+
+                let is_function = info & 0xf == STT_FUNC;
+                let is_global = matches!(info >> 4, STB_GLOBAL | STB_WEAK);
+                let is_visible = !matches!(other & 0x3, STV_HIDDEN | STV_INTERNAL);
+
+                if is_function && is_global && is_visible && section != SHN_UNDEF {
+                    names.push(self.string(strings, name as usize)?);
+                }
+            }
+        }
+
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::exports::{compare_exports, Elf};
+    use crate::lang::c::{Function, FunctionSignature, Meta};
+    use crate::{InventoryBuilder, Symbol};
+
+    #[test]
+    fn reports_both_directions() {
+        let function = |name: &str| Symbol::Function(Function::new(name.to_string(), FunctionSignature::default(), Meta::new()));
+        let inventory = InventoryBuilder::new().register(function("a")).register(function("b")).inventory();
+        let exported = vec!["b".to_string(), "c".to_string()];
+
+        let report = compare_exports(&inventory, &exported);
+
+        assert!(!report.is_ok());
+        assert_eq!(report.not_exported(), ["a"]);
+        assert_eq!(report.not_in_inventory(), ["c"]);
+    }
+
+    #[test]
+    fn rejects_non_elf() {
+        assert!(Elf::parse(b"MZ\x90\x00").is_err());
+        assert!(Elf::parse(b"\x7fELF\x02\x01").unwrap().exported_functions().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::writer::IndentWriter;
+use crate::writer::{IndentWriter, OutputSet};
 use crate::Error;
 use std::fs::File;
 use std::path::Path;
@@ -17,4 +17,12 @@ pub trait Interop {
 
         self.write_to(&mut writer)
     }
+
+    /// Generates FFI bindings into one or more named files of an [`OutputSet`].
+    ///
+    /// `file_name` names the primary file. By default it receives the output of [`write_to`](Self::write_to);
+    /// backends splitting their output (e.g., into headers, stubs and docs) override this and may derive further names from it.
+    fn write_output_set(&self, output: &mut OutputSet, file_name: &str) -> Result<(), Error> {
+        output.write(file_name, |w| self.write_to(w))
+    }
 }
@@ -11,7 +11,10 @@
 
 use crate::patterns::TypePattern;
 use crate::util::{capitalize_first_letter, ctypes_from_type_recursive, IdPrettifier};
-use std::collections::HashSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::hash::{Hash, Hasher};
 
 // /// If a name like `abc::XXX` is given, strips the `abc::` part.
@@ -22,6 +25,7 @@ use std::hash::{Hash, Hasher};
 
 /// A primitive value expressible on C-level.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PrimitiveValue {
     Bool(bool),
     U8(u8),
@@ -38,11 +42,72 @@ pub enum PrimitiveValue {
 
 /// The value of a constant.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum ConstantValue {
     Primitive(PrimitiveValue),
+    /// A `&'static str`.
+    String(String),
+    /// A fixed-size array such as `[u8; 4]`, with one value per element.
+    Array(ArrayType, Vec<ConstantValue>),
+    /// A struct literal, with one value per field in declaration order.
+    Composite(Box<CompositeType>, Vec<(String, ConstantValue)>),
 }
 
 impl ConstantValue {
+    /// Creates an array constant with elements of type `element_type`.
+    pub fn array(element_type: CType, values: Vec<ConstantValue>) -> Self {
+        Self::Array(ArrayType::new(element_type, values.len()), values)
+    }
+
+    /// Creates a struct constant of `the_type`, sorting `fields` into declaration order.
+    ///
+    /// # Panics
+    ///
+    /// If `the_type` is not a composite, or if `fields` doesn't have a value for each of its fields.
+    pub fn composite(the_type: CType, mut fields: Vec<(String, ConstantValue)>) -> Self {
+        let CType::Composite(composite) = the_type else {
+            panic!("Struct constants must be of a composite type.");
+        };
+
+        let values = composite
+            .fields()
+            .iter()
+            .map(|field| {
+                let index = fields.iter().position(|(name, _)| name == field.name()).expect("Must have value for every field.");
+                fields.swap_remove(index)
+            })
+            .collect();
+
+        Self::Composite(Box::new(composite), values)
+    }
+
+    /// Returns the type of this value.
+    pub fn the_type(&self) -> CType {
+        match self {
+            ConstantValue::Primitive(x) => CType::Primitive(match x {
+                PrimitiveValue::Bool(_) => PrimitiveType::Bool,
+                PrimitiveValue::U8(_) => PrimitiveType::U8,
+                PrimitiveValue::U16(_) => PrimitiveType::U16,
+                PrimitiveValue::U32(_) => PrimitiveType::U32,
+                PrimitiveValue::U64(_) => PrimitiveType::U64,
+                PrimitiveValue::I8(_) => PrimitiveType::I8,
+                PrimitiveValue::I16(_) => PrimitiveType::I16,
+                PrimitiveValue::I32(_) => PrimitiveType::I32,
+                PrimitiveValue::I64(_) => PrimitiveType::I64,
+                PrimitiveValue::F32(_) => PrimitiveType::F32,
+                PrimitiveValue::F64(_) => PrimitiveType::F64,
+            }),
+            ConstantValue::String(_) => CType::Pattern(TypePattern::CStrPointer),
+            ConstantValue::Array(x, _) => CType::Array(x.clone()),
+            ConstantValue::Composite(x, _) => CType::Composite(x.as_ref().clone()),
+        }
+    }
+
+    /// Checks if this is an array or a struct, which backends usually define after their types.
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self, Self::Array(_, _) | Self::Composite(_, _))
+    }
+
     pub(crate) fn fucking_hash_it_already<H: Hasher>(&self, h: &mut H) {
         match self {
             ConstantValue::Primitive(x) => match x {
@@ -58,12 +123,22 @@ impl ConstantValue {
                 PrimitiveValue::F32(x) => x.to_le_bytes().hash(h),
                 PrimitiveValue::F64(x) => x.to_le_bytes().hash(h),
             },
+            ConstantValue::String(x) => x.hash(h),
+            ConstantValue::Array(t, values) => {
+                t.hash(h);
+                values.iter().for_each(|x| x.fucking_hash_it_already(h));
+            }
+            ConstantValue::Composite(t, values) => {
+                t.hash(h);
+                values.iter().for_each(|(_, x)| x.fucking_hash_it_already(h));
+            }
         }
     }
 }
 
 /// A Rust `const` definition with a name and value, might become a `#define`.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Constant {
     name: String,
     value: ConstantValue,
@@ -89,26 +164,13 @@ impl Constant {
 
     /// Returns the type of this constant.
     pub fn the_type(&self) -> CType {
-        match &self.value {
-            ConstantValue::Primitive(x) => CType::Primitive(match x {
-                PrimitiveValue::Bool(_) => PrimitiveType::Bool,
-                PrimitiveValue::U8(_) => PrimitiveType::U8,
-                PrimitiveValue::U16(_) => PrimitiveType::U16,
-                PrimitiveValue::U32(_) => PrimitiveType::U32,
-                PrimitiveValue::U64(_) => PrimitiveType::U64,
-                PrimitiveValue::I8(_) => PrimitiveType::I8,
-                PrimitiveValue::I16(_) => PrimitiveType::I16,
-                PrimitiveValue::I32(_) => PrimitiveType::I32,
-                PrimitiveValue::I64(_) => PrimitiveType::I64,
-                PrimitiveValue::F32(_) => PrimitiveType::F32,
-                PrimitiveValue::F64(_) => PrimitiveType::F64,
-            }),
-        }
+        self.value.the_type()
     }
 }
 
 /// A type that can exist at the FFI boundary.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum CType {
     Primitive(PrimitiveType),
     Array(ArrayType),
@@ -145,6 +207,11 @@ impl CType {
                 PrimitiveType::I64 => 8,
                 PrimitiveType::F32 => 4,
                 PrimitiveType::F64 => 8,
+                PrimitiveType::Char => 4,
+                PrimitiveType::USize => std::mem::size_of::<usize>(),
+                PrimitiveType::ISize => std::mem::size_of::<isize>(),
+                PrimitiveType::U128 => 16,
+                PrimitiveType::I128 => 16,
             },
             // TODO
             _ => 999,
@@ -236,6 +303,7 @@ impl CType {
             CType::Opaque(t) => Some(t.meta.namespace()),
             CType::Composite(t) => Some(t.meta.namespace()),
             CType::Pattern(TypePattern::NamedCallback(t)) => Some(t.meta().namespace()),
+            CType::Pattern(TypePattern::ContextCallback(t)) => Some(t.meta().namespace()),
             _ => None,
         }
     }
@@ -243,6 +311,7 @@ impl CType {
 
 /// A primitive type that natively exists in C and is FFI safe.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum PrimitiveType {
     Void,
     Bool,
@@ -256,6 +325,14 @@ pub enum PrimitiveType {
     I64,
     F32,
     F64,
+    /// A `char` passed as its 32-bit Unicode scalar value, see [`FFIChar`](crate::patterns::primitives::FFIChar).
+    Char,
+    /// Pointer-sized unsigned integer, `size_t` in C.
+    USize,
+    /// Pointer-sized signed integer, `intptr_t` in C.
+    ISize,
+    U128,
+    I128,
 }
 
 impl PrimitiveType {
@@ -273,12 +350,18 @@ impl PrimitiveType {
             PrimitiveType::I64 => "i64",
             PrimitiveType::F32 => "f32",
             PrimitiveType::F64 => "f64",
+            PrimitiveType::Char => "char",
+            PrimitiveType::USize => "usize",
+            PrimitiveType::ISize => "isize",
+            PrimitiveType::U128 => "u128",
+            PrimitiveType::I128 => "i128",
         }
     }
 }
 
 /// A (C-style) `type[N]` containing a fixed number of elements of the same type.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ArrayType {
     array_type: Box<CType>,
     len: usize,
@@ -311,6 +394,7 @@ impl ArrayType {
 
 /// A (C-style) `enum` containing numbered variants.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct EnumType {
     name: String,
     variants: Vec<Variant>,
@@ -346,15 +430,28 @@ impl EnumType {
 
 /// Variant and value of a [`EnumType`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Variant {
     name: String,
     value: usize,
     documentation: Documentation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deprecation: Deprecation,
 }
 
 impl Variant {
     pub fn new(name: String, value: usize, documentation: Documentation) -> Self {
-        Self { name, value, documentation }
+        Self {
+            name,
+            value,
+            documentation,
+            deprecation: Deprecation::NotDeprecated,
+        }
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = deprecation;
+        self
     }
 
     pub fn name(&self) -> &str {
@@ -368,6 +465,10 @@ impl Variant {
     pub fn documentation(&self) -> &Documentation {
         &self.documentation
     }
+
+    pub fn deprecation(&self) -> &Deprecation {
+        &self.deprecation
+    }
 }
 
 /// Used for Rust and C `struct` with named fields, must be `#[repr(C)]`.
@@ -385,11 +486,13 @@ impl Variant {
 /// } MyComposite;
 /// ```
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CompositeType {
     name: String,
     fields: Vec<Field>,
     repr: Representation,
     meta: Meta,
+    memory_layout: Option<MemoryLayout>,
 }
 
 impl CompositeType {
@@ -405,12 +508,30 @@ impl CompositeType {
             fields,
             meta,
             repr: Default::default(),
+            memory_layout: None,
         }
     }
 
     /// Creates a new composite with the given name and type-level documentation.
     pub fn with_meta_repr(name: String, fields: Vec<Field>, meta: Meta, repr: Representation) -> Self {
-        Self { name, fields, repr, meta }
+        Self {
+            name,
+            fields,
+            repr,
+            meta,
+            memory_layout: None,
+        }
+    }
+
+    /// Creates a new composite which also knows how Rust laid it out in memory.
+    pub fn with_meta_repr_layout(name: String, fields: Vec<Field>, meta: Meta, repr: Representation, memory_layout: MemoryLayout) -> Self {
+        Self {
+            name,
+            fields,
+            repr,
+            meta,
+            memory_layout: Some(memory_layout),
+        }
     }
 
     /// Gets the type's name `
@@ -439,17 +560,155 @@ impl CompositeType {
     pub fn repr(&self) -> &Representation {
         &self.repr
     }
+
+    /// Size, alignment and field offsets as observed in Rust, if known.
+    pub fn memory_layout(&self) -> Option<&MemoryLayout> {
+        self.memory_layout.as_ref()
+    }
+}
+
+/// Size, alignment and field offsets of a [`CompositeType`] as laid out by the Rust compiler.
+///
+/// Values are specific to the target the inventory was produced on. They are therefore ignored when
+/// comparing or hashing types, so that, e.g., API guard hashes stay the same across platforms.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct MemoryLayout {
+    size: usize,
+    alignment: usize,
+    field_offsets: Vec<usize>,
+}
+
+impl MemoryLayout {
+    /// Creates a new layout, `field_offsets` must be ordered like the composite's fields.
+    pub fn new(size: usize, alignment: usize, field_offsets: Vec<usize>) -> Self {
+        Self { size, alignment, field_offsets }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Offset of each field in bytes, in the same order as [`CompositeType::fields`].
+    pub fn field_offsets(&self) -> &[usize] {
+        &self.field_offsets
+    }
+}
+
+impl PartialEq for MemoryLayout {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for MemoryLayout {}
+
+impl PartialOrd for MemoryLayout {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MemoryLayout {
+    fn cmp(&self, _: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for MemoryLayout {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Whether an item is marked `#[deprecated]`, with the optional `note`.
+///
+/// Deprecation is ignored when hashing items, so deprecating a function or type doesn't change the
+/// [`inventory_hash`](crate::patterns::api_guard::inventory_hash) and is not a breaking change.
+#[derive(Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Deprecation {
+    #[default]
+    NotDeprecated,
+    Deprecated(Option<String>),
+}
+
+impl Deprecation {
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self, Self::Deprecated(_))
+    }
+
+    /// The `note` given in `#[deprecated(note = "...")]`, if any.
+    pub fn note(&self) -> Option<&str> {
+        match self {
+            Self::Deprecated(note) => note.as_deref(),
+            Self::NotDeprecated => None,
+        }
+    }
+}
+
+impl Hash for Deprecation {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Per-backend overrides of an item's language-facing name, and backends which should not emit it.
+///
+/// Backends are identified by a short key, `"c"`, `"csharp"` or `"python"`. Like [`Deprecation`], overrides
+/// are ignored when hashing items, as they don't change what is exported from Rust.
+#[derive(Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct BackendOverrides {
+    names: BTreeMap<String, String>,
+    skipped: BTreeSet<String>,
+}
+
+impl BackendOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `name` instead of the Rust name when emitting the item in `backend`.
+    pub fn with_name(mut self, backend: &str, name: &str) -> Self {
+        self.names.insert(backend.to_string(), name.to_string());
+        self
+    }
+
+    /// Omits the item when generating bindings for `backend`.
+    pub fn with_skip(mut self, backend: &str) -> Self {
+        self.skipped.insert(backend.to_string());
+        self
+    }
+
+    /// The name to use in `backend`, if it was overridden.
+    pub fn name(&self, backend: &str) -> Option<&str> {
+        self.names.get(backend).map(|x| x.as_str())
+    }
+
+    pub fn is_skipped(&self, backend: &str) -> bool {
+        self.skipped.contains(backend)
+    }
+}
+
+impl Hash for BackendOverrides {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
 }
 
 /// Doesn't exist in C, but other languages can benefit from accidentally using 'private' fields.
-#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+///
+/// On types and functions `Private` means anything short of `pub`, e.g., `pub(crate)`.
+#[derive(Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Visibility {
+    #[default]
     Public,
     Private,
 }
 
 /// How a struct is laid out in memory.
 #[derive(Clone, Copy, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum Layout {
     C,
     Transparent,
@@ -461,6 +720,7 @@ pub enum Layout {
 
 /// How a type is represented in memory.
 #[derive(Clone, Copy, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Representation {
     layout: Layout,
     alignment: Option<usize>,
@@ -491,11 +751,14 @@ impl Representation {
 
 /// Fields of a [`CompositeType`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Field {
     name: String,
     visibility: Visibility,
     the_type: CType,
     documentation: Documentation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deprecation: Deprecation,
 }
 
 impl Field {
@@ -509,9 +772,15 @@ impl Field {
             visibility,
             the_type,
             documentation,
+            deprecation: Deprecation::NotDeprecated,
         }
     }
 
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -527,10 +796,15 @@ impl Field {
     pub fn documentation(&self) -> &Documentation {
         &self.documentation
     }
+
+    pub fn deprecation(&self) -> &Deprecation {
+        &self.deprecation
+    }
 }
 
 /// A named `struct` that becomes a fieldless `typedef struct S S;` in C.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct OpaqueType {
     name: String,
     meta: Meta,
@@ -552,9 +826,15 @@ impl OpaqueType {
 
 /// Additional information for user-defined types.
 #[derive(Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Meta {
     documentation: Documentation,
     namespace: String,
+    visibility: Visibility,
+    #[cfg_attr(feature = "serde", serde(default))]
+    deprecation: Deprecation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    overrides: BackendOverrides,
 }
 
 impl Meta {
@@ -563,7 +843,27 @@ impl Meta {
     }
 
     pub fn with_namespace_documentation(namespace: String, documentation: Documentation) -> Self {
-        Self { documentation, namespace }
+        Self::with_namespace_documentation_visibility(namespace, documentation, Visibility::Public)
+    }
+
+    pub fn with_namespace_documentation_visibility(namespace: String, documentation: Documentation, visibility: Visibility) -> Self {
+        Self {
+            documentation,
+            namespace,
+            visibility,
+            deprecation: Deprecation::NotDeprecated,
+            overrides: BackendOverrides::default(),
+        }
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: BackendOverrides) -> Self {
+        self.overrides = overrides;
+        self
     }
 
     pub fn with_documentation(documentation: Documentation) -> Self {
@@ -578,6 +878,20 @@ impl Meta {
         &self.namespace
     }
 
+    /// The Rust visibility of the annotated item, `pub` or anything less.
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    pub fn deprecation(&self) -> &Deprecation {
+        &self.deprecation
+    }
+
+    /// Per-backend names and exclusions, set via `name(...)` and `skip(...)` on the item's attribute.
+    pub fn overrides(&self) -> &BackendOverrides {
+        &self.overrides
+    }
+
     /// Convenience method used in generators
     pub fn is_namespace(&self, namespace: &str) -> bool {
         self.namespace == namespace
@@ -586,15 +900,35 @@ impl Meta {
 
 /// A named, exported `#[no_mangle] extern "C" fn f()` function.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Function {
     name: String,
     meta: Meta,
     signature: FunctionSignature,
+    async_callback: Option<String>,
 }
 
 impl Function {
     pub fn new(name: String, signature: FunctionSignature, meta: Meta) -> Self {
-        Self { name, meta, signature }
+        Self {
+            name,
+            meta,
+            signature,
+            async_callback: None,
+        }
+    }
+
+    /// Creates a function whose parameter `async_callback` is a callback invoked exactly once on completion.
+    ///
+    /// Only a failed `FFIError` return value signals that the callback will not be invoked; backends
+    /// ignore any other return value, so such functions must always complete through the callback.
+    pub fn with_async_callback(name: String, signature: FunctionSignature, meta: Meta, async_callback: String) -> Self {
+        Self {
+            name,
+            meta,
+            signature,
+            async_callback: Some(async_callback),
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -609,6 +943,14 @@ impl Function {
         &self.meta
     }
 
+    /// The completion callback parameter, if this function was marked `async_callback`.
+    ///
+    /// Backends can use this to emit `Task` or `asyncio` wrappers that resolve once the callback fires.
+    pub fn async_callback(&self) -> Option<&Parameter> {
+        let name = self.async_callback.as_ref()?;
+        self.signature.params().iter().find(|x| x.name() == name)
+    }
+
     pub fn prettifier(&self) -> IdPrettifier {
         IdPrettifier::from_rust_lower(self.name())
     }
@@ -624,6 +966,7 @@ impl Function {
 
 /// Represents multiple `in` and a single `out` parameters.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FunctionSignature {
     params: Vec<Parameter>,
     rval: CType,
@@ -645,14 +988,21 @@ impl FunctionSignature {
 
 /// Parameters of a [`FunctionSignature`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Parameter {
     name: String,
     the_type: CType,
+    #[cfg_attr(feature = "serde", serde(default))]
+    documentation: Documentation,
 }
 
 impl Parameter {
     pub fn new(name: String, the_type: CType) -> Self {
-        Self { name, the_type }
+        Self::with_documentation(name, the_type, Documentation::new())
+    }
+
+    pub fn with_documentation(name: String, the_type: CType, documentation: Documentation) -> Self {
+        Self { name, the_type, documentation }
     }
 
     pub fn name(&self) -> &str {
@@ -662,10 +1012,15 @@ impl Parameter {
     pub fn the_type(&self) -> &CType {
         &self.the_type
     }
+
+    pub fn documentation(&self) -> &Documentation {
+        &self.documentation
+    }
 }
 
 /// Represents `extern "C" fn()` types in Rust and `(*f)().` in C.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FnPointerType {
     name: Option<String>,
     signature: Box<FunctionSignature>,
@@ -709,6 +1064,7 @@ impl FnPointerType {
 
 /// Markdown generated from the `///` you put on Rust code.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Documentation {
     lines: Vec<String>,
 }
@@ -735,4 +1091,180 @@ impl Documentation {
     pub fn lines(&self) -> &[String] {
         &self.lines
     }
+
+    /// Returns the documentation of parameter `name` from a `# Parameters` section.
+    ///
+    /// The section lists parameters as `` * `name` - text ``, with `-` or `:` separating name and
+    /// text. Indented lines following an item continue it. `# Arguments` is accepted as well.
+    pub fn parameter(&self, name: &str) -> Documentation {
+        let Some(section) = self.parameter_section() else {
+            return Documentation::new();
+        };
+
+        let mut lines = Vec::new();
+        let mut in_item = false;
+
+        for line in &self.lines[section] {
+            let trimmed = line.trim();
+
+            if let Some(item) = trimmed.strip_prefix("* ").or_else(|| trimmed.strip_prefix("- ")) {
+                let item = item.trim_start();
+                let rest = item.strip_prefix('`').and_then(|x| x.strip_prefix(name)).and_then(|x| x.strip_prefix('`'));
+
+                in_item = rest.is_some();
+
+                if let Some(rest) = rest {
+                    let text = rest.trim_start().trim_start_matches(['-', ':']).trim();
+                    lines.push(format!(" {}", text));
+                }
+            } else if trimmed.is_empty() {
+                in_item = false;
+            } else if in_item {
+                lines.push(format!(" {}", trimmed));
+            }
+        }
+
+        Documentation::from_lines(lines)
+    }
+
+    /// Returns the documentation without its `# Parameters` section, see [`parameter`](Self::parameter).
+    pub fn without_parameters(&self) -> Documentation {
+        let Some(section) = self.parameter_section() else {
+            return self.clone();
+        };
+
+        let rest = &self.lines[section.end..];
+        let blank = rest.iter().take_while(|x| x.trim().is_empty()).count();
+
+        let mut lines = self.lines[..section.start - 1].to_vec();
+        lines.extend_from_slice(&rest[blank..]);
+
+        while lines.last().is_some_and(|x| x.trim().is_empty()) {
+            lines.pop();
+        }
+
+        Documentation::from_lines(lines)
+    }
+
+    /// Lines after the `# Parameters` heading, up to the first paragraph that is neither a list item
+    /// nor indented to continue one.
+    ///
+    /// Lines in code fences are never headings, as there `# ` merely hides a line from rustdoc.
+    fn parameter_section(&self) -> Option<std::ops::Range<usize>> {
+        let mut in_fence = false;
+        let headings = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| {
+                let trimmed = x.trim();
+
+                if trimmed.starts_with("```") {
+                    in_fence = !in_fence;
+                    return None;
+                }
+
+                (!in_fence && trimmed.starts_with('#')).then(|| (i, trimmed.trim_start_matches('#').trim()))
+            })
+            .collect::<Vec<_>>();
+
+        let (heading, _) = headings.iter().find(|(_, title)| *title == "Parameters" || *title == "Arguments")?;
+        let indentation = |x: &str| x.len() - x.trim_start().len();
+        let base = indentation(&self.lines[*heading]);
+        let start = heading + 1;
+        let mut end = start;
+        let mut new_paragraph = true;
+
+        for (i, line) in self.lines.iter().enumerate().skip(start) {
+            let trimmed = line.trim();
+
+            if trimmed.is_empty() {
+                new_paragraph = true;
+                continue;
+            }
+
+            let is_item = trimmed.starts_with("* ") || trimmed.starts_with("- ");
+
+            if new_paragraph && !is_item && indentation(line) <= base {
+                break;
+            }
+
+            new_paragraph = false;
+            end = i + 1;
+        }
+
+        Some(start..end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lang::c::Documentation;
+
+    fn documentation(text: &str) -> Documentation {
+        Documentation::from_lines(text.lines().map(|x| format!(" {}", x)).collect())
+    }
+
+    fn lines(documentation: &Documentation) -> Vec<&str> {
+        documentation.lines().iter().map(|x| x.as_str()).collect()
+    }
+
+    #[test]
+    fn parameter_bullet_styles() {
+        let doc = documentation("Adds.\n\n# Parameters\n\n* `x` - First.\n- `y`: Second.\n*   `z` -  Third.");
+
+        assert_eq!(lines(&doc.parameter("x")), [" First."]);
+        assert_eq!(lines(&doc.parameter("y")), [" Second."]);
+        assert_eq!(lines(&doc.parameter("z")), [" Third."]);
+        assert_eq!(lines(&doc.without_parameters()), [" Adds."]);
+    }
+
+    #[test]
+    fn parameter_continuation_lines() {
+        let doc = documentation("# Parameters\n\n* `x` - First line,\n  second line.\n* `y` - Other.\n\nNot part of `y`.");
+
+        assert_eq!(lines(&doc.parameter("x")), [" First line,", " second line."]);
+        assert_eq!(lines(&doc.parameter("y")), [" Other."]);
+        assert_eq!(lines(&doc.without_parameters()), [" Not part of `y`."]);
+    }
+
+    #[test]
+    fn parameter_missing() {
+        let doc = documentation("Adds.\n\n# Parameters\n\n* `x` - First.\n* `xy` - Second.");
+
+        assert!(doc.parameter("y").lines().is_empty());
+        assert!(documentation("Adds.").parameter("x").lines().is_empty());
+        assert_eq!(lines(&documentation("Adds.").without_parameters()), [" Adds."]);
+    }
+
+    #[test]
+    fn parameter_arguments_heading() {
+        let doc = documentation("Adds.\n\n# Arguments\n\n* `x` - First.\n\n# Safety\n\nNone.");
+
+        assert_eq!(lines(&doc.parameter("x")), [" First."]);
+        assert_eq!(lines(&doc.without_parameters()), [" Adds.", " ", " # Safety", " ", " None."]);
+    }
+
+    #[test]
+    fn parameter_fenced_code_block() {
+        let doc = documentation("Adds.\n\n```\n# use foo::Bar;\nlet x = 1;\n```\n\n# Parameters\n\n* `x` - First.\n\n```\n# use foo::Bar;\n```");
+
+        assert_eq!(lines(&doc.parameter("x")), [" First."]);
+        assert_eq!(
+            lines(&doc.without_parameters()),
+            [
+                " Adds.",
+                " ",
+                " ```",
+                " # use foo::Bar;",
+                " let x = 1;",
+                " ```",
+                " ",
+                " ```",
+                " # use foo::Bar;",
+                " ```"
+            ]
+        );
+        assert!(documentation("```\n# Parameters\n* `x` - Hidden.\n```").parameter("x").lines().is_empty());
+    }
 }
@@ -13,6 +13,16 @@ pub unsafe trait ConstantInfo {
     fn constant_info() -> Constant;
 }
 
+/// Implemented for values a [`ffi_constant`](crate::ffi_constant) can be initialized with, other than struct literals.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be the value of an `#[ffi_constant]`",
+    label = "unsupported constant initializer",
+    note = "struct constants must be initialized with a struct literal such as `{Self} {{ .. }}`, other initializers can't be inspected"
+)]
+pub trait ConstantValueInfo {
+    fn constant_value(self) -> ConstantValue;
+}
+
 /// Implemented for a type via [`ffi_type`](crate::ffi_type), gives meta info for a type.
 ///
 /// # Safety
@@ -74,6 +84,12 @@ macro_rules! impl_const_value_primitive {
                 Self::Primitive($x(x))
             }
         }
+
+        impl ConstantValueInfo for $rust_type {
+            fn constant_value(self) -> ConstantValue {
+                self.into()
+            }
+        }
     };
 }
 
@@ -89,6 +105,36 @@ impl_const_value_primitive!(f32, PrimitiveValue::F32);
 impl_const_value_primitive!(f64, PrimitiveValue::F64);
 impl_const_value_primitive!(bool, PrimitiveValue::Bool);
 
+impl From<&str> for ConstantValue {
+    fn from(x: &str) -> Self {
+        Self::String(x.to_string())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ConstantValue
+where
+    T: CTypeInfo + Into<ConstantValue>,
+{
+    fn from(x: [T; N]) -> Self {
+        Self::array(T::type_info(), x.into_iter().map(Into::into).collect())
+    }
+}
+
+impl ConstantValueInfo for &str {
+    fn constant_value(self) -> ConstantValue {
+        self.into()
+    }
+}
+
+impl<T, const N: usize> ConstantValueInfo for [T; N]
+where
+    T: CTypeInfo + ConstantValueInfo,
+{
+    fn constant_value(self) -> ConstantValue {
+        ConstantValue::array(T::type_info(), self.into_iter().map(ConstantValueInfo::constant_value).collect())
+    }
+}
+
 impl_ctype_primitive!(std::ffi::c_void, PrimitiveType::Void);
 impl_ctype_primitive!((), PrimitiveType::Void);
 impl_ctype_primitive!(u8, PrimitiveType::U8);
@@ -102,6 +148,10 @@ impl_ctype_primitive!(i64, PrimitiveType::I64);
 impl_ctype_primitive!(f32, PrimitiveType::F32);
 impl_ctype_primitive!(f64, PrimitiveType::F64);
 impl_ctype_primitive!(bool, PrimitiveType::Bool);
+impl_ctype_primitive!(usize, PrimitiveType::USize);
+impl_ctype_primitive!(isize, PrimitiveType::ISize);
+impl_ctype_primitive!(u128, PrimitiveType::U128);
+impl_ctype_primitive!(i128, PrimitiveType::I128);
 impl_ctype_primitive!(std::num::NonZeroU8, PrimitiveType::U8);
 impl_ctype_primitive!(std::num::NonZeroU16, PrimitiveType::U16);
 impl_ctype_primitive!(std::num::NonZeroU32, PrimitiveType::U32);
@@ -110,6 +160,10 @@ impl_ctype_primitive!(std::num::NonZeroI8, PrimitiveType::I8);
 impl_ctype_primitive!(std::num::NonZeroI16, PrimitiveType::I16);
 impl_ctype_primitive!(std::num::NonZeroI32, PrimitiveType::I32);
 impl_ctype_primitive!(std::num::NonZeroI64, PrimitiveType::I64);
+impl_ctype_primitive!(std::num::NonZeroUsize, PrimitiveType::USize);
+impl_ctype_primitive!(std::num::NonZeroIsize, PrimitiveType::ISize);
+impl_ctype_primitive!(std::num::NonZeroU128, PrimitiveType::U128);
+impl_ctype_primitive!(std::num::NonZeroI128, PrimitiveType::I128);
 impl_ctype_primitive!(Option<std::num::NonZeroU8>, PrimitiveType::U8);
 impl_ctype_primitive!(Option<std::num::NonZeroU16>, PrimitiveType::U16);
 impl_ctype_primitive!(Option<std::num::NonZeroU32>, PrimitiveType::U32);
@@ -118,6 +172,10 @@ impl_ctype_primitive!(Option<std::num::NonZeroI8>, PrimitiveType::I8);
 impl_ctype_primitive!(Option<std::num::NonZeroI16>, PrimitiveType::I16);
 impl_ctype_primitive!(Option<std::num::NonZeroI32>, PrimitiveType::I32);
 impl_ctype_primitive!(Option<std::num::NonZeroI64>, PrimitiveType::I64);
+impl_ctype_primitive!(Option<std::num::NonZeroUsize>, PrimitiveType::USize);
+impl_ctype_primitive!(Option<std::num::NonZeroIsize>, PrimitiveType::ISize);
+impl_ctype_primitive!(Option<std::num::NonZeroU128>, PrimitiveType::U128);
+impl_ctype_primitive!(Option<std::num::NonZeroI128>, PrimitiveType::I128);
 
 unsafe impl<T> CTypeInfo for NonNull<T>
 where
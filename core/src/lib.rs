@@ -92,7 +92,7 @@
 //! See the [**reference project**](https://github.com/ralfbiedert/interoptopus/tree/master/reference_project/src) for an overview:
 //! - [functions](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/functions.rs) (`extern "C"` functions and delegates)
 //! - [types](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/types.rs) (composites, enums, opaques, references, ...)
-//! - [constants](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/constants.rs) (primitive constants; results of const evaluation)
+//! - [constants](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/constants.rs) (primitive, string, array and struct constants; results of const evaluation)
 //! - [patterns](https://github.com/ralfbiedert/interoptopus/tree/master/reference_project/src/patterns) (ASCII pointers, options, slices, classes, ...)
 //!
 //!
@@ -131,6 +131,7 @@
 //!
 //! - `derive` - Proc macros such as `ffi_type`, ...
 //! - `serde` - Serde attributes on internal types.
+//! - `inventory_export` - Embed the [`Inventory`] into your library, see [`inventory_export`](crate::inventory_export).
 //! - `log` - Invoke [log](https://crates.io/crates/log) on FFI errors.
 //!
 //!
@@ -186,8 +187,11 @@ pub use generators::Interop;
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))] // does this work?
 pub use interoptopus_proc::{ffi_constant, ffi_function, ffi_service, ffi_service_ctor, ffi_service_ignore, ffi_service_method, ffi_type};
 
+pub mod conformance;
 mod core;
+pub mod docs;
 mod error;
+pub mod exports;
 mod generators;
 pub mod patterns;
 pub mod testing;
@@ -49,11 +49,66 @@
 //! - will even react to benign API changes (e.g., just adding functions),
 //! - might even react to documentation changes (subject to change; feedback welcome).
 //!
-use crate::lang::c::CType;
+//!
+//! # Layout Guard
+//!
+//! The hash above only tells you _that_ something changed. To additionally verify all structs
+//! have the same size and alignment on both sides, export a function returning a [`LayoutTable`]
+//! built by [`type_layouts`]. Backends supporting layout guards will then compare each entry
+//! against their own marshalled sizes when loading the library, and name the offending struct:
+//!
+//! ```
+//! use interoptopus::{ffi_function, Inventory, InventoryBuilder, function};
+//! use interoptopus::patterns::api_guard::{type_layouts, LayoutTable, TypeLayout};
+//! use std::sync::OnceLock;
+//!
+//! #[ffi_function]
+//! pub fn my_layout_guard() -> LayoutTable {
+//!     static LAYOUTS: OnceLock<Vec<TypeLayout>> = OnceLock::new();
+//!     LAYOUTS.get_or_init(|| type_layouts(&my_inventory())).as_slice().into()
+//! }
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new()
+//!         .register(function!(my_layout_guard))
+//!         .inventory()
+//! }
+//! ```
+//!
+//! Entries are keyed by [`layout_key`], the [`type_name_hash`] of the type's namespace and name, so
+//! same-named types in different namespaces are told apart. Only composites whose layout is known,
+//! i.e., ones produced by `#[ffi_type]` or the built-in patterns, are listed.
+//!
+//!
+//! # Embedded Inventory
+//!
+//! With the `inventory_export` feature enabled, [`inventory_export!`](crate::inventory_export) additionally
+//! embeds the whole serialized [`Inventory`] into your library. Tools such as `interoptopus_cli` can then
+//! generate bindings from a built `.dll` / `.so` alone, without access to the source tree.
+//!
+//! ```
+//! # #[cfg(feature = "inventory_export")]
+//! # mod x {
+//! use interoptopus::{Inventory, InventoryBuilder, inventory_export};
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new().inventory()
+//! }
+//!
+//! // Exports an `extern "C"` function `interoptopus_inventory` returning the inventory as JSON.
+//! inventory_export!(my_inventory);
+//! # }
+//! ```
+//!
+use crate::lang::c::{CType, CompositeType, Documentation, Field, Function, Layout, MemoryLayout, Meta, PrimitiveType, Representation, Visibility};
 use crate::lang::rust::CTypeInfo;
+use crate::patterns::slice::FFISlice;
 use crate::patterns::TypePattern;
 use crate::Inventory;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+#[cfg(feature = "inventory_export")]
+use std::ffi::{c_char, CStr};
 use std::hash::{Hash, Hasher};
 
 /// Holds the API version hash of the given library.
@@ -112,3 +167,184 @@ pub fn inventory_hash(inventory: &Inventory) -> u64 {
 
     hasher.finish()
 }
+
+/// Size and alignment of a single type as laid out by Rust, see [`type_layouts`].
+#[repr(C)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct TypeLayout {
+    /// The [`layout_key`] of the type.
+    pub name_hash: u64,
+    /// Size in bytes.
+    pub size: u64,
+    /// Alignment in bytes.
+    pub align: u64,
+}
+
+unsafe impl CTypeInfo for TypeLayout {
+    fn type_info() -> CType {
+        let fields = vec![
+            Field::with_documentation(
+                "name_hash".to_string(),
+                CType::Primitive(PrimitiveType::U64),
+                Visibility::Public,
+                Documentation::from_line("Hash of the type's namespace and name."),
+            ),
+            Field::with_documentation(
+                "size".to_string(),
+                CType::Primitive(PrimitiveType::U64),
+                Visibility::Public,
+                Documentation::from_line("Size in bytes."),
+            ),
+            Field::with_documentation(
+                "align".to_string(),
+                CType::Primitive(PrimitiveType::U64),
+                Visibility::Public,
+                Documentation::from_line("Alignment in bytes."),
+            ),
+        ];
+
+        let doc = Documentation::from_line("Size and alignment of a single type as laid out by Rust.");
+        let meta = Meta::with_documentation(doc);
+        let repr = Representation::new(Layout::C, None);
+        let offsets = vec![
+            std::mem::offset_of!(Self, name_hash),
+            std::mem::offset_of!(Self, size),
+            std::mem::offset_of!(Self, align),
+        ];
+        let layout = MemoryLayout::new(std::mem::size_of::<Self>(), std::mem::align_of::<Self>(), offsets);
+
+        CType::Composite(CompositeType::with_meta_repr_layout("TypeLayout".to_string(), fields, meta, repr, layout))
+    }
+}
+
+/// Returned by layout guard functions, one [`TypeLayout`] per composite.
+pub type LayoutTable = FFISlice<'static, TypeLayout>;
+
+/// Stable hash of a type name used to key [`TypeLayout`] entries (64 bit FNV-1a).
+///
+/// Other than the [`inventory_hash`] this must be computed by backends as well, so it must not
+/// depend on Rust's hasher implementation.
+pub const fn type_name_hash(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        i += 1;
+    }
+
+    hash
+}
+
+/// Key of a composite's [`TypeLayout`] entry, the [`type_name_hash`] of `namespace::Name`, or just `Name` in the default namespace.
+pub fn layout_key(composite: &CompositeType) -> u64 {
+    match composite.meta().namespace() {
+        "" => type_name_hash(composite.rust_name()),
+        namespace => type_name_hash(&format!("{}::{}", namespace, composite.rust_name())),
+    }
+}
+
+/// Returns all composites of an inventory whose layout is known and is reported by [`type_layouts`], one per [`layout_key`].
+pub fn layout_composites(inventory: &Inventory) -> Vec<&CompositeType> {
+    let mut keys = HashSet::new();
+
+    inventory
+        .ctypes()
+        .iter()
+        .filter_map(|x| match x {
+            CType::Composite(c) => Some(c),
+            CType::Pattern(TypePattern::Slice(c) | TypePattern::SliceMut(c) | TypePattern::Option(c)) => Some(c),
+            _ => None,
+        })
+        .filter(|x| x.memory_layout().is_some() && keys.insert(layout_key(x)))
+        .collect()
+}
+
+/// Computes the table a layout guard function should return.
+pub fn type_layouts(inventory: &Inventory) -> Vec<TypeLayout> {
+    layout_composites(inventory)
+        .into_iter()
+        .filter_map(|c| {
+            let layout = c.memory_layout()?;
+
+            Some(TypeLayout {
+                name_hash: layout_key(c),
+                size: layout.size() as u64,
+                align: layout.alignment() as u64,
+            })
+        })
+        .collect()
+}
+
+/// Returns `true` if the function is a layout guard, i.e., returns a [`LayoutTable`].
+pub fn is_layout_guard(function: &Function) -> bool {
+    function.signature().rval() == &LayoutTable::type_info()
+}
+
+/// Name of the function exported by [`inventory_export!`](crate::inventory_export).
+#[cfg(feature = "inventory_export")]
+pub const INVENTORY_SYMBOL: &str = "interoptopus_inventory";
+
+/// Serializes an inventory for embedding, see [`inventory_export!`](crate::inventory_export).
+#[cfg(feature = "inventory_export")]
+pub fn inventory_to_json(inventory: &Inventory) -> Result<String, crate::Error> {
+    Ok(serde_json::to_string(inventory)?)
+}
+
+/// Deserializes an inventory produced by [`inventory_to_json`].
+#[cfg(feature = "inventory_export")]
+pub fn inventory_from_json(json: &str) -> Result<Inventory, crate::Error> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Reads the inventory returned by a library's [`INVENTORY_SYMBOL`] function.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid, nul-terminated string, e.g., as returned by a function
+/// generated through [`inventory_export!`](crate::inventory_export).
+#[cfg(feature = "inventory_export")]
+pub unsafe fn inventory_from_ptr(ptr: *const c_char) -> Result<Inventory, crate::Error> {
+    if ptr.is_null() {
+        return Err(crate::Error::Null);
+    }
+
+    let json = unsafe { CStr::from_ptr(ptr) }.to_str()?;
+    inventory_from_json(json)
+}
+
+/// Exports the given inventory function's result through an `extern "C"` function [`INVENTORY_SYMBOL`].
+///
+/// The exported function returns the inventory as a nul-terminated JSON string owned by the library and
+/// valid until it is unloaded. It is not part of the inventory itself, so no bindings are generated for it.
+///
+/// # Example
+///
+/// ```
+/// use interoptopus::{Inventory, InventoryBuilder, inventory_export};
+///
+/// pub fn my_inventory() -> Inventory {
+///     InventoryBuilder::new().inventory()
+/// }
+///
+/// inventory_export!(my_inventory);
+/// ```
+#[cfg(feature = "inventory_export")]
+#[cfg_attr(docsrs, doc(cfg(feature = "inventory_export")))]
+#[macro_export]
+macro_rules! inventory_export {
+    ($inventory:path) => {
+        #[no_mangle]
+        pub extern "C" fn interoptopus_inventory() -> *const ::std::ffi::c_char {
+            static JSON: ::std::sync::OnceLock<::std::ffi::CString> = ::std::sync::OnceLock::new();
+
+            JSON.get_or_init(|| {
+                let json = $crate::patterns::api_guard::inventory_to_json(&$inventory()).expect("Inventory must be serializable.");
+                ::std::ffi::CString::new(json).expect("JSON must not contain nul bytes.")
+            })
+            .as_ptr()
+        }
+    };
+}
@@ -32,6 +32,29 @@
 //! ```
 //!
 //!
+//! # Callbacks with context
+//!
+//! Plain callbacks have no room for user data, so closures or instance methods on the calling side
+//! need globals. The macro [**`callback_with_context`**](crate::callback_with_context) instead pairs
+//! the function pointer with an opaque `context` it is invoked with, and an optional `destructor`
+//! Rust calls with that context once it drops the callback:
+//!
+//! ```c
+//! typedef struct my_closure
+//!     {
+//!     uint32_t (*callback)(uint32_t value, const void* context);
+//!     const void* context;
+//!     void (*destructor)(const void* context);
+//!     } my_closure;
+//! ```
+//!
+//! Backends supporting this pattern can then accept regular closures, e.g., by keeping a C# delegate alive
+//! through a `GCHandle` until the destructor runs.
+//!
+//! Passing such a callback by value hands its context over to Rust, which runs the destructor once the
+//! value is dropped. The same instance must therefore never be passed twice; the C# and Python wrappers
+//! raise an error when that is attempted.
+//!
 //! # Code Generation
 //!
 //! The macro [**`callback`**](crate::callback) enables two use cases:
@@ -110,10 +133,13 @@
 //!     SumFunction(Some(my_sum_function))
 //! }
 //! ```
-use crate::lang::c::{FnPointerType, Meta};
+use crate::lang::c::{CType, CompositeType, Field, FnPointerType, FunctionSignature, Meta, Parameter, PrimitiveType};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Internal helper naming a generated callback type wrapper.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct NamedCallback {
     fnpointer: FnPointerType,
     meta: Meta,
@@ -149,6 +175,75 @@ impl NamedCallback {
     }
 }
 
+/// Internal helper describing a callback with a user-data context, see [`callback_with_context`](crate::callback_with_context).
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ContextCallback {
+    composite: CompositeType,
+    signature: Box<FunctionSignature>,
+}
+
+impl ContextCallback {
+    /// Creates a new callback type `name`, `signature` being the one seen by users, without the context.
+    pub fn new(name: String, signature: FunctionSignature, meta: Meta) -> Self {
+        let context = CType::ReadPointer(Box::new(CType::Primitive(PrimitiveType::Void)));
+
+        let mut callback_params = signature.params().to_vec();
+        callback_params.push(Parameter::new("context".to_string(), context.clone()));
+
+        let callback = FnPointerType::new(FunctionSignature::new(callback_params, signature.rval().clone()));
+        let destructor_params = vec![Parameter::new("context".to_string(), context.clone())];
+        let destructor = FnPointerType::new(FunctionSignature::new(destructor_params, CType::Primitive(PrimitiveType::Void)));
+
+        let fields = vec![
+            Field::new("callback".to_string(), CType::FnPointer(callback)),
+            Field::new("context".to_string(), context),
+            Field::new("destructor".to_string(), CType::FnPointer(destructor)),
+        ];
+
+        Self {
+            composite: CompositeType::with_meta(name, fields, meta),
+            signature: Box::new(signature),
+        }
+    }
+
+    /// Gets the type name of this callback.
+    pub fn name(&self) -> &str {
+        self.composite.rust_name()
+    }
+
+    /// Gets the type's meta.
+    pub fn meta(&self) -> &Meta {
+        self.composite.meta()
+    }
+
+    /// The signature users implement, without the trailing `context` parameter.
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+
+    /// The struct holding `callback`, `context` and `destructor`, as seen by C.
+    pub fn composite(&self) -> &CompositeType {
+        &self.composite
+    }
+
+    /// The function pointer invoked with the signature's parameters and the context.
+    pub fn callback(&self) -> &FnPointerType {
+        match self.composite.fields()[0].the_type() {
+            CType::FnPointer(x) => x,
+            _ => unreachable!("First field is always the callback."),
+        }
+    }
+
+    /// The function pointer releasing the context.
+    pub fn destructor(&self) -> &FnPointerType {
+        match self.composite.fields()[2].the_type() {
+            CType::FnPointer(x) => x,
+            _ => unreachable!("Last field is always the destructor."),
+        }
+    }
+}
+
 /// Defines a callback type, akin to a `fn f(T) -> R` wrapped in an [Option](std::option).
 ///
 /// A named delegate will be emitted in languages supporting them, otherwise a regular
@@ -258,3 +353,162 @@ macro_rules! callback {
         }
     };
 }
+
+/// Defines a callback type carrying a user-data context, akin to a boxed `FnMut(T) -> R`.
+///
+/// Unlike [`callback`](crate::callback) the generated type holds the function pointer, an opaque
+/// `context` pointer passed as the last argument on each call, and an optional `destructor` which is
+/// invoked with the context once the callback is dropped on the Rust side. For details, please
+/// see the [**callbacks module**](crate::patterns::callbacks).
+///
+/// # Example
+///
+/// This defines a type `MyClosure` with a parameter `value` returning an `u32`.
+///
+/// ```
+/// use interoptopus::callback_with_context;
+///
+/// callback_with_context!(MyClosure(value: u32) -> u32);
+/// ```
+///
+/// The generated type definition is similar to:
+///
+/// ```
+/// # use std::ffi::c_void;
+/// #[repr(C)]
+/// pub struct MyClosure {
+///     callback: Option<extern "C" fn(u32, *const c_void) -> u32>,
+///     context: *const c_void,
+///     destructor: Option<extern "C" fn(*const c_void)>,
+/// }
+/// ```
+///
+/// For testing, the callback can be created from any Rust closure, which is dropped along with the callback:
+///
+/// ```
+/// use interoptopus::callback_with_context;
+///
+/// callback_with_context!(MyClosure(value: u32) -> u32);
+///
+/// let mut sum = 0;
+/// let mut callback = MyClosure::from_closure(move |x| {
+///     sum += x;
+///     sum
+/// });
+///
+/// assert_eq!(callback.call(1), 1);
+/// assert_eq!(callback.call(2), 3);
+/// ```
+#[macro_export]
+macro_rules! callback_with_context {
+    ($name:ident($($param:ident: $ty:ty),*)) => {
+        callback_with_context!($name($($param: $ty),*) -> ());
+    };
+
+    ($name:ident($($param:ident: $ty:ty),*) -> $rval:ty $(, namespace = $ns:expr)?) => {
+        #[repr(C)]
+        pub struct $name {
+            callback: Option<extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval>,
+            context: *const ::std::ffi::c_void,
+            destructor: Option<extern "C" fn(*const ::std::ffi::c_void)>,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    callback: None,
+                    context: ::std::ptr::null(),
+                    destructor: None,
+                }
+            }
+        }
+
+        impl $name {
+            /// Creates a new instance invoking `callback` with the given `context`.
+            pub fn new(callback: extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval, context: *const ::std::ffi::c_void) -> Self {
+                Self {
+                    callback: Some(callback),
+                    context,
+                    destructor: None,
+                }
+            }
+
+            /// Creates a new instance which passes `context` to `destructor` once dropped.
+            pub fn with_destructor(
+                callback: extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval,
+                context: *const ::std::ffi::c_void,
+                destructor: extern "C" fn(*const ::std::ffi::c_void),
+            ) -> Self {
+                Self {
+                    callback: Some(callback),
+                    context,
+                    destructor: Some(destructor),
+                }
+            }
+
+            /// Creates a new instance from a Rust closure, mostly useful for testing.
+            pub fn from_closure<F: FnMut($($ty),*) -> $rval + 'static>(f: F) -> Self {
+                extern "C" fn call<F: FnMut($($ty),*) -> $rval>($($param: $ty,)* context: *const ::std::ffi::c_void) -> $rval {
+                    let f = unsafe { &mut *(context as *mut F) };
+                    f($($param),*)
+                }
+
+                extern "C" fn destroy<F>(context: *const ::std::ffi::c_void) {
+                    drop(unsafe { ::std::boxed::Box::from_raw(context as *mut F) });
+                }
+
+                let context = ::std::boxed::Box::into_raw(::std::boxed::Box::new(f)) as *const ::std::ffi::c_void;
+                Self::with_destructor(call::<F>, context, destroy::<F>)
+            }
+
+            /// Will call function if it exists, panic otherwise.
+            ///
+            /// Takes `&mut self` since the callback may mutate the state behind its context.
+            pub fn call(&mut self, $($param: $ty),*) -> $rval {
+                self.callback.expect("Assumed function would exist but it didn't.")($($param,)* self.context)
+            }
+
+            /// Will call function only if it exists
+            pub fn call_if_some(&mut self, $($param: $ty),*) -> Option<$rval> {
+                match self.callback {
+                    Some(c) => Some(c($($param,)* self.context)),
+                    None => None
+                }
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if let Some(destructor) = self.destructor {
+                    destructor(self.context);
+                }
+            }
+        }
+
+        unsafe impl interoptopus::lang::rust::CTypeInfo for $name {
+            fn type_info() -> interoptopus::lang::c::CType {
+                use interoptopus::lang::rust::CTypeInfo;
+                use interoptopus::lang::c::{Meta, Documentation};
+
+                let rval = < $rval as CTypeInfo >::type_info();
+
+                let params = vec![
+                $(
+                    interoptopus::lang::c::Parameter::new(stringify!($param).to_string(), < $ty as CTypeInfo >::type_info()),
+                )*
+                ];
+
+                let mut namespace = String::new();
+                $(
+                    namespace = String::from($ns);
+                )*
+
+                let meta = Meta::with_namespace_documentation(namespace, Documentation::new());
+                let sig = interoptopus::lang::c::FunctionSignature::new(params, rval);
+                let callback = interoptopus::patterns::callbacks::ContextCallback::new(stringify!($name).to_string(), sig, meta);
+
+                interoptopus::lang::c::CType::Pattern(interoptopus::patterns::TypePattern::ContextCallback(callback))
+            }
+        }
+    };
+}
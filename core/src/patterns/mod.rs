@@ -78,9 +78,11 @@
 
 use crate::lang::c::{CType, CompositeType, PrimitiveType};
 use crate::lang::rust::CTypeInfo;
-use crate::patterns::callbacks::NamedCallback;
+use crate::patterns::callbacks::{ContextCallback, NamedCallback};
 use crate::patterns::result::FFIErrorEnum;
 use crate::patterns::service::Service;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::ffi::c_char;
 
 #[doc(hidden)]
@@ -97,6 +99,7 @@ pub mod surrogates;
 
 /// A pattern on a library level, usually involving both methods and types.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum LibraryPattern {
     Service(Service),
 }
@@ -116,6 +119,7 @@ impl From<Service> for LibraryPattern {
 /// A pattern on a type level.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 #[allow(clippy::large_enum_variant)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub enum TypePattern {
     CStrPointer,
     APIVersion,
@@ -126,6 +130,7 @@ pub enum TypePattern {
     Bool,
     CChar,
     NamedCallback(NamedCallback),
+    ContextCallback(ContextCallback),
 }
 
 impl TypePattern {
@@ -141,6 +146,7 @@ impl TypePattern {
             TypePattern::SliceMut(x) => CType::Composite(x.clone()),
             TypePattern::Option(x) => CType::Composite(x.clone()),
             TypePattern::NamedCallback(x) => CType::FnPointer(x.fnpointer().clone()),
+            TypePattern::ContextCallback(x) => CType::Composite(x.composite().clone()),
             TypePattern::Bool => CType::Primitive(PrimitiveType::U8),
             TypePattern::CChar => c_char::type_info(),
             TypePattern::APIVersion => CType::Primitive(PrimitiveType::U64),
@@ -27,7 +27,7 @@
 //! void set_value(optionu8 x);
 //! ```
 //!
-use crate::lang::c::{CType, CompositeType, Documentation, Field, Layout, Meta, PrimitiveType, Representation, Visibility};
+use crate::lang::c::{CType, CompositeType, Documentation, Field, Layout, MemoryLayout, Meta, PrimitiveType, Representation, Visibility};
 use crate::lang::rust::CTypeInfo;
 
 use crate::patterns::primitives::FFIBool;
@@ -149,7 +149,12 @@ where
         let repr = Representation::new(Layout::C, None);
         let meta = Meta::with_namespace_documentation(T::type_info().namespace().map(|e| e.into()).unwrap_or_else(String::new), doc);
         let name = capitalize_first_letter(T::type_info().name_within_lib());
-        let composite = CompositeType::with_meta_repr(format!("Option{}", name), fields, meta, repr);
+        let layout = MemoryLayout::new(
+            std::mem::size_of::<Self>(),
+            std::mem::align_of::<Self>(),
+            vec![std::mem::offset_of!(Self, t), std::mem::offset_of!(Self, is_some)],
+        );
+        let composite = CompositeType::with_meta_repr_layout(format!("Option{}", name), fields, meta, repr, layout);
         CType::Pattern(TypePattern::Option(composite))
     }
 }
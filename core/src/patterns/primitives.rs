@@ -1,10 +1,11 @@
 //! Additional support for primitives like `bool`.
 
-use crate::lang::c::CType;
+use crate::lang::c::{CType, PrimitiveType};
 use crate::lang::rust::CTypeInfo;
 use crate::patterns::TypePattern;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::char::CharTryFromError;
 use std::ops::Not;
 use std::os::raw::c_char;
 
@@ -99,10 +100,55 @@ impl From<FFICChar> for c_char {
     }
 }
 
+/// A Rust `char` passed as its 32-bit Unicode scalar value.
+///
+/// Unlike `char` itself any `u32` is a valid value, so foreign code passing, say, a surrogate
+/// `0xD800` won't trigger undefined behavior. Use [`FFIChar::as_char`] or `char::try_from` to
+/// check the value.
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(Debug, Copy, Clone, PartialEq, Default, Deserialize, Serialize))]
+#[cfg_attr(not(feature = "serde"), derive(Debug, Copy, Clone, PartialEq, Default))]
+pub struct FFIChar {
+    value: u32,
+}
+
+impl FFIChar {
+    /// The `char` this value represents, or `None` if it is not a Unicode scalar value.
+    pub fn as_char(self) -> Option<char> {
+        char::from_u32(self.value)
+    }
+
+    /// The raw value as passed by foreign code.
+    pub fn value(self) -> u32 {
+        self.value
+    }
+}
+
+unsafe impl CTypeInfo for FFIChar {
+    fn type_info() -> CType {
+        CType::Primitive(PrimitiveType::Char)
+    }
+}
+
+impl From<char> for FFIChar {
+    fn from(x: char) -> Self {
+        FFIChar { value: x as u32 }
+    }
+}
+
+impl TryFrom<FFIChar> for char {
+    type Error = CharTryFromError;
+
+    fn try_from(x: FFIChar) -> Result<Self, Self::Error> {
+        char::try_from(x.value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::patterns::primitives::FFIBool;
     use crate::patterns::primitives::FFICChar;
+    use crate::patterns::primitives::FFIChar;
     use std::os::raw::c_char;
 
     #[test]
@@ -111,6 +157,14 @@ mod test {
         assert!(!FFIBool::FALSE.is());
     }
 
+    #[test]
+    fn char_works() {
+        assert_eq!(FFIChar::from('ä').as_char(), Some('ä'));
+        assert_eq!(char::try_from(FFIChar::from('x')), Ok('x'));
+        assert!(FFIChar { value: 0xD800 }.as_char().is_none());
+        assert!(char::try_from(FFIChar { value: 0x110000 }).is_err());
+    }
+
     #[test]
     fn cchar_works() {
         assert!(c_char::from(FFICChar::MAX) == c_char::MAX);
@@ -30,6 +30,8 @@
 
 use crate::lang::c::{EnumType, Variant};
 use crate::util::log_error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::fmt::Debug;
 use std::panic::AssertUnwindSafe;
@@ -94,6 +96,7 @@ pub trait FFIError: Sized {
 
 /// Internal helper derived for enums that are an [`FFIError`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FFIErrorEnum {
     the_enum: EnumType,
     success_variant: Variant,
@@ -101,6 +101,84 @@
 //! x.Dispose();
 //! ```
 //!
+//! Associated functions without `self` can be exported as static class members by marking them
+//! `#[ffi_service_method(static)]`, e.g., a `pub fn supported_formats() -> u32` becomes
+//! `SimpleService.SupportedFormats()` in C#, and `simple_service_supported_formats()` in C.
+//!
+//! Pairs of methods marked `#[ffi_service_method(getter = "value")]` and `#[ffi_service_method(setter = "value")]`
+//! become a `Value { get; set; }` property in C#, and a `@property` in Python.
+//!
+//! Methods marked `#[ffi_service_method(returns_service)]` return another service, e.g.,
+//! `pub fn create_buffer(&self) -> Result<Buffer, Error>`. Like constructors, they hand out the new
+//! instance via an out-parameter on the C level, while C# and Python return a wrapped `Buffer` which keeps its creator alive.
+//! Services only created this way don't need a constructor of their own.
+//!
+//! Services marked `#[ffi_service(shared)]` are reference counted via `Arc` instead of being boxed. They get
+//! an additional `_retain` function adding a reference, and their destructor becomes `_release`, only dropping the
+//! instance once the last reference is gone. C# wrappers can `Clone()` them, Python ones support `copy.copy`.
+//! Since instances may be shared, their methods must not take `&mut self`, and the type must be `Send` and
+//! `Sync`, as references can end up on any thread:
+//!
+//! ```compile_fail
+//! # use std::fmt::{Display, Formatter};
+//! #
+//! # #[derive(Debug)]
+//! # pub enum Error {
+//! #     Bad,
+//! # }
+//! #
+//! # impl Display for Error {
+//! #     fn fmt(&self, _: &mut Formatter<'_>) -> std::fmt::Result {
+//! #         Ok(())
+//! #     }
+//! # }
+//! #
+//! # impl std::error::Error for Error {}
+//! #
+//! # #[ffi_type(error)]
+//! # pub enum MyFFIError {
+//! #     Ok = 0,
+//! #     NullPassed = 1,
+//! #     Panic = 2,
+//! #     OtherError = 3,
+//! # }
+//! #
+//! # impl FFIError for MyFFIError {
+//! #     const SUCCESS: Self = Self::Ok;
+//! #     const NULL: Self = Self::NullPassed;
+//! #     const PANIC: Self = Self::Panic;
+//! # }
+//! #
+//! # impl From<Error> for MyFFIError {
+//! #     fn from(x: Error) -> Self {
+//! #         match x {
+//! #             Error::Bad => Self::OtherError,
+//! #         }
+//! #     }
+//! # }
+//! #
+//! # use interoptopus::{ffi_type, ffi_service, ffi_service_ctor};
+//! # use interoptopus::patterns::result::FFIError;
+//! # use std::rc::Rc;
+//! #[ffi_type(opaque)]
+//! pub struct Counter {
+//!     value: Rc<u32>, // Neither `Send` nor `Sync`, fails to compile.
+//! }
+//!
+//! #[ffi_service(error = "MyFFIError", shared)]
+//! impl Counter {
+//!     #[ffi_service_ctor]
+//!     pub fn new_with(value: u32) -> Result<Self, Error> {
+//!         Ok(Self { value: Rc::new(value) })
+//!     }
+//! }
+//! ```
+//!
+//! Whether a service is `Send` and `Sync` is recorded as [`ThreadSafety`]. Backends mention it in the generated
+//! docs, and C# and Python can optionally serialize calls to services not `Sync` with a lock. In debug builds,
+//! using a service which is not `Send` from a thread other than the one which created it panics, which is turned into an error.
+//! Methods marked `on_panic = "return_default"` can't report errors, so they silently return their default value instead.
+//!
 //! In other languages and on the C FFI level the following methods would be emitted:
 //!
 //! ```c
@@ -112,51 +190,117 @@
 //!
 
 use crate::lang::c::{CType, Function, OpaqueType};
-use crate::patterns::TypePattern;
+use crate::patterns::{LibraryPattern, TypePattern};
 use crate::util::longest_common_prefix;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, ThreadId};
 
 /// Combines a receiver, constructor, destructor and multiple methods in one entity.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Service {
     the_type: OpaqueType,
     constructors: Vec<Function>,
     destructor: Function,
     methods: Vec<Function>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    static_methods: Vec<Function>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    properties: Vec<ServiceProperty>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    factories: Vec<Function>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    retain: Option<Function>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    thread_safety: ThreadSafety,
 }
 
 impl Service {
+    /// Creates a service around the opaque type the `destructor` operates on.
+    ///
+    /// # Panics
+    ///
+    /// If the destructor doesn't take the opaque type, or if the opaque type and the destructor are in different namespaces.
     pub fn new(constructors: Vec<Function>, destructor: Function, methods: Vec<Function>) -> Self {
-        let first_type = &constructors.first().and_then(|x| x.first_param_type()).expect("Must have type");
-        let the_type = extract_obvious_opaque_from_parameter(first_type).expect("First parameter must point to opaque.");
+        // Services created by other services might not have constructors, but always have a destructor.
+        let first_type = destructor.first_param_type().expect("Must have type");
+        let the_type = extract_obvious_opaque_from_parameter(&first_type).expect("First parameter must point to opaque.");
+
+        assert_eq!(
+            the_type.meta().namespace(),
+            destructor.meta().namespace(),
+            "Service `{}` and its type must be in the same namespace.",
+            the_type.rust_name()
+        );
 
         Self {
             the_type,
             constructors,
             destructor,
             methods,
+            static_methods: Vec::new(),
+            properties: Vec::new(),
+            factories: Vec::new(),
+            retain: None,
+            thread_safety: ThreadSafety::default(),
         }
     }
 
+    /// Adds functions not operating on an instance, emitted as static members of the service.
+    pub fn with_static_methods(mut self, static_methods: Vec<Function>) -> Self {
+        self.static_methods = static_methods;
+        self
+    }
+
+    /// Adds properties, the getters and setters of which must also be part of `methods`.
+    pub fn with_properties(mut self, properties: Vec<ServiceProperty>) -> Self {
+        self.properties = properties;
+        self
+    }
+
+    /// Adds methods returning another service via their second parameter, compare [`factory_type`](Self::factory_type).
+    pub fn with_factories(mut self, factories: Vec<Function>) -> Self {
+        self.factories = factories;
+        self
+    }
+
+    /// Makes this a reference counted service, where `retain` adds a reference and the destructor releases one.
+    pub fn with_retain(mut self, retain: Function) -> Self {
+        self.retain = Some(retain);
+        self
+    }
+
+    /// Records whether the service's type is `Send` and `Sync`.
+    pub fn with_thread_safety(mut self, thread_safety: ThreadSafety) -> Self {
+        self.thread_safety = thread_safety;
+        self
+    }
+
     /// Checks if the signature of this service is compatible with the `Service` pattern, panic with
     /// error message otherwise.
     ///
     /// This function is mainly called during compile time therefore panicking with a good error
     /// message is beneficial.
     pub fn assert_valid(&self) {
-        let constructor_fist_parameter = self.constructors.first().and_then(|x| x.first_param_type()).expect("Must have type");
         let destructor_first_parameter = self.destructor.first_param_type().expect("Must have type");
 
-        match constructor_fist_parameter {
-            CType::ReadWritePointer(x) => match *x {
-                CType::ReadWritePointer(ref x) => match **x {
-                    CType::Opaque(_) => {}
+        for constructor in &self.constructors {
+            match constructor.first_param_type().expect("Must have type") {
+                CType::ReadWritePointer(x) => match *x {
+                    CType::ReadWritePointer(ref x) => match **x {
+                        CType::Opaque(_) => {}
+                        _ => panic!("First parameter must be opaque type"),
+                    },
                     _ => panic!("First parameter must be opaque type"),
                 },
-                _ => panic!("First parameter must be opaque type"),
-            },
-            CType::Opaque(_) => {}
-            _ => panic!("First parameter must be RwPointer(RwPointer(Opaque)) type"),
+                CType::Opaque(_) => {}
+                _ => panic!("First parameter must be RwPointer(RwPointer(Opaque)) type"),
+            }
         }
 
         match destructor_first_parameter {
@@ -182,6 +326,33 @@ impl Service {
             CType::Pattern(TypePattern::FFIErrorEnum(_)) => {}
             _ => panic!("Destructor must return a `ffi_error` type pattern."),
         }
+
+        for property in &self.properties {
+            property.assert_valid();
+        }
+
+        if let Some(retain) = &self.retain {
+            match retain.first_param_type() {
+                Some(CType::ReadPointer(x)) if matches!(*x, CType::Opaque(_)) => {}
+                _ => panic!("First parameter of `retain` must be ReadPointer(Opaque) type"),
+            }
+
+            match retain.signature().rval() {
+                CType::Pattern(TypePattern::FFIErrorEnum(_)) => {}
+                _ => panic!("Retain function must return a `ffi_error` type pattern."),
+            }
+        }
+
+        for factory in &self.factories {
+            if self.factory_type(factory).is_none() {
+                panic!("Method `{}` must return another service via its second parameter.", factory.name());
+            }
+
+            match factory.signature().rval() {
+                CType::Pattern(TypePattern::FFIErrorEnum(_)) => {}
+                _ => panic!("Method returning a service must return a `ffi_error` type pattern."),
+            }
+        }
     }
 
     pub fn the_type(&self) -> &OpaqueType {
@@ -200,14 +371,309 @@ impl Service {
         &self.methods
     }
 
+    /// Methods marked `#[ffi_service_method(static)]`, which don't take the service context.
+    pub fn static_methods(&self) -> &[Function] {
+        &self.static_methods
+    }
+
+    /// Returns this service without the constructors and methods marked `skip(backend)`.
+    pub fn without_skipped(&self, backend: &str) -> Self {
+        let keep = |x: &&Function| !x.meta().overrides().is_skipped(backend);
+
+        Self {
+            the_type: self.the_type.clone(),
+            constructors: self.constructors.iter().filter(keep).cloned().collect(),
+            destructor: self.destructor.clone(),
+            methods: self.methods.iter().filter(keep).cloned().collect(),
+            static_methods: self.static_methods.iter().filter(keep).cloned().collect(),
+            properties: self
+                .properties
+                .iter()
+                .map(|x| ServiceProperty {
+                    name: x.name.clone(),
+                    getter: x.getter.iter().find(keep).cloned(),
+                    setter: x.setter.iter().find(keep).cloned(),
+                })
+                .filter(|x| x.getter.is_some() || x.setter.is_some())
+                .collect(),
+            factories: self.factories.iter().filter(keep).cloned().collect(),
+            retain: self.retain.clone(),
+            thread_safety: self.thread_safety,
+        }
+    }
+
+    /// The function adding a reference to an instance, if this service is marked `shared`.
+    pub fn retain(&self) -> Option<&Function> {
+        self.retain.as_ref()
+    }
+
+    /// Whether instances are reference counted, compare [`retain`](Self::retain).
+    pub fn is_shared(&self) -> bool {
+        self.retain.is_some()
+    }
+
+    /// Whether instances may be used from other threads, or concurrently.
+    pub fn thread_safety(&self) -> ThreadSafety {
+        self.thread_safety
+    }
+
+    /// Methods creating another service, which they return via an out-parameter like constructors.
+    pub fn factories(&self) -> &[Function] {
+        &self.factories
+    }
+
+    /// The service type created by `function`, if it is one of this service's [`factories`](Self::factories).
+    ///
+    /// On the C level its second parameter is a `**Opaque`, right after this service's context.
+    pub fn factory_type(&self, function: &Function) -> Option<OpaqueType> {
+        if !self.factories.contains(function) {
+            return None;
+        }
+
+        match function.signature().params().get(1)?.the_type() {
+            CType::ReadWritePointer(x) => match x.as_ref() {
+                CType::ReadWritePointer(x) => match x.as_ref() {
+                    CType::Opaque(x) => Some(x.clone()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Getter and setter pairs, emitted as properties in languages supporting them.
+    pub fn properties(&self) -> &[ServiceProperty] {
+        &self.properties
+    }
+
+    /// Returns the property `function` is a getter or setter of, if any.
+    pub fn property_of(&self, function: &Function) -> Option<&ServiceProperty> {
+        self.properties.iter().find(|x| x.is_accessor(function))
+    }
+
+    /// The namespace of the service's functions, as set by `#[ffi_service(namespace = "...")]`.
+    pub fn namespace(&self) -> &str {
+        self.destructor.meta().namespace()
+    }
+
+    /// Returns constructors, methods, static methods, factories and the destructor, in that order.
+    pub fn all_functions(&self) -> Vec<Function> {
+        let mut rval = self.constructors().to_vec();
+        rval.extend_from_slice(self.methods());
+        rval.extend_from_slice(self.static_methods());
+        rval.extend_from_slice(self.factories());
+        rval.push(self.destructor().clone());
+        rval
+    }
+
     /// Returns the longest common prefix all methods of this service share.
     pub fn common_prefix(&self) -> String {
         let mut all_methods = self.methods().to_vec();
         all_methods.extend_from_slice(self.constructors());
+        all_methods.extend_from_slice(self.static_methods());
+        all_methods.extend_from_slice(self.factories());
         longest_common_prefix(all_methods.as_slice())
     }
 }
 
+/// How instances of a service are handed to foreign code, implemented by `#[ffi_service]`.
+///
+/// Constructors and methods returning services use this to place new instances on the heap, so that services
+/// marked `shared` end up in an [`Arc`] while all others are boxed.
+pub trait ServiceHandle: Sized {
+    /// Whether handles must only be used on the thread which created them, checked in debug builds of services not `Send`.
+    const GUARD_THREAD: bool;
+
+    /// Moves `self` to the heap and returns the pointer handed out over FFI.
+    fn into_handle(self) -> *mut Self;
+}
+
+/// Helper for [`ServiceHandle`] implementations of regular services.
+pub fn boxed_handle<T>(value: T) -> *mut T {
+    Box::into_raw(Box::new(value))
+}
+
+/// Helper for [`ServiceHandle`] implementations of `shared` services.
+///
+/// References to shared instances may be used from any thread at the same time, hence `T` must be `Send` and `Sync`.
+pub fn shared_handle<T: Send + Sync>(value: T) -> *mut T {
+    Arc::into_raw(Arc::new(value)).cast_mut()
+}
+
+/// Whether the opaque type of a [`Service`] is `Send` and `Sync`, as detected by `#[ffi_service]`.
+///
+/// Unless set otherwise services are assumed to be neither.
+#[derive(Clone, Copy, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ThreadSafety {
+    is_send: bool,
+    is_sync: bool,
+}
+
+impl ThreadSafety {
+    pub fn new(is_send: bool, is_sync: bool) -> Self {
+        Self { is_send, is_sync }
+    }
+
+    /// Whether instances may be used from threads other than the one which created them.
+    pub fn is_send(&self) -> bool {
+        self.is_send
+    }
+
+    /// Whether instances may be used from multiple threads at the same time.
+    pub fn is_sync(&self) -> bool {
+        self.is_sync
+    }
+
+    /// A sentence on how instances may be used across threads, for generated documentation.
+    pub fn description(&self) -> &'static str {
+        match (self.is_send, self.is_sync) {
+            (true, true) => "Thread safe, instances may be used from multiple threads at the same time.",
+            (true, false) => "Instances may be used from any thread, but not from multiple threads at the same time.",
+            (false, _) => "Instances must only be used from the thread which created them.",
+        }
+    }
+}
+
+/// Detects whether `T` is `Send` and `Sync` at compile time, used by `#[ffi_service]`.
+///
+/// The inherent constants only exist if `T` implements the respective trait, otherwise the defaults of
+/// [`ProbeNotSend`] and [`ProbeNotSync`] apply. Both traits must be in scope, and `T` must be a concrete type.
+#[doc(hidden)]
+pub struct ThreadSafetyProbe<T: ?Sized>(PhantomData<T>);
+
+#[doc(hidden)]
+pub trait ProbeNotSend {
+    const IS_SEND: bool = false;
+}
+
+#[doc(hidden)]
+pub trait ProbeNotSync {
+    const IS_SYNC: bool = false;
+}
+
+impl<T: ?Sized> ProbeNotSend for ThreadSafetyProbe<T> {}
+impl<T: ?Sized> ProbeNotSync for ThreadSafetyProbe<T> {}
+
+impl<T: ?Sized + Send> ThreadSafetyProbe<T> {
+    pub const IS_SEND: bool = true;
+}
+
+impl<T: ?Sized + Sync> ThreadSafetyProbe<T> {
+    pub const IS_SYNC: bool = true;
+}
+
+/// The threads which created instances of services not `Send`, by handle address.
+static OWNER_THREADS: Mutex<BTreeMap<usize, ThreadId>> = Mutex::new(BTreeMap::new());
+
+/// Remembers the current thread as the owner of `handle`, see [`ServiceHandle::GUARD_THREAD`].
+#[doc(hidden)]
+pub fn thread_guard_register(handle: *const ()) {
+    let mut owners = OWNER_THREADS.lock().unwrap_or_else(PoisonError::into_inner);
+    owners.insert(handle as usize, thread::current().id());
+}
+
+/// Panics if `handle` is used by `function` outside the thread which created it.
+#[doc(hidden)]
+pub fn thread_guard_check(handle: *const (), function: &str) {
+    let owner = OWNER_THREADS.lock().unwrap_or_else(PoisonError::into_inner).get(&(handle as usize)).copied();
+
+    if let Some(owner) = owner.filter(|x| *x != thread::current().id()) {
+        panic!(
+            "Service used in `{}` from {:?}, but it is not `Send` and was created on {:?}.",
+            function,
+            thread::current().id(),
+            owner
+        );
+    }
+}
+
+/// Forgets the owner of `handle` once it was destroyed.
+#[doc(hidden)]
+pub fn thread_guard_release(handle: *const ()) {
+    OWNER_THREADS.lock().unwrap_or_else(PoisonError::into_inner).remove(&(handle as usize));
+}
+
+/// Whether any service in `patterns` returns instances of `the_type` from one of its [`factories`](Service::factories).
+///
+/// Backends use this to let such instances keep their creator alive.
+pub fn is_created_by_service(patterns: &[LibraryPattern], the_type: &OpaqueType) -> bool {
+    patterns.iter().any(|x| match x {
+        LibraryPattern::Service(x) => x.factories().iter().any(|f| x.factory_type(f).as_ref() == Some(the_type)),
+    })
+}
+
+/// A named value of a [`Service`], read and written through getter and setter methods.
+///
+/// Created by `#[ffi_service_method(getter = "x")]` and `#[ffi_service_method(setter = "x")]`. The accessors
+/// also remain regular [`methods`](Service::methods), so backends without properties (e.g., C) emit them as-is.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ServiceProperty {
+    name: String,
+    getter: Option<Function>,
+    setter: Option<Function>,
+}
+
+impl ServiceProperty {
+    pub fn new(name: String, getter: Option<Function>, setter: Option<Function>) -> Self {
+        Self { name, getter, setter }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Method taking the service context and returning the value.
+    pub fn getter(&self) -> Option<&Function> {
+        self.getter.as_ref()
+    }
+
+    /// Method taking the service context and the new value.
+    pub fn setter(&self) -> Option<&Function> {
+        self.setter.as_ref()
+    }
+
+    /// The type of the property's value.
+    pub fn the_type(&self) -> &CType {
+        match (&self.getter, &self.setter) {
+            (Some(getter), _) => getter.signature().rval(),
+            (None, Some(setter)) => setter.signature().params()[1].the_type(),
+            (None, None) => panic!("Property `{}` must have a getter or setter.", self.name),
+        }
+    }
+
+    /// Whether `function` is the getter or setter of this property.
+    pub fn is_accessor(&self, function: &Function) -> bool {
+        self.getter.as_ref() == Some(function) || self.setter.as_ref() == Some(function)
+    }
+
+    fn assert_valid(&self) {
+        if let Some(getter) = &self.getter {
+            if getter.signature().params().len() != 1 || getter.signature().rval().is_void() {
+                panic!("Getter of property `{}` must only take `&self` and return a value.", self.name);
+            }
+
+            if matches!(getter.signature().rval(), CType::Pattern(TypePattern::FFIErrorEnum(_))) {
+                panic!("Getter of property `{}` must return a value, use `on_panic = \"return_default\"`.", self.name);
+            }
+        }
+
+        if let Some(setter) = &self.setter {
+            if setter.signature().params().len() != 2 {
+                panic!("Setter of property `{}` must take `&mut self` and the new value.", self.name);
+            }
+        }
+
+        if let (Some(getter), Some(setter)) = (&self.getter, &self.setter) {
+            if getter.signature().rval() != setter.signature().params()[1].the_type() {
+                panic!("Getter and setter of property `{}` must use the same type.", self.name);
+            }
+        }
+    }
+}
+
 /// Walks the type until it finds the first "obvious" Opaque.
 ///
 /// An Opaque is obvious if it is at a singular position (e.g., `*const Opaque`),
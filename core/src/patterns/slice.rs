@@ -38,7 +38,7 @@
 //! ```
 //!
 
-use crate::lang::c::{CType, CompositeType, Documentation, Field, Layout, Meta, PrimitiveType, Representation, Visibility};
+use crate::lang::c::{CType, CompositeType, Documentation, Field, Layout, MemoryLayout, Meta, PrimitiveType, Representation, Visibility};
 use crate::lang::rust::CTypeInfo;
 use crate::patterns::TypePattern;
 use crate::util::capitalize_first_letter;
@@ -139,7 +139,8 @@ where
         let repr = Representation::new(Layout::C, None);
         let meta = Meta::with_namespace_documentation(T::type_info().namespace().map(|e| e.into()).unwrap_or_else(String::new), doc);
         let name = capitalize_first_letter(T::type_info().name_within_lib());
-        let composite = CompositeType::with_meta_repr(format!("Slice{}", name), fields, meta, repr);
+        let layout = MemoryLayout::new(std::mem::size_of::<Self>(), std::mem::align_of::<Self>(), vec![std::mem::offset_of!(Self, data), std::mem::offset_of!(Self, len)]);
+        let composite = CompositeType::with_meta_repr_layout(format!("Slice{}", name), fields, meta, repr, layout);
         CType::Pattern(TypePattern::Slice(composite))
     }
 }
@@ -247,7 +248,8 @@ where
         let repr = Representation::new(Layout::C, None);
         let meta = Meta::with_namespace_documentation(T::type_info().namespace().map(|e| e.into()).unwrap_or_else(String::new), doc);
         let name = capitalize_first_letter(T::type_info().name_within_lib());
-        let composite = CompositeType::with_meta_repr(format!("SliceMut{}", name), fields, meta, repr);
+        let layout = MemoryLayout::new(std::mem::size_of::<Self>(), std::mem::align_of::<Self>(), vec![std::mem::offset_of!(Self, data), std::mem::offset_of!(Self, len)]);
+        let composite = CompositeType::with_meta_repr_layout(format!("SliceMut{}", name), fields, meta, repr, layout);
         CType::Pattern(TypePattern::SliceMut(composite))
     }
 }
@@ -262,7 +264,7 @@ mod test {
         let empty = FFISlice::<u8>::empty();
         let some = FFISlice::<u8>::from_slice(slice);
 
-        assert_eq!(empty.as_slice(), &[]);
+        assert_eq!(empty.as_slice(), &[] as &[u8]);
         assert_eq!(some.as_slice(), slice);
     }
 
@@ -276,7 +278,7 @@ mod test {
         sub[0] = 6;
         some[0] = 5;
 
-        assert_eq!(empty.as_slice(), &[]);
+        assert_eq!(empty.as_slice(), &[] as &[u8]);
         assert_eq!(slice, &[5, 6, 2, 3, 5]);
     }
 
@@ -293,7 +295,7 @@ mod test {
         sub[0] = 6;
         target[0] = 5;
 
-        assert_eq!(empty.as_slice(), &[]);
+        assert_eq!(empty.as_slice(), &[] as &[u8]);
         assert_eq!(slice, &[5, 6, 2, 3, 5]);
     }
 }
@@ -1,12 +1,213 @@
 //! Test generated bindings for various languages.
+//!
+//! Backends compare their output against checked-in `.expected` files. On mismatch a unified diff
+//! is printed. To accept the new output instead, run the tests with the [`BLESS_VAR`] environment
+//! variable set, which rewrites all `.expected` files involved:
+//!
+//! ```text
+//! INTEROPTOPUS_BLESS=1 cargo test
+//! ```
+use crate::writer::OutputSet;
+use std::fs::{read_to_string, write};
+use std::path::Path;
 
-use std::fs::read_to_string;
+/// If set to anything but `0` tests rewrite their `.expected` files instead of comparing against them.
+pub const BLESS_VAR: &str = "INTEROPTOPUS_BLESS";
+
+/// Lines of unchanged context shown around each change.
+const DIFF_CONTEXT: usize = 3;
+
+/// Above this many `expected * actual` lines the diff no longer tries to find common lines.
+const DIFF_MAX_CELLS: usize = 16_000_000;
+
+/// Returns `true` if tests should update `.expected` files, see [`BLESS_VAR`].
+pub fn is_blessing() -> bool {
+    std::env::var(BLESS_VAR).map(|x| !x.is_empty() && x != "0").unwrap_or(false)
+}
 
 /// Used by backends to verify a `file.ext` matches an existing `file.ext.expected`.
 #[track_caller]
 pub fn assert_file_matches_generated(file: &str) {
     let actual = read_to_string(file).unwrap_or_else(|_| panic!("Must be able to read file '{}'", file));
-    let expected = read_to_string(format!("{}.expected", file)).unwrap_or_else(|_| panic!("Must be able to read pre-generated file for '{}'", file));
 
-    assert_eq!(expected, actual);
+    assert_generated_matches(format!("{}.expected", file), &actual);
+}
+
+/// Verifies in-memory output matches the given `.expected` file, without writing the output anywhere.
+#[track_caller]
+pub fn assert_generated_matches(expected_file: impl AsRef<Path>, actual: &str) {
+    let expected_file = expected_file.as_ref();
+
+    if is_blessing() {
+        write(expected_file, actual).unwrap_or_else(|_| panic!("Must be able to write file '{}'", expected_file.display()));
+        return;
+    }
+
+    let expected = read_to_string(expected_file).unwrap_or_else(|_| {
+        panic!(
+            "Must be able to read pre-generated file '{}', run with {}=1 to create it",
+            expected_file.display(),
+            BLESS_VAR
+        )
+    });
+
+    if expected != actual {
+        panic!(
+            "Generated output differs from '{}', run with {}=1 to accept it.\n\n{}",
+            expected_file.display(),
+            BLESS_VAR,
+            unified_diff(&expected, actual)
+        );
+    }
+}
+
+/// Verifies each file of an [`OutputSet`] matches `folder/{file}.expected`.
+#[track_caller]
+pub fn assert_output_set_matches(folder: impl AsRef<Path>, output: &OutputSet) {
+    for (name, content) in output.files() {
+        assert_generated_matches(folder.as_ref().join(format!("{}.expected", name)), content);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Renders a unified diff turning `expected` into `actual`.
+pub fn unified_diff(expected: &str, actual: &str) -> String {
+    let old = expected.lines().collect::<Vec<_>>();
+    let new = actual.lines().collect::<Vec<_>>();
+    let edits = line_edits(&old, &new);
+
+    let mut rval = String::from("--- expected\n+++ actual\n");
+
+    // Position in `old` and `new` before each edit.
+    let mut positions = Vec::with_capacity(edits.len() + 1);
+    let (mut o, mut n) = (0, 0);
+    for edit in &edits {
+        positions.push((o, n));
+        match edit {
+            Edit::Keep => (o, n) = (o + 1, n + 1),
+            Edit::Remove => o += 1,
+            Edit::Add => n += 1,
+        }
+    }
+    positions.push((o, n));
+
+    let changes = edits.iter().enumerate().filter(|(_, x)| **x != Edit::Keep).map(|(i, _)| i).collect::<Vec<_>>();
+    let mut i = 0;
+
+    while i < changes.len() {
+        // Merge changes whose context would overlap into one hunk.
+        let mut last = i;
+        while last + 1 < changes.len() && changes[last + 1] - changes[last] <= 2 * DIFF_CONTEXT + 1 {
+            last += 1;
+        }
+
+        let start = changes[i].saturating_sub(DIFF_CONTEXT);
+        let end = (changes[last] + DIFF_CONTEXT + 1).min(edits.len());
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+
+        rval.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + 1,
+            old_end - old_start,
+            new_start + 1,
+            new_end - new_start
+        ));
+
+        for (edit, (o, n)) in edits[start..end].iter().zip(&positions[start..end]) {
+            match edit {
+                Edit::Keep => rval.push_str(&format!(" {}\n", old[*o])),
+                Edit::Remove => rval.push_str(&format!("-{}\n", old[*o])),
+                Edit::Add => rval.push_str(&format!("+{}\n", new[*n])),
+            }
+        }
+
+        i = last + 1;
+    }
+
+    rval
+}
+
+/// Computes a shortest edit script via the longest common subsequence of lines.
+fn line_edits(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut edits = vec![Edit::Keep; prefix];
+
+    if old_mid.len().saturating_mul(new_mid.len()) > DIFF_MAX_CELLS {
+        edits.extend(std::iter::repeat_n(Edit::Remove, old_mid.len()));
+        edits.extend(std::iter::repeat_n(Edit::Add, new_mid.len()));
+    } else {
+        // lcs[i][j] is the LCS length of `old_mid[i..]` and `new_mid[j..]`.
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() || j < new_mid.len() {
+            if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+                edits.push(Edit::Keep);
+                (i, j) = (i + 1, j + 1);
+            } else if i < old_mid.len() && (j == new_mid.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                edits.push(Edit::Remove);
+                i += 1;
+            } else {
+                edits.push(Edit::Add);
+                j += 1;
+            }
+        }
+    }
+
+    edits.extend(std::iter::repeat_n(Edit::Keep, suffix));
+    edits
+}
+
+#[cfg(test)]
+mod test {
+    use super::unified_diff;
+
+    #[test]
+    fn diff_shows_context_around_changes() {
+        let expected = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let actual = "a\nb\nc\nd\nE\nf\ng\nh\ni\nj\nk\n";
+
+        let diff = unified_diff(expected, actual);
+
+        assert_eq!(diff, "--- expected\n+++ actual\n@@ -2,9 +2,10 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n i\n j\n+k\n");
+    }
+
+    #[test]
+    fn diff_splits_distant_hunks() {
+        let expected = (0..20).map(|x| x.to_string()).collect::<Vec<_>>().join("\n");
+        let actual = expected
+            .lines()
+            .map(|x| if x == "2" || x == "18" { "changed" } else { x })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let diff = unified_diff(&expected, &actual);
+
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,6 +1,6 @@\n 0\n 1\n-2\n+changed\n 3\n 4\n 5\n"));
+        assert!(diff.contains("@@ -16,5 +16,5 @@\n 15\n 16\n 17\n-18\n+changed\n 19\n"));
+    }
 }
@@ -1,7 +1,10 @@
 //! Helpers for backend authors.
 
-use crate::lang::c::{CType, Function};
+use crate::lang::c::{CType, EnumType, Function};
 use crate::patterns::TypePattern;
+use crate::Inventory;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
@@ -31,6 +34,56 @@ pub fn safe_name(name: &str) -> String {
     rval
 }
 
+/// How control characters are written by [`escape_string_literal`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ControlEscape {
+    /// Octal escapes `\ooo` of each UTF-8 byte, as C's `\x` would consume any following hex digits.
+    Octal,
+    /// Escapes `\uXXXX`, as C#'s `\x` would consume up to 4 hex digits.
+    Unicode,
+    /// Escapes `\xXX` as understood by Python.
+    Hex,
+}
+
+/// Escapes a string so it can be placed between double quotes in C, C# or Python source.
+///
+/// # Example
+///
+/// ```
+/// use interoptopus::util::{escape_string_literal, ControlEscape};
+///
+/// assert_eq!(escape_string_literal("a \"b\"\n", ControlEscape::Hex), r#"a \"b\"\n"#);
+/// assert_eq!(escape_string_literal("\u{1}a", ControlEscape::Octal), r#"\001a"#);
+/// assert_eq!(escape_string_literal("\u{85}", ControlEscape::Octal), r#"\302\205"#);
+/// assert_eq!(escape_string_literal("\u{1}a", ControlEscape::Unicode), r#"\u0001a"#);
+/// assert_eq!(escape_string_literal("\u{1}a", ControlEscape::Hex), r#"\x01a"#);
+/// ```
+pub fn escape_string_literal(s: &str, control: ControlEscape) -> String {
+    let mut rval = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '\\' => rval.push_str("\\\\"),
+            '"' => rval.push_str("\\\""),
+            '\n' => rval.push_str("\\n"),
+            '\r' => rval.push_str("\\r"),
+            '\t' => rval.push_str("\\t"),
+            c if c.is_control() => match control {
+                ControlEscape::Octal => {
+                    for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                        rval.push_str(&format!("\\{byte:03o}"));
+                    }
+                }
+                ControlEscape::Unicode => rval.push_str(&format!("\\u{:04x}", c as u32)),
+                ControlEscape::Hex => rval.push_str(&format!("\\x{:02x}", c as u32)),
+            },
+            c => rval.push(c),
+        }
+    }
+
+    rval
+}
+
 // TODO: Create a few unit tests for this.
 /// Sorts types so the latter entries will find their dependents earlier in this list.
 pub fn sort_types_by_dependencies(mut types: Vec<CType>) -> Vec<CType> {
@@ -164,6 +217,12 @@ pub(crate) fn ctypes_from_type_recursive(start: &CType, types: &mut HashSet<CTyp
                     ctypes_from_type_recursive(param.the_type(), types);
                 }
             }
+            TypePattern::ContextCallback(x) => {
+                ctypes_from_type_recursive(x.signature().rval(), types);
+                for param in x.signature().params() {
+                    ctypes_from_type_recursive(param.the_type(), types);
+                }
+            }
             TypePattern::Slice(x) => {
                 for field in x.fields() {
                     ctypes_from_type_recursive(field.the_type(), types);
@@ -223,6 +282,9 @@ pub(crate) fn extract_namespaces_from_types(types: &[CType], into: &mut HashSet<
                 TypePattern::Bool => {}
                 TypePattern::CChar => {}
                 TypePattern::NamedCallback(_) => {}
+                TypePattern::ContextCallback(x) => {
+                    into.insert(x.meta().namespace().to_string());
+                }
             },
         }
     }
@@ -230,6 +292,7 @@ pub(crate) fn extract_namespaces_from_types(types: &[CType], into: &mut HashSet<
 
 /// Maps an internal namespace like `common` to a language namespace like `Company.Common`.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(transparent))]
 pub struct NamespaceMappings {
     mappings: HashMap<String, String>,
 }
@@ -318,10 +381,30 @@ pub fn is_global_type(t: &CType) -> bool {
             TypePattern::Bool => true,
             TypePattern::CChar => true,
             TypePattern::NamedCallback(_) => false,
+            TypePattern::ContextCallback(_) => false,
         },
     }
 }
 
+/// Checks whether any function, constant, type, field or enum variant in the inventory is `#[deprecated]`.
+///
+/// Backends use this to only emit deprecation helpers (e.g., macros or pragmas) when needed.
+pub fn has_deprecations(inventory: &Inventory) -> bool {
+    let enum_deprecated = |x: &EnumType| x.meta().deprecation().is_deprecated() || x.variants().iter().any(|v| v.deprecation().is_deprecated());
+
+    let type_deprecated = |t: &CType| match t {
+        CType::Composite(x) => x.meta().deprecation().is_deprecated() || x.fields().iter().any(|f| f.deprecation().is_deprecated()),
+        CType::Enum(x) => enum_deprecated(x),
+        CType::Opaque(x) => x.meta().deprecation().is_deprecated(),
+        CType::Pattern(TypePattern::FFIErrorEnum(x)) => enum_deprecated(x.the_enum()),
+        _ => false,
+    };
+
+    inventory.functions().iter().any(|x| x.meta().deprecation().is_deprecated())
+        || inventory.constants().iter().any(|x| x.meta().deprecation().is_deprecated())
+        || inventory.ctypes().iter().any(type_deprecated)
+}
+
 /// Debug macro resolving to the current file and line number.
 ///
 /// ```
@@ -359,11 +442,30 @@ pub fn capitalize_first_letter(s: String) -> String {
 
 #[cfg(test)]
 mod test {
-    use crate::util::IdPrettifier;
+    use crate::lang::c::{CType, Deprecation, Function, FunctionSignature, Meta, PrimitiveType};
+    use crate::patterns::api_guard::inventory_hash;
+    use crate::util::{has_deprecations, IdPrettifier};
+    use crate::{InventoryBuilder, Symbol};
 
     #[test]
     fn is_pretty() {
         assert_eq!(IdPrettifier::from_rust_lower("hello_world").to_camel_case(), "HelloWorld");
         assert_eq!(IdPrettifier::from_rust_lower("single").to_camel_case(), "Single");
     }
+
+    #[test]
+    fn deprecation_does_not_change_hash() {
+        let inventory = |deprecation: Deprecation| {
+            let signature = FunctionSignature::new(vec![], CType::Primitive(PrimitiveType::Void));
+            let function = Function::new("f".to_string(), signature, Meta::new().with_deprecation(deprecation));
+            InventoryBuilder::new().register(Symbol::Function(function)).inventory()
+        };
+
+        let current = inventory(Deprecation::NotDeprecated);
+        let deprecated = inventory(Deprecation::Deprecated(Some("Use `g` instead.".to_string())));
+
+        assert!(!has_deprecations(&current));
+        assert!(has_deprecations(&deprecated));
+        assert_eq!(inventory_hash(&current), inventory_hash(&deprecated));
+    }
 }
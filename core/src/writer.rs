@@ -1,6 +1,9 @@
 //! Types used by backends to produce pretty output.
 use crate::Error;
+use std::collections::BTreeMap;
+use std::fs;
 use std::io::Write;
+use std::path::Path;
 
 /// You might not realize how hard it can be to type exactly 4 spaces.
 pub const FOUR_SPACES: &str = "    ";
@@ -91,6 +94,80 @@ impl<'a> IndentWriter<'a> {
     }
 }
 
+/// A set of named, in-memory files a backend produced, e.g., a header and its docs.
+///
+/// Files are kept in memory until written, which allows tests to inspect them and
+/// [`write_to_directory`](Self::write_to_directory) to skip files whose content did not change.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OutputSet {
+    files: BTreeMap<String, String>,
+}
+
+impl OutputSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders `f` into the file `name`, replacing any previous file of that name.
+    pub fn write(&mut self, name: &str, f: impl FnOnce(&mut IndentWriter) -> Result<(), Error>) -> Result<(), Error> {
+        let mut buffer = Vec::new();
+        let mut writer = IndentWriter::new(&mut buffer);
+
+        f(&mut writer)?;
+
+        self.insert(name, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    /// Adds the file `name` with the given content, replacing any previous file of that name.
+    pub fn insert(&mut self, name: &str, content: String) {
+        self.files.insert(name.to_string(), content);
+    }
+
+    /// Returns the content of file `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.files.get(name).map(|x| x.as_str())
+    }
+
+    /// Iterates over all `(name, content)` pairs, ordered by name.
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files.iter().map(|(name, content)| (name.as_str(), content.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Writes all files into `folder`, returning the names of files actually written.
+    ///
+    /// Files already on disk with identical content are not touched, so their modification time
+    /// stays the same and build systems won't rebuild for unchanged bindings.
+    pub fn write_to_directory(&self, folder: impl AsRef<Path>) -> Result<Vec<String>, Error> {
+        let mut written = Vec::new();
+
+        for (name, content) in &self.files {
+            let path = folder.as_ref().join(name);
+
+            if fs::read(&path).is_ok_and(|existing| existing == content.as_bytes()) {
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(&path, content)?;
+            written.push(name.clone());
+        }
+
+        Ok(written)
+    }
+}
+
 /// Writes a line of code, possibly with multiple indentations. Used in backends.
 #[macro_export]
 macro_rules! indented {
@@ -162,3 +239,28 @@ macro_rules! unindented {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use crate::writer::OutputSet;
+    use crate::Error;
+
+    #[test]
+    fn output_set_only_rewrites_changed() -> Result<(), Error> {
+        let folder = std::env::temp_dir().join(format!("interoptopus_output_set_{}", std::process::id()));
+
+        let mut output = OutputSet::new();
+        output.write("a.txt", |w| indented!(w, "a"))?;
+        output.insert("nested/b.txt", "b".to_string());
+
+        assert_eq!(output.get("a.txt"), Some("a\n"));
+        assert_eq!(output.write_to_directory(&folder)?, vec!["a.txt", "nested/b.txt"]);
+        assert!(output.write_to_directory(&folder)?.is_empty());
+
+        output.insert("a.txt", "changed".to_string());
+        assert_eq!(output.write_to_directory(&folder)?, vec!["a.txt"]);
+
+        std::fs::remove_dir_all(&folder)?;
+        Ok(())
+    }
+}
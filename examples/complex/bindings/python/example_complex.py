@@ -82,6 +82,10 @@ def _errcheck(returned, success):
     else: raise Exception(f"Function returned error: {returned}")
 
 
+# Completion callbacks of pending `async` calls, kept alive until native code invoked them.
+_pending_callbacks = {}
+
+
 class CallbackVars(object):
     """Helper to be used `lambda x: setattr(cv, "x", x)` when getting values from callbacks."""
     def __str__(self):
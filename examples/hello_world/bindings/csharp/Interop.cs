@@ -14,9 +14,6 @@ namespace My.Company
     {
         public const string NativeLib = "example_hello_world";
 
-        static InteropClass()
-        {
-        }
 
 
         /// Function using the type.
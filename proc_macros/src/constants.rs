@@ -1,18 +1,63 @@
-use crate::util::extract_doc_lines;
+use crate::macros::darling_parse;
+use crate::util::{extract_deprecation, extract_doc_lines};
 use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::ItemConst;
+use syn::{Expr, ItemConst, Member};
 
 #[derive(Debug, FromMeta)]
-pub struct Attributes {}
+pub struct Attributes {
+    #[darling(default)]
+    namespace: Option<String>,
+}
+
+/// Produces the `ConstantValue` of `access`, descending into struct literals as their fields can't be converted otherwise.
+fn constant_value(access: TokenStream, expr: &Expr) -> TokenStream {
+    match expr {
+        Expr::Struct(x) if x.rest.is_none() => {
+            let path = &x.path;
+            let fields = x.fields.iter().map(|field| {
+                let member = &field.member;
+                let name = match member {
+                    Member::Named(x) => x.to_string(),
+                    Member::Unnamed(x) => x.index.to_string(),
+                };
+                let value = constant_value(quote! { #access.#member }, &field.expr);
+
+                quote! { (#name.to_string(), #value) }
+            });
+
+            quote! {
+                ::interoptopus::lang::c::ConstantValue::composite(<#path as ::interoptopus::lang::rust::CTypeInfo>::type_info(), vec![#(#fields),*])
+            }
+        }
+        Expr::Array(x) if x.elems.iter().all(|x| matches!(x, Expr::Struct(_))) => {
+            let Some(Expr::Struct(first)) = x.elems.first() else {
+                return quote! { ::interoptopus::lang::rust::ConstantValueInfo::constant_value(#access) };
+            };
+
+            let path = &first.path;
+            let values = x.elems.iter().enumerate().map(|(i, x)| constant_value(quote! { #access[#i] }, x));
+
+            quote! {
+                ::interoptopus::lang::c::ConstantValue::array(<#path as ::interoptopus::lang::rust::CTypeInfo>::type_info(), vec![#(#values),*])
+            }
+        }
+        Expr::Paren(x) => constant_value(access, &x.expr),
+        _ => quote! { ::interoptopus::lang::rust::ConstantValueInfo::constant_value(#access) },
+    }
+}
 
-pub fn ffi_constant(_attr: TokenStream, input: TokenStream) -> TokenStream {
+pub fn ffi_constant(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let attributes = darling_parse!(Attributes, attr);
     let const_item: ItemConst = syn::parse2(input.clone()).expect("Must be item.");
 
     let const_ident = const_item.ident;
     let const_name = const_ident.to_string();
     let doc_line = extract_doc_lines(&const_item.attrs).join("\n");
+    let deprecation = extract_deprecation(&const_item.attrs);
+    let namespace = attributes.namespace.unwrap_or_default();
+    let value = constant_value(quote! { #const_ident }, &const_item.expr);
 
     quote! {
         #input
@@ -21,12 +66,13 @@ pub fn ffi_constant(_attr: TokenStream, input: TokenStream) -> TokenStream {
         #[allow(clippy::redundant_pub_crate)]
         pub(crate) struct #const_ident {}
 
+        #[allow(deprecated)]
         unsafe impl ::interoptopus::lang::rust::ConstantInfo for #const_ident {
             fn constant_info() -> interoptopus::lang::c::Constant {
 
                 let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
-                let meta = ::interoptopus::lang::c::Meta::with_documentation(documentation);
-                let value = ::interoptopus::lang::c::ConstantValue::from(#const_ident);
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation).with_deprecation(#deprecation);
+                let value = #value;
 
                 ::interoptopus::lang::c::Constant::new(#const_name.to_string(), value, meta)
             }
@@ -2,13 +2,105 @@ use crate::functions::freestanding::ffi_function_freestanding;
 use crate::macros::darling_parse;
 use darling::FromMeta;
 use proc_macro2::TokenStream;
+use quote::quote;
 
 mod freestanding;
 
+/// Names to use in specific backends instead of the Rust name, e.g., `name(csharp = "...")`.
+#[derive(Debug, Default, FromMeta)]
+pub struct BackendNames {
+    #[darling(default)]
+    c: Option<String>,
+
+    #[darling(default)]
+    csharp: Option<String>,
+
+    #[darling(default)]
+    python: Option<String>,
+}
+
+/// Backends which should not emit an item, e.g., `skip(python)`.
+#[derive(Debug, Default, FromMeta)]
+pub struct BackendSkip {
+    #[darling(default)]
+    c: bool,
+
+    #[darling(default)]
+    csharp: bool,
+
+    #[darling(default)]
+    python: bool,
+}
+
+impl BackendNames {
+    fn entries(&self) -> Vec<(&'static str, &String)> {
+        [("c", &self.c), ("csharp", &self.csharp), ("python", &self.python)]
+            .into_iter()
+            .filter_map(|(backend, name)| name.as_ref().map(|x| (backend, x)))
+            .collect()
+    }
+}
+
+impl BackendSkip {
+    fn entries(&self) -> Vec<&'static str> {
+        [("c", self.c), ("csharp", self.csharp), ("python", self.python)]
+            .into_iter()
+            .filter_map(|(backend, skip)| skip.then_some(backend))
+            .collect()
+    }
+}
+
+/// Produces the `BackendOverrides` expression stored in an item's `Meta`.
+pub fn backend_overrides_tokens(names: &BackendNames, skip: &BackendSkip) -> TokenStream {
+    let (name_backends, names): (Vec<_>, Vec<_>) = names.entries().into_iter().unzip();
+    let skip_backends = skip.entries();
+
+    quote! {
+        ::interoptopus::lang::c::BackendOverrides::new()
+            #( .with_name(#name_backends, #names) )*
+            #( .with_skip(#skip_backends) )*
+    }
+}
+
+/// Produces `name(...)` and `skip(...)` arguments to forward to a generated `#[ffi_function]`.
+pub fn backend_overrides_args(names: &BackendNames, skip: &BackendSkip) -> Vec<TokenStream> {
+    let mut args = Vec::new();
+
+    let names = names.entries();
+    if !names.is_empty() {
+        let backends = names.iter().map(|(backend, _)| syn::Ident::new(backend, proc_macro2::Span::call_site()));
+        let names = names.iter().map(|(_, name)| name);
+        args.push(quote! { name(#(#backends = #names),*) });
+    }
+
+    let skip = skip.entries();
+    if !skip.is_empty() {
+        let backends = skip.iter().map(|backend| syn::Ident::new(backend, proc_macro2::Span::call_site()));
+        args.push(quote! { skip(#(#backends),*) });
+    }
+
+    args
+}
+
 #[derive(Debug, FromMeta)]
 pub struct Attributes {
     #[darling(default)]
     debug: bool,
+
+    #[darling(default)]
+    async_callback: Option<String>,
+
+    #[darling(default)]
+    namespace: Option<String>,
+
+    #[darling(default)]
+    export_name: Option<String>,
+
+    #[darling(default)]
+    name: BackendNames,
+
+    #[darling(default)]
+    skip: BackendSkip,
 }
 
 pub fn ffi_function(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -3,7 +3,7 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{FnArg, GenericParam, ItemFn, Pat, ReturnType, Signature, Type};
 
-use crate::functions::Attributes;
+use crate::functions::{backend_overrides_tokens, Attributes};
 use crate::util;
 
 pub fn fn_signature_type(signature: Signature) -> TokenStream {
@@ -63,11 +63,14 @@ pub fn rval_tokens(return_type: &ReturnType) -> TokenStream {
     }
 }
 
-pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStream) -> TokenStream {
+pub fn ffi_function_freestanding(ffi_attributes: &Attributes, input: TokenStream) -> TokenStream {
     let mut item_fn = syn::parse2::<ItemFn>(input).expect("Must be a function.");
     let docs = util::extract_doc_lines(&item_fn.attrs);
+    let visibility = util::visibility_tokens(&item_fn.vis);
+    let deprecation = util::extract_deprecation(&item_fn.attrs);
 
     let mut args_name = Vec::new();
+    let mut args_rust_name = Vec::new();
     let mut args_type = Vec::new();
     let mut generic_parameters = Vec::new();
     let mut generic_ident = Vec::new();
@@ -87,7 +90,7 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
     }
 
     let function_ident = item_fn.sig.ident.clone();
-    let function_ident_str = function_ident.to_string();
+    let function_ident_str = ffi_attributes.export_name.clone().unwrap_or_else(|| function_ident.to_string());
     let mut generic_params = quote! {};
     let mut phantom_fields = quote! {};
 
@@ -112,6 +115,7 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
 
             let clean_name = name.strip_prefix('_').unwrap_or(&name);
             args_name.push(clean_name.to_string());
+            args_rust_name.push(name.clone());
 
             let token = match util::purge_lifetimes_from_type(pat.ty.as_ref()) {
                 Type::Path(x) => x.path.to_token_stream(),
@@ -131,12 +135,30 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
         }
     }
 
+    let namespace = ffi_attributes.namespace.clone().unwrap_or_default();
+    let overrides = backend_overrides_tokens(&ffi_attributes.name, &ffi_attributes.skip);
+    let function_ctor = match &ffi_attributes.async_callback {
+        Some(async_callback) => {
+            if !args_name.contains(async_callback) {
+                panic!("Parameter `{}` given as `async_callback` does not exist.", async_callback);
+            }
+
+            quote! { ::interoptopus::lang::c::Function::with_async_callback(#function_ident_str.to_string(), signature, meta, #async_callback.to_string()) }
+        }
+        None => quote! { ::interoptopus::lang::c::Function::new(#function_ident_str.to_string(), signature, meta) },
+    };
+
     // Ensure we have the right attributes
     if !item_fn.sig.abi.is_some() {
         item_fn.sig.abi = Some(syn::parse_quote!(extern "C"));
     }
 
-    if !item_fn.attrs.iter().any(|attr| attr.path().is_ident("no_mangle")) {
+    if let Some(export_name) = &ffi_attributes.export_name {
+        item_fn
+            .attrs
+            .retain(|attr| !attr.path().is_ident("no_mangle") && !attr.path().is_ident("export_name"));
+        item_fn.attrs.push(syn::parse_quote!(#[export_name = #export_name]));
+    } else if !item_fn.attrs.iter().any(|attr| attr.path().is_ident("no_mangle")) {
         item_fn.attrs.push(syn::parse_quote!(#[no_mangle]));
     }
 
@@ -156,18 +178,20 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
                 let mut params = ::std::vec::Vec::new();
 
                 #(
-                    params.push(::interoptopus::lang::c::Parameter::new(#args_name.to_string(), #args_type));
+                    doc_lines.push(#docs.to_string());
                 )*
 
+                let all_documentation = ::interoptopus::lang::c::Documentation::from_lines(doc_lines);
+
                 #(
-                    doc_lines.push(#docs.to_string());
+                    params.push(::interoptopus::lang::c::Parameter::with_documentation(#args_name.to_string(), #args_type, all_documentation.parameter(#args_rust_name)));
                 )*
 
                 let mut signature = ::interoptopus::lang::c::FunctionSignature::new(params, #rval);
-                let documentation = ::interoptopus::lang::c::Documentation::from_lines(doc_lines);
-                let meta = ::interoptopus::lang::c::Meta::with_documentation(documentation);
+                let documentation = all_documentation.without_parameters();
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation_visibility(#namespace.to_string(), documentation, #visibility).with_deprecation(#deprecation).with_overrides(#overrides);
 
-                ::interoptopus::lang::c::Function::new(#function_ident_str.to_string(), signature, meta)
+                #function_ctor
             }
         }
     };
@@ -96,6 +96,11 @@ pub fn ffi_type(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// | Parameter |  Explanation |
 /// | --- | ---  |
 /// | `debug` | Print generated helper code in console.
+/// | `async_callback` | Names a callback parameter invoked exactly once on completion; backends may emit `async` wrappers for it. Unless the function returns a failed `FFIError` it must always invoke the callback, otherwise awaiting the wrapper never completes.
+/// | `namespace = "X"` | Determine which namespace or file the function should go, compare [`ffi_type`](macro@crate::ffi_type).
+/// | `export_name = "X"` | Export the function under symbol `X` instead of its Rust name.
+/// | `name(csharp = "X", ...)` | Use name `X` in the given backend (`c`, `csharp`, `python`), e.g., if the Rust name is reserved there.
+/// | `skip(python, ...)` | Do not emit the function in the given backends.
 ///
 /// # Safety
 ///
@@ -125,10 +130,21 @@ pub fn ffi_function(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This will derive [`ConstantInfo`](https://docs.rs/interoptopus/latest/interoptopus/lang/rust/trait.ConstantInfo.html) for a helper struct of the
 /// same name containing the const's name and value.
 ///
-/// Constant evaluation is supported.
+/// Constant evaluation is supported. Besides primitives, constants may be `&str`, arrays, or
+/// struct literals of [`ffi_type`](macro@crate::ffi_type) structs. Struct constants must be initialized
+/// by the literal itself, e.g., `Vec2 { x: 0.0, y: 0.0 }`; other initializers such as `Vec2::ZERO`
+/// or `Vec2::new(0.0, 0.0)` can't be inspected and are rejected at compile time.
 ///
 /// In order to appear in generated bindings the constant also has to be mentioned in the inventory function.
 ///
+/// # Parameters
+///
+/// The following parameters can be provided:
+///
+/// | Parameter |  Explanation |
+/// | --- | ---  |
+/// | `namespace = "X"` | Determine which namespace or file the constant should go, compare [`ffi_type`](macro@crate::ffi_type).
+///
 /// # Examples
 ///
 /// ```
@@ -141,6 +157,11 @@ pub fn ffi_function(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// #[ffi_constant]
 /// const COMPUTED_CONST: u8 = double(12); // will export 24
 ///
+/// #[ffi_constant]
+/// const VERSION: &str = "1.0";
+///
+/// #[ffi_constant]
+/// const MAGIC: [u8; 4] = *b"MAGC";
 /// ```
 #[proc_macro_attribute] // Can now be used as `#[my_attribute]`
 pub fn ffi_constant(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -173,6 +194,13 @@ pub fn ffi_constant(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// | --- | ---  |
 /// | `error = "t"` | Use `t` as the [`FFIError`](https://docs.rs/interoptopus/latest/interoptopus/patterns/result/trait.FFIError.html) type, mandatory.
 /// | `prefix  = "p"` | Add `p` to all generated method names. If not given the prefix will be inferred from the type.
+/// | `shared` | Reference count instances via `Arc`, generating `p_retain` and `p_release` (instead of `p_destroy`); methods must not take `&mut self` and the type must be `Send + Sync`.
+/// | `namespace = "X"` | Place all generated functions and the service itself in namespace `X`, compare [`ffi_type`](macro@crate::ffi_type).
+///
+/// Whether `SomeType` is `Send` and `Sync` is detected automatically and recorded in the service's metadata. In debug builds, using
+/// a service which is not `Send` from another thread than the one which created it panics. Like any other panic this is returned
+/// as `FFIError::PANIC` by `ffi_error` methods, but `return_default` methods silently return their default value instead.
+/// `undefined_behavior` methods can't catch the panic, so they abort the process.
 ///
 /// # Example
 ///
@@ -247,6 +275,8 @@ pub fn ffi_service(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - The attribute must be used inside an `impl SomeType {}` block marked with [`#[ffi_service]`](macro@crate::ffi_service).
 /// - The method must return `Result<Self, Error>`.
 ///
+/// Like [`#[ffi_service_method]`](macro@crate::ffi_service_method), it accepts `name(...)` and `skip(...)` to rename or omit the constructor per backend.
+///
 /// We recommend to have a look at the [reference project](https://github.com/ralfbiedert/interoptopus/blob/master/interoptopus_reference_project/src/patterns/service.rs).
 ///
 /// # Example
@@ -328,7 +358,15 @@ pub fn ffi_service_ctor(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// | Parameter |  Explanation |
 /// | --- | ---  |
 /// | `on_panic` | Determines what will happen on a panic (`ffi_error`, `return_default`, `undefined_behavior`) and, as a side effect, _also_ determine how return values will be handled. See below.
+/// | `async_callback` | Names a callback parameter invoked exactly once on completion, see [`#[ffi_function]`](macro@crate::ffi_function).
+/// | `static` | The method has no `self` and is emitted as a static member of the service class, or a prefixed function in C.
+/// | `getter = "x"`, `setter = "x"` | Read or write property `x`; C# and Python emit a property, C keeps the flat function.
+/// | `returns_service` | The method returns `Result<S, Error>` for another service `S`, which C# and Python return as a wrapped object keeping its creator alive; C receives it via an out-parameter.
+/// | `name(csharp = "X", ...)` | Use name `X` for the method in the given backend, see [`#[ffi_function]`](macro@crate::ffi_function).
+/// | `skip(python, ...)` | Do not emit the method in the given backends.
 ///
+/// Unlike [`#[ffi_function]`](macro@crate::ffi_function), service methods don't accept `export_name`; they are
+/// always exported as the service's prefix followed by the method name.
 ///
 /// ## Wrapping Behavior
 ///
@@ -345,7 +383,8 @@ pub fn ffi_service_ctor(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// ⚠️ Generated methods add panic guards when used with `ffi_error` and `return_default`. However, since `return_default` methods
 /// have no other way to signal errors they will return [`Default::default()`] instead if a panic
 /// is encountered. If you compiled Interoptopus with the `log` feature a message will be emitted
-/// in that case.
+/// in that case. This includes using a service which is not `Send` from the wrong thread in debug builds, see
+/// [`ffi_service`](macro@crate::ffi_service). `undefined_behavior` methods check the thread as well, but abort on a mismatch.
 ///
 /// # Safety
 ///
@@ -1,9 +1,9 @@
 use crate::macros::darling_parse;
-use crate::service::function_impl::{generate_service_dtor, generate_service_method};
+use crate::service::function_impl::{generate_service_dtor, generate_service_method, generate_service_retain};
 use crate::util::{get_type_name, pascal_to_snake_case};
 use darling::FromMeta;
-use function_impl::MethodType;
-use proc_macro2::TokenStream;
+use function_impl::{Descriptor, MethodType};
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::{ImplItem, ItemImpl, Visibility};
 
@@ -19,6 +19,12 @@ pub struct Attributes {
 
     #[darling(default)]
     prefix: String,
+
+    #[darling(default)]
+    namespace: Option<String>,
+
+    #[darling(default)]
+    shared: bool,
 }
 
 impl Attributes {
@@ -30,6 +36,76 @@ impl Attributes {
             self.prefix.clone()
         }
     }
+
+    /// Whether instances are reference counted, set via `#[ffi_service(shared)]`.
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+
+    /// The `#[ffi_function]` attribute for generated functions, placing them into the service's namespace.
+    ///
+    /// The `overrides` are the per-method `name(...)` and `skip(...)` arguments.
+    pub fn ffi_function_attribute(&self, async_callback: Option<&String>, overrides: Vec<TokenStream>) -> TokenStream {
+        let mut args = overrides;
+
+        if let Some(async_callback) = async_callback {
+            args.push(quote! { async_callback = #async_callback });
+        }
+
+        if let Some(namespace) = &self.namespace {
+            args.push(quote! { namespace = #namespace });
+        }
+
+        if args.is_empty() {
+            quote! { #[interoptopus::ffi_function] }
+        } else {
+            quote! { #[interoptopus::ffi_function(#(#args),*)] }
+        }
+    }
+}
+
+/// Groups methods marked `getter = "x"` and `setter = "x"` into `ServiceProperty` expressions, in order of appearance.
+fn properties(descriptors: &[Descriptor]) -> Vec<TokenStream> {
+    let mut properties: Vec<(String, Option<Ident>, Option<Ident>)> = Vec::new();
+
+    for descriptor in descriptors {
+        let MethodType::Method(x) = &descriptor.method_type else {
+            continue;
+        };
+
+        for (name, is_getter) in x.getter().map(|x| (x, true)).into_iter().chain(x.setter().map(|x| (x, false))) {
+            let index = match properties.iter().position(|(x, _, _)| x == name) {
+                Some(index) => index,
+                None => {
+                    properties.push((name.clone(), None, None));
+                    properties.len() - 1
+                }
+            };
+
+            let (_, getter, setter) = &mut properties[index];
+            let accessor = if is_getter { getter } else { setter };
+
+            if accessor.is_some() {
+                panic!("Property `{}` has more than one {}.", name, if is_getter { "getter" } else { "setter" });
+            }
+
+            *accessor = Some(descriptor.ident.clone());
+        }
+    }
+
+    let function_info = |x: &Option<Ident>| match x {
+        Some(x) => quote! { Some({ use #x as x; x::function_info() }) },
+        None => quote! { None },
+    };
+
+    properties
+        .iter()
+        .map(|(name, getter, setter)| {
+            let getter = function_info(getter);
+            let setter = function_info(setter);
+            quote! { ::interoptopus::patterns::service::ServiceProperty::new(#name.to_string(), #getter, #setter) }
+        })
+        .collect()
 }
 
 pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -54,9 +130,20 @@ pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
     let ffi_dtor = generate_service_dtor(&attributes, &item);
     let ffi_method_ident = function_descriptors
         .iter()
-        .filter(|x| matches!(x.method_type, MethodType::Method(_)))
+        .filter(|x| matches!(&x.method_type, MethodType::Method(x) if !x.is_static() && !x.returns_service()))
+        .map(|x| x.ident.clone())
+        .collect::<Vec<_>>();
+    let ffi_factory_ident = function_descriptors
+        .iter()
+        .filter(|x| matches!(&x.method_type, MethodType::Method(x) if x.returns_service()))
+        .map(|x| x.ident.clone())
+        .collect::<Vec<_>>();
+    let ffi_static_method_ident = function_descriptors
+        .iter()
+        .filter(|x| matches!(&x.method_type, MethodType::Method(x) if x.is_static()))
         .map(|x| x.ident.clone())
         .collect::<Vec<_>>();
+    let ffi_properties = properties(&function_descriptors);
     let ffi_ctors = function_descriptors
         .iter()
         .filter(|x| matches!(x.method_type, MethodType::Constructor(_)))
@@ -66,6 +153,18 @@ pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
     let ffi_dtor_quote = &ffi_dtor.ffi_function_tokens;
     let ffi_dtor_ident = &ffi_dtor.ident;
 
+    let ffi_retain = attributes.is_shared().then(|| generate_service_retain(&attributes, &item));
+    let ffi_retain_quote = ffi_retain.as_ref().map(|x| x.ffi_function_tokens.clone());
+    let with_retain = ffi_retain.as_ref().map(|x| {
+        let ident = &x.ident;
+        quote! { .with_retain({ use #ident as x; x::function_info() }) }
+    });
+    let handle_helper = if attributes.is_shared() {
+        quote! { ::interoptopus::patterns::service::shared_handle }
+    } else {
+        quote! { ::interoptopus::patterns::service::boxed_handle }
+    };
+
     let lifetimes = item.generics.lifetimes();
     let lt = quote! { #(#lifetimes),* };
 
@@ -78,13 +177,36 @@ pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
 
         #ffi_dtor_quote
 
+        #ffi_retain_quote
+
+        impl <#lt> ::interoptopus::patterns::service::ServiceHandle for #service_type {
+            const GUARD_THREAD: bool = {
+                use ::interoptopus::patterns::service::ProbeNotSend;
+                cfg!(debug_assertions) && !::interoptopus::patterns::service::ThreadSafetyProbe::<Self>::IS_SEND
+            };
+
+            fn into_handle(self) -> *mut Self {
+                let handle = #handle_helper(self);
+
+                if Self::GUARD_THREAD {
+                    ::interoptopus::patterns::service::thread_guard_register(handle.cast_const().cast());
+                }
+
+                handle
+            }
+        }
+
+        #[allow(deprecated)]
         impl <#lt> ::interoptopus::patterns::LibraryPatternInfo for #service_type {
             fn pattern_info() -> ::interoptopus::patterns::LibraryPattern {
 
                 use ::interoptopus::lang::rust::CTypeInfo;
                 use ::interoptopus::lang::rust::FunctionInfo;
+                use ::interoptopus::patterns::service::{ProbeNotSend, ProbeNotSync, ThreadSafetyProbe};
 
                 let mut methods = Vec::new();
+                let mut static_methods = Vec::new();
+                let mut factories = Vec::new();
                 let mut ctors = Vec::new();
 
                 #(
@@ -95,6 +217,20 @@ pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
                 )*
 
 
+                #(
+                    {
+                        use #ffi_static_method_ident as x;
+                        static_methods.push(x::function_info());
+                    }
+                )*
+
+                #(
+                    {
+                        use #ffi_factory_ident as x;
+                        factories.push(x::function_info());
+                    }
+                )*
+
                 #(
                     {
                         use #ffi_ctors as x;
@@ -107,9 +243,19 @@ pub fn ffi_service(attr: TokenStream, input: TokenStream) -> TokenStream {
                     x::function_info()
                 };
 
+                let properties = vec![
+                    #(
+                        #ffi_properties
+                    ),*
+                ];
+
                 let service = ::interoptopus::patterns::service::Service::new(
                     ctors, dtor, methods,
-                );
+                ).with_static_methods(static_methods).with_properties(properties).with_factories(factories)#with_retain
+                .with_thread_safety(::interoptopus::patterns::service::ThreadSafety::new(
+                    ThreadSafetyProbe::<Self>::IS_SEND,
+                    ThreadSafetyProbe::<Self>::IS_SYNC,
+                ));
 
                 service.assert_valid();
 
@@ -1,11 +1,12 @@
+use crate::functions::{backend_overrides_args, BackendNames, BackendSkip};
 use crate::service::Attributes;
 use crate::util::{extract_doc_lines, purge_lifetimes_from_type};
 use darling::FromMeta;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use quote::quote_spanned;
 use std::ops::Deref;
 use syn::spanned::Spanned;
-use syn::{FnArg, GenericParam, ImplItemFn, ItemImpl, Pat, ReturnType};
+use syn::{FnArg, GenericArgument, GenericParam, ImplItemFn, ItemImpl, Meta, Pat, PathArguments, ReturnType, Type};
 
 pub struct Descriptor {
     pub ffi_function_tokens: TokenStream,
@@ -18,29 +19,114 @@ pub enum MethodType {
     Constructor(AttributeCtor),
     Method(AttributeMethod),
     Destructor,
+    Retain,
 }
 
 #[derive(Debug, Default, FromMeta)]
-pub struct AttributeCtor {}
+pub struct AttributeCtor {
+    #[darling(default)]
+    name: BackendNames,
 
-#[derive(Debug, FromMeta)]
+    #[darling(default)]
+    skip: BackendSkip,
+}
+
+#[derive(Debug, Default, FromMeta)]
 pub enum OnPanic {
+    #[default]
     FfiError,
     ReturnDefault,
     UndefinedBehavior,
 }
 
-#[derive(Debug, FromMeta)]
+#[derive(Debug, Default, FromMeta)]
 pub struct AttributeMethod {
+    #[darling(default)]
     on_panic: OnPanic,
+
+    #[darling(default)]
+    async_callback: Option<String>,
+
+    #[darling(default)]
+    is_static: bool,
+
+    #[darling(default)]
+    getter: Option<String>,
+
+    #[darling(default)]
+    setter: Option<String>,
+
+    #[darling(default)]
+    returns_service: bool,
+
+    #[darling(default)]
+    name: BackendNames,
+
+    #[darling(default)]
+    skip: BackendSkip,
 }
 
-impl Default for AttributeMethod {
-    fn default() -> Self {
-        Self { on_panic: OnPanic::FfiError }
+impl AttributeMethod {
+    /// Whether this is an associated function without `self`, marked `#[ffi_service_method(static)]`.
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// The property this method reads, set via `getter = "..."`.
+    pub fn getter(&self) -> Option<&String> {
+        self.getter.as_ref()
+    }
+
+    /// The property this method writes, set via `setter = "..."`.
+    pub fn setter(&self) -> Option<&String> {
+        self.setter.as_ref()
+    }
+
+    /// Whether this method creates another service, marked `#[ffi_service_method(returns_service)]`.
+    pub fn returns_service(&self) -> bool {
+        self.returns_service
     }
 }
 
+/// Extracts `T` from a `Result<T, E>` return type.
+fn result_ok_type(output: &ReturnType) -> Option<&Type> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+
+    let Type::Path(path) = ty.deref() else {
+        return None;
+    };
+
+    let segment = path.path.segments.last().filter(|x| x.ident == "Result")?;
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        GenericArgument::Type(x) => Some(x),
+        _ => None,
+    }
+}
+
+/// Renames a `static` in `#[ffi_service_method(static)]` to `is_static`, as keywords can't be parsed as meta items.
+fn without_static_keyword(meta: &Meta) -> Meta {
+    let mut meta = meta.clone();
+
+    if let Meta::List(list) = &mut meta {
+        list.tokens = std::mem::take(&mut list.tokens)
+            .into_iter()
+            .map(|token| match token {
+                TokenTree::Ident(x) if x == "static" => TokenTree::Ident(Ident::new("is_static", x.span())),
+                x => x,
+            })
+            .collect();
+    }
+
+    meta
+}
+
 /// Inspects all attributes and determines the method type to generate.
 fn method_type(function: &ImplItemFn) -> MethodType {
     let attrs = function.attrs.as_slice();
@@ -49,6 +135,7 @@ fn method_type(function: &ImplItemFn) -> MethodType {
     if function.attrs.iter().any(|x| format!("{:?}", x).contains("ffi_service_ctor")) {
         let ctor_attributes = attrs
             .iter()
+            .filter(|x| format!("{:?}", x).contains("ffi_service_ctor"))
             .filter_map(|attribute| AttributeCtor::from_meta(&attribute.meta).ok())
             .next()
             .unwrap_or_default();
@@ -61,7 +148,7 @@ fn method_type(function: &ImplItemFn) -> MethodType {
         let function_attributes = attrs
             .iter()
             .filter(|x| format!("{:?}", x).contains("ffi_service_method"))
-            .map(|attribute| AttributeMethod::from_meta(&attribute.meta).unwrap())
+            .map(|attribute| AttributeMethod::from_meta(&without_static_keyword(&attribute.meta)).unwrap())
             .next()
             .unwrap_or_default();
 
@@ -73,9 +160,19 @@ fn method_type(function: &ImplItemFn) -> MethodType {
         // If it has default output type, we can get away with "return default"
         ReturnType::Default => MethodType::Method(AttributeMethod {
             on_panic: OnPanic::ReturnDefault,
+            ..AttributeMethod::default()
         }),
         // Otherwise, use FFI error conversion.
-        ReturnType::Type(_, _) => MethodType::Method(AttributeMethod { on_panic: OnPanic::FfiError }),
+        ReturnType::Type(_, _) => MethodType::Method(AttributeMethod::default()),
+    }
+}
+
+/// Panics if a service not `Send` is used outside the thread which created it, in debug builds only.
+fn thread_guard(service_type: &Type, context: TokenStream, ffi_fn_ident: &Ident) -> TokenStream {
+    quote_spanned! { ffi_fn_ident.span() =>
+        if <#service_type as ::interoptopus::patterns::service::ServiceHandle>::GUARD_THREAD {
+            ::interoptopus::patterns::service::thread_guard_check(#context, stringify!(#ffi_fn_ident));
+        }
     }
 }
 
@@ -95,6 +192,7 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
     let error_ident = Ident::new(&attributes.error, function.span());
     let without_lifetimes = purge_lifetimes_from_type(&impl_block.self_ty);
     let doc_lines = extract_doc_lines(&function.attrs);
+    let deprecated_attrs = function.attrs.iter().filter(|x| x.path().is_ident("deprecated")).collect::<Vec<_>>();
 
     let span_rval = function.sig.output.span();
     let span_function = function.span();
@@ -107,6 +205,48 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
     };
 
     let method_type = method_type(function);
+    let guard = match function.sig.receiver() {
+        Some(_) => thread_guard(
+            &without_lifetimes,
+            quote_spanned!(span_function=> ::std::ptr::addr_of!(*context).cast()),
+            &ffi_fn_ident,
+        ),
+        None => TokenStream::new(),
+    };
+
+    if let MethodType::Method(x) = &method_type {
+        if x.is_static && function.sig.receiver().is_some() {
+            panic!("Method `{}` marked `static` must not take `self`.", orig_fn_ident);
+        }
+
+        if x.getter.is_some() && x.setter.is_some() {
+            panic!("Method `{}` can't be both `getter` and `setter`.", orig_fn_ident);
+        }
+
+        if (x.getter.is_some() || x.setter.is_some()) && function.sig.receiver().is_none() {
+            panic!("Property accessor `{}` must take `self`.", orig_fn_ident);
+        }
+
+        if attributes.is_shared() && function.sig.receiver().is_some_and(|x| x.mutability.is_some()) {
+            panic!("Method `{}` of a `shared` service must not take `&mut self`.", orig_fn_ident);
+        }
+
+        if x.returns_service && (function.sig.receiver().is_none() || x.getter.is_some() || x.setter.is_some()) {
+            panic!(
+                "Method `{}` marked `returns_service` must take `self` and can't be a property accessor.",
+                orig_fn_ident
+            );
+        }
+    }
+
+    // Methods returning a service pass it via an out-parameter right after `self`.
+    let returned_service = match &method_type {
+        MethodType::Method(x) if x.returns_service => match result_ok_type(&function.sig.output) {
+            Some(x) => Some(x.clone()),
+            None => panic!("Method `{}` marked `returns_service` must return `Result<Service, Error>`.", orig_fn_ident),
+        },
+        _ => None,
+    };
 
     // Constructor needs extra arg for ptr
     if let MethodType::Constructor(_) = &method_type {
@@ -124,6 +264,10 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                 }
 
                 arg_names.push(quote_spanned!(span_arg=> context));
+
+                if let Some(returned_service) = &returned_service {
+                    inputs.push(quote_spanned!(span_rval=> instance: &mut *mut #returned_service));
+                }
             }
             FnArg::Typed(pat) => match pat.pat.deref() {
                 Pat::Ident(x) => {
@@ -142,16 +286,23 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
         }
     }
 
+    let ffi_function_attribute = match &method_type {
+        MethodType::Constructor(x) => attributes.ffi_function_attribute(None, backend_overrides_args(&x.name, &x.skip)),
+        MethodType::Method(x) => attributes.ffi_function_attribute(x.async_callback.as_ref(), backend_overrides_args(&x.name, &x.skip)),
+        MethodType::Destructor | MethodType::Retain => attributes.ffi_function_attribute(None, Vec::new()),
+    };
+
     let generated_function = match &method_type {
         MethodType::Constructor(_) => {
             quote_spanned! { span_function =>
-                #[interoptopus::ffi_function]
+                #ffi_function_attribute
                 #[no_mangle]
-                #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                #[allow(unused_mut, unsafe_op_in_unsafe_fn, deprecated)]
                 #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
                 #(
                     #[doc = #doc_lines]
                 )*
+                #(#deprecated_attrs)*
                 pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #error_ident {
 
                     *context = ::std::ptr::null_mut();
@@ -162,9 +313,46 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
 
                     match result_result {
                         Ok(Ok(obj)) => {
-                            let boxed = ::std::boxed::Box::new(obj);
-                            let raw = ::std::boxed::Box::into_raw(boxed);
-                            *context = raw;
+                            *context = ::interoptopus::patterns::service::ServiceHandle::into_handle(obj);
+
+                            <#error_ident as ::interoptopus::patterns::result::FFIError>::SUCCESS
+                        }
+
+                        Ok(Err(e)) => {
+                            ::interoptopus::util::log_error(|| format!("Error in ({}): {:?}", stringify!(#ffi_fn_ident), e));
+                            e.into()
+                        }
+
+                        Err(e) => {
+                            ::interoptopus::util::log_error(|| format!("Panic in ({}): {}", stringify!(#ffi_fn_ident), ::interoptopus::patterns::result::get_panic_message(e.as_ref())));
+                            <#error_ident as ::interoptopus::patterns::result::FFIError>::PANIC
+                        }
+                    }
+                }
+            }
+        }
+        MethodType::Method(x) if x.returns_service => {
+            quote_spanned! { span_function =>
+                #ffi_function_attribute
+                #[no_mangle]
+                #[allow(unused_mut, unsafe_op_in_unsafe_fn, deprecated)]
+                #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
+                #(
+                    #[doc = #doc_lines]
+                )*
+                #(#deprecated_attrs)*
+                pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #error_ident {
+
+                    *instance = ::std::ptr::null_mut();
+
+                    let result_result = std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {
+                        #guard
+                        <#without_lifetimes>::#orig_fn_ident( #(#arg_names),* )
+                    }));
+
+                    match result_result {
+                        Ok(Ok(obj)) => {
+                            *instance = ::interoptopus::patterns::service::ServiceHandle::into_handle(obj);
 
                             <#error_ident as ::interoptopus::patterns::result::FFIError>::SUCCESS
                         }
@@ -185,19 +373,21 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
         MethodType::Method(x) => match x.on_panic {
             OnPanic::ReturnDefault => {
                 quote_spanned! { span_function =>
-                    #[interoptopus::ffi_function]
+                    #ffi_function_attribute
                     #[no_mangle]
-                    #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                    #[allow(unused_mut, unsafe_op_in_unsafe_fn, deprecated)]
                     #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
                     #(
                         #[doc = #doc_lines]
                     )*
+                    #(#deprecated_attrs)*
                     pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #rval {
                         let result_result = ::std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                             // Make sure we only have a FnOnce closure and prevent lifetime errors.
                             #(
                                 let #arg_names = #arg_names;
                             )*
+                            #guard
                             <#without_lifetimes>::#orig_fn_ident( #(#arg_names),* )
                         }));
 
@@ -213,31 +403,35 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
             }
             OnPanic::UndefinedBehavior => {
                 quote_spanned! { span_function =>
-                    #[interoptopus::ffi_function]
+                    #ffi_function_attribute
                     #[no_mangle]
-                    #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                    #[allow(unused_mut, unsafe_op_in_unsafe_fn, deprecated)]
                     #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
                     #(
                         #[doc = #doc_lines]
                     )*
+                    #(#deprecated_attrs)*
                     pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #rval {
+                        #guard
                         <#without_lifetimes>::#orig_fn_ident( #(#arg_names),* )
                     }
                 }
             }
             OnPanic::FfiError => {
                 let block = quote_spanned! { span_body =>
+                    #guard
                     <#without_lifetimes>::#orig_fn_ident( #(#arg_names),* )
                 };
 
                 quote_spanned! { span_function =>
-                    #[interoptopus::ffi_function]
+                    #ffi_function_attribute
                     #[no_mangle]
-                    #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                    #[allow(unused_mut, unsafe_op_in_unsafe_fn, deprecated)]
                     #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
                     #(
                         #[doc = #doc_lines]
                     )*
+                    #(#deprecated_attrs)*
                     pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #error_ident {
                         ::interoptopus::patterns::result::panics_and_errors_to_ffi_enum(move || {
                             #block
@@ -246,7 +440,7 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                 }
             }
         },
-        MethodType::Destructor => panic!("Must not happen."),
+        MethodType::Destructor | MethodType::Retain => panic!("Must not happen."),
     };
 
     Some(Descriptor {
@@ -258,20 +452,51 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
 
 pub fn generate_service_dtor(attributes: &Attributes, impl_block: &ItemImpl) -> Descriptor {
     let service_prefix = attributes.prefered_service_name(impl_block);
-    let ffi_fn_ident = Ident::new(&format!("{}destroy", service_prefix), impl_block.span());
+    let dtor_name = if attributes.is_shared() { "release" } else { "destroy" };
+    let ffi_fn_ident = Ident::new(&format!("{}{}", service_prefix, dtor_name), impl_block.span());
     let error_ident = Ident::new(&attributes.error, impl_block.span());
     let without_lifetimes = purge_lifetimes_from_type(&impl_block.self_ty);
 
     let span_service_ty = impl_block.self_ty.span();
 
+    let ffi_function_attribute = attributes.ffi_function_attribute(None, Vec::new());
+
+    let guard = thread_guard(&without_lifetimes, quote_spanned!(span_service_ty=> (*context).cast_const().cast()), &ffi_fn_ident);
+    let guard_release = quote_spanned! {span_service_ty=>
+        if <#without_lifetimes as ::interoptopus::patterns::service::ServiceHandle>::GUARD_THREAD {
+            ::interoptopus::patterns::service::thread_guard_release((*context).cast_const().cast());
+        }
+    };
+
+    let (docs, drop_instance) = if attributes.is_shared() {
+        (
+            "Releases a reference to the given instance, destroying it once no references remain.",
+            quote_spanned! {span_service_ty=>
+                let instance = ::std::sync::Arc::from_raw(*context);
+                if ::std::sync::Arc::strong_count(&instance) == 1 {
+                    #guard_release
+                }
+                drop(instance)
+            },
+        )
+    } else {
+        (
+            "Destroys the given instance.",
+            quote_spanned! {span_service_ty=>
+                #guard_release
+                drop(::std::boxed::Box::from_raw(*context))
+            },
+        )
+    };
+
     let generated_function = quote_spanned! {span_service_ty=>
-        /// Destroys the given instance.
+        #[doc = #docs]
         ///
         /// # Safety
         ///
         /// The passed parameter MUST have been created with the corresponding init function;
         /// passing any other value results in undefined behavior.
-        #[interoptopus::ffi_function]
+        #ffi_function_attribute
         #[allow(unused_mut, unsafe_op_in_unsafe_fn, unused_unsafe)]
         #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
         #[no_mangle]
@@ -282,7 +507,8 @@ pub fn generate_service_dtor(attributes: &Attributes, impl_block: &ItemImpl) ->
             }
 
             let result_result = ::std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                unsafe { drop(::std::boxed::Box::from_raw(*context)) };
+                #guard
+                unsafe { #drop_instance };
             }));
 
             *context = ::std::ptr::null_mut();
@@ -303,3 +529,52 @@ pub fn generate_service_dtor(attributes: &Attributes, impl_block: &ItemImpl) ->
         method_type: MethodType::Destructor,
     }
 }
+
+pub fn generate_service_retain(attributes: &Attributes, impl_block: &ItemImpl) -> Descriptor {
+    let service_prefix = attributes.prefered_service_name(impl_block);
+    let ffi_fn_ident = Ident::new(&format!("{}retain", service_prefix), impl_block.span());
+    let error_ident = Ident::new(&attributes.error, impl_block.span());
+    let without_lifetimes = purge_lifetimes_from_type(&impl_block.self_ty);
+
+    let span_service_ty = impl_block.self_ty.span();
+
+    let ffi_function_attribute = attributes.ffi_function_attribute(None, Vec::new());
+    let guard = thread_guard(&without_lifetimes, quote_spanned!(span_service_ty=> context.cast()), &ffi_fn_ident);
+
+    let generated_function = quote_spanned! {span_service_ty=>
+        /// Adds a reference to the given instance, which must be released separately.
+        ///
+        /// # Safety
+        ///
+        /// The passed parameter MUST have been created with the corresponding init function
+        /// and not been released yet; passing any other value results in undefined behavior.
+        #ffi_function_attribute
+        #[allow(unused_mut, unsafe_op_in_unsafe_fn, unused_unsafe)]
+        #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_fn_ident(context: *const #without_lifetimes) -> #error_ident {
+            if context.is_null() {
+                return <#error_ident as ::interoptopus::patterns::result::FFIError>::NULL;
+            }
+
+            let result_result = ::std::panic::catch_unwind(|| {
+                #guard
+                unsafe { ::std::sync::Arc::increment_strong_count(context) };
+            });
+
+            match result_result {
+                Ok(_) => <#error_ident as ::interoptopus::patterns::result::FFIError>::SUCCESS,
+                Err(e) => {
+                    ::interoptopus::util::log_error(|| format!("Panic in ({}): {}", stringify!(#ffi_fn_ident), ::interoptopus::patterns::result::get_panic_message(e.as_ref())));
+                    <#error_ident as ::interoptopus::patterns::result::FFIError>::PANIC
+                }
+            }
+        }
+    };
+
+    Descriptor {
+        ffi_function_tokens: generated_function,
+        ident: ffi_fn_ident,
+        method_type: MethodType::Retain,
+    }
+}
@@ -1,21 +1,22 @@
 use crate::types::{Attributes, TypeRepresentation};
-use crate::util::extract_doc_lines;
+use crate::util::{extract_deprecation, extract_doc_lines, visibility_tokens};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{Expr, ItemEnum, Lit};
 
-fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], values: &[i32], docs: &[String]) -> TokenStream {
+fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], values: &[i32], docs: &[String], deprecations: &[TokenStream]) -> TokenStream {
     let name = item.ident.to_string();
     let name_ident = syn::Ident::new(&name, item.ident.span());
 
     quote! {
+        #[allow(deprecated)]
         unsafe impl ::interoptopus::lang::rust::VariantInfo for #name_ident {
             fn variant_info(&self) -> ::interoptopus::lang::c::Variant {
                 match self {
                     #(
                        Self::#idents => {
                             let documentation = ::interoptopus::lang::c::Documentation::from_line(#docs);
-                            ::interoptopus::lang::c::Variant::new(#names.to_string(), #values as usize, documentation)
+                            ::interoptopus::lang::c::Variant::new(#names.to_string(), #values as usize, documentation).with_deprecation(#deprecations)
                        },
                     )*
                 }
@@ -26,6 +27,8 @@ fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], value
 
 pub fn ffi_type_enum(attributes: &Attributes, _input: TokenStream, mut item: ItemEnum) -> TokenStream {
     let doc_line = extract_doc_lines(&item.attrs).join("\n");
+    let visibility = visibility_tokens(&item.vis);
+    let deprecation = extract_deprecation(&item.attrs);
     let (type_repr, align) = attributes.type_repr_align();
 
     let span = item.ident.span();
@@ -38,6 +41,7 @@ pub fn ffi_type_enum(attributes: &Attributes, _input: TokenStream, mut item: Ite
     let mut variant_idents = Vec::new();
     let mut variant_values = Vec::new();
     let mut variant_docs = Vec::new();
+    let mut variant_deprecations = Vec::new();
     let mut next_id = 0;
 
     for variant in &item.variants {
@@ -67,10 +71,11 @@ pub fn ffi_type_enum(attributes: &Attributes, _input: TokenStream, mut item: Ite
             variant_names.push(ident);
             variant_values.push(this_id);
             variant_docs.push(variant_doc_line);
+            variant_deprecations.push(extract_deprecation(&variant.attrs));
         }
     }
 
-    let variant_infos = derive_variant_info(item.clone(), &variant_idents, &variant_names, &variant_values, &variant_docs);
+    let variant_infos = derive_variant_info(item.clone(), &variant_idents, &variant_names, &variant_values, &variant_docs, &variant_deprecations);
 
     let ctype_info_return = if attributes.error {
         quote! {
@@ -123,13 +128,14 @@ pub fn ffi_type_enum(attributes: &Attributes, _input: TokenStream, mut item: Ite
 
         #variant_infos
 
+        #[allow(deprecated)]
         unsafe impl ::interoptopus::lang::rust::CTypeInfo for #name_ident {
             fn type_info() -> ::interoptopus::lang::c::CType {
                 use ::interoptopus::lang::rust::VariantInfo;
 
                 let mut variants = ::std::vec::Vec::new();
                 let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
-                let mut meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
+                let mut meta = ::interoptopus::lang::c::Meta::with_namespace_documentation_visibility(#namespace.to_string(), documentation, #visibility).with_deprecation(#deprecation);
 
                 #({
                     variants.push(Self::#variant_idents.variant_info());
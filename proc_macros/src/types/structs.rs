@@ -1,5 +1,5 @@
 use crate::types::{Attributes, TypeRepresentation};
-use crate::util::extract_doc_lines;
+use crate::util::{extract_deprecation, extract_doc_lines, visibility_tokens};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::spanned::Spanned;
@@ -45,6 +45,8 @@ use syn::{GenericParam, ItemStruct, Type};
 pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: ItemStruct) -> TokenStream {
     let namespace = attributes.namespace.clone().unwrap_or_default();
     let doc_line = extract_doc_lines(&item.attrs).join("\n");
+    let type_visibility = visibility_tokens(&item.vis);
+    let type_deprecation = extract_deprecation(&item.attrs);
 
     let (type_repr, align) = attributes.type_repr_align();
 
@@ -57,6 +59,8 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
     let mut field_types = Vec::new();
     let mut field_docs = Vec::new();
     let mut field_visibilities = Vec::new();
+    let mut field_deprecations = Vec::new();
+    let mut field_members = Vec::new();
 
     let mut has_generics = false;
     let mut generic_params_needing_ctypeinfo_bounds = Vec::new();
@@ -122,6 +126,11 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
         field_names.push(name.clone());
         field_docs.push(extract_doc_lines(&field.attrs).join("\n"));
         field_visibilities.push(visibility);
+        field_deprecations.push(extract_deprecation(&field.attrs));
+        field_members.push(match &field.ident {
+            Some(ident) => quote! { #ident },
+            None => syn::Index::from(i).to_token_stream(),
+        });
 
         let token = match &field.ty {
             Type::Path(x) => {
@@ -157,7 +166,8 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
                 #({
                     let documentation = ::interoptopus::lang::c::Documentation::from_line(#field_docs);
                     let the_type = #field_type_info;
-                    let field = ::interoptopus::lang::c::Field::with_documentation(#field_names.to_string(), the_type, #field_visibilities, documentation);
+                    let field = ::interoptopus::lang::c::Field::with_documentation(#field_names.to_string(), the_type, #field_visibilities, documentation)
+                        .with_deprecation(#field_deprecations);
                     fields.push(field);
                 })*
         },
@@ -210,11 +220,47 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
         },
         false => quote! {
             let repr = ::interoptopus::lang::c::Representation::new(#layout, #align);
-            let rval = ::interoptopus::lang::c::CompositeType::with_meta_repr(name, fields, meta, repr);
+            let offsets = ::std::vec![#(::std::mem::offset_of!(Self, #field_members)),*];
+            let memory_layout = ::interoptopus::lang::c::MemoryLayout::new(::std::mem::size_of::<Self>(), ::std::mem::align_of::<Self>(), offsets);
+            let rval = ::interoptopus::lang::c::CompositeType::with_meta_repr_layout(name, fields, meta, repr, memory_layout);
             ::interoptopus::lang::c::CType::Composite(rval)
         },
     };
 
+    // Const assertions that fields exported to C are laid out by C rules, e.g., skipped fields must not take up space.
+    let layout_asserts = match (attributes.opaque, has_generics, type_repr) {
+        (false, false, TypeRepresentation::C | TypeRepresentation::Packed) => {
+            let packed = matches!(type_repr, TypeRepresentation::Packed);
+            let struct_align = attributes.type_repr_align().1.unwrap_or(1);
+            let message_offsets = field_names
+                .iter()
+                .map(|x| format!("Field `{}` of `{}` is not where C expects it.", x, struct_ident))
+                .collect::<Vec<_>>();
+            let message_size = format!("Size of `{}` differs from what C expects.", struct_ident);
+
+            quote! {
+                #[allow(deprecated)]
+                const _: () = {
+                    let mut offset = 0usize;
+                    let mut align = #struct_align;
+
+                    #({
+                        let field_align = if #packed { 1 } else { ::std::mem::align_of::<#field_types>() };
+                        offset = offset.next_multiple_of(field_align);
+                        assert!(::std::mem::offset_of!(#struct_ident, #field_members) == offset, #message_offsets);
+                        offset += ::std::mem::size_of::<#field_types>();
+                        if field_align > align {
+                            align = field_align;
+                        }
+                    })*
+
+                    assert!(::std::mem::size_of::<#struct_ident>() == offset.next_multiple_of(align), #message_size);
+                };
+            }
+        }
+        _ => quote! {},
+    };
+
     if item.attrs.iter().any(|attr| attr.path().is_ident("repr")) {
         panic!("Since 0.15 you must not add any `#[repr()] attributes to your struct; Interoptopus will handle that for you.");
     } else {
@@ -226,11 +272,15 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
             quote! {
                 #item
 
+                #layout_asserts
+
+                #[allow(deprecated)]
                 unsafe impl #param_param ::interoptopus::lang::rust::CTypeInfo for #struct_ident #param_struct #param_where {
 
                     fn type_info() -> ::interoptopus::lang::c::CType {
                         let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
-                        let mut meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
+                        let mut meta = ::interoptopus::lang::c::Meta::with_namespace_documentation_visibility(#namespace.to_string(), documentation, #type_visibility)
+                            .with_deprecation(#type_deprecation);
                         let mut fields: ::std::vec::Vec<interoptopus::lang::c::Field> = ::std::vec::Vec::new();
                         let mut generics: ::std::vec::Vec<String> = ::std::vec::Vec::new();
 
@@ -249,6 +299,7 @@ pub fn ffi_type_struct(attributes: &Attributes, _input: TokenStream, mut item: I
             quote! {
                 #item
 
+                #[allow(deprecated)]
                 unsafe impl #param_param ::interoptopus::lang::rust::CTypeInfo for #struct_ident #param_struct #param_where {
 
                     fn type_info() -> ::interoptopus::lang::c::CType {
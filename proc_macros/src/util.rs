@@ -1,6 +1,8 @@
 use darling::ToTokens;
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Expr, GenericArgument, ItemImpl, Lit, Meta, PathArguments, Type, TypePath};
+use syn::{Attribute, Expr, ExprLit, GenericArgument, ItemImpl, Lit, LitStr, Meta, PathArguments, Type, TypePath, Visibility};
 
 /// From a let of attributes to an item, extracts the ones that are documentation, as strings.
 pub fn extract_doc_lines(attributes: &[Attribute]) -> Vec<String> {
@@ -27,6 +29,53 @@ pub fn extract_doc_lines(attributes: &[Attribute]) -> Vec<String> {
     docs
 }
 
+/// From a list of attributes to an item, extracts `#[deprecated]` as an `interoptopus::lang::c::Deprecation`.
+pub fn extract_deprecation(attributes: &[Attribute]) -> TokenStream {
+    for attr in attributes {
+        if &attr.path().to_token_stream().to_string() != "deprecated" {
+            continue;
+        }
+
+        let note = match &attr.meta {
+            Meta::Path(_) => None,
+            Meta::NameValue(x) => match &x.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(x), .. }) => Some(x.value()),
+                _ => panic!("Unexpected content in #[deprecated]: not a string."),
+            },
+            Meta::List(_) => {
+                let mut note = None;
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("note") {
+                        let x: LitStr = meta.value()?.parse()?;
+                        note = Some(x.value());
+                    } else {
+                        // Ignore `since` and anything else we don't understand.
+                        let _: Expr = meta.value()?.parse()?;
+                    }
+                    Ok(())
+                })
+                .expect("Unexpected content in #[deprecated(...)].");
+                note
+            }
+        };
+
+        return match note {
+            Some(note) => quote! { ::interoptopus::lang::c::Deprecation::Deprecated(Some(#note.to_string())) },
+            None => quote! { ::interoptopus::lang::c::Deprecation::Deprecated(None) },
+        };
+    }
+
+    quote! { ::interoptopus::lang::c::Deprecation::NotDeprecated }
+}
+
+/// Maps the Rust visibility of an item to its `interoptopus::lang::c::Visibility`, anything but `pub` becomes private.
+pub fn visibility_tokens(vis: &Visibility) -> TokenStream {
+    match vis {
+        Visibility::Public(_) => quote! { ::interoptopus::lang::c::Visibility::Public },
+        _ => quote! { ::interoptopus::lang::c::Visibility::Private },
+    }
+}
+
 /// Ugly, incomplete function to purge `'a` from a `Generic<'a, T>`.
 pub fn purge_lifetimes_from_type(the_type: &Type) -> Type {
     let mut rval = the_type.clone();
@@ -1,5 +1,6 @@
 //! Various ways to define constants.
 
+use crate::types::Vec3f32;
 use interoptopus::ffi_constant;
 
 const fn f(x: i32) -> i32 {
@@ -14,3 +15,16 @@ pub const F32_MIN_POSITIVE: f32 = f32::MIN_POSITIVE;
 
 #[ffi_constant]
 pub const COMPUTED_I32: i32 = f(i32::MAX);
+
+/// Version string of the reference project.
+#[ffi_constant]
+pub const VERSION: &str = "1.2.3 \"reference\"";
+
+#[ffi_constant]
+pub const MAGIC: [u8; 4] = *b"IOPS";
+
+#[ffi_constant]
+pub const UP: Vec3f32 = Vec3f32 { x: 0.0, y: 1.0, z: 0.0 };
+
+#[ffi_constant(namespace = "common")]
+pub const NAMESPACED: u32 = 42;
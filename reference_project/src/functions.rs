@@ -2,10 +2,12 @@
 
 use crate::patterns::result::{Error, FFIError};
 use crate::types::{
-    ambiguous1, ambiguous2, common, Array, BooleanAlignment, Callbacku8u8, EnumDocumented, EnumRenamedXYZ, Generic, Generic2, Generic3, Generic4, Packed1, Packed2,
-    Phantom, StructDocumented, StructRenamedXYZ, Transparent, Tupled, Vec3f32, Visibility1, Visibility2, Weird1, Weird2,
+    ambiguous1, ambiguous2, common, Array, BooleanAlignment, Callbacku8u8, EnumDeprecated, EnumDocumented, EnumRenamedXYZ, Generic, Generic2, Generic3, Generic4,
+    Packed1, Packed2, Phantom, StructDeprecated, StructDocumented, StructRenamedXYZ, Transparent, Tupled, Vec3f32, Visibility1, Visibility2, VisibilityCrate, Weird1,
+    Weird2,
 };
 use interoptopus::patterns::option::FFIOption;
+use interoptopus::patterns::primitives::FFIChar;
 use interoptopus::patterns::result::panics_and_errors_to_ffi_enum;
 use interoptopus::patterns::slice::FFISlice;
 use interoptopus::patterns::slice::FFISliceMut;
@@ -44,24 +46,52 @@ pub fn primitive_u64(x: u64) -> u64 {
     u64::MAX - x
 }
 
+// Negating `MIN` overflows, which panics in debug builds and would abort when conformance tests
+// pass it. Wrapping keeps the result release builds already returned, `MIN` maps to itself.
 #[ffi_function]
 pub fn primitive_i8(x: i8) -> i8 {
-    -x
+    x.wrapping_neg()
 }
 
 #[ffi_function]
 pub fn primitive_i16(x: i16) -> i16 {
-    -x
+    x.wrapping_neg()
 }
 
 #[ffi_function]
 pub fn primitive_i32(x: i32) -> i32 {
-    -x
+    x.wrapping_neg()
 }
 
 #[ffi_function]
 pub fn primitive_i64(x: i64) -> i64 {
-    -x
+    x.wrapping_neg()
+}
+
+#[ffi_function]
+pub fn primitive_usize(x: usize) -> usize {
+    usize::MAX - x
+}
+
+#[ffi_function]
+pub fn primitive_isize(x: isize) -> isize {
+    x.wrapping_neg()
+}
+
+#[ffi_function]
+pub fn primitive_u128(x: u128) -> u128 {
+    u128::MAX - x
+}
+
+#[ffi_function]
+pub fn primitive_i128(x: i128) -> i128 {
+    x.wrapping_neg()
+}
+
+/// Returns the Unicode scalar value of `x`, or `u32::MAX` if `x` is not a valid `char`.
+#[ffi_function]
+pub fn primitive_char(x: FFIChar) -> u32 {
+    x.as_char().map_or(u32::MAX, |x| x as u32)
 }
 
 #[ffi_function]
@@ -215,11 +245,23 @@ pub fn renamed(x: StructRenamedXYZ) -> EnumRenamedXYZ {
 // }
 
 /// This function has documentation.
+///
+/// # Parameters
+///
+/// * `_x` - A documented struct, its
+///   fields are documented as well.
 #[ffi_function]
 pub fn documented(_x: StructDocumented) -> EnumDocumented {
     EnumDocumented::A
 }
 
+/// Superseded by `documented`.
+#[deprecated(note = "Use `documented` instead.")]
+#[ffi_function]
+pub fn deprecated(_x: StructDeprecated) -> EnumDeprecated {
+    EnumDeprecated::Current
+}
+
 #[ffi_function]
 pub fn ambiguous_1(x: ambiguous1::Vec) -> ambiguous1::Vec {
     x
@@ -255,6 +297,30 @@ pub fn namespaced_inner_slice_mut(x: FFISliceMut<common::Vec>) -> FFISliceMut<co
     x
 }
 
+/// Emitted into the `common` namespace.
+#[ffi_function(namespace = "common")]
+pub fn namespaced_function(x: common::Vec) -> f64 {
+    x.x + x.z
+}
+
+/// Named after a C# keyword, which uses another name instead.
+#[ffi_function(name(csharp = "CheckedValue"))]
+pub fn checked(x: u32) -> u32 {
+    x
+}
+
+/// Exported as `renamed_symbol`, aliased in C and not emitted for Python.
+#[ffi_function(export_name = "renamed_symbol", name(c = "renamed_export"), skip(python))]
+pub fn renamed_export(x: u32) -> u32 {
+    x
+}
+
+/// Not emitted for C#, whose API guard must still match the full inventory.
+#[ffi_function(skip(csharp))]
+pub fn skipped_in_csharp(x: u32) -> u32 {
+    x
+}
+
 #[ffi_function]
 #[allow(unreachable_code)]
 pub fn panics() -> FFIError {
@@ -279,3 +345,8 @@ pub fn weird_1(_x: Weird1<u32>, _y: Weird2<u8, 5>) -> bool {
 
 #[ffi_function]
 pub fn visibility(_x: Visibility1, _y: Visibility2) {}
+
+#[ffi_function]
+pub(crate) fn visibility_crate(x: VisibilityCrate) -> u8 {
+    x.x
+}
@@ -7,6 +7,7 @@
 //! Note, many items here are deliberately not documented as testing how and if documentation
 //! is generated is part of the test.
 
+use interoptopus::conformance::Recorder;
 use interoptopus::{constant, extra_type, function, pattern, Inventory, InventoryBuilder};
 
 pub mod constants;
@@ -41,6 +42,11 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(functions::primitive_i16))
             .register(function!(functions::primitive_i32))
             .register(function!(functions::primitive_i64))
+            .register(function!(functions::primitive_usize))
+            .register(function!(functions::primitive_isize))
+            .register(function!(functions::primitive_u128))
+            .register(function!(functions::primitive_i128))
+            .register(function!(functions::primitive_char))
             .register(function!(functions::boolean_alignment))
             .register(function!(functions::boolean_alignment2))
             // .register(function!(functions::aligned_to_packed1))
@@ -66,6 +72,7 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(functions::generic_4))
             .register(function!(functions::array_1))
             .register(function!(functions::documented))
+            .register(function!(functions::deprecated))
             .register(function!(functions::ambiguous_1))
             .register(function!(functions::ambiguous_2))
             .register(function!(functions::ambiguous_3))
@@ -73,11 +80,16 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(functions::namespaced_inner_option))
             .register(function!(functions::namespaced_inner_slice))
             .register(function!(functions::namespaced_inner_slice_mut))
+            .register(function!(functions::namespaced_function))
+            .register(function!(functions::checked))
+            .register(function!(functions::renamed_export))
+            .register(function!(functions::skipped_in_csharp))
             .register(function!(functions::panics))
             .register(function!(functions::renamed))
             .register(function!(functions::sleep))
             .register(function!(functions::weird_1))
             .register(function!(functions::visibility))
+            .register(function!(functions::visibility_crate))
             .register(function!(functions::repr_transparent))
             .register(function!(patterns::ascii_pointer::pattern_ascii_pointer_1))
             .register(function!(patterns::ascii_pointer::pattern_ascii_pointer_2))
@@ -100,6 +112,7 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(patterns::primitives::pattern_ffi_cchar_const_pointer))
             .register(function!(patterns::primitives::pattern_ffi_cchar_mut_pointer))
             .register(function!(patterns::api_guard::pattern_api_guard))
+            .register(function!(patterns::api_guard::pattern_layout_guard))
             .register(function!(patterns::callbacks::pattern_callback_1))
             .register(function!(patterns::callbacks::pattern_callback_2))
             .register(function!(patterns::callbacks::pattern_callback_3))
@@ -107,17 +120,70 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(patterns::callbacks::pattern_callback_5))
             .register(function!(patterns::callbacks::pattern_callback_6))
             .register(function!(patterns::callbacks::pattern_callback_7))
+            .register(function!(patterns::callbacks::pattern_callback_8))
+            .register(function!(patterns::callbacks::pattern_callback_9))
             .register(function!(patterns::surrogates::pattern_surrogates_1))
             // Constants
             .register(constant!(constants::U8))
             .register(constant!(constants::F32_MIN_POSITIVE))
             .register(constant!(constants::COMPUTED_I32))
+            .register(constant!(constants::VERSION))
+            .register(constant!(constants::MAGIC))
+            .register(constant!(constants::UP))
+            .register(constant!(constants::NAMESPACED))
             // Extra Types
             .register(extra_type!(types::ExtraType<f32>))
             // Patterns
             .register(pattern!(patterns::service::SimpleService))
+            .register(pattern!(patterns::service::SimpleServiceChild))
             .register(pattern!(patterns::service::SimpleServiceLifetime))
+            .register(pattern!(patterns::service::SimpleServiceNamespaced))
+            .register(pattern!(patterns::service::SharedService))
+            .register(pattern!(patterns::service::SingleThreadedService))
             .validate()
             .inventory()
     }
 }
+
+/// Functions backends run conformance tests against, see [`interoptopus::conformance`].
+pub fn ffi_recorder() -> Recorder {
+    use functions::*;
+
+    Recorder::new()
+        .record("primitive_void", || primitive_void())
+        .record("primitive_void2", || primitive_void2())
+        .record("primitive_bool", |x| primitive_bool(x))
+        .record("primitive_u8", |x| primitive_u8(x))
+        .record("primitive_u16", |x| primitive_u16(x))
+        .record("primitive_u32", |x| primitive_u32(x))
+        .record("primitive_u64", |x| primitive_u64(x))
+        .record("primitive_i8", |x| primitive_i8(x))
+        .record("primitive_i16", |x| primitive_i16(x))
+        .record("primitive_i32", |x| primitive_i32(x))
+        .record("primitive_i64", |x| primitive_i64(x))
+        .record("many_args_5", |x0, x1, x2, x3, x4| many_args_5(x0, x1, x2, x3, x4))
+        .record("many_args_10", |x0, x1, x2, x3, x4, x5, x6, x7, x8, x9| {
+            many_args_10(x0, x1, x2, x3, x4, x5, x6, x7, x8, x9)
+        })
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod test {
+    use interoptopus::exports::check_elf_exports;
+
+    /// Needs the built library, which `cargo test` produces for doc tests but `cargo test --lib`
+    /// does not; run `cargo build -p interoptopus_reference_project` first in that case.
+    #[test]
+    fn exports_match_inventory() {
+        // Test binaries live in `target/<profile>/deps`, next to the library built for doc tests.
+        let exe = std::env::current_exe().unwrap();
+        let library = exe.with_file_name("libinteroptopus_reference_project.so");
+
+        assert!(library.exists(), "{} not found, build the library first", library.display());
+
+        let report = check_elf_exports(&crate::ffi_inventory(), library).unwrap();
+
+        assert!(report.is_ok(), "{}", report);
+    }
+}
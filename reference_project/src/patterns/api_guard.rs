@@ -1,7 +1,60 @@
-use interoptopus::ffi_function;
-use interoptopus::patterns::api_guard::APIVersion;
+use interoptopus::patterns::api_guard::{type_layouts, APIVersion, LayoutTable, TypeLayout};
+use interoptopus::{ffi_function, inventory_export};
+use std::sync::OnceLock;
 
 #[ffi_function]
 pub fn pattern_api_guard() -> APIVersion {
     crate::ffi_inventory().into()
 }
+
+#[ffi_function]
+pub fn pattern_layout_guard() -> LayoutTable {
+    static LAYOUTS: OnceLock<Vec<TypeLayout>> = OnceLock::new();
+    LAYOUTS.get_or_init(|| type_layouts(&crate::ffi_inventory())).as_slice().into()
+}
+
+// Embeds the inventory so bindings can be generated from the built library alone.
+inventory_export!(crate::ffi_inventory);
+
+#[cfg(test)]
+mod test {
+    use super::interoptopus_inventory;
+    use interoptopus::patterns::api_guard::inventory_from_ptr;
+
+    #[test]
+    fn embedded_inventory_matches() {
+        let inventory = unsafe { inventory_from_ptr(interoptopus_inventory()) }.unwrap();
+
+        assert_eq!(inventory, crate::ffi_inventory());
+    }
+
+    #[test]
+    fn layout_guard_reports_composites() {
+        use interoptopus::patterns::api_guard::type_name_hash;
+
+        let layouts = super::pattern_layout_guard();
+        let vec3 = layouts.as_slice().iter().find(|x| x.name_hash == type_name_hash("Vec3f32")).unwrap();
+
+        assert_eq!(vec3.size, 12);
+        assert_eq!(vec3.align, 4);
+    }
+
+    #[test]
+    fn layout_guard_keys_by_namespace() {
+        use interoptopus::lang::c::{CType, CompositeType, Field, Layout, MemoryLayout, Meta, PrimitiveType, Representation};
+        use interoptopus::patterns::api_guard::{layout_key, type_name_hash};
+
+        let layouts = super::pattern_layout_guard();
+        assert!(layouts.as_slice().iter().any(|x| x.name_hash == type_name_hash("common::Vec")));
+
+        let composite = |namespace: &str| {
+            let fields = vec![Field::new("x".to_string(), CType::Primitive(PrimitiveType::U8))];
+            let meta = Meta::with_namespace_documentation(namespace.to_string(), Default::default());
+            let layout = MemoryLayout::new(1, 1, vec![0]);
+            CompositeType::with_meta_repr_layout("Same".to_string(), fields, meta, Representation::new(Layout::C, None), layout)
+        };
+
+        assert_eq!(layout_key(&composite("")), type_name_hash("Same"));
+        assert_ne!(layout_key(&composite("")), layout_key(&composite("other")));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::patterns::result::FFIError;
-use interoptopus::{callback, ffi_function, ffi_type};
+use interoptopus::{callback, callback_with_context, ffi_function, ffi_type};
 use std::ffi::c_void;
 
 callback!(MyCallback(value: u32) -> u32);
@@ -10,6 +10,8 @@ callback!(SumDelegate1());
 callback!(SumDelegate2(x: i32, y: i32) -> i32);
 callback!(SumDelegateReturn(x: i32, y: i32) -> FFIError);
 callback!(SumDelegateReturn2(x: i32, y: i32));
+callback!(MyCallbackCompletion(value: u32));
+callback_with_context!(MyClosure(value: u32) -> u32);
 
 #[ffi_type]
 pub struct DelegateCallback<C> {
@@ -67,6 +69,19 @@ pub fn pattern_callback_7(c1: SumDelegateReturn, c2: SumDelegateReturn2, x: i32,
     FFIError::Ok
 }
 
+/// Completes by invoking `completion` exactly once, which bindings may expose as an `async` call.
+#[ffi_function(async_callback = "completion")]
+pub fn pattern_callback_8(x: u32, completion: MyCallbackCompletion) -> FFIError {
+    completion.call(x + 1);
+    FFIError::Ok
+}
+
+/// Invokes `callback` once; its destructor runs when the callback is dropped on return.
+#[ffi_function]
+pub fn pattern_callback_9(mut callback: MyClosure, x: u32) -> u32 {
+    callback.call(x)
+}
+
 pub extern "C" fn exposed_sum1() {}
 
 pub extern "C" fn exposed_sum2(x: i32, y: i32) -> i32 {
@@ -75,8 +90,9 @@ pub extern "C" fn exposed_sum2(x: i32, y: i32) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use super::{MyCallback, MyCallbackNamespaced};
+    use super::{pattern_callback_9, MyCallback, MyCallbackNamespaced, MyClosure};
     use interoptopus::lang::rust::CTypeInfo;
+    use std::rc::Rc;
 
     #[test]
     fn namespaces_assigned_correctly() {
@@ -86,4 +102,17 @@ mod tests {
         assert_eq!(ti1.namespace(), Some(""));
         assert_eq!(ti2.namespace(), Some("common"));
     }
+
+    #[test]
+    fn closure_is_called_and_released() {
+        let captured = Rc::new(10);
+        let closure = {
+            let captured = captured.clone();
+            MyClosure::from_closure(move |x| x + *captured)
+        };
+
+        assert_eq!(Rc::strong_count(&captured), 2);
+        assert_eq!(pattern_callback_9(closure, 5), 15);
+        assert_eq!(Rc::strong_count(&captured), 1);
+    }
 }
@@ -1,10 +1,12 @@
-use crate::patterns::callbacks::{MyCallback, SumDelegateReturn};
+use crate::patterns::callbacks::{MyCallback, MyCallbackCompletion, SumDelegateReturn};
 use crate::patterns::result::{Error, FFIError};
 use interoptopus::patterns::primitives::FFIBool;
 use interoptopus::patterns::slice::{FFISlice, FFISliceMut};
 use interoptopus::patterns::string::CStrPointer;
 use interoptopus::{ffi_service, ffi_service_ctor, ffi_service_ignore, ffi_service_method, ffi_type};
+use std::cell::Cell;
 use std::ffi::CString;
+use std::rc::Rc;
 
 /// Some struct we want to expose as a class.
 #[ffi_type(opaque)]
@@ -19,6 +21,10 @@ pub struct SimpleService {
 #[ffi_service(error = "FFIError")]
 impl SimpleService {
     /// The constructor must return a `Result<Self, Error>`.
+    ///
+    /// # Parameters
+    ///
+    /// - `some_value`: Stored in the service.
     #[ffi_service_ctor]
     pub fn new_with(some_value: u32) -> Result<Self, Error> {
         Ok(Self {
@@ -51,6 +57,53 @@ impl SimpleService {
         Err(Error::Bad)
     }
 
+    /// Doesn't need an instance, e.g., to query capabilities before construction.
+    #[ffi_service_method(static, on_panic = "return_default")]
+    pub fn supported_formats() -> u32 {
+        3
+    }
+
+    /// Static methods can fail like any other method.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: Must not be `0`.
+    #[ffi_service_method(static)]
+    pub fn validate(value: u32) -> Result<(), Error> {
+        match value {
+            0 => Err(Error::Bad),
+            _ => Ok(()),
+        }
+    }
+
+    /// The value this service was created with.
+    #[ffi_service_method(getter = "value", on_panic = "return_default")]
+    pub fn get_value(&self) -> u32 {
+        self.some_value
+    }
+
+    /// Setters may fail, which raises an exception in languages with properties.
+    #[ffi_service_method(setter = "value")]
+    pub fn set_value(&mut self, value: u32) -> Result<(), Error> {
+        match value {
+            0 => Err(Error::Bad),
+            _ => {
+                self.some_value = value;
+                Ok(())
+            }
+        }
+    }
+
+    /// Creates another service, which is returned as a fully wrapped object.
+    ///
+    /// # Parameters
+    ///
+    /// - `offset`: Added to this service's value.
+    #[ffi_service_method(returns_service)]
+    pub fn create_child(&self, offset: u32) -> Result<SimpleServiceChild, Error> {
+        Ok(SimpleServiceChild { value: self.some_value + offset })
+    }
+
     /// Methods returning a Result<(), _> are the default and do not
     /// need annotations.
     pub fn method_result(&self, _: u32) -> Result<(), Error> {
@@ -71,6 +124,14 @@ impl SimpleService {
     /// Regular void functions don't need an annotation.
     pub fn method_void2(&self) {}
 
+    #[deprecated(note = "Use `method_void` instead.")]
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn method_void_deprecated(&self) {}
+
+    /// Named after a Python keyword, and not emitted for C.
+    #[ffi_service_method(on_panic = "return_default", name(python = "lambda_"), skip(c))]
+    pub fn lambda(&self) {}
+
     #[ffi_service_method(on_panic = "return_default")]
     pub fn method_mut_self(&mut self, slice: FFISlice<u8>) -> u8 {
         *slice.as_slice().first().unwrap_or(&0)
@@ -152,6 +213,12 @@ impl SimpleService {
         Ok(())
     }
 
+    #[ffi_service_method(async_callback = "completion")]
+    pub fn method_callback_completion(&mut self, x: u32, completion: MyCallbackCompletion) -> Result<(), Error> {
+        completion.call(x + 1);
+        Ok(())
+    }
+
     /// No FFI bindings are generated for non-pub methods.
     #[allow(unused)]
     fn not_exposed<T>(&mut self, _: T) -> Result<(), Error> {
@@ -186,3 +253,133 @@ impl<'a> SimpleServiceLifetime<'a> {
         Ok(())
     }
 }
+
+/// A service whose functions and class are emitted into the `common` namespace.
+#[ffi_type(opaque, namespace = "common")]
+pub struct SimpleServiceNamespaced {
+    value: u32,
+}
+
+#[ffi_service(error = "FFIError", namespace = "common")]
+impl SimpleServiceNamespaced {
+    #[ffi_service_ctor]
+    pub fn new_with(value: u32) -> Result<Self, Error> {
+        Ok(Self { value })
+    }
+
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// A service without constructors, only created by [`SimpleService::create_child`].
+#[ffi_type(opaque)]
+pub struct SimpleServiceChild {
+    value: u32,
+}
+
+#[ffi_service(error = "FFIError")]
+impl SimpleServiceChild {
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// A reference counted service, instances of which can be shared between owners.
+#[ffi_type(opaque)]
+pub struct SharedService {
+    value: u32,
+}
+
+#[ffi_service(error = "FFIError", shared)]
+impl SharedService {
+    #[ffi_service_ctor]
+    pub fn new_with(value: u32) -> Result<Self, Error> {
+        Ok(Self { value })
+    }
+
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// A service neither `Send` nor `Sync`, which must stay on the thread that created it.
+#[ffi_type(opaque)]
+pub struct SingleThreadedService {
+    value: Rc<Cell<u32>>,
+}
+
+#[ffi_service(error = "FFIError")]
+impl SingleThreadedService {
+    #[ffi_service_ctor]
+    pub fn new_with(value: u32) -> Result<Self, Error> {
+        Ok(Self {
+            value: Rc::new(Cell::new(value)),
+        })
+    }
+
+    pub fn increment(&self) -> Result<(), Error> {
+        self.value.set(self.value.get() + 1);
+        Ok(())
+    }
+
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn value(&self) -> u32 {
+        self.value.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        single_threaded_service_destroy, single_threaded_service_increment, single_threaded_service_new_with, single_threaded_service_value, SimpleService,
+        SingleThreadedService,
+    };
+    use crate::patterns::result::FFIError;
+    use interoptopus::patterns::service::{thread_guard_check, ThreadSafety};
+    use interoptopus::patterns::{LibraryPattern, LibraryPatternInfo};
+    use std::ptr::null_mut;
+    use std::thread;
+
+    fn thread_safety<T: LibraryPatternInfo>() -> ThreadSafety {
+        match T::pattern_info() {
+            LibraryPattern::Service(x) => x.thread_safety(),
+        }
+    }
+
+    #[test]
+    fn send_and_sync_detected() {
+        assert_eq!(thread_safety::<SimpleService>(), ThreadSafety::new(true, true));
+        assert_eq!(thread_safety::<SingleThreadedService>(), ThreadSafety::new(false, false));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn use_from_other_thread_is_rejected() {
+        let mut context = null_mut();
+        assert!(single_threaded_service_new_with(&mut context, 1) == FFIError::Ok);
+
+        let address = context as usize;
+        let (increment, value) = thread::spawn(move || {
+            let service = unsafe { &*(address as *const SingleThreadedService) };
+            (single_threaded_service_increment(service), single_threaded_service_value(service))
+        })
+        .join()
+        .unwrap();
+
+        // Guard violations are reported as panics, except by `return_default` methods returning their default.
+        assert!(increment == FFIError::Panic);
+        assert_eq!(value, 0);
+
+        let service = unsafe { &*context };
+        assert!(single_threaded_service_increment(service) == FFIError::Ok);
+        assert_eq!(single_threaded_service_value(service), 2);
+        assert!(unsafe { single_threaded_service_destroy(&mut context) } == FFIError::Ok);
+
+        // Once destroyed, the address may be reused by any thread.
+        thread::spawn(move || thread_guard_check(address as *const (), "test")).join().unwrap();
+    }
+}
@@ -112,6 +112,14 @@ pub enum EnumDocumented {
     C,
 }
 
+/// Enum with a deprecated variant.
+#[ffi_type]
+pub enum EnumDeprecated {
+    Current,
+    #[deprecated(note = "Use `Current` instead.")]
+    Legacy,
+}
+
 #[ffi_type(name = "EnumRenamed")]
 pub enum EnumRenamedXYZ {
     X,
@@ -124,6 +132,14 @@ pub struct StructDocumented {
     pub x: f32,
 }
 
+/// Struct with a deprecated field.
+#[ffi_type]
+pub struct StructDeprecated {
+    pub x: u32,
+    #[deprecated(note = "Use `x` instead.")]
+    pub old_x: u32,
+}
+
 #[ffi_type]
 pub struct ExtraType<T> {
     pub x: T,
@@ -173,6 +189,11 @@ pub struct Visibility2 {
     pblc2: u8,
 }
 
+#[ffi_type]
+pub(crate) struct VisibilityCrate {
+    pub x: u8,
+}
+
 #[ffi_type(packed)]
 pub struct Packed1 {
     pub x: u8,
@@ -1,16 +1,7 @@
 #
-# Publish all crates.
+# Regenerate all `.expected` files of backend UI tests from the current output.
 #
 
 PROJECT_ROOT="$( cd "$(dirname "$0")/.." ; pwd -P )" # this file
 
-cp "$PROJECT_ROOT"/backends/c/tests/output_nodocs/my_header.h "$PROJECT_ROOT"/backends/c/tests/output_nodocs/my_header.h.expected
-cp "$PROJECT_ROOT"/backends/c/tests/output_docs_inline/my_header.h "$PROJECT_ROOT"/backends/c/tests/output_docs_inline/my_header.h.expected
-cp "$PROJECT_ROOT"/backends/c/tests/output_typedefs/my_header.h "$PROJECT_ROOT"/backends/c/tests/output_typedefs/my_header.h.expected
-cp "$PROJECT_ROOT"/backends/cpython/tests/output/reference_project.py "$PROJECT_ROOT"/backends/cpython/tests/output/reference_project.py.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.common.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.common.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.common.cs.expected
+cd "$PROJECT_ROOT" && INTEROPTOPUS_BLESS=1 cargo test --workspace --no-fail-fast "$@"